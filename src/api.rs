@@ -2,11 +2,13 @@
 
 #![allow(dead_code)]
 
+use std::collections::HashMap;
 use std::io::{Read, Write};
-use std::net::TcpStream;
+use std::net::{IpAddr, SocketAddr, TcpStream, ToSocketAddrs};
 use std::time::Duration;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use crate::epg::Program;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Category {
@@ -16,6 +18,52 @@ pub struct Category {
     pub parent_id: i64,
 }
 
+/// The three category lists fetched on login (cached to disk per provider)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CategoryLists {
+    pub live: Vec<Category>,
+    pub movies: Vec<Category>,
+    pub series: Vec<Category>,
+}
+
+/// Movie metadata from `get_vod_info`. Providers often send empty strings
+/// (or numbers) for these - empty values become `None`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VodInfo {
+    pub name: Option<String>,
+    pub plot: Option<String>,
+    pub cast: Option<String>,
+    pub director: Option<String>,
+    pub genre: Option<String>,
+    pub rating: Option<String>,
+    pub duration: Option<String>,
+    pub release_date: Option<String>,
+    /// Poster URL (`movie_image`, else `cover_big` / `cover` / the first backdrop)
+    pub movie_image: Option<String>,
+}
+
+impl VodInfo {
+    /// Parse a `get_vod_info` response (fields live under `info`)
+    pub fn from_value(info: &Value) -> Self {
+        use crate::models::{json_first_str, json_str};
+        let info = info.get("info").unwrap_or(info);
+        Self {
+            name: json_str(info, "name"),
+            plot: json_str(info, "plot").or_else(|| json_str(info, "description")),
+            cast: json_str(info, "cast").or_else(|| json_str(info, "actors")),
+            director: json_str(info, "director"),
+            genre: json_str(info, "genre"),
+            rating: json_str(info, "rating"),
+            duration: json_str(info, "duration"),
+            release_date: json_str(info, "releasedate").or_else(|| json_str(info, "release_date")),
+            movie_image: json_str(info, "movie_image")
+                .or_else(|| json_str(info, "cover_big"))
+                .or_else(|| json_str(info, "cover"))
+                .or_else(|| json_first_str(info, "backdrop_path")),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Stream {
     pub stream_id: i64,
@@ -28,6 +76,63 @@ pub struct Stream {
     pub stream_icon: Option<String>,
     #[serde(default)]
     pub container_extension: Option<String>,
+    // Some providers include the genre in the VOD list
+    #[serde(default)]
+    pub genre: Option<String>,
+    // Live streams that can be replayed: tv_archive = 1, tv_archive_duration in days
+    #[serde(default, deserialize_with = "deserialize_loose_i64")]
+    pub tv_archive: Option<i64>,
+    #[serde(default, deserialize_with = "deserialize_loose_i64")]
+    pub tv_archive_duration: Option<i64>,
+}
+
+impl Stream {
+    /// Days of archive available for this stream (`None` when it has no archive)
+    pub fn archive_days(&self) -> Option<u32> {
+        if self.tv_archive != Some(1) {
+            return None;
+        }
+        Some(self.tv_archive_duration.unwrap_or(0).clamp(0, 365) as u32)
+    }
+}
+
+/// Providers send numeric flags as numbers, strings ("1") or null
+fn deserialize_loose_i64<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<i64>, D::Error> {
+    Ok(match Value::deserialize(deserializer)? {
+        Value::Number(n) => n.as_i64().or_else(|| n.as_f64().map(|f| f as i64)),
+        Value::String(s) => s.trim().parse().ok(),
+        Value::Bool(b) => Some(b as i64),
+        _ => None,
+    })
+}
+
+/// Percent-encode everything but unreserved characters (RFC 3986)
+pub fn url_encode(s: &str) -> String {
+    s.bytes().map(|b| match b {
+        b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+        _ => format!("%{:02X}", b),
+    }).collect()
+}
+
+/// The panel's UTC offset from `server_info` - its local `time_now`
+/// ("YYYY-MM-DD HH:MM:SS") against `timestamp_now`, rounded to 15 minutes
+pub fn server_utc_offset(time_now: &str, timestamp_now: i64) -> Option<i64> {
+    let local = chrono::NaiveDateTime::parse_from_str(time_now.trim(), "%Y-%m-%d %H:%M:%S").ok()?;
+    let offset = local.and_utc().timestamp() - timestamp_now;
+    let rounded = (offset as f64 / 900.0).round() as i64 * 900;
+    (rounded.abs() <= 14 * 3600).then_some(rounded)
+}
+
+/// Xtream archive (timeshift) URL replaying a live stream from `start` (UTC) for the
+/// length of `start..stop`. The panel reads the start as `YYYY-MM-DD:HH-MM` in its own
+/// timezone (`utc_offset_secs` from `server_utc_offset`) and the duration in minutes.
+pub fn xtream_timeshift_url(server: &str, username: &str, password: &str, stream_id: i64, start: i64, stop: i64, utc_offset_secs: i64) -> String {
+    let start_time = chrono::DateTime::from_timestamp(start + utc_offset_secs, 0).unwrap_or_default();
+    let minutes = ((stop - start).max(60) + 59) / 60;
+    format!(
+        "{}/streaming/timeshift.php?username={}&password={}&stream={}&start={}&duration={}",
+        server.trim_end_matches('/'), url_encode(username), url_encode(password), stream_id, start_time.format("%Y-%m-%d:%H-%M"), minutes
+    )
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -53,6 +158,349 @@ pub struct Episode {
     pub episode_num: i32,
     pub season: i32,
     pub container_extension: String,
+    pub air_date: Option<String>, // YYYY-MM-DD when the provider sends one
+}
+
+/// Parse every episode of every season from a `get_series_info` response,
+/// ordered by season (the server's order is kept within a season)
+pub fn parse_series_episodes(info: &Value) -> Vec<Episode> {
+    let Some(seasons) = info.get("episodes").and_then(|e| e.as_object()) else {
+        return Vec::new();
+    };
+
+    let mut all: Vec<Episode> = seasons.iter()
+        .filter_map(|(key, eps)| Some((key.parse::<i32>().ok()?, eps.as_array()?)))
+        .flat_map(|(season, arr)| arr.iter().filter_map(move |ep| {
+            let id = ep.get("id")?.as_str()?.parse().ok()?;
+            let title = ep.get("title")
+                .and_then(|v| v.as_str())
+                .unwrap_or("Unknown")
+                .to_string();
+            // Some providers send the number as a string
+            let episode_num = ep.get("episode_num")
+                .and_then(|v| v.as_i64().or_else(|| v.as_str()?.trim().parse().ok()))
+                .unwrap_or(0) as i32;
+            let container = ep.get("container_extension")
+                .and_then(|v| v.as_str())
+                .unwrap_or("mp4")
+                .to_string();
+
+            Some(Episode {
+                id,
+                title,
+                episode_num,
+                season,
+                container_extension: container,
+                air_date: episode_air_date(ep),
+            })
+        }))
+        .collect();
+    // Stable sort keeps the server's order within a season
+    all.sort_by_key(|ep| ep.season);
+    all
+}
+
+/// Air date of an episode object (`air_date`, or `info.air_date` / `info.releasedate`),
+/// normalised to YYYY-MM-DD so dates sort as strings
+fn episode_air_date(ep: &Value) -> Option<String> {
+    let info = ep.get("info");
+    ["air_date", "releasedate", "release_date"].iter()
+        .filter_map(|key| ep.get(*key).or_else(|| info?.get(*key)))
+        .filter_map(|v| v.as_str())
+        .find_map(|date| {
+            let date = date.trim().get(..10)?;
+            let valid = date.char_indices().all(|(i, c)| if i == 4 || i == 7 { c == '-' } else { c.is_ascii_digit() });
+            (valid && !date.starts_with("0000")).then(|| date.to_string())
+        })
+}
+
+/// Order episodes by air date, falling back to the episode number for ties and
+/// for episodes without a date (which go last)
+pub fn sort_episodes_by_air_date(episodes: &mut [Episode]) {
+    episodes.sort_by(|a, b| {
+        a.air_date.is_none().cmp(&b.air_date.is_none())
+            .then_with(|| a.air_date.cmp(&b.air_date))
+            .then_with(|| a.episode_num.cmp(&b.episode_num))
+    });
+}
+
+/// Episode after `current_id` in list order, as long as it belongs to the same season
+pub fn next_episode(episodes: &[Episode], current_id: i64) -> Option<&Episode> {
+    let idx = episodes.iter().position(|ep| ep.id == current_id)?;
+    episodes.get(idx + 1).filter(|next| next.season == episodes[idx].season)
+}
+
+/// Programs from a `get_short_epg` response. Titles and descriptions arrive
+/// base64-encoded; times come from `start_timestamp`/`stop_timestamp` (falling
+/// back to `start`/`end` as epoch or "YYYY-MM-DD HH:MM:SS" UTC strings).
+/// Listings without a channel id are assigned `fallback_channel_id`.
+pub fn parse_short_epg(info: &Value, fallback_channel_id: &str) -> Vec<Program> {
+    let Some(listings) = info.get("epg_listings").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    let text = |item: &Value, key: &str| -> Option<String> {
+        let raw = item.get(key)?.as_str()?.trim();
+        let decoded = decode_base64(raw).unwrap_or_else(|| raw.to_string());
+        let decoded = decoded.trim();
+        (!decoded.is_empty()).then(|| decoded.to_string())
+    };
+    let time = |item: &Value, keys: &[&str]| keys.iter().find_map(|key| short_epg_time(item.get(*key)?));
+    
+    let mut programs: Vec<Program> = listings.iter()
+        .filter_map(|item| {
+            let start = time(item, &["start_timestamp", "start"])?;
+            let stop = time(item, &["stop_timestamp", "end", "stop"])?;
+            let channel_id = ["channel_id", "epg_id"].iter()
+                .find_map(|key| item.get(*key)?.as_str().map(str::trim).filter(|s| !s.is_empty()))
+                .unwrap_or(fallback_channel_id);
+            let title = text(item, "title")?;
+            (stop > start).then(|| Program {
+                channel_id: channel_id.to_string(),
+                title,
+                description: text(item, "description"),
+                start,
+                stop,
+                category: None,
+                episode: None,
+                icon: None,
+            })
+        })
+        .collect();
+    programs.sort_by_key(|p| p.start);
+    programs
+}
+
+/// Epoch seconds from a number, a numeric string or a "YYYY-MM-DD HH:MM:SS" (UTC) string
+fn short_epg_time(value: &Value) -> Option<i64> {
+    match value {
+        Value::Number(n) => n.as_i64(),
+        Value::String(s) => s.trim().parse().ok().or_else(|| {
+            chrono::NaiveDateTime::parse_from_str(s.trim(), "%Y-%m-%d %H:%M:%S")
+                .ok()
+                .map(|dt| dt.and_utc().timestamp())
+        }),
+        _ => None,
+    }
+}
+
+/// Decode standard base64 (padding optional) into UTF-8 text
+fn decode_base64(input: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(input.len() * 3 / 4);
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for c in input.bytes().filter(|c| !c.is_ascii_whitespace()) {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            b'=' => break,
+            _ => return None,
+        };
+        buffer = (buffer << 6) | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// Switch an Xtream live stream URL (`/live/user/pass/{id}.ts`) between the
+/// MPEG-TS and HLS outputs. Other URLs are returned unchanged.
+pub fn live_url_with_format(url: &str, hls: bool) -> String {
+    if !url.contains("/live/") {
+        return url.to_string();
+    }
+    let Some((base, ext)) = url.rsplit_once('.') else {
+        return url.to_string();
+    };
+    if ext.contains('/') || !(ext == "ts" || ext == "m3u8") {
+        return url.to_string();
+    }
+    format!("{}.{}", base, if hls { "m3u8" } else { "ts" })
+}
+
+/// Xtream endpoints people paste along with the server address
+const SERVER_URL_ENDPOINTS: &[&str] = &["player_api.php", "panel_api.php", "get.php", "xmltv.php"];
+
+/// Clean up a server address from the login form or a saved playlist:
+/// adds `http://` when missing, drops the query string, known endpoints
+/// (`/player_api.php`, `/get.php`, ...) and trailing slashes.
+/// `provider.tv:8080/get.php?username=a&password=b` becomes `http://provider.tv:8080`.
+pub fn normalize_server_url(input: &str) -> String {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return String::new();
+    }
+    let lower = trimmed.to_ascii_lowercase();
+    let mut url = if lower.starts_with("http://") || lower.starts_with("https://") {
+        let scheme_len = lower.find("://").unwrap_or(0);
+        format!("{}{}", &lower[..scheme_len], &trimmed[scheme_len..])
+    } else {
+        format!("http://{}", trimmed)
+    };
+    
+    if let Some(pos) = url.find(['?', '#']) {
+        url.truncate(pos);
+    }
+    let host_start = url.find("://").map_or(0, |p| p + 3);
+    loop {
+        let path = url[host_start..].trim_end_matches('/').to_ascii_lowercase();
+        url.truncate(host_start + path.len());
+        let Some(endpoint) = SERVER_URL_ENDPOINTS.iter().find(|ep| path.ends_with(&format!("/{}", ep))) else {
+            break;
+        };
+        url.truncate(url.len() - endpoint.len() - 1);
+    }
+    url
+}
+
+/// Clean up a provider's `container_extension` for use in a stream URL: whitespace and
+/// leading dots are stripped, and anything that isn't a plain alphanumeric token
+/// (empty, `mp4?token=..`, `../x`) is rejected so the caller can use its default.
+pub fn clean_container_extension(ext: Option<&str>) -> Option<&str> {
+    let ext = ext?.trim().trim_start_matches('.').trim();
+    (!ext.is_empty() && ext.chars().all(|c| c.is_ascii_alphanumeric())).then_some(ext)
+}
+
+/// Short container label (TS / HLS / MP4 / MKV ...) from the URL's extension,
+/// falling back to the Xtream `container_extension`. `None` when neither is recognised.
+pub fn stream_container_label(url: &str, container_extension: Option<&str>) -> Option<&'static str> {
+    let path = url.split(['?', '#']).next().unwrap_or("");
+    let last_segment = path.rsplit('/').next().unwrap_or("");
+    let url_ext = last_segment.rsplit_once('.').map(|(_, ext)| ext);
+    
+    let label = |ext: &str| match ext.trim().to_ascii_lowercase().as_str() {
+        "ts" | "mpegts" => Some("TS"),
+        "m3u8" | "m3u" => Some("HLS"),
+        "mpd" => Some("DASH"),
+        "mp4" | "m4v" => Some("MP4"),
+        "mkv" => Some("MKV"),
+        "avi" => Some("AVI"),
+        "mov" => Some("MOV"),
+        "webm" => Some("WEBM"),
+        "flv" => Some("FLV"),
+        _ => None,
+    };
+    url_ext.and_then(label).or_else(|| container_extension.and_then(label))
+}
+
+/// Build a fallback logo URL for a stream without a `stream_icon`.
+/// Placeholders: `{server}`, `{stream_id}`, `{epg_id}` and `{name}` (the last two percent-encoded).
+/// Returns `None` for an empty template or when it needs an EPG id the stream lacks.
+pub fn logo_from_template(template: &str, server: &str, stream_id: i64, name: &str, epg_channel_id: Option<&str>) -> Option<String> {
+    let template = template.trim();
+    if template.is_empty() {
+        return None;
+    }
+    let epg_id = epg_channel_id.unwrap_or("").trim();
+    if template.contains("{epg_id}") && epg_id.is_empty() {
+        return None;
+    }
+    Some(template
+        .replace("{server}", server.trim_end_matches('/'))
+        .replace("{stream_id}", &stream_id.to_string())
+        .replace("{epg_id}", &url_encode(epg_id))
+        .replace("{name}", &url_encode(name.trim())))
+}
+
+/// How a provider's hostname is resolved, for providers with broken DNS or IPv6.
+/// The default leaves resolution to the system.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HostResolution {
+    /// Only connect over IPv4
+    pub force_ipv4: bool,
+    /// Connect to this address whenever the provider's host is requested.
+    /// The Host header (and TLS name) keep the original hostname.
+    pub ip_override: Option<(String, IpAddr)>,
+}
+
+impl HostResolution {
+    /// `ip_override` applies to the host of `provider_url`; it is ignored when
+    /// empty or not a valid IP address
+    pub fn new(force_ipv4: bool, ip_override: &str, provider_url: &str) -> Self {
+        let ip_override = ip_override.trim().parse::<IpAddr>().ok()
+            .zip(url_host(provider_url))
+            .map(|(ip, host)| (host, ip));
+        Self { force_ipv4, ip_override }
+    }
+
+    fn override_for(&self, host: &str) -> Option<IpAddr> {
+        self.ip_override.as_ref()
+            .filter(|(h, _)| h.eq_ignore_ascii_case(host))
+            .map(|(_, ip)| *ip)
+    }
+
+    /// Addresses to try for `host:port`
+    pub fn resolve(&self, host: &str, port: u16) -> std::io::Result<Vec<SocketAddr>> {
+        if let Some(ip) = self.override_for(host) {
+            return Ok(vec![SocketAddr::new(ip, port)]);
+        }
+        let addrs: Vec<_> = (host, port).to_socket_addrs()?
+            .filter(|a| !self.force_ipv4 || a.is_ipv4())
+            .collect();
+        if addrs.is_empty() {
+            return Err(std::io::Error::new(std::io::ErrorKind::NotFound, format!("No IPv4 address for {}", host)));
+        }
+        Ok(addrs)
+    }
+
+    /// Build a ureq agent that follows these rules
+    pub fn agent(&self, config: ureq::config::ConfigBuilder<ureq::typestate::AgentScope>) -> ureq::Agent {
+        let config = if self.force_ipv4 {
+            config.ip_family(ureq::config::IpFamily::Ipv4Only)
+        } else {
+            config
+        };
+        match &self.ip_override {
+            Some((host, ip)) => ureq::Agent::with_parts(
+                config.build(),
+                ureq::unversioned::transport::DefaultConnector::new(),
+                OverrideResolver { host: host.clone(), ip: *ip },
+            ),
+            None => config.build().new_agent(),
+        }
+    }
+}
+
+/// ureq resolver that pins one hostname to a fixed address
+#[derive(Debug)]
+struct OverrideResolver {
+    host: String,
+    ip: IpAddr,
+}
+
+impl ureq::unversioned::resolver::Resolver for OverrideResolver {
+    fn resolve(
+        &self,
+        uri: &ureq::http::Uri,
+        config: &ureq::config::Config,
+        timeout: ureq::unversioned::transport::NextTimeout,
+    ) -> Result<ureq::unversioned::resolver::ResolvedSocketAddrs, ureq::Error> {
+        if !uri.host().is_some_and(|h| h.eq_ignore_ascii_case(&self.host)) {
+            return ureq::unversioned::resolver::DefaultResolver::default().resolve(uri, config, timeout);
+        }
+        let port = uri.port_u16()
+            .unwrap_or(if uri.scheme_str() == Some("https") { 443 } else { 80 });
+        let mut addrs = ureq::unversioned::resolver::Resolver::empty(self);
+        addrs.push(SocketAddr::new(self.ip, port));
+        Ok(addrs)
+    }
+}
+
+/// Hostname of an http(s) URL, without userinfo or port
+fn url_host(url: &str) -> Option<String> {
+    let rest = url.trim().split_once("://").map_or(url.trim(), |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host_port = authority.rsplit_once('@').map_or(authority, |(_, h)| h);
+    let host = match host_port.strip_prefix('[') {
+        Some(v6) => v6.split(']').next()?,
+        None => host_port.split(':').next()?,
+    };
+    (!host.is_empty()).then(|| host.to_string())
 }
 
 pub struct XtreamClient {
@@ -61,6 +509,9 @@ pub struct XtreamClient {
     password: String,
     user_agent: String,
     use_post: bool,
+    connect_timeout: Duration,
+    read_timeout: Duration,
+    resolution: HostResolution,
 }
 
 impl XtreamClient {
@@ -71,6 +522,9 @@ impl XtreamClient {
             password: password.to_string(),
             user_agent: "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36".to_string(),
             use_post: false,
+            connect_timeout: Duration::from_secs(30),
+            read_timeout: Duration::from_secs(30),
+            resolution: HostResolution::default(),
         }
     }
 
@@ -84,6 +538,18 @@ impl XtreamClient {
         self
     }
 
+    /// Set connect and read timeouts in seconds (0 is treated as 1)
+    pub fn with_timeouts(mut self, connect_secs: u64, read_secs: u64) -> Self {
+        self.connect_timeout = Duration::from_secs(connect_secs.max(1));
+        self.read_timeout = Duration::from_secs(read_secs.max(1));
+        self
+    }
+
+    pub fn with_host_resolution(mut self, resolution: HostResolution) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
     fn api_url(&self, action: &str) -> String {
         format!(
             "{}/player_api.php?username={}&password={}&action={}",
@@ -103,10 +569,25 @@ impl XtreamClient {
         let url = url.trim();
         let (host, port, path) = parse_http_url(url)?;
 
-        // Connect with timeout
-        let addr = format!("{}:{}", host, port);
-        let mut stream = TcpStream::connect(&addr)?;
-        stream.set_read_timeout(Some(Duration::from_secs(30)))?;
+        // Connect with timeout (try each resolved address)
+        let addrs = self.resolution.resolve(&host, port)?;
+        let mut last_err = None;
+        let mut stream = None;
+        for addr in addrs {
+            match TcpStream::connect_timeout(&addr, self.connect_timeout) {
+                Ok(s) => {
+                    stream = Some(s);
+                    break;
+                }
+                Err(e) => last_err = Some(e),
+            }
+        }
+        let mut stream = match (stream, last_err) {
+            (Some(s), _) => s,
+            (None, Some(e)) => return Err(e.into()),
+            (None, None) => return Err(format!("Could not resolve {}", host).into()),
+        };
+        stream.set_read_timeout(Some(self.read_timeout))?;
         stream.set_write_timeout(Some(Duration::from_secs(10)))?;
 
         // Send HTTP request (GET or POST)
@@ -170,73 +651,104 @@ impl XtreamClient {
             self.server, self.username, self.password
         );
         let response = self.make_request(&url)?;
-        let json: Value = serde_json::from_str(&response)?;
+        let json: Value = parse_json(&response)?;
         Ok(json)
     }
 
     pub fn get_live_categories(&self) -> Result<Vec<Category>, Box<dyn std::error::Error + Send + Sync>> {
         let url = self.api_url("get_live_categories");
         let response = self.make_request(&url)?;
-        let categories: Vec<Category> = serde_json::from_str(&response)?;
+        let categories: Vec<Category> = parse_json(&response)?;
         Ok(categories)
     }
 
     pub fn get_vod_categories(&self) -> Result<Vec<Category>, Box<dyn std::error::Error + Send + Sync>> {
         let url = self.api_url("get_vod_categories");
         let response = self.make_request(&url)?;
-        let categories: Vec<Category> = serde_json::from_str(&response)?;
+        let categories: Vec<Category> = parse_json(&response)?;
         Ok(categories)
     }
 
     pub fn get_series_categories(&self) -> Result<Vec<Category>, Box<dyn std::error::Error + Send + Sync>> {
         let url = self.api_url("get_series_categories");
         let response = self.make_request(&url)?;
-        let categories: Vec<Category> = serde_json::from_str(&response)?;
+        let categories: Vec<Category> = parse_json(&response)?;
         Ok(categories)
     }
 
     pub fn get_live_streams(&self, category_id: &str) -> Result<Vec<Stream>, Box<dyn std::error::Error + Send + Sync>> {
         let url = self.api_url_with_param("get_live_streams", "category_id", category_id);
         let response = self.make_request(&url)?;
-        let streams: Vec<Stream> = serde_json::from_str(&response)?;
+        let streams: Vec<Stream> = parse_json(&response)?;
         Ok(streams)
     }
 
     pub fn get_vod_streams(&self, category_id: &str) -> Result<Vec<Stream>, Box<dyn std::error::Error + Send + Sync>> {
         let url = self.api_url_with_param("get_vod_streams", "category_id", category_id);
         let response = self.make_request(&url)?;
-        let streams: Vec<Stream> = serde_json::from_str(&response)?;
+        let streams: Vec<Stream> = parse_json(&response)?;
         Ok(streams)
     }
 
     pub fn get_series(&self, category_id: &str) -> Result<Vec<SeriesInfo>, Box<dyn std::error::Error + Send + Sync>> {
         let url = self.api_url_with_param("get_series", "category_id", category_id);
         let response = self.make_request(&url)?;
-        let series: Vec<SeriesInfo> = serde_json::from_str(&response)?;
+        let series: Vec<SeriesInfo> = parse_json(&response)?;
         Ok(series)
     }
 
+    /// Count items per category_id using an action that returns every stream when no
+    /// category is given (get_live_streams, get_vod_streams, get_series)
+    pub fn get_category_counts(&self, action: &str) -> Result<HashMap<String, usize>, Box<dyn std::error::Error + Send + Sync>> {
+        let url = self.api_url(action);
+        let response = self.make_request(&url)?;
+        let items: Vec<Value> = parse_json(&response)?;
+        
+        let mut counts = HashMap::new();
+        for item in &items {
+            let category_id = match item.get("category_id") {
+                Some(Value::String(id)) => id.clone(),
+                Some(Value::Number(id)) => id.to_string(),
+                _ => continue,
+            };
+            *counts.entry(category_id).or_insert(0) += 1;
+        }
+        Ok(counts)
+    }
+
     pub fn get_series_info(&self, series_id: i64) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
         let url = self.api_url_with_param("get_series_info", "series_id", &series_id.to_string());
         let response = self.make_request(&url)?;
-        let info: Value = serde_json::from_str(&response)?;
+        let info: Value = parse_json(&response)?;
         Ok(info)
     }
 
-    pub fn get_vod_info(&self, vod_id: i64) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+    pub fn get_vod_info(&self, vod_id: i64) -> Result<VodInfo, Box<dyn std::error::Error + Send + Sync>> {
         let url = self.api_url_with_param("get_vod_info", "vod_id", &vod_id.to_string());
         let response = self.make_request(&url)?;
-        let info: Value = serde_json::from_str(&response)?;
-        Ok(info)
+        let info: Value = parse_json(&response)?;
+        Ok(VodInfo::from_value(&info))
     }
 
     pub fn get_epg(&self, stream_id: i64) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
         let url = self.api_url_with_param("get_short_epg", "stream_id", &stream_id.to_string());
         let response = self.make_request(&url)?;
-        let epg: Value = serde_json::from_str(&response)?;
+        let epg: Value = parse_json(&response)?;
         Ok(epg)
     }
 
+    /// Current and upcoming programs for one live stream, from the provider itself
+    /// (no XMLTV download needed). `limit` caps the number of listings (0 = server default).
+    pub fn get_short_epg(&self, stream_id: i64, limit: u32) -> Result<Vec<Program>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut url = self.api_url_with_param("get_short_epg", "stream_id", &stream_id.to_string());
+        if limit > 0 {
+            url.push_str(&format!("&limit={}", limit));
+        }
+        let response = self.make_request(&url)?;
+        let info: Value = parse_json(&response)?;
+        Ok(parse_short_epg(&info, &stream_id.to_string()))
+    }
+
     pub fn get_xmltv(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
         let url = format!(
             "{}/xmltv.php?username={}&password={}",
@@ -246,6 +758,19 @@ impl XtreamClient {
     }
 }
 
+/// Parse a player_api.php response, explaining the common non-JSON replies
+/// (panels that answer bad credentials or downtime with an HTML page and a 200 status)
+pub fn parse_json<T: serde::de::DeserializeOwned>(body: &str) -> Result<T, Box<dyn std::error::Error + Send + Sync>> {
+    let trimmed = body.trim_start_matches('\u{feff}').trim_start();
+    if trimmed.is_empty() {
+        return Err("Server returned an empty response — credentials or URL may be wrong".into());
+    }
+    if trimmed.starts_with('<') {
+        return Err("Server returned an HTML page — credentials or URL may be wrong".into());
+    }
+    serde_json::from_str(trimmed).map_err(|e| format!("Invalid server response: {}", e).into())
+}
+
 fn parse_http_url(url: &str) -> Result<(String, u16, String), Box<dyn std::error::Error + Send + Sync>> {
     let url = url.strip_prefix("http://")
         .or_else(|| url.strip_prefix("https://"))
@@ -304,3 +829,7 @@ fn decode_chunked(body: &str) -> String {
 
     result
 }
+
+#[cfg(test)]
+#[path = "api_tests.rs"]
+mod tests;
@@ -0,0 +1,255 @@
+//! Tests for Xtream API response handling
+
+use crate::api::*;
+use serde_json::Value;
+
+#[test]
+fn test_parse_json_html_page() {
+    let body = "\r\n<!DOCTYPE html><html><body>Access denied</body></html>";
+    let err = parse_json::<Value>(body).unwrap_err().to_string();
+    assert!(err.contains("HTML page"), "{}", err);
+
+    let err = parse_json::<Vec<Category>>("  ").unwrap_err().to_string();
+    assert!(err.contains("empty response"), "{}", err);
+}
+
+#[test]
+fn test_episode_air_dates_and_sorting() {
+    let info: Value = serde_json::from_str(r#"{"episodes": {"1": [
+        {"id": "10", "title": "Pilot", "episode_num": "1", "info": {"releasedate": "2021-03-04"}},
+        {"id": "11", "title": "Special", "episode_num": 0, "air_date": "2021-02-01 20:00:00"},
+        {"id": "12", "title": "Finale", "episode_num": 9},
+        {"id": "13", "title": "Second", "episode_num": 2, "info": {"air_date": "0000-00-00"}},
+        {"id": "14", "title": "Third", "episode_num": 3, "info": {"air_date": "2021-03-11"}}
+    ]}}"#).unwrap();
+
+    let mut episodes = parse_series_episodes(&info);
+    assert_eq!(episodes[0].episode_num, 1);
+    assert_eq!(episodes[0].air_date.as_deref(), Some("2021-03-04"));
+    assert_eq!(episodes[1].air_date.as_deref(), Some("2021-02-01"));
+    assert_eq!(episodes[3].air_date, None);
+
+    sort_episodes_by_air_date(&mut episodes);
+    let titles: Vec<_> = episodes.iter().map(|e| e.title.as_str()).collect();
+    assert_eq!(titles, ["Special", "Pilot", "Third", "Second", "Finale"]);
+}
+
+#[test]
+fn test_next_episode() {
+    let episode = |id: i64, season: i32| Episode {
+        id,
+        title: format!("Episode {}", id),
+        episode_num: id as i32,
+        season,
+        container_extension: "mp4".to_string(),
+        air_date: None,
+    };
+    let episodes = [episode(1, 1), episode(2, 1), episode(3, 2)];
+    assert_eq!(next_episode(&episodes, 1).map(|ep| ep.id), Some(2));
+    // The season ends here - no rolling into the next one
+    assert!(next_episode(&episodes, 2).is_none());
+    assert!(next_episode(&episodes, 3).is_none());
+    assert!(next_episode(&episodes, 99).is_none());
+}
+
+#[test]
+fn test_host_resolution_override() {
+    let res = HostResolution::new(false, " 10.1.2.3 ", "http://user:pw@Provider.example:8080/get.php?x=1");
+    assert_eq!(res.ip_override, Some(("Provider.example".to_string(), "10.1.2.3".parse().unwrap())));
+    assert_eq!(res.resolve("provider.example", 8080).unwrap(), ["10.1.2.3:8080".parse().unwrap()]);
+
+    // Other hosts resolve normally
+    assert!(res.resolve("127.0.0.1", 80).unwrap().iter().all(|a| a.ip().is_loopback()));
+
+    // Invalid or empty overrides are ignored
+    assert_eq!(HostResolution::new(false, "not an ip", "http://a.example"), HostResolution::default());
+    assert_eq!(HostResolution::new(false, "", "http://a.example"), HostResolution::default());
+
+    let v6 = HostResolution::new(false, "::1", "https://[2001:db8::1]:443/");
+    assert_eq!(v6.ip_override.unwrap().0, "2001:db8::1");
+}
+
+#[test]
+fn test_host_resolution_ipv4_only() {
+    let res = HostResolution { force_ipv4: true, ip_override: None };
+    assert!(res.resolve("::1", 80).is_err());
+    assert_eq!(res.resolve("127.0.0.1", 80).unwrap(), ["127.0.0.1:80".parse().unwrap()]);
+}
+
+#[test]
+fn test_normalize_server_url() {
+    for (input, expected) in [
+        ("provider.tv:8080", "http://provider.tv:8080"),
+        ("  http://provider.tv:8080/  ", "http://provider.tv:8080"),
+        ("http://provider.tv:8080///", "http://provider.tv:8080"),
+        ("http://provider.tv:8080/player_api.php", "http://provider.tv:8080"),
+        ("http://provider.tv:8080/player_api.php?username=a&password=b", "http://provider.tv:8080"),
+        ("http://provider.tv/get.php?username=a&password=b&type=m3u_plus&output=ts", "http://provider.tv"),
+        ("https://provider.tv/panel_api.php/", "https://provider.tv"),
+        ("HTTPS://Provider.tv:443/PLAYER_API.PHP", "https://Provider.tv:443"),
+        // Providers behind a path prefix keep it
+        ("http://provider.tv/iptv/player_api.php", "http://provider.tv/iptv"),
+        ("http://provider.tv/iptv/", "http://provider.tv/iptv"),
+    ] {
+        assert_eq!(normalize_server_url(input), expected, "{}", input);
+    }
+    assert_eq!(normalize_server_url("   "), "");
+}
+
+#[test]
+fn test_stream_container_label() {
+    assert_eq!(stream_container_label("http://s/live/u/p/1.ts", None), Some("TS"));
+    assert_eq!(stream_container_label("http://s/live/u/p/1.m3u8?token=a.b", None), Some("HLS"));
+    assert_eq!(stream_container_label("http://s/movie/u/p/7.MKV", Some("mp4")), Some("MKV"));
+    // Extensionless URLs fall back to the provider's container
+    assert_eq!(stream_container_label("http://s.example/play/42", Some("mp4")), Some("MP4"));
+    assert_eq!(stream_container_label("http://s.example/play/42", None), None);
+    assert_eq!(stream_container_label("http://s.example/stream.php", None), None);
+}
+
+#[test]
+fn test_clean_container_extension() {
+    assert_eq!(clean_container_extension(Some("mkv")), Some("mkv"));
+    assert_eq!(clean_container_extension(Some(" .mp4 ")), Some("mp4"));
+    assert_eq!(clean_container_extension(Some("..ts")), Some("ts"));
+    assert_eq!(clean_container_extension(Some("")), None);
+    assert_eq!(clean_container_extension(Some(" . ")), None);
+    assert_eq!(clean_container_extension(Some("mp4?token=abc")), None);
+    assert_eq!(clean_container_extension(Some("mp4/../x")), None);
+    assert_eq!(clean_container_extension(Some("m p4")), None);
+    assert_eq!(clean_container_extension(None), None);
+}
+
+#[test]
+fn test_logo_from_template() {
+    assert_eq!(
+        logo_from_template("{server}/logo/{stream_id}.png", "http://tv.example:8080/", 42, "News", None),
+        Some("http://tv.example:8080/logo/42.png".to_string())
+    );
+    assert_eq!(
+        logo_from_template("https://cdn.example/{epg_id}/{name}.png", "", 1, "BBC One HD", Some("bbc1.uk")),
+        Some("https://cdn.example/bbc1.uk/BBC%20One%20HD.png".to_string())
+    );
+    assert_eq!(
+        logo_from_template("https://cdn.example/{epg_id}.png", "", 1, "x", Some("Sky Sports & News")),
+        Some("https://cdn.example/Sky%20Sports%20%26%20News.png".to_string())
+    );
+    assert_eq!(logo_from_template("https://cdn.example/{epg_id}.png", "", 1, "x", None), None);
+    assert_eq!(logo_from_template("  ", "http://tv.example", 1, "x", None), None);
+}
+
+#[test]
+fn test_parse_json_valid_and_invalid() {
+    let cats: Vec<Category> = parse_json("\u{feff}[{\"category_id\":\"1\",\"category_name\":\"News\"}]").unwrap();
+    assert_eq!(cats.len(), 1);
+    assert_eq!(cats[0].category_name, "News");
+
+    let err = parse_json::<Value>("{\"user_info\":").unwrap_err().to_string();
+    assert!(err.starts_with("Invalid server response"), "{}", err);
+}
+
+#[test]
+fn test_parse_short_epg() {
+    let info: Value = serde_json::from_str(r#"{"epg_listings": [
+        {"id": "2", "epg_id": "news.uk", "title": "TGF0ZSBOZXdz", "description": "",
+         "start": "2024-01-01 11:00:00", "end": "2024-01-01 12:00:00",
+         "start_timestamp": "1704106800", "stop_timestamp": "1704110400"},
+        {"id": "1", "epg_id": "", "title": "TW9ybmluZyBTaG93IOKAkyBMaXZl", "description": "QnJlYWtmYXN0IG5ld3M=",
+         "start": "2024-01-01 10:00:00", "end": "2024-01-01 11:00:00"},
+        {"id": "3", "title": "", "start_timestamp": "1704110400", "stop_timestamp": "1704114000"}
+    ]}"#).unwrap();
+
+    let programs = parse_short_epg(&info, "42");
+    assert_eq!(programs.len(), 2);
+    assert_eq!(programs[0].title, "Morning Show \u{2013} Live");
+    assert_eq!(programs[0].description.as_deref(), Some("Breakfast news"));
+    assert_eq!((programs[0].start, programs[0].stop), (1704103200, 1704106800));
+    assert_eq!(programs[0].channel_id, "42");
+    assert_eq!(programs[1].title, "Late News");
+    assert_eq!(programs[1].description, None);
+    assert_eq!(programs[1].channel_id, "news.uk");
+
+    assert!(parse_short_epg(&serde_json::json!({"epg_listings": []}), "42").is_empty());
+    assert!(parse_short_epg(&serde_json::json!([]), "42").is_empty());
+}
+
+#[test]
+fn test_vod_info_from_value() {
+    let info: Value = serde_json::from_str(r#"{"info": {
+        "name": "The Movie", "plot": "", "description": "A story.", "cast": " ", "actors": "A. Actor",
+        "director": "", "genre": "Drama", "rating": 7.5, "duration": "01:52:00",
+        "movie_image": "", "cover_big": "http://img/cover.jpg", "backdrop_path": ["", "http://img/bd.jpg"]
+    }, "movie_data": {"stream_id": 5}}"#).unwrap();
+
+    let vod = VodInfo::from_value(&info);
+    assert_eq!(vod.name.as_deref(), Some("The Movie"));
+    assert_eq!(vod.plot.as_deref(), Some("A story."));
+    assert_eq!(vod.cast.as_deref(), Some("A. Actor"));
+    assert_eq!(vod.director, None);
+    assert_eq!(vod.rating.as_deref(), Some("7.5"));
+    assert_eq!(vod.movie_image.as_deref(), Some("http://img/cover.jpg"));
+
+    // Some providers answer unknown ids with an empty list
+    assert_eq!(VodInfo::from_value(&serde_json::json!([])), VodInfo::default());
+}
+
+#[test]
+fn test_stream_archive_fields() {
+    let streams: Vec<Stream> = serde_json::from_str(r#"[
+        {"stream_id": 1, "name": "News", "tv_archive": 1, "tv_archive_duration": "7"},
+        {"stream_id": 2, "name": "Sport", "tv_archive": "0", "tv_archive_duration": 3},
+        {"stream_id": 3, "name": "Movies", "tv_archive": null}
+    ]"#).unwrap();
+    assert_eq!(streams[0].archive_days(), Some(7));
+    assert_eq!(streams[1].archive_days(), None);
+    assert_eq!(streams[2].archive_days(), None);
+
+    assert_eq!(
+        xtream_timeshift_url("http://tv.example:8080/", "u", "p", 42, 1704103200, 1704106800, 0),
+        "http://tv.example:8080/streaming/timeshift.php?username=u&password=p&stream=42&start=2024-01-01:10-00&duration=60"
+    );
+    // Start in the panel's timezone, credentials encoded
+    assert_eq!(
+        xtream_timeshift_url("http://tv.example", "a b", "p&w=1", 42, 1704103200, 1704105000, 3600),
+        "http://tv.example/streaming/timeshift.php?username=a%20b&password=p%26w%3D1&stream=42&start=2024-01-01:11-00&duration=30"
+    );
+}
+
+#[test]
+fn test_server_utc_offset() {
+    // 2024-01-01 10:00:00 UTC
+    assert_eq!(server_utc_offset("2024-01-01 10:00:00", 1704103200), Some(0));
+    assert_eq!(server_utc_offset("2024-01-01 05:00:03", 1704103200), Some(-5 * 3600));
+    assert_eq!(server_utc_offset("2024-01-01 15:29:58", 1704103200), Some(5 * 3600 + 1800));
+    assert_eq!(server_utc_offset("2024-01-03 10:00:00", 1704103200), None);
+    assert_eq!(server_utc_offset("", 1704103200), None);
+}
+
+#[test]
+fn test_parse_series_episodes() {
+    // Seasons come as a map keyed by number - "10" must sort after "2", and
+    // the server's order within a season is kept (episode 2 listed before 1 stays first)
+    let info: Value = serde_json::from_str(r#"{"episodes": {
+        "10": [{"id": "100", "title": "Finale", "episode_num": 1, "container_extension": "mkv"}],
+        "2": [
+            {"id": "21", "title": "Second", "episode_num": 2},
+            {"id": "20", "title": "First", "episode_num": "1"}
+        ],
+        "3": [],
+        "x": [{"id": "99", "title": "Not a season"}],
+        "1": [{"id": "bad", "title": "No numeric id"}, {"id": "1"}]
+    }}"#).unwrap();
+    let episodes = parse_series_episodes(&info);
+    let ids: Vec<(i32, i64)> = episodes.iter().map(|ep| (ep.season, ep.id)).collect();
+    assert_eq!(ids, vec![(1, 1), (2, 21), (2, 20), (10, 100)]);
+    assert_eq!(episodes[0].title, "Unknown");
+    assert_eq!(episodes[0].container_extension, "mp4");
+    assert_eq!(episodes[2].episode_num, 1);
+    assert_eq!(episodes[3].container_extension, "mkv");
+
+    // Series without episodes: no map, an empty map, or a list instead of a map
+    for body in [r#"{"info": {}}"#, r#"{"episodes": {}}"#, r#"{"episodes": []}"#] {
+        let info: Value = serde_json::from_str(body).unwrap();
+        assert!(parse_series_episodes(&info).is_empty(), "{}", body);
+    }
+}
@@ -0,0 +1,61 @@
+//! Optional PIN that locks the whole app at startup
+//!
+//! Only a salted PBKDF2-HMAC-SHA256 hash of the PIN is stored in the config,
+//! formatted as `pbkdf2-sha256$<iterations>$<salt hex>$<hash hex>`.
+
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use std::num::NonZeroU32;
+
+const HASH_PREFIX: &str = "pbkdf2-sha256";
+const ITERATIONS: u32 = 100_000;
+const SALT_LEN: usize = 16;
+const HASH_LEN: usize = 32;
+
+/// Wrong PINs allowed before the app closes
+pub const MAX_PIN_ATTEMPTS: u32 = 5;
+
+/// PINs are 4 to 12 digits
+pub fn is_valid_pin(pin: &str) -> bool {
+    (4..=12).contains(&pin.len()) && pin.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Salted hash of a PIN for storing in the config
+pub fn hash_pin(pin: &str) -> Option<String> {
+    let mut salt = [0u8; SALT_LEN];
+    SystemRandom::new().fill(&mut salt).ok()?;
+    let mut hash = [0u8; HASH_LEN];
+    pbkdf2::derive(pbkdf2::PBKDF2_HMAC_SHA256, NonZeroU32::new(ITERATIONS)?, &salt, pin.as_bytes(), &mut hash);
+    Some(format!("{}${}${}${}", HASH_PREFIX, ITERATIONS, to_hex(&salt), to_hex(&hash)))
+}
+
+/// Check a PIN against a stored hash (constant time). Malformed hashes never match.
+pub fn verify_pin(pin: &str, stored: &str) -> bool {
+    let mut parts = stored.split('$');
+    let (Some(HASH_PREFIX), Some(iterations), Some(salt), Some(hash), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return false;
+    };
+    let (Some(iterations), Some(salt), Some(hash)) =
+        (iterations.parse().ok().and_then(NonZeroU32::new), from_hex(salt), from_hex(hash))
+    else {
+        return false;
+    };
+    pbkdf2::verify(pbkdf2::PBKDF2_HMAC_SHA256, iterations, &salt, pin.as_bytes(), &hash).is_ok()
+}
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub(crate) fn from_hex(s: &str) -> Option<Vec<u8>> {
+    if s.is_empty() || !s.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..s.len()).step_by(2).map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok()).collect()
+}
+
+#[cfg(test)]
+#[path = "app_lock_tests.rs"]
+mod tests;
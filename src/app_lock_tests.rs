@@ -0,0 +1,35 @@
+//! Tests for the app PIN lock
+
+use crate::app_lock::*;
+
+#[test]
+fn test_valid_pins() {
+    assert!(is_valid_pin("1234"));
+    assert!(is_valid_pin("000000000000"));
+    assert!(!is_valid_pin("123"));
+    assert!(!is_valid_pin("1234567890123"));
+    assert!(!is_valid_pin("12a4"));
+    assert!(!is_valid_pin(" 1234"));
+}
+
+#[test]
+fn test_hash_and_verify() {
+    let stored = hash_pin("4821").unwrap();
+    assert!(stored.starts_with("pbkdf2-sha256$"));
+    assert!(!stored.contains("4821"));
+    assert!(verify_pin("4821", &stored));
+    assert!(!verify_pin("4822", &stored));
+    assert!(!verify_pin("", &stored));
+
+    // Salted - the same PIN hashes differently each time
+    let again = hash_pin("4821").unwrap();
+    assert_ne!(stored, again);
+    assert!(verify_pin("4821", &again));
+}
+
+#[test]
+fn test_malformed_hash_never_matches() {
+    for stored in ["", "4821", "pbkdf2-sha256$0$00$00", "pbkdf2-sha256$1000$zz$00", "md5$1000$00$00", "pbkdf2-sha256$1000$00$00$00"] {
+        assert!(!verify_pin("4821", stored), "{}", stored);
+    }
+}
@@ -0,0 +1,45 @@
+//! Command-line launch options
+//!
+//! - `--play <url>`     - play a stream URL right after startup
+//! - `--playlist <url>` - load an M3U/XSPF playlist (URL or file path)
+//!
+//! Both also accept the `--flag=value` form. Anything else is ignored so
+//! normal startup is unaffected.
+
+/// What to do once the window is up
+#[derive(Debug, Clone, PartialEq)]
+pub enum LaunchIntent {
+    Play(String),
+    Playlist(String),
+}
+
+/// Parse launch options from the process arguments (without the program name).
+/// The last recognised option wins.
+pub fn parse_launch_args<I: IntoIterator<Item = String>>(args: I) -> Option<LaunchIntent> {
+    let mut intent = None;
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value.to_string())),
+            _ => (arg.clone(), None),
+        };
+        let make: fn(String) -> LaunchIntent = match flag.as_str() {
+            "--play" => LaunchIntent::Play,
+            "--playlist" => LaunchIntent::Playlist,
+            _ => continue,
+        };
+        if let Some(value) = inline_value.or_else(|| args.next()) {
+            let value = value.trim();
+            if !value.is_empty() {
+                intent = Some(make(value.to_string()));
+            }
+        }
+    }
+
+    intent
+}
+
+#[cfg(test)]
+#[path = "cli_tests.rs"]
+mod tests;
@@ -0,0 +1,35 @@
+//! Tests for command-line launch options
+
+use crate::cli::*;
+
+fn args(list: &[&str]) -> Vec<String> {
+    list.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_no_args() {
+    assert_eq!(parse_launch_args(args(&[])), None);
+    assert_eq!(parse_launch_args(args(&["--verbose", "foo"])), None);
+}
+
+#[test]
+fn test_play_and_playlist() {
+    assert_eq!(
+        parse_launch_args(args(&["--play", "http://example.com/live/1.ts"])),
+        Some(LaunchIntent::Play("http://example.com/live/1.ts".to_string()))
+    );
+    assert_eq!(
+        parse_launch_args(args(&["--playlist=http://example.com/get.php?a=1&type=m3u"])),
+        Some(LaunchIntent::Playlist("http://example.com/get.php?a=1&type=m3u".to_string()))
+    );
+}
+
+#[test]
+fn test_missing_value_and_last_wins() {
+    assert_eq!(parse_launch_args(args(&["--play"])), None);
+    assert_eq!(parse_launch_args(args(&["--play="])), None);
+    assert_eq!(
+        parse_launch_args(args(&["--play", "a", "--playlist", "b"])),
+        Some(LaunchIntent::Playlist("b".to_string()))
+    );
+}
@@ -3,8 +3,10 @@
 #![allow(dead_code)]
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
 pub enum ConnectionQuality {
@@ -58,6 +60,30 @@ impl ListLayout {
     }
 }
 
+/// View mode for Movies and Series (text list or poster wall)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum ViewMode {
+    #[default]
+    List,       // Text list (uses ListLayout columns)
+    Grid,       // Poster thumbnails
+}
+
+impl ViewMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ViewMode::List => "List",
+            ViewMode::Grid => "Posters",
+        }
+    }
+    
+    pub fn icon(&self) -> &'static str {
+        match self {
+            ViewMode::List => "☰",
+            ViewMode::Grid => "🖼",
+        }
+    }
+}
+
 /// Font size options
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
 pub enum FontSize {
@@ -88,6 +114,38 @@ impl FontSize {
     }
 }
 
+/// Whole-interface zoom, on top of the display's own scaling
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum UiScale {
+    Small,      // 85%
+    #[default]
+    Medium,     // 100%
+    Large,      // 125%
+    XLarge,     // 150%
+}
+
+impl UiScale {
+    pub const ALL: [UiScale; 4] = [UiScale::Small, UiScale::Medium, UiScale::Large, UiScale::XLarge];
+    
+    pub fn label(&self) -> &'static str {
+        match self {
+            UiScale::Small => "Small (85%)",
+            UiScale::Medium => "Medium (100%)",
+            UiScale::Large => "Large (125%)",
+            UiScale::XLarge => "XL (150%)",
+        }
+    }
+    
+    pub fn zoom_factor(&self) -> f32 {
+        match self {
+            UiScale::Small => 0.85,
+            UiScale::Medium => 1.0,
+            UiScale::Large => 1.25,
+            UiScale::XLarge => 1.5,
+        }
+    }
+}
+
 /// Sort order for content lists
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
 pub enum SortOrder {
@@ -95,6 +153,7 @@ pub enum SortOrder {
     Default,      // Server order (as received)
     NameAsc,      // A-Z
     NameDesc,     // Z-A
+    MostWatched,  // Play count, highest first
 }
 
 impl SortOrder {
@@ -103,6 +162,7 @@ impl SortOrder {
             SortOrder::Default => "Default",
             SortOrder::NameAsc => "Name A-Z",
             SortOrder::NameDesc => "Name Z-A",
+            SortOrder::MostWatched => "Most watched",
         }
     }
     
@@ -110,7 +170,8 @@ impl SortOrder {
         match self {
             SortOrder::Default => SortOrder::NameAsc,
             SortOrder::NameAsc => SortOrder::NameDesc,
-            SortOrder::NameDesc => SortOrder::Default,
+            SortOrder::NameDesc => SortOrder::MostWatched,
+            SortOrder::MostWatched => SortOrder::Default,
         }
     }
     
@@ -119,10 +180,93 @@ impl SortOrder {
             SortOrder::Default => "⇅",
             SortOrder::NameAsc => "↑",
             SortOrder::NameDesc => "↓",
+            SortOrder::MostWatched => "🔥",
+        }
+    }
+}
+
+/// Play counts are halved once any stream reaches this, so recent habits can overtake old ones
+const PLAY_COUNT_DECAY_AT: u32 = 100;
+
+/// Count a play of `url` for the "Most watched" sort
+pub fn record_play(counts: &mut HashMap<String, u32>, url: &str) {
+    let count = counts.entry(url.to_string()).or_insert(0);
+    *count += 1;
+    if *count >= PLAY_COUNT_DECAY_AT {
+        counts.retain(|_, c| {
+            *c /= 2;
+            *c > 0
+        });
+    }
+}
+
+/// What to do when the app starts
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum StartupAction {
+    OpenPlaylistManager,
+    #[default]
+    AutoLoginFirstEnabled,
+    LoadLastUsed,
+    DoNothing,
+}
+
+impl StartupAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            StartupAction::OpenPlaylistManager => "Open Playlist Manager",
+            StartupAction::AutoLoginFirstEnabled => "Auto-login first enabled",
+            StartupAction::LoadLastUsed => "Load last used playlist",
+            StartupAction::DoNothing => "Do nothing",
+        }
+    }
+    
+    pub fn all() -> [StartupAction; 4] {
+        [
+            StartupAction::OpenPlaylistManager,
+            StartupAction::AutoLoginFirstEnabled,
+            StartupAction::LoadLastUsed,
+            StartupAction::DoNothing,
+        ]
+    }
+}
+
+/// EPG grid tint for programs whose XMLTV <category> contains one of the keywords
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EpgCategoryColor {
+    pub label: String,
+    pub keywords: Vec<String>,
+    pub color: [u8; 3],
+}
+
+impl EpgCategoryColor {
+    fn new(label: &str, keywords: &[&str], color: [u8; 3]) -> Self {
+        Self {
+            label: label.to_string(),
+            keywords: keywords.iter().map(|k| k.to_string()).collect(),
+            color,
         }
     }
 }
 
+pub fn default_epg_category_colors() -> Vec<EpgCategoryColor> {
+    vec![
+        EpgCategoryColor::new("Movies", &["movie", "film"], [95, 55, 110]),
+        EpgCategoryColor::new("Sports", &["sport", "football", "soccer"], [40, 90, 120]),
+        EpgCategoryColor::new("News", &["news", "weather"], [120, 60, 50]),
+        EpgCategoryColor::new("Kids", &["kids", "children", "animation", "cartoon"], [125, 100, 35]),
+        EpgCategoryColor::new("Documentary", &["documentar", "nature", "history", "science"], [45, 100, 85]),
+        EpgCategoryColor::new("Music", &["music", "concert"], [115, 50, 90]),
+    ]
+}
+
+/// First palette entry matching a program category (case-insensitive substring)
+pub fn epg_category_color<'a>(palette: &'a [EpgCategoryColor], category: &str) -> Option<&'a EpgCategoryColor> {
+    let category = category.to_lowercase();
+    palette.iter().find(|entry| {
+        entry.keywords.iter().any(|k| !k.is_empty() && category.contains(&k.to_lowercase()))
+    })
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
     #[serde(default)]
@@ -131,6 +275,27 @@ pub struct AppConfig {
     pub buffer_seconds: u32,
     #[serde(default)]
     pub connection_quality: ConnectionQuality,
+    // Network timeouts for API, playlist and EPG requests
+    #[serde(default = "default_connect_timeout")]
+    pub connect_timeout_secs: u64,
+    #[serde(default = "default_read_timeout")]
+    pub read_timeout_secs: u64,
+    // Provider host resolution: IPv4 only, and an optional fixed IP for the provider's host
+    #[serde(default)]
+    pub force_ipv4: bool,
+    #[serde(default)]
+    pub ip_override: String,
+    // Request live streams as HLS (.m3u8) instead of MPEG-TS
+    #[serde(default)]
+    pub live_hls: bool,
+    // Fallback logo URL for streams without a stream_icon, e.g. "{server}/logo/{stream_id}.png"
+    #[serde(default)]
+    pub logo_url_template: String,
+    // Preferred audio / subtitle languages for external players (e.g. "eng,en"), empty = player default
+    #[serde(default)]
+    pub audio_language: String,
+    #[serde(default)]
+    pub subtitle_language: String,
     #[serde(default = "default_true")]
     pub dark_mode: bool,
     #[serde(default = "default_font_size")]
@@ -143,8 +308,40 @@ pub struct AppConfig {
     pub use_custom_user_agent: bool,
     #[serde(default = "default_true")]
     pub pass_user_agent_to_player: bool,
+    // Separate user agent for stream playback (API requests keep the one above)
+    #[serde(default)]
+    pub separate_player_user_agent: bool,
+    #[serde(default)]
+    pub player_selected_user_agent: usize,
+    #[serde(default)]
+    pub player_custom_user_agent: String,
+    #[serde(default)]
+    pub player_use_custom_user_agent: bool,
     #[serde(default = "default_true")]
     pub single_window_mode: bool,
+    // Keep the main window (and pop-out player) above other windows
+    #[serde(default)]
+    pub always_on_top: bool,
+    // Low data mode: no logos, probing or auto-updates, fewer repaints
+    #[serde(default)]
+    pub low_data_mode: bool,
+    // Muted TS / HLS / MP4 ... label next to streams in lists
+    #[serde(default = "default_true")]
+    pub show_container_labels: bool,
+    // Channel logos (stream_icon) next to channel names, downloaded as rows scroll into view
+    #[serde(default)]
+    pub show_channel_logos: bool,
+    // Ask which variant to play when an .m3u8 stream offers several qualities
+    #[serde(default)]
+    pub choose_hls_quality: bool,
+    // Extra arguments appended to the player command ({url} and {title} are substituted)
+    #[serde(default)]
+    pub player_extra_args: String,
+    // Main window inner size and outer position when the app last closed (None = default layout)
+    #[serde(default)]
+    pub window_size: Option<[f32; 2]>,
+    #[serde(default)]
+    pub window_position: Option<[f32; 2]>,
     // Saved state
     #[serde(default)]
     pub save_state: bool,
@@ -159,12 +356,45 @@ pub struct AppConfig {
     // Hardware acceleration
     #[serde(default = "default_true")]
     pub hw_accel: bool,
+    // Open the internal player in its own OS window instead of an in-app window
+    #[serde(default)]
+    pub internal_player_viewport: bool,
+    // Reconnect attempts when an internal player stream fails (0 = off)
+    #[serde(default = "default_player_retries")]
+    pub internal_player_max_retries: u32,
+    // Start the next episode of the season when one ends in the internal player
+    #[serde(default = "default_true")]
+    pub autoplay_next_episode: bool,
+    // Show a banner when the subscription expires within this many days (0 = off)
+    #[serde(default = "default_expiry_warning_days")]
+    pub expiry_warning_days: u32,
     // Favorites (stored as JSON)
     #[serde(default)]
     pub favorites_json: String,
     // Recently watched (stored as JSON)
     #[serde(default)]
     pub recent_watched_json: String,
+    // EPG program reminders (stored as JSON)
+    #[serde(default)]
+    pub reminders_json: String,
+    // Local display names keyed by stream URL
+    #[serde(default)]
+    pub channel_aliases: HashMap<String, String>,
+    // Streams hidden from lists, keyed by URL (series://<host>/<user>/<id> for series), value is the name
+    #[serde(default)]
+    pub hidden_channels: HashMap<String, String>,
+    // Starred program titles - live channels airing one get a ⭐ badge
+    #[serde(default)]
+    pub favorite_programs: Vec<String>,
+    // Local folder scanned for .m3u/.m3u8/.xspf playlists (empty = none)
+    #[serde(default)]
+    pub playlists_dir: String,
+    // Folder ffmpeg recordings are written to (empty = ask on first recording)
+    #[serde(default)]
+    pub recording_dir: String,
+    // Play counts keyed by stream URL (for the "Most watched" sort)
+    #[serde(default)]
+    pub play_counts: HashMap<String, u32>,
     // EPG settings
     #[serde(default)]
     pub epg_url: String,
@@ -174,8 +404,21 @@ pub struct AppConfig {
     pub epg_time_offset: f32,
     #[serde(default)]
     pub epg_show_actual_time: bool,
+    // Show "→ Next: <title>" after the current program in channel lists
+    #[serde(default)]
+    pub epg_show_next_inline: bool,
     #[serde(default = "default_true")]
     pub epg_load_on_startup: bool,
+    #[serde(default = "default_epg_category_colors")]
+    pub epg_category_colors: Vec<EpgCategoryColor>,
+    // EPG timeline grid span and time axis step
+    #[serde(default = "default_epg_grid_window_hours")]
+    pub epg_grid_window_hours: u32,
+    #[serde(default = "default_epg_grid_step_mins")]
+    pub epg_grid_step_mins: u32,
+    // Scroll through the whole day at a fixed scale instead of a fixed window
+    #[serde(default)]
+    pub epg_grid_full_day: bool,
     // Sort settings
     #[serde(default)]
     pub live_sort_order: SortOrder,
@@ -183,26 +426,76 @@ pub struct AppConfig {
     pub movie_sort_order: SortOrder,
     #[serde(default)]
     pub series_sort_order: SortOrder,
+    // Sort within each favorites group (Default = date added)
+    #[serde(default)]
+    pub favorites_sort_order: SortOrder,
+    // Sort episode lists by air date instead of episode number
+    #[serde(default)]
+    pub episodes_by_air_date: bool,
+    // Each category keeps its own sort order (stored per playlist)
+    #[serde(default)]
+    pub remember_sort_per_category: bool,
     // UI settings
     #[serde(default = "default_channel_name_width")]
     pub channel_name_width: f32,
     #[serde(default)]
     pub list_layout: ListLayout,
     #[serde(default)]
+    pub vod_view_mode: ViewMode,
+    #[serde(default)]
     pub font_size_setting: FontSize,
+    #[serde(default)]
+    pub ui_scale: UiScale,
+    // Startup settings
+    #[serde(default)]
+    pub startup_action: StartupAction,
+    // Maximum simultaneous API/playlist/EPG downloads
+    #[serde(default = "default_max_fetches")]
+    pub max_concurrent_fetches: usize,
+    #[serde(default)]
+    pub last_playlist_name: String,
+    // Opt-in LAN server re-serving the loaded channels as /playlist.m3u
+    #[serde(default)]
+    pub serve_playlist: bool,
+    #[serde(default = "default_serve_playlist_addr")]
+    pub serve_playlist_addr: String,
+    // Salted hash of the startup PIN (empty = no lock)
+    #[serde(default)]
+    pub app_pin_hash: String,
+    // Header of the master passphrase saved Xtream passwords are encrypted with
+    // (empty = passwords are stored as plain text)
+    #[serde(default)]
+    pub password_vault: String,
 }
 
 fn default_buffer() -> u32 { 5 }
+fn default_connect_timeout() -> u64 { 30 }
+fn default_read_timeout() -> u64 { 60 }
 fn default_font_size() -> u32 { 12 }
 fn default_true() -> bool { true }
 fn default_channel_name_width() -> f32 { 200.0 }
 fn default_epg_auto_update() -> u8 { 3 } // 1 Day
+fn default_epg_grid_window_hours() -> u32 { 4 }
+fn default_epg_grid_step_mins() -> u32 { 30 }
+fn default_max_fetches() -> usize { 4 }
+// No host = this machine's LAN address only
+pub fn default_serve_playlist_addr() -> String { ":8899".to_string() }
+fn default_player_retries() -> u32 { 3 }
+fn default_expiry_warning_days() -> u32 { 7 }
 
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
             external_player: String::new(),
             buffer_seconds: 5,
+            connect_timeout_secs: 30, // default_connect_timeout
+            read_timeout_secs: 60, // default_read_timeout
+            force_ipv4: false,
+            ip_override: String::new(),
+            live_hls: false,
+            logo_url_template: String::new(),
+            audio_language: String::new(),
+            subtitle_language: String::new(),
             connection_quality: ConnectionQuality::Normal,
             dark_mode: true,
             font_size: 12,
@@ -210,7 +503,19 @@ impl Default for AppConfig {
             custom_user_agent: String::new(),
             use_custom_user_agent: false,
             pass_user_agent_to_player: true,
+            separate_player_user_agent: false,
+            player_selected_user_agent: 0,
+            player_custom_user_agent: String::new(),
+            player_use_custom_user_agent: false,
             single_window_mode: true,
+            always_on_top: false,
+            low_data_mode: false,
+            show_container_labels: true,
+            show_channel_logos: false,
+            choose_hls_quality: false,
+            player_extra_args: String::new(),
+            window_size: None,
+            window_position: None,
             // Saved state defaults
             save_state: false,
             saved_server: String::new(),
@@ -218,28 +523,81 @@ impl Default for AppConfig {
             saved_password: String::new(),
             auto_login: false,
             hw_accel: true,
+            internal_player_viewport: false,
+            internal_player_max_retries: 3, // default_player_retries
+            autoplay_next_episode: true,
+            expiry_warning_days: 7, // default_expiry_warning_days
             favorites_json: String::new(),
             recent_watched_json: String::new(),
+            reminders_json: String::new(),
+            channel_aliases: HashMap::new(),
+            hidden_channels: HashMap::new(),
+            favorite_programs: Vec::new(),
+            playlists_dir: String::new(),
+            recording_dir: String::new(),
+            play_counts: HashMap::new(),
             epg_url: String::new(),
             epg_auto_update_index: 3, // 1 Day
             epg_time_offset: 0.0,
             epg_show_actual_time: false,
+            epg_show_next_inline: false,
             epg_load_on_startup: true,
+            epg_category_colors: default_epg_category_colors(),
+            epg_grid_window_hours: default_epg_grid_window_hours(),
+            epg_grid_step_mins: default_epg_grid_step_mins(),
+            epg_grid_full_day: false,
             live_sort_order: SortOrder::Default,
             movie_sort_order: SortOrder::Default,
             series_sort_order: SortOrder::Default,
+            favorites_sort_order: SortOrder::Default,
+            episodes_by_air_date: false,
+            remember_sort_per_category: false,
             channel_name_width: 200.0,
             list_layout: ListLayout::Single,
+            vod_view_mode: ViewMode::List,
             font_size_setting: FontSize::Default,
+            ui_scale: UiScale::Medium,
+            startup_action: StartupAction::AutoLoginFirstEnabled,
+            max_concurrent_fetches: 4, // default_max_fetches
+            last_playlist_name: String::new(),
+            serve_playlist: false,
+            serve_playlist_addr: default_serve_playlist_addr(),
+            app_pin_hash: String::new(),
+            password_vault: String::new(),
         }
     }
 }
 
+/// Replace a file's contents without ever leaving it half-written: the data goes to
+/// a temporary file next to it, which is flushed to disk and renamed over the original
+pub fn write_atomic(path: &Path, content: &str) -> std::io::Result<()> {
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+    
+    let result = fs::File::create(&tmp_path)
+        .and_then(|mut file| {
+            file.write_all(content.as_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&tmp_path, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result
+}
+
+/// Directory holding config.json, playlists.json and the address book
+pub fn config_dir() -> PathBuf {
+    let mut path = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
+    path.push("xtreme_iptv");
+    fs::create_dir_all(&path).ok();
+    path
+}
+
 impl AppConfig {
     fn config_path() -> PathBuf {
-        let mut path = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
-        path.push("xtreme_iptv");
-        fs::create_dir_all(&path).ok();
+        let mut path = config_dir();
         path.push("config.json");
         path
     }
@@ -260,9 +618,19 @@ impl AppConfig {
 
     pub fn save(&self) {
         let path = Self::config_path();
-        if let Ok(content) = serde_json::to_string_pretty(self) {
-            let _ = fs::write(path, content);
+        if let Some(content) = self.to_json() {
+            let _ = write_atomic(&path, &content);
+        }
+    }
+    
+    /// JSON as written to disk - the saved login password is encrypted once a
+    /// master passphrase is set and unlocked, like the playlist entries
+    pub fn to_json(&self) -> Option<String> {
+        if !crate::vault::is_unlocked() {
+            return serde_json::to_string_pretty(self).ok();
         }
+        let sealed = Self { saved_password: crate::vault::seal(&self.saved_password), ..self.clone() };
+        serde_json::to_string_pretty(&sealed).ok()
     }
 }
 
@@ -338,6 +706,32 @@ pub struct PlaylistEntry {
     pub buffer_seconds: u32,
     #[serde(default)]
     pub connection_quality: ConnectionQuality,
+    #[serde(default)]
+    pub player_extra_args: String,
+    // Play this provider's streams in the built-in player
+    #[serde(default)]
+    pub use_internal_player: bool,
+    // Network timeouts
+    #[serde(default = "default_connect_timeout")]
+    pub connect_timeout_secs: u64,
+    #[serde(default = "default_read_timeout")]
+    pub read_timeout_secs: u64,
+    // Provider host resolution: IPv4 only, and an optional fixed IP for the provider's host
+    #[serde(default)]
+    pub force_ipv4: bool,
+    #[serde(default)]
+    pub ip_override: String,
+    // Request live streams as HLS (.m3u8) instead of MPEG-TS
+    #[serde(default)]
+    pub live_hls: bool,
+    // Fallback logo URL for streams without a stream_icon, e.g. "{server}/logo/{stream_id}.png"
+    #[serde(default)]
+    pub logo_url_template: String,
+    // Preferred audio / subtitle languages for external players (e.g. "eng,en"), empty = player default
+    #[serde(default)]
+    pub audio_language: String,
+    #[serde(default)]
+    pub subtitle_language: String,
     // User agent settings
     #[serde(default)]
     pub selected_user_agent: usize,
@@ -347,6 +741,20 @@ pub struct PlaylistEntry {
     pub use_custom_user_agent: bool,
     #[serde(default = "default_true")]
     pub pass_user_agent_to_player: bool,
+    #[serde(default)]
+    pub separate_player_user_agent: bool,
+    #[serde(default)]
+    pub player_selected_user_agent: usize,
+    #[serde(default)]
+    pub player_custom_user_agent: String,
+    #[serde(default)]
+    pub player_use_custom_user_agent: bool,
+    // Pinned categories ("{stream_type}:{category_id}")
+    #[serde(default)]
+    pub favorite_categories: Vec<String>,
+    // Sort order chosen inside a category ("{stream_type}:{category_id}")
+    #[serde(default)]
+    pub category_sort_orders: HashMap<String, SortOrder>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -359,9 +767,22 @@ pub enum PlaylistType {
     M3U {
         url: String,
     },
+    /// Stalker / Ministra (MAG) portal, authenticated by MAC address
+    Stalker {
+        portal: String,
+        mac: String,
+    },
 }
 
 impl PlaylistEntry {
+    /// Create a new Stalker portal entry with default settings
+    pub fn new_stalker(name: String, portal: String, mac: String) -> Self {
+        Self {
+            entry_type: PlaylistType::Stalker { portal, mac },
+            ..Self::new_m3u(name, String::new())
+        }
+    }
+    
     /// Create a new M3U playlist entry with default settings
     pub fn new_m3u(name: String, url: String) -> Self {
         let now = std::time::SystemTime::now()
@@ -385,10 +806,26 @@ impl PlaylistEntry {
             external_player: String::new(),
             buffer_seconds: 5, // default_buffer
             connection_quality: ConnectionQuality::Normal,
+            player_extra_args: String::new(),
+            use_internal_player: false,
+            connect_timeout_secs: 30, // default_connect_timeout
+            read_timeout_secs: 60, // default_read_timeout
+            force_ipv4: false,
+            ip_override: String::new(),
+            live_hls: false,
+            logo_url_template: String::new(),
+            audio_language: String::new(),
+            subtitle_language: String::new(),
             selected_user_agent: 0,
             custom_user_agent: String::new(),
             use_custom_user_agent: false,
             pass_user_agent_to_player: true,
+            separate_player_user_agent: false,
+            player_selected_user_agent: 0,
+            player_custom_user_agent: String::new(),
+            player_use_custom_user_agent: false,
+            favorite_categories: Vec::new(),
+            category_sort_orders: HashMap::new(),
         }
     }
     
@@ -415,18 +852,82 @@ impl PlaylistEntry {
             external_player: String::new(),
             buffer_seconds: 5, // default_buffer
             connection_quality: ConnectionQuality::Normal,
+            player_extra_args: String::new(),
+            use_internal_player: false,
+            connect_timeout_secs: 30, // default_connect_timeout
+            read_timeout_secs: 60, // default_read_timeout
+            force_ipv4: false,
+            ip_override: String::new(),
+            live_hls: false,
+            logo_url_template: String::new(),
+            audio_language: String::new(),
+            subtitle_language: String::new(),
             selected_user_agent: 0,
             custom_user_agent: String::new(),
             use_custom_user_agent: false,
             pass_user_agent_to_player: true,
+            separate_player_user_agent: false,
+            player_selected_user_agent: 0,
+            player_custom_user_agent: String::new(),
+            player_use_custom_user_agent: false,
+            favorite_categories: Vec::new(),
+            category_sort_orders: HashMap::new(),
+        }
+    }
+    
+    /// Clone this entry as a new "(copy)" with fresh timestamps and auto-login off
+    pub fn duplicate(&self) -> Self {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        
+        Self {
+            name: format!("{} (copy)", self.name),
+            saved_at: now,
+            auto_login: false,
+            last_updated: now,
+            epg_last_updated: 0,
+            ..self.clone()
         }
     }
+    
+    /// Copy player, buffer, connection and user agent settings from another
+    /// entry, leaving name, credentials and EPG settings untouched
+    pub fn copy_settings_from(&mut self, other: &PlaylistEntry) {
+        self.external_player = other.external_player.clone();
+        self.player_extra_args = other.player_extra_args.clone();
+        self.use_internal_player = other.use_internal_player;
+        self.buffer_seconds = other.buffer_seconds;
+        self.connection_quality = other.connection_quality;
+        self.connect_timeout_secs = other.connect_timeout_secs;
+        self.read_timeout_secs = other.read_timeout_secs;
+        self.force_ipv4 = other.force_ipv4;
+        self.ip_override = other.ip_override.clone();
+        self.live_hls = other.live_hls;
+        self.logo_url_template = other.logo_url_template.clone();
+        self.audio_language = other.audio_language.clone();
+        self.subtitle_language = other.subtitle_language.clone();
+        self.selected_user_agent = other.selected_user_agent;
+        self.custom_user_agent = other.custom_user_agent.clone();
+        self.use_custom_user_agent = other.use_custom_user_agent;
+        self.pass_user_agent_to_player = other.pass_user_agent_to_player;
+        self.separate_player_user_agent = other.separate_player_user_agent;
+        self.player_selected_user_agent = other.player_selected_user_agent;
+        self.player_custom_user_agent = other.player_custom_user_agent.clone();
+        self.player_use_custom_user_agent = other.player_use_custom_user_agent;
+    }
+}
+
+/// Records the running instance's loopback port (see `single_instance`)
+pub fn instance_lock_path() -> PathBuf {
+    let mut path = config_dir();
+    path.push("instance.lock");
+    path
 }
 
 fn playlist_manager_path() -> PathBuf {
-    let mut path = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
-    path.push("xtreme_iptv");
-    fs::create_dir_all(&path).ok();
+    let mut path = config_dir();
     path.push("playlists.json");
     path
 }
@@ -445,17 +946,48 @@ pub fn load_playlist_entries() -> Vec<PlaylistEntry> {
     Vec::new()
 }
 
+/// Whether any saved Xtream password is encrypted with a master passphrase
+pub fn has_sealed_passwords(entries: &[PlaylistEntry]) -> bool {
+    entries.iter().any(|e| matches!(&e.entry_type, PlaylistType::Xtream { password, .. } if crate::vault::is_sealed(password)))
+}
+
+/// Decrypt saved Xtream passwords with the unlocked master passphrase
+pub fn open_sealed_passwords(entries: &mut [PlaylistEntry]) {
+    for entry in entries {
+        if let PlaylistType::Xtream { password, .. } = &mut entry.entry_type {
+            if let Some(plain) = crate::vault::open(password) {
+                *password = plain;
+            }
+        }
+    }
+}
+
 pub fn save_playlist_entries(entries: &[PlaylistEntry]) {
-    let path = playlist_manager_path();
-    if let Ok(content) = serde_json::to_string_pretty(entries) {
-        let _ = fs::write(path, content);
+    if let Some(content) = playlist_entries_json(entries) {
+        let _ = write_atomic(&playlist_manager_path(), &content);
     }
 }
 
+/// JSON of the playlist entries as written to disk
+pub fn playlist_entries_json(entries: &[PlaylistEntry]) -> Option<String> {
+    // Passwords are encrypted at rest once a master passphrase is set and unlocked
+    let sealed: Vec<PlaylistEntry>;
+    let entries = if crate::vault::is_unlocked() {
+        sealed = entries.iter().cloned().map(|mut e| {
+            if let PlaylistType::Xtream { password, .. } = &mut e.entry_type {
+                *password = crate::vault::seal(password);
+            }
+            e
+        }).collect();
+        &sealed
+    } else {
+        entries
+    };
+    serde_json::to_string_pretty(entries).ok()
+}
+
 fn address_book_path() -> PathBuf {
-    let mut path = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
-    path.push("xtreme_iptv");
-    fs::create_dir_all(&path).ok();
+    let mut path = config_dir();
     path.push("address_book.json");
     path
 }
@@ -477,26 +1009,34 @@ pub fn load_address_book() -> Vec<SavedCredential> {
 pub fn save_address_book(book: &[SavedCredential]) {
     let path = address_book_path();
     if let Ok(content) = serde_json::to_string_pretty(book) {
-        let _ = fs::write(path, content);
+        let _ = write_atomic(&path, &content);
     }
 }
 
-fn epg_cache_path(server: &str, username: &str) -> PathBuf {
-    let mut path = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
-    path.push("xtreme_iptv");
+/// Directory holding cached EPG data, one file per provider login
+pub fn epg_cache_dir() -> PathBuf {
+    let mut path = config_dir();
     path.push("epg_cache");
-    fs::create_dir_all(&path).ok();
-    // Create filename from server+username hash to avoid path issues
-    let key = format!("{}_{}", username, server.replace(['/', ':', '.'], "_"));
-    path.push(format!("{}.json", key));
+    path
+}
+
+/// File name stem for per-provider caches (server characters that upset paths are replaced)
+fn provider_cache_key(server: &str, username: &str) -> String {
+    format!("{}_{}", username, server.replace(['/', ':', '.'], "_"))
+}
+
+fn epg_cache_path(server: &str, username: &str) -> PathBuf {
+    let mut path = epg_cache_dir();
+    path.push(format!("{}.json", provider_cache_key(server, username)));
     path
 }
 
 pub fn save_epg_cache<T: serde::Serialize>(server: &str, username: &str, data: &T) {
     let path = epg_cache_path(server, username);
+    fs::create_dir_all(epg_cache_dir()).ok();
     // Use non-pretty JSON for smaller file size (EPG can be large)
     if let Ok(content) = serde_json::to_string(data) {
-        let _ = fs::write(path, content);
+        let _ = write_atomic(&path, &content);
     }
 }
 
@@ -511,3 +1051,48 @@ pub fn load_epg_cache<T: serde::de::DeserializeOwned>(server: &str, username: &s
     }
     None
 }
+
+/// Location of the on-disk EPG cache for a provider
+pub fn epg_cache_file(server: &str, username: &str) -> PathBuf {
+    epg_cache_path(server, username)
+}
+
+/// Age of the on-disk EPG cache in seconds (None if there is no cache file)
+pub fn epg_cache_age_secs(server: &str, username: &str) -> Option<u64> {
+    let modified = fs::metadata(epg_cache_path(server, username)).ok()?.modified().ok()?;
+    Some(modified.elapsed().map(|d| d.as_secs()).unwrap_or(0))
+}
+
+/// Delete the on-disk EPG cache for a provider - returns true if a file was removed
+pub fn clear_epg_cache(server: &str, username: &str) -> bool {
+    fs::remove_file(epg_cache_path(server, username)).is_ok()
+}
+
+fn category_cache_path(server: &str, username: &str) -> PathBuf {
+    let mut path = config_dir();
+    path.push("category_cache");
+    fs::create_dir_all(&path).ok();
+    path.push(format!("{}.json", provider_cache_key(server, username)));
+    path
+}
+
+/// Remember a provider's category lists so the next login can show them straight away
+pub fn save_category_cache<T: serde::Serialize>(server: &str, username: &str, data: &T) {
+    if let Ok(content) = serde_json::to_string(data) {
+        let _ = write_atomic(&category_cache_path(server, username), &content);
+    }
+}
+
+pub fn load_category_cache<T: serde::de::DeserializeOwned>(server: &str, username: &str) -> Option<T> {
+    let content = fs::read_to_string(category_cache_path(server, username)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Delete the cached category lists for a provider
+pub fn clear_category_cache(server: &str, username: &str) -> bool {
+    fs::remove_file(category_cache_path(server, username)).is_ok()
+}
+
+#[cfg(test)]
+#[path = "config_tests.rs"]
+mod tests;
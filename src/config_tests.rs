@@ -0,0 +1,37 @@
+//! Tests for config persistence
+
+use crate::config::*;
+use crate::vault;
+
+#[test]
+fn test_no_plaintext_password_once_encrypted() {
+    let password = "hunter2-secret";
+    let config = AppConfig { saved_password: password.to_string(), ..AppConfig::default() };
+    let entry = PlaylistEntry {
+        entry_type: PlaylistType::Xtream {
+            server: "http://tv.example".to_string(),
+            username: "user".to_string(),
+            password: password.to_string(),
+        },
+        ..PlaylistEntry::new_m3u("Provider".to_string(), String::new())
+    };
+
+    let (_, key) = vault::create("correct horse").unwrap();
+    vault::set_key(Some(key));
+    let config_json = config.to_json().unwrap();
+    let entries_json = playlist_entries_json(std::slice::from_ref(&entry)).unwrap();
+    vault::set_key(None);
+
+    assert!(!config_json.contains(password));
+    assert!(!entries_json.contains(password));
+
+    // Both decrypt back with the key
+    let loaded: AppConfig = serde_json::from_str(&config_json).unwrap();
+    assert_eq!(vault::open_with(&key, &loaded.saved_password).as_deref(), Some(password));
+    let mut entries: Vec<PlaylistEntry> = serde_json::from_str(&entries_json).unwrap();
+    assert!(has_sealed_passwords(&entries));
+    vault::set_key(Some(key));
+    open_sealed_passwords(&mut entries);
+    vault::set_key(None);
+    assert!(matches!(&entries[0].entry_type, PlaylistType::Xtream { password: p, .. } if p == password));
+}
@@ -0,0 +1,102 @@
+//! Tests for EPG time helpers
+
+use crate::epg::*;
+use chrono::{FixedOffset, MappedLocalTime, NaiveDate, NaiveDateTime, Offset, TimeZone};
+
+/// Time zone with a single clock change at `at` (UTC), offsets in seconds east
+#[derive(Clone, Copy, Debug)]
+struct ClockChange {
+    at: i64,
+    before: i32,
+    after: i32,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct ClockChangeOffset {
+    zone: ClockChange,
+    secs: i32,
+}
+
+impl Offset for ClockChangeOffset {
+    fn fix(&self) -> FixedOffset {
+        FixedOffset::east_opt(self.secs).unwrap()
+    }
+}
+
+impl ClockChange {
+    fn offset(&self, secs: i32) -> ClockChangeOffset {
+        ClockChangeOffset { zone: *self, secs }
+    }
+}
+
+impl TimeZone for ClockChange {
+    type Offset = ClockChangeOffset;
+
+    fn from_offset(offset: &ClockChangeOffset) -> Self {
+        offset.zone
+    }
+
+    fn offset_from_local_date(&self, local: &NaiveDate) -> MappedLocalTime<ClockChangeOffset> {
+        self.offset_from_local_datetime(&local.and_hms_opt(0, 0, 0).unwrap())
+    }
+
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> MappedLocalTime<ClockChangeOffset> {
+        // Each offset that maps this wall-clock time back onto its own side of the change
+        let fits = |secs: i32| {
+            let utc = local.and_utc().timestamp() - secs as i64;
+            (utc < self.at) == (secs == self.before)
+        };
+        match (fits(self.before), fits(self.after)) {
+            (true, true) => MappedLocalTime::Ambiguous(self.offset(self.before), self.offset(self.after)),
+            (true, false) => MappedLocalTime::Single(self.offset(self.before)),
+            (false, true) => MappedLocalTime::Single(self.offset(self.after)),
+            (false, false) => MappedLocalTime::None,
+        }
+    }
+
+    fn offset_from_utc_date(&self, utc: &NaiveDate) -> ClockChangeOffset {
+        self.offset_from_utc_datetime(&utc.and_hms_opt(0, 0, 0).unwrap())
+    }
+
+    fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> ClockChangeOffset {
+        if utc.and_utc().timestamp() < self.at {
+            self.offset(self.before)
+        } else {
+            self.offset(self.after)
+        }
+    }
+}
+
+#[test]
+fn test_day_start_fixed_offset() {
+    let ist = FixedOffset::east_opt(5 * 3600 + 1800).unwrap();
+    // 2024-10-27 20:00 UTC is already 01:30 on the 28th in India
+    assert_eq!(day_start_in(&ist, 1730059200), 1730053800);
+    assert_eq!(day_start_in(&ist, 1730053800), 1730053800);
+}
+
+#[test]
+fn test_day_start_dst_fallback() {
+    // UK clocks go back at 02:00 BST on 2024-10-27 - that day is 25 hours long
+    let london = ClockChange { at: 1729990800, before: 3600, after: 0 };
+    let start = day_start_in(&london, 1730030400);
+    assert_eq!(start, 1729983600); // 2024-10-26 23:00 UTC
+    assert_eq!(day_start_in(&london, start + 30 * 3600) - start, 25 * 3600);
+    // The repeated hour still belongs to the same day
+    assert_eq!(day_start_in(&london, 1729990800 + 1800), start);
+}
+
+#[test]
+fn test_day_start_skipped_midnight() {
+    // Clocks jumping from 00:00 straight to 01:00 start the day at 01:00
+    let zone = ClockChange { at: 1725768000, before: -4 * 3600, after: -3 * 3600 };
+    assert_eq!(day_start_in(&zone, 1725807600), 1725768000);
+}
+
+#[test]
+fn test_local_day_start() {
+    let ts = 1730030400;
+    let start = local_day_start(ts);
+    assert!(start <= ts && ts - start < 26 * 3600);
+    assert_eq!(local_day_start(start), start);
+}
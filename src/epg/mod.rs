@@ -7,10 +7,13 @@ mod parser;
 // Re-export public types
 pub use parser::{
     EpgData,
+    EpgSourceStats,
     Program,
     EpgDownloader,
+    EpgParser,
     DownloadConfig,
     ProgressCallback,
+    split_epg_sources,
 };
 
 /// EPG auto-update interval settings
@@ -101,6 +104,38 @@ pub fn format_time(ts: i64) -> String {
     }
 }
 
+/// Parse a local "HH:MM" (on the same day as `reference`) or "YYYY-MM-DD HH:MM" into a Unix timestamp
+pub fn parse_local_time(input: &str, reference: i64) -> Option<i64> {
+    use chrono::{Local, NaiveDateTime, NaiveTime, TimeZone};
+    
+    let input = input.trim();
+    let naive = match NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M") {
+        Ok(dt) => dt,
+        Err(_) => {
+            let time = NaiveTime::parse_from_str(input, "%H:%M").ok()?;
+            Local.timestamp_opt(reference, 0).single()?.date_naive().and_time(time)
+        }
+    };
+    Local.from_local_datetime(&naive).earliest().map(|dt| dt.timestamp())
+}
+
+/// Local midnight starting the day that contains `ts`
+pub fn local_day_start(ts: i64) -> i64 {
+    day_start_in(&chrono::Local, ts)
+}
+
+/// Midnight in `tz` starting the day that contains `ts`. Where a clock change
+/// skips midnight the day starts at the first hour that exists.
+pub fn day_start_in<Tz: chrono::TimeZone>(tz: &Tz, ts: i64) -> i64 {
+    tz.timestamp_opt(ts, 0).single()
+        .and_then(|dt| {
+            let date = dt.date_naive();
+            (0..24).find_map(|hour| tz.from_local_datetime(&date.and_hms_opt(hour, 0, 0)?).earliest())
+        })
+        .map(|dt| dt.timestamp())
+        .unwrap_or(ts - ts.rem_euclid(86400))
+}
+
 /// Format a Unix timestamp as local datetime YYYY-MM-DD HH:MM
 pub fn format_datetime(ts: i64) -> String {
     use chrono::{TimeZone, Local};
@@ -144,3 +179,7 @@ pub fn format_datetime(ts: i64) -> String {
         format!("{:04}-{:02}-{:02} {:02}:{:02}", year, month, day, hours, mins)
     }
 }
+
+#[cfg(test)]
+#[path = "epg_tests.rs"]
+mod tests;
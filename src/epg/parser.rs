@@ -19,7 +19,7 @@ pub struct Program {
     pub start: i64,
     /// End time as Unix timestamp
     pub stop: i64,
-    /// Category/genre (optional)
+    /// Category/genre (optional) - several `<category>` tags are joined with ", "
     pub category: Option<String>,
     /// Episode info (optional) e.g., "S01E05"
     pub episode: Option<String>,
@@ -49,6 +49,18 @@ pub struct EpgData {
     pub parse_errors: Vec<String>,
     /// Total count of parse errors
     pub parse_error_count: usize,
+    /// What each XMLTV source contributed when several were merged
+    #[serde(default)]
+    pub sources: Vec<EpgSourceStats>,
+}
+
+/// Channel and programme counts for one XMLTV source (or why it failed)
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct EpgSourceStats {
+    pub source: String,
+    pub channels: usize,
+    pub programs: usize,
+    pub error: Option<String>,
 }
 
 impl EpgData {
@@ -95,6 +107,102 @@ impl EpgData {
     pub fn program_count(&self) -> usize {
         self.programs.values().map(|v| v.len()).sum()
     }
+
+    /// Combine another source's guide into this one. When both have programmes
+    /// for a channel id, the source with more of them wins (channel info included).
+    pub fn merge(&mut self, other: EpgData) {
+        let EpgData { channels, programs, parse_errors, parse_error_count, sources } = other;
+        let mut replaced = std::collections::HashSet::new();
+        for (id, programs) in programs {
+            let current = self.programs.get(&id).map_or(0, Vec::len);
+            if programs.len() > current {
+                self.programs.insert(id.clone(), programs);
+                replaced.insert(id);
+            }
+        }
+        for (id, channel) in channels {
+            if replaced.contains(&id) || !self.channels.contains_key(&id) {
+                self.channels.insert(id, channel);
+            }
+        }
+        let room = 50usize.saturating_sub(self.parse_errors.len());
+        self.parse_errors.extend(parse_errors.into_iter().take(room));
+        self.parse_error_count += parse_error_count;
+        self.sources.extend(sources);
+    }
+
+    /// Serialize the given channels and their programmes as an XMLTV document.
+    /// Ids without guide data are skipped; times are written in UTC.
+    pub fn to_xmltv(&self, channel_ids: &[String]) -> String {
+        use std::fmt::Write;
+
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!DOCTYPE tv SYSTEM \"xmltv.dtd\">\n<tv generator-info-name=\"Xtreme IPTV\">\n");
+        let ids: Vec<&String> = channel_ids.iter()
+            .filter(|id| self.channels.contains_key(*id) || self.programs.contains_key(*id))
+            .collect();
+
+        for id in &ids {
+            let _ = writeln!(out, "  <channel id=\"{}\">", escape_xml(id));
+            let name = self.channels.get(*id).map(|c| c.name.as_str()).filter(|n| !n.is_empty()).unwrap_or(id);
+            let _ = writeln!(out, "    <display-name>{}</display-name>", escape_xml(name));
+            if let Some(icon) = self.channels.get(*id).and_then(|c| c.icon.as_deref()) {
+                let _ = writeln!(out, "    <icon src=\"{}\"/>", escape_xml(icon));
+            }
+            out.push_str("  </channel>\n");
+        }
+
+        for id in &ids {
+            for prog in self.programs.get(*id).map(Vec::as_slice).unwrap_or_default() {
+                let _ = writeln!(
+                    out,
+                    "  <programme start=\"{}\" stop=\"{}\" channel=\"{}\">",
+                    format_xmltv_time(prog.start), format_xmltv_time(prog.stop), escape_xml(id)
+                );
+                let _ = writeln!(out, "    <title>{}</title>", escape_xml(&prog.title));
+                if let Some(desc) = &prog.description {
+                    let _ = writeln!(out, "    <desc>{}</desc>", escape_xml(desc));
+                }
+                for category in prog.category.iter().flat_map(|c| c.split(", ")) {
+                    let _ = writeln!(out, "    <category>{}</category>", escape_xml(category));
+                }
+                if let Some(episode) = &prog.episode {
+                    match episode_to_xmltv_ns(episode) {
+                        Some(ns) => { let _ = writeln!(out, "    <episode-num system=\"xmltv_ns\">{}</episode-num>", ns); }
+                        None => { let _ = writeln!(out, "    <episode-num system=\"onscreen\">{}</episode-num>", escape_xml(episode)); }
+                    }
+                }
+                if let Some(icon) = &prog.icon {
+                    let _ = writeln!(out, "    <icon src=\"{}\"/>", escape_xml(icon));
+                }
+                out.push_str("  </programme>\n");
+            }
+        }
+
+        out.push_str("</tv>\n");
+        out
+    }
+}
+
+/// Unix timestamp -> XMLTV time ("20240115120000 +0000")
+fn format_xmltv_time(ts: i64) -> String {
+    chrono::DateTime::from_timestamp(ts, 0)
+        .map(|dt| dt.format("%Y%m%d%H%M%S +0000").to_string())
+        .unwrap_or_default()
+}
+
+/// "S01E05" (as produced by `format_episode`) -> xmltv_ns "0.4."
+fn episode_to_xmltv_ns(episode: &str) -> Option<String> {
+    let (season, ep) = episode.strip_prefix('S')?.split_once('E')?;
+    let season: u32 = season.parse().ok()?;
+    let ep: u32 = ep.parse().ok()?;
+    (season > 0 && ep > 0).then(|| format!("{}.{}.", season - 1, ep - 1))
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
 }
 
 fn current_timestamp() -> i64 {
@@ -128,8 +236,83 @@ impl EpgParser {
 
     /// Parse EPG from a reader - streaming, handles large files
     pub fn parse_reader<R: BufRead>(reader: R) -> Result<EpgData, String> {
+        let mut epg = Self::parse_events(reader, 0);
+
+        // Sort programs by start time, dropping duplicates and overlaps
+        for programs in epg.programs.values_mut() {
+            normalize_programs(programs);
+        }
+
+        // Return what we got, even if partially parsed
+        Ok(epg)
+    }
+
+    /// Parse on worker threads. The stream is cut into chunks of roughly `chunk_size`
+    /// bytes right after a `</programme>` or `</channel>`, each chunk is wrapped in
+    /// `<tv>`...`</tv>` so it parses as its own document, and the results are merged
+    /// back in file order - the outcome matches `parse_reader`.
+    pub fn parse_reader_parallel<R: BufRead>(mut reader: R, threads: usize, chunk_size: usize) -> Result<EpgData, String> {
+        use std::sync::{mpsc, Mutex};
+
+        let threads = threads.max(1);
+        // Bounded so the splitter can't run far ahead of the parsers (memory)
+        let (chunk_tx, chunk_rx) = mpsc::sync_channel::<(usize, u64, Vec<u8>)>(threads * 2);
+        let chunk_rx = Mutex::new(chunk_rx);
+
+        let mut parsed = std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads).map(|_| scope.spawn(|| {
+                let mut parsed = Vec::new();
+                loop {
+                    let next = chunk_rx.lock().map_err(|_| ()).and_then(|rx| rx.recv().map_err(|_| ()));
+                    let Ok((idx, offset, chunk)) = next else { break };
+                    parsed.push((idx, Self::parse_events(chunk.as_slice(), offset)));
+                }
+                parsed
+            })).collect();
+
+            let mut idx = 0;
+            let split = split_into_chunks(&mut reader, chunk_size, |chunk, offset| {
+                let sent = chunk_tx.send((idx, offset, chunk)).is_ok();
+                idx += 1;
+                sent
+            });
+            drop(chunk_tx);
+
+            let mut parsed = Vec::new();
+            for worker in workers {
+                parsed.extend(worker.join().map_err(|_| "EPG parser thread panicked".to_string())?);
+            }
+            split.map(|_| parsed)
+        })?;
+
+        // Merge in file order: later channel entries win, errors keep the first 50
+        parsed.sort_by_key(|(idx, _)| *idx);
+        let mut epg = EpgData::new();
+        for (_, chunk) in parsed {
+            for channel in chunk.channels.into_values() {
+                merge_channel(&mut epg.channels, channel);
+            }
+            for (channel_id, programs) in chunk.programs {
+                epg.programs.entry(channel_id).or_default().extend(programs);
+            }
+            let room = 50usize.saturating_sub(epg.parse_errors.len());
+            epg.parse_errors.extend(chunk.parse_errors.into_iter().take(room));
+            epg.parse_error_count += chunk.parse_error_count;
+        }
+
+        for programs in epg.programs.values_mut() {
+            normalize_programs(programs);
+        }
+        Ok(epg)
+    }
+
+    /// Stream XMLTV events into unsorted EPG data, counting (and skipping past) errors.
+    /// `base_offset` is added to error positions when parsing a chunk of a larger file.
+    fn parse_events<R: BufRead>(reader: R, base_offset: u64) -> EpgData {
         let mut xml_reader = Reader::from_reader(reader);
-        xml_reader.config_mut().trim_text(true);
+        // Text is trimmed per field on </title> etc. - trimming each event would eat
+        // the spaces around entities ("News &amp; Weather")
+        xml_reader.config_mut().trim_text(false);
 
         let mut epg = EpgData::new();
         let mut buf = Vec::with_capacity(8192);
@@ -142,7 +325,7 @@ impl EpgParser {
         let mut errors: Vec<String> = Vec::new();
 
         loop {
-            let position = xml_reader.buffer_position();
+            let position = base_offset + xml_reader.buffer_position();
             match xml_reader.read_event_into(&mut buf) {
                 Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
                     let name = e.name();
@@ -235,6 +418,20 @@ impl EpgParser {
                         _ => {}
                     }
                 }
+                Ok(Event::GeneralRef(e)) => {
+                    // Entities (&amp;, &#233; ...) arrive separately from the surrounding text
+                    let entity = format!("&{};", String::from_utf8_lossy(e.as_ref()));
+                    if matches!(
+                        state,
+                        ParserState::Title
+                            | ParserState::Desc
+                            | ParserState::Category
+                            | ParserState::DisplayName
+                            | ParserState::EpisodeNum
+                    ) {
+                        text_buf.push_str(&decode_xml_entities(&entity));
+                    }
+                }
                 Ok(Event::End(ref e)) => {
                     let name = e.name();
                     let name_bytes = name.as_ref();
@@ -243,7 +440,7 @@ impl EpgParser {
                         b"channel" => {
                             if let Some(channel) = current_channel.take() {
                                 if !channel.id.is_empty() {
-                                    epg.channels.insert(channel.id.clone(), channel);
+                                    merge_channel(&mut epg.channels, channel);
                                 }
                             }
                             state = ParserState::Root;
@@ -276,9 +473,16 @@ impl EpgParser {
                         }
                         b"category" => {
                             if let Some(ref mut prog) = current_program {
-                                let cat = text_buf.trim().to_string();
+                                let cat = text_buf.trim();
                                 if !cat.is_empty() {
-                                    prog.category = Some(cat);
+                                    match prog.category {
+                                        Some(ref mut joined) if !joined.split(", ").any(|c| c == cat) => {
+                                            joined.push_str(", ");
+                                            joined.push_str(cat);
+                                        }
+                                        Some(_) => {}
+                                        None => prog.category = Some(cat.to_string()),
+                                    }
                                 }
                             }
                             state = ParserState::Programme;
@@ -332,28 +536,142 @@ impl EpgParser {
             buf.clear();
         }
 
-        // Sort programs by start time
-        for programs in epg.programs.values_mut() {
-            programs.sort_by_key(|p| p.start);
-        }
-
         // Store errors in epg for reporting
         epg.parse_errors = errors;
         epg.parse_error_count = error_count;
-
-        // Return what we got, even if partially parsed
-        Ok(epg)
+        epg
     }
 
-    /// Parse EPG from file path - streams from disk
+    /// Parse EPG from file path - streams from disk, gzip files are decompressed on the fly
+    /// and xz files are unpacked to a temporary file next to it first
     pub fn parse_file(path: &str) -> Result<EpgData, String> {
         let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
-        let reader = std::io::BufReader::with_capacity(64 * 1024, file);
+        let mut reader = std::io::BufReader::with_capacity(64 * 1024, file);
+        
+        // Detect compression by magic bytes rather than extension
+        let is_xz = reader.fill_buf().map(|buf| buf.starts_with(&XZ_MAGIC)).unwrap_or(false);
+        if is_xz {
+            let unpacked_path = format!("{}.unpacked.xml", path);
+            let unpacked = Self::unpack_xz(&mut reader, &unpacked_path)
+                .and_then(|_| Self::parse_file(&unpacked_path));
+            let _ = std::fs::remove_file(&unpacked_path);
+            return unpacked;
+        }
+
+        let threads = std::thread::available_parallelism()
+            .map(|n| n.get().min(PARSE_MAX_THREADS))
+            .unwrap_or(1);
+        let parse = |reader: Box<dyn BufRead>| {
+            if threads > 1 {
+                Self::parse_reader_parallel(reader, threads, PARSE_CHUNK_SIZE)
+            } else {
+                Self::parse_reader(reader)
+            }
+        };
+
+        let is_gzip = reader.fill_buf().map(|buf| buf.starts_with(&[0x1f, 0x8b])).unwrap_or(false);
+        if is_gzip {
+            let decoder = flate2::bufread::GzDecoder::new(reader);
+            let reader = std::io::BufReader::with_capacity(64 * 1024, decoder);
+            return parse(Box::new(SanitizingBufReader::new(reader)));
+        }
+
         let sanitizing_reader = SanitizingBufReader::new(reader);
-        Self::parse_reader(sanitizing_reader)
+        parse(Box::new(sanitizing_reader))
+    }
+    
+    /// Decompress an xz stream to `output_path` (the xz decoder can't be read from incrementally)
+    fn unpack_xz<R: BufRead>(reader: &mut R, output_path: &str) -> Result<(), String> {
+        use std::io::Write;
+        let file = std::fs::File::create(output_path).map_err(|e| format!("Failed to create {}: {}", output_path, e))?;
+        let mut writer = std::io::BufWriter::with_capacity(256 * 1024, file);
+        lzma_rs::xz_decompress(reader, &mut writer).map_err(|e| format!("Invalid xz file: {}", e))?;
+        writer.flush().map_err(|e| e.to_string())
     }
 }
 
+/// Start of every .xz file
+const XZ_MAGIC: [u8; 6] = [0xfd, b'7', b'z', b'X', b'Z', 0x00];
+
+/// Parser threads used for EPG files (decompression and splitting take one more)
+const PARSE_MAX_THREADS: usize = 8;
+/// Approximate bytes per chunk handed to a parser thread
+const PARSE_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+/// Cut an XMLTV stream into self-contained `<tv>` documents for `parse_reader_parallel`.
+/// `emit` gets each chunk and its offset in the stream, and returns false to stop.
+fn split_into_chunks<R: BufRead>(
+    reader: &mut R,
+    chunk_size: usize,
+    mut emit: impl FnMut(Vec<u8>, u64) -> bool,
+) -> Result<(), String> {
+    let mut buf: Vec<u8> = Vec::with_capacity(chunk_size + 64 * 1024);
+    let mut target = chunk_size;
+    let mut offset: u64 = 0;
+    let mut first = true;
+
+    loop {
+        // Fill to the target size (or EOF)
+        let mut eof = false;
+        while buf.len() < target {
+            let available = reader.fill_buf().map_err(|e| e.to_string())?;
+            if available.is_empty() {
+                eof = true;
+                break;
+            }
+            let n = available.len();
+            buf.extend_from_slice(available);
+            reader.consume(n);
+        }
+
+        if eof {
+            emit(wrap_chunk(&buf, first, true), chunk_offset(offset, first));
+            return Ok(());
+        }
+
+        // Cut after the last complete top-level element - without one, read on
+        let cut = rfind_bytes(&buf, b"</programme>").map(|p| p + b"</programme>".len())
+            .or_else(|| rfind_bytes(&buf, b"</channel>").map(|p| p + b"</channel>".len()));
+        let Some(cut) = cut else {
+            target = buf.len() + 64 * 1024;
+            continue;
+        };
+
+        if !emit(wrap_chunk(&buf[..cut], first, false), chunk_offset(offset, first)) {
+            return Ok(());
+        }
+        buf.drain(..cut);
+        offset += cut as u64;
+        first = false;
+        target = chunk_size;
+    }
+}
+
+const CHUNK_WRAP_START: &[u8] = b"<tv>";
+const CHUNK_WRAP_END: &[u8] = b"</tv>";
+
+/// Every piece after the first re-opens `<tv>`, every piece before the last closes it
+fn wrap_chunk(piece: &[u8], first: bool, last: bool) -> Vec<u8> {
+    let mut chunk = Vec::with_capacity(piece.len() + CHUNK_WRAP_START.len() + CHUNK_WRAP_END.len());
+    if !first {
+        chunk.extend_from_slice(CHUNK_WRAP_START);
+    }
+    chunk.extend_from_slice(piece);
+    if !last {
+        chunk.extend_from_slice(CHUNK_WRAP_END);
+    }
+    chunk
+}
+
+/// Offset to report errors against, so positions stay relative to the original stream
+fn chunk_offset(offset: u64, first: bool) -> u64 {
+    if first { offset } else { offset.saturating_sub(CHUNK_WRAP_START.len() as u64) }
+}
+
+fn rfind_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).rposition(|w| w == needle)
+}
+
 /// BufReader wrapper that filters out illegal XML 1.0 characters on read
 /// Legal XML 1.0: #x9 | #xA | #xD | [#x20-#xD7FF] | [#xE000-#xFFFD] | [#x10000-#x10FFFF]
 /// Also handles some common XML issues like invalid UTF-8 and unescaped ampersands
@@ -510,6 +828,46 @@ fn decode_xml_entities(s: &str) -> String {
     result
 }
 
+/// Add a channel, merging with an earlier `<channel>` block of the same id:
+/// later non-empty name/icon win, missing ones don't erase what's known
+fn merge_channel(channels: &mut HashMap<String, EpgChannel>, channel: EpgChannel) {
+    match channels.get_mut(&channel.id) {
+        Some(existing) => {
+            if !channel.name.is_empty() {
+                existing.name = channel.name;
+            }
+            if channel.icon.is_some() {
+                existing.icon = channel.icon;
+            }
+        }
+        None => {
+            channels.insert(channel.id.clone(), channel);
+        }
+    }
+}
+
+/// Sort a channel's programs (in file order) by start time and make them non-overlapping,
+/// which the `partition_point` lookups rely on. Of several programs starting at the same
+/// time the later-declared one is kept; a program running into the next one is cut short.
+fn normalize_programs(programs: &mut Vec<Program>) {
+    // Stable sort keeps declaration order among equal start times
+    programs.sort_by_key(|p| p.start);
+    let mut kept: Vec<Program> = Vec::with_capacity(programs.len());
+    for program in programs.drain(..) {
+        match kept.last_mut() {
+            Some(last) if last.start == program.start => *last = program,
+            Some(last) => {
+                if last.stop > program.start {
+                    last.stop = program.start;
+                }
+                kept.push(program);
+            }
+            None => kept.push(program),
+        }
+    }
+    *programs = kept;
+}
+
 /// Get attribute value from XML element
 fn get_attribute(e: &quick_xml::events::BytesStart, name: &[u8]) -> Option<String> {
     for attr in e.attributes().flatten() {
@@ -627,6 +985,8 @@ pub struct DownloadConfig {
     pub chunk_size: usize,
     /// User agent string
     pub user_agent: String,
+    /// Where partial downloads are kept so they can resume after a restart (temp dir if None)
+    pub partial_dir: Option<std::path::PathBuf>,
 }
 
 impl Default for DownloadConfig {
@@ -638,10 +998,45 @@ impl Default for DownloadConfig {
             read_timeout_secs: 120,
             chunk_size: 64 * 1024, // 64KB chunks
             user_agent: "XtremeIPTV/1.0".to_string(),
+            partial_dir: None,
         }
     }
 }
 
+/// Saved next to a partial download so a later run can check the file is unchanged
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PartialDownload {
+    pub url: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl PartialDownload {
+    fn from_headers(url: &str, headers: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            etag: header_value(headers, "etag").map(str::to_string),
+            last_modified: header_value(headers, "last-modified").map(str::to_string),
+        }
+    }
+
+    /// If-Range value: the server only honours the Range header while this still matches
+    pub fn validator(&self) -> Option<&str> {
+        // Weak ETags aren't allowed in If-Range
+        self.etag.as_deref()
+            .filter(|e| !e.starts_with("W/"))
+            .or(self.last_modified.as_deref())
+    }
+}
+
+/// Case-insensitive lookup of an HTTP response header
+fn header_value<'a>(headers: &'a str, name: &str) -> Option<&'a str> {
+    headers.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        key.trim().eq_ignore_ascii_case(name).then(|| value.trim()).filter(|v| !v.is_empty())
+    })
+}
+
 /// Download progress callback
 pub type ProgressCallback = Box<dyn Fn(u64, Option<u64>) + Send>;
 
@@ -661,16 +1056,31 @@ impl EpgDownloader {
 
         let mut attempts = 0;
         let mut downloaded_bytes: u64 = 0;
+        let meta_path = format!("{}.meta", output_path);
 
-        // Check if partial file exists for resume
+        // A partial file left by an earlier run is only resumed for the same URL and
+        // when there's an ETag/Last-Modified to confirm the server copy hasn't changed
+        let mut validator = None;
         if let Ok(metadata) = std::fs::metadata(output_path) {
-            downloaded_bytes = metadata.len();
+            let saved = std::fs::read_to_string(&meta_path).ok()
+                .and_then(|s| serde_json::from_str::<PartialDownload>(&s).ok())
+                .filter(|m| m.url == url);
+            match saved.as_ref().and_then(|m| m.validator()) {
+                Some(v) => {
+                    downloaded_bytes = metadata.len();
+                    validator = Some(v.to_string());
+                }
+                None => {
+                    let _ = std::fs::remove_file(output_path);
+                    let _ = std::fs::remove_file(&meta_path);
+                }
+            }
         }
 
         loop {
             attempts += 1;
             
-            match Self::try_download(url, output_path, downloaded_bytes, config, &progress) {
+            match Self::try_download(url, output_path, downloaded_bytes, &mut validator, config, &progress) {
                 Ok(total) => {
                     if let Some(ref cb) = progress {
                         cb(total, Some(total));
@@ -682,9 +1092,14 @@ impl EpgDownloader {
                         return Err(format!("Download failed after {} attempts: {}", attempts, e));
                     }
                     
-                    // Update downloaded bytes for resume
-                    if let Ok(metadata) = std::fs::metadata(output_path) {
-                        downloaded_bytes = metadata.len();
+                    // Update downloaded bytes for resume - a partial that was thrown
+                    // away (e.g. after a 416) means the next try starts from scratch
+                    match std::fs::metadata(output_path) {
+                        Ok(metadata) => downloaded_bytes = metadata.len(),
+                        Err(_) => {
+                            downloaded_bytes = 0;
+                            validator = None;
+                        }
                     }
                     
                     // Wait before retry
@@ -698,6 +1113,7 @@ impl EpgDownloader {
         url: &str,
         output_path: &str,
         resume_from: u64,
+        validator: &mut Option<String>,
         config: &DownloadConfig,
         progress: &Option<ProgressCallback>,
     ) -> Result<u64, String> {
@@ -719,11 +1135,12 @@ impl EpgDownloader {
             .set_write_timeout(Some(Duration::from_secs(config.connect_timeout_secs)))
             .ok();
 
-        // Build request with Range header for resume
-        let range_header = if resume_from > 0 {
-            format!("Range: bytes={}-\r\n", resume_from)
-        } else {
-            String::new()
+        // Build request with Range header for resume (If-Range makes the server send
+        // the whole file instead if it changed since the partial was saved)
+        let range_header = match (resume_from > 0, validator.as_deref()) {
+            (true, Some(v)) => format!("Range: bytes={}-\r\nIf-Range: {}\r\n", resume_from, v),
+            (true, None) => format!("Range: bytes={}-\r\n", resume_from),
+            _ => String::new(),
         };
 
         let request = format!(
@@ -765,10 +1182,23 @@ impl EpgDownloader {
         let supports_resume = status_line.contains("206");
         let is_ok = status_line.contains("200") || status_line.contains("206");
         
+        if status_line.contains("416") {
+            // Partial is already complete or longer than the server copy - start over
+            let _ = std::fs::remove_file(output_path);
+            let _ = std::fs::remove_file(format!("{}.meta", output_path));
+            return Err(format!("Cannot resume download: {}", status_line));
+        }
         if !is_ok {
             return Err(format!("HTTP error: {}", status_line));
         }
 
+        // Remember the validators before any body arrives, so an interrupted run can resume
+        let partial = PartialDownload::from_headers(url, &headers);
+        if let Ok(json) = serde_json::to_string(&partial) {
+            let _ = std::fs::write(format!("{}.meta", output_path), json);
+        }
+        *validator = partial.validator().map(str::to_string);
+
         // Parse Content-Length if present
         let content_length: Option<u64> = headers
             .lines()
@@ -884,8 +1314,10 @@ impl EpgDownloader {
         config: &DownloadConfig,
         progress: Option<ProgressCallback>,
     ) -> Result<EpgData, String> {
-        // Create temp file
-        let temp_path = std::env::temp_dir().join("xtreme_iptv_epg.xml");
+        // Partial file named after the URL, kept on failure so the next load can resume
+        let dir = config.partial_dir.clone().unwrap_or_else(std::env::temp_dir);
+        let _ = std::fs::create_dir_all(&dir);
+        let temp_path = dir.join(partial_file_name(url));
         let temp_path_str = temp_path.to_string_lossy().to_string();
 
         // Download with retry
@@ -896,11 +1328,50 @@ impl EpgDownloader {
 
         // Clean up temp file
         let _ = std::fs::remove_file(&temp_path);
+        let _ = std::fs::remove_file(format!("{}.meta", temp_path_str));
 
         result
     }
 }
 
+/// Individual XMLTV sources from the EPG field: one per line, or comma-separated
+/// where the comma starts another URL or absolute path (URLs can contain commas)
+pub fn split_epg_sources(input: &str) -> Vec<String> {
+    let mut sources: Vec<String> = Vec::new();
+    for line in input.lines() {
+        let mut rest = line;
+        loop {
+            let split = rest.match_indices(',')
+                .map(|(i, _)| i)
+                .find(|&i| starts_epg_source(rest[i + 1..].trim_start()));
+            let source = rest[..split.unwrap_or(rest.len())].trim();
+            if !source.is_empty() && !sources.iter().any(|s| s == source) {
+                sources.push(source.to_string());
+            }
+            match split {
+                Some(i) => rest = &rest[i + 1..],
+                None => break,
+            }
+        }
+    }
+    sources
+}
+
+/// Whether text after a comma begins a new EPG source rather than continuing a URL
+fn starts_epg_source(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    let drive_path = bytes.len() >= 3 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && matches!(bytes[2], b'\\' | b'/');
+    ["http://", "https://", "file://", "/"].iter().any(|prefix| s.starts_with(prefix)) || drive_path
+}
+
+/// Stable per-URL file name for a partial EPG download (FNV-1a hash of the URL)
+fn partial_file_name(url: &str) -> String {
+    let hash = url.bytes().fold(0xcbf29ce484222325u64, |h, b| {
+        (h ^ b as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("epg_download_{:016x}.xml.part", hash)
+}
+
 /// Parse URL into (host, port, path)
 fn parse_url(url: &str) -> Result<(String, u16, String), String> {
     let url = url.trim();
@@ -941,6 +1412,40 @@ mod tests {
         assert_eq!(ts2 - ts1, 3600);
     }
 
+    #[test]
+    fn test_to_xmltv_round_trip() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<tv>
+  <channel id="bbc1"><display-name>BBC One</display-name><icon src="http://example.com/bbc1.png"/></channel>
+  <channel id="itv"><display-name>ITV</display-name></channel>
+  <programme start="20240115120000 +0000" stop="20240115130000 +0000" channel="bbc1">
+    <title>News &amp; Weather</title>
+    <desc>Daily "news"</desc>
+    <category>News</category>
+    <category>Weather</category>
+    <category>News</category>
+    <episode-num system="xmltv_ns">0.4.</episode-num>
+  </programme>
+  <programme start="20240115120000 +0000" stop="20240115130000 +0000" channel="itv">
+    <title>Quiz</title>
+  </programme>
+</tv>"#;
+        let epg = EpgParser::parse(xml).unwrap();
+        let exported = epg.to_xmltv(&["bbc1".to_string(), "missing".to_string()]);
+        assert!(!exported.contains("itv") && !exported.contains("missing"));
+
+        let reparsed = EpgParser::parse(&exported).unwrap();
+        assert_eq!(reparsed.channels.len(), 1);
+        assert_eq!(reparsed.channels["bbc1"].icon.as_deref(), Some("http://example.com/bbc1.png"));
+        let prog = &reparsed.programs["bbc1"][0];
+        let original = &epg.programs["bbc1"][0];
+        assert_eq!((prog.start, prog.stop), (original.start, original.stop));
+        assert_eq!(prog.title, "News & Weather");
+        assert_eq!(prog.description.as_deref(), Some("Daily \"news\""));
+        assert_eq!(prog.category.as_deref(), Some("News, Weather"));
+        assert_eq!(prog.episode.as_deref(), Some("S01E05"));
+    }
+
     #[test]
     fn test_format_episode() {
         assert_eq!(format_episode("0.4."), "S01E05");
@@ -970,6 +1475,45 @@ mod tests {
         assert_eq!(epg.programs.get("bbc1").unwrap()[0].title, "News at Noon");
     }
 
+    #[test]
+    fn test_duplicate_channels_are_merged() {
+        let xml = r#"<tv>
+  <channel id="bbc1"><display-name>BBC One</display-name></channel>
+  <channel id="bbc1"><icon src="http://example.com/bbc1.png"/></channel>
+  <channel id="bbc1"><display-name>BBC One HD</display-name></channel>
+</tv>"#;
+        for epg in [EpgParser::parse(xml).unwrap(), EpgParser::parse_reader_parallel(xml.as_bytes(), 2, 64).unwrap()] {
+            assert_eq!(epg.channels.len(), 1);
+            let channel = &epg.channels["bbc1"];
+            assert_eq!(channel.name, "BBC One HD");
+            assert_eq!(channel.icon.as_deref(), Some("http://example.com/bbc1.png"));
+        }
+    }
+
+    #[test]
+    fn test_overlapping_programs_are_resolved() {
+        // Out of order, a duplicate start (later entry wins) and a show running into the next
+        let xml = r#"<tv>
+  <programme start="20240115140000 +0000" stop="20240115150000 +0000" channel="ch1"><title>Late</title></programme>
+  <programme start="20240115120000 +0000" stop="20240115133000 +0000" channel="ch1"><title>Long</title></programme>
+  <programme start="20240115130000 +0000" stop="20240115140000 +0000" channel="ch1"><title>Old listing</title></programme>
+  <programme start="20240115130000 +0000" stop="20240115140000 +0000" channel="ch1"><title>New listing</title></programme>
+</tv>"#;
+        for epg in [EpgParser::parse(xml).unwrap(), EpgParser::parse_reader_parallel(xml.as_bytes(), 2, 64).unwrap()] {
+            let programs = &epg.programs["ch1"];
+            let titles: Vec<_> = programs.iter().map(|p| p.title.as_str()).collect();
+            assert_eq!(titles, ["Long", "New listing", "Late"]);
+            // "Long" is cut off where "New listing" starts
+            assert_eq!(programs[0].stop, programs[1].start);
+            assert!(programs.windows(2).all(|w| w[0].stop <= w[1].start));
+
+            // The lookup used by the guide lands on the right show
+            let at = programs[1].start + 60;
+            let idx = programs.partition_point(|p| p.stop <= at);
+            assert_eq!(programs[idx].title, "New listing");
+        }
+    }
+
     #[test]
     fn test_program_count() {
         let xml = r#"<tv>
@@ -982,6 +1526,210 @@ mod tests {
         assert_eq!(epg.program_count(), 3);
     }
 
+    #[test]
+    fn test_partial_download_validator() {
+        let headers = "HTTP/1.1 200 OK\r\nETag: \"abc123\"\r\nlast-modified: Mon, 15 Jan 2024 12:00:00 GMT\r\n\r\n";
+        let partial = PartialDownload::from_headers("http://example.com/epg.xml", headers);
+        assert_eq!(partial.etag.as_deref(), Some("\"abc123\""));
+        assert_eq!(partial.validator(), Some("\"abc123\""));
+
+        // Weak ETags fall back to Last-Modified, and nothing to validate means no resume
+        let weak = "ETag: W/\"abc\"\r\nLast-Modified: Mon, 15 Jan 2024 12:00:00 GMT\r\n";
+        let partial = PartialDownload::from_headers("http://example.com/epg.xml", weak);
+        assert_eq!(partial.validator(), Some("Mon, 15 Jan 2024 12:00:00 GMT"));
+        assert_eq!(PartialDownload::from_headers("u", "Content-Length: 5\r\n").validator(), None);
+
+        assert_eq!(partial_file_name("http://a/epg.xml"), partial_file_name("http://a/epg.xml"));
+        assert_ne!(partial_file_name("http://a/epg.xml"), partial_file_name("http://b/epg.xml"));
+    }
+
+    #[test]
+    fn test_download_restarts_after_416() {
+        use std::io::{Read, Write};
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/epg.xml", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for response in [
+                "HTTP/1.1 416 Range Not Satisfiable\r\nContent-Length: 0\r\n\r\n",
+                "HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nfresh",
+            ] {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut buf = [0u8; 4096];
+                let n = stream.read(&mut buf).unwrap();
+                requests.push(String::from_utf8_lossy(&buf[..n]).to_string());
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+
+        // A partial with a validator from an earlier run, so the first try resumes
+        let output = std::env::temp_dir().join(format!("xtreme_iptv_416_test_{}.part", std::process::id()));
+        let output = output.to_string_lossy().to_string();
+        let meta = format!("{}.meta", output);
+        std::fs::write(&output, "stale partial").unwrap();
+        let partial = PartialDownload { url: url.clone(), etag: Some("\"v1\"".to_string()), ..Default::default() };
+        std::fs::write(&meta, serde_json::to_string(&partial).unwrap()).unwrap();
+
+        let config = DownloadConfig { max_retries: 3, retry_delay_ms: 0, ..Default::default() };
+        let result = EpgDownloader::download_to_file(&url, &output, &config, None);
+        let requests = server.join().unwrap();
+        let content = std::fs::read_to_string(&output);
+        let _ = std::fs::remove_file(&output);
+        let _ = std::fs::remove_file(&meta);
+
+        assert!(result.is_ok(), "{:?}", result);
+        assert_eq!(content.unwrap(), "fresh");
+        assert!(requests[0].contains("Range: bytes=13-"), "{}", requests[0]);
+        assert!(!requests[1].contains("Range:"), "{}", requests[1]);
+    }
+
+    /// XMLTV with `channels` channels of `per_channel` half-hour programmes each
+    fn synthetic_xmltv(channels: usize, per_channel: usize) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<tv generator-info-name=\"test\">\n");
+        for c in 0..channels {
+            xml.push_str(&format!("  <channel id=\"ch{c}\"><display-name>Channel {c}</display-name></channel>\n"));
+        }
+        for p in 0..per_channel {
+            for c in 0..channels {
+                let start = 20240115000000u64 + (p as u64 / 2) * 10000 + (p as u64 % 2) * 3000;
+                xml.push_str(&format!(
+                    "  <programme start=\"{start} +0000\" stop=\"{start} +0000\" channel=\"ch{c}\">\
+<title>Show {p} on {c}</title><desc>Episode {p} &amp; more</desc><category>News</category></programme>\n"
+                ));
+            }
+        }
+        xml.push_str("</tv>\n");
+        xml
+    }
+
+    impl EpgParser {
+        fn parse_file_sequential(path: &str) -> Result<EpgData, String> {
+            let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+            Self::parse_reader(SanitizingBufReader::new(std::io::BufReader::new(file)))
+        }
+    }
+
+    fn assert_same_epg(a: &EpgData, b: &EpgData) {
+        assert_eq!(a.channels.len(), b.channels.len());
+        assert_eq!(a.program_count(), b.program_count());
+        assert_eq!(a.parse_error_count, b.parse_error_count);
+        for (id, programs) in &a.programs {
+            let titles: Vec<_> = programs.iter().map(|p| (&p.title, p.start)).collect();
+            let other: Vec<_> = b.programs[id].iter().map(|p| (&p.title, p.start)).collect();
+            assert_eq!(titles, other, "channel {}", id);
+        }
+    }
+
+    #[test]
+    fn test_parallel_parse_matches_sequential() {
+        let mut xml = synthetic_xmltv(7, 40);
+        // One broken programme in the middle - error recovery must count it the same way
+        let broken = "  <programme start=\"20240115120000 +0000\" stop=\"20240115130000 +0000\" channel=\"ch1\"><title>Broken</titel></programme>\n";
+        let mid = xml.len() / 2;
+        let insert_at = mid + xml[mid..].find("</programme>\n").unwrap() + "</programme>\n".len();
+        xml.insert_str(insert_at, broken);
+
+        let sequential = EpgParser::parse(&xml).unwrap();
+        assert_eq!(sequential.channels.len(), 7);
+        assert!(sequential.parse_error_count > 0);
+
+        for (threads, chunk_size) in [(1, 1 << 20), (3, 2048), (4, 300)] {
+            let parallel = EpgParser::parse_reader_parallel(xml.as_bytes(), threads, chunk_size).unwrap();
+            assert_same_epg(&sequential, &parallel);
+        }
+    }
+
+    #[test]
+    fn test_merge_sources() {
+        let a = EpgParser::parse_reader(r#"<tv>
+            <channel id="news"><display-name>News A</display-name></channel>
+            <channel id="film"><display-name>Film A</display-name></channel>
+            <programme channel="news" start="20240101100000 +0000" stop="20240101110000 +0000"><title>A1</title></programme>
+            <programme channel="film" start="20240101100000 +0000" stop="20240101110000 +0000"><title>F1</title></programme>
+            <programme channel="film" start="20240101110000 +0000" stop="20240101120000 +0000"><title>F2</title></programme>
+        </tv>"#.as_bytes()).unwrap();
+        let b = EpgParser::parse_reader(r#"<tv>
+            <channel id="news"><display-name>News B</display-name></channel>
+            <channel id="film"><display-name>Film B</display-name></channel>
+            <channel id="kids"><display-name>Kids B</display-name></channel>
+            <programme channel="news" start="20240101100000 +0000" stop="20240101103000 +0000"><title>B1</title></programme>
+            <programme channel="news" start="20240101103000 +0000" stop="20240101110000 +0000"><title>B2</title></programme>
+            <programme channel="film" start="20240101100000 +0000" stop="20240101120000 +0000"><title>G1</title></programme>
+            <programme channel="kids" start="20240101100000 +0000" stop="20240101120000 +0000"><title>K1</title></programme>
+        </tv>"#.as_bytes()).unwrap();
+
+        let mut merged = a;
+        merged.merge(b);
+        // More programmes wins, channel info follows the winner
+        assert_eq!(merged.programs["news"].iter().map(|p| p.title.as_str()).collect::<Vec<_>>(), ["B1", "B2"]);
+        assert_eq!(merged.channels["news"].name, "News B");
+        assert_eq!(merged.programs["film"].len(), 2);
+        assert_eq!(merged.channels["film"].name, "Film A");
+        assert_eq!(merged.channels["kids"].name, "Kids B");
+        assert_eq!(merged.program_count(), 5);
+
+        assert_eq!(
+            split_epg_sources(" http://a/epg.xml\nhttp://b/epg.xml.gz, /tmp/c.xml\n\nhttp://a/epg.xml "),
+            ["http://a/epg.xml", "http://b/epg.xml.gz", "/tmp/c.xml"]
+        );
+        // Commas inside a URL don't split it
+        assert_eq!(
+            split_epg_sources("http://a/xmltv.php?ids=1,2,3,https://b/epg.xml,C:\\EPG\\c.xml"),
+            ["http://a/xmltv.php?ids=1,2,3", "https://b/epg.xml", "C:\\EPG\\c.xml"]
+        );
+    }
+
+    #[test]
+    fn test_parse_compressed_files() {
+        use std::io::Write;
+        let xml = synthetic_xmltv(3, 4);
+        let dir = std::env::temp_dir();
+        let plain = EpgParser::parse_reader(xml.as_bytes()).unwrap();
+
+        let mut gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        gz.write_all(xml.as_bytes()).unwrap();
+        let mut xz = Vec::new();
+        lzma_rs::xz_compress(&mut xml.as_bytes(), &mut xz).unwrap();
+
+        for (name, bytes) in [("gz", gz.finish().unwrap()), ("xz", xz)] {
+            // No extension - the format is detected from the content
+            let path = dir.join(format!("xtreme_iptv_epg_test_{}_{}", name, std::process::id()));
+            std::fs::write(&path, bytes).unwrap();
+            let path = path.to_string_lossy().to_string();
+            let parsed = EpgParser::parse_file(&path).unwrap();
+            let _ = std::fs::remove_file(&path);
+            assert_same_epg(&plain, &parsed);
+            assert!(!std::path::Path::new(&format!("{}.unpacked.xml", path)).exists());
+        }
+    }
+
+    /// cargo test --release bench_parallel_epg_parse -- --ignored --nocapture
+    #[test]
+    #[ignore]
+    fn bench_parallel_epg_parse() {
+        let xml = synthetic_xmltv(500, 1000); // ~100MB
+        let path = std::env::temp_dir().join("xtreme_iptv_epg_bench.xml");
+        std::fs::write(&path, &xml).unwrap();
+        let path = path.to_string_lossy().to_string();
+
+        let started = std::time::Instant::now();
+        let sequential = EpgParser::parse_file_sequential(&path).unwrap();
+        let sequential_time = started.elapsed();
+
+        let started = std::time::Instant::now();
+        let parallel = EpgParser::parse_file(&path).unwrap();
+        let parallel_time = started.elapsed();
+        let _ = std::fs::remove_file(&path);
+
+        println!(
+            "{} MB, {} programmes: sequential {:.2?}, parallel {:.2?} ({:.1}x)",
+            xml.len() / 1_048_576, parallel.program_count(), sequential_time, parallel_time,
+            sequential_time.as_secs_f64() / parallel_time.as_secs_f64()
+        );
+        assert_same_epg(&sequential, &parallel);
+    }
+
     #[test]
     fn test_parse_url() {
         let (host, port, path) = parse_url("http://example.com/epg.xml").unwrap();
@@ -0,0 +1,90 @@
+//! Limit on concurrent network fetches
+//!
+//! Every API call and playlist download runs on its own thread,
+//! but first waits for a permit so that rapid navigation can't open an
+//! unbounded number of connections to the provider.
+
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+
+struct PoolState {
+    active: usize,
+    max: usize,
+}
+
+/// Counting semaphore shared by all background fetches (cheap to clone)
+#[derive(Clone)]
+pub struct FetchPool {
+    inner: Arc<(Mutex<PoolState>, Condvar)>,
+}
+
+/// Held while a fetch is running - the slot is released on drop (even on panic)
+pub struct FetchPermit {
+    inner: Arc<(Mutex<PoolState>, Condvar)>,
+}
+
+impl FetchPool {
+    pub fn new(max: usize) -> Self {
+        Self {
+            inner: Arc::new((
+                Mutex::new(PoolState { active: 0, max: max.max(1) }),
+                Condvar::new(),
+            )),
+        }
+    }
+
+    /// Change the concurrency limit - running fetches are not interrupted
+    pub fn set_max(&self, max: usize) {
+        let (state, cvar) = &*self.inner;
+        let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+        state.max = max.max(1);
+        cvar.notify_all();
+    }
+
+    pub fn max(&self) -> usize {
+        let (state, _) = &*self.inner;
+        state.lock().unwrap_or_else(|e| e.into_inner()).max
+    }
+
+    /// Number of fetches currently holding a permit
+    pub fn active(&self) -> usize {
+        let (state, _) = &*self.inner;
+        state.lock().unwrap_or_else(|e| e.into_inner()).active
+    }
+
+    /// Block until a slot is free
+    pub fn acquire(&self) -> FetchPermit {
+        let (state, cvar) = &*self.inner;
+        let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+        while state.active >= state.max {
+            state = cvar.wait(state).unwrap_or_else(|e| e.into_inner());
+        }
+        state.active += 1;
+        FetchPermit { inner: Arc::clone(&self.inner) }
+    }
+
+    /// Run a job on a new thread once a slot is free
+    pub fn spawn<F>(&self, job: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let pool = self.clone();
+        thread::spawn(move || {
+            let _permit = pool.acquire();
+            job();
+        });
+    }
+}
+
+impl Drop for FetchPermit {
+    fn drop(&mut self) {
+        let (state, cvar) = &*self.inner;
+        let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+        state.active = state.active.saturating_sub(1);
+        cvar.notify_one();
+    }
+}
+
+#[cfg(test)]
+#[path = "fetch_pool_tests.rs"]
+mod tests;
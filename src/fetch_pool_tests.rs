@@ -0,0 +1,77 @@
+//! Tests for the concurrent fetch limit
+
+use crate::fetch_pool::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::channel;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[test]
+fn test_spawn_never_exceeds_max() {
+    let pool = FetchPool::new(2);
+    let running = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+    let (done_tx, done_rx) = channel();
+
+    for _ in 0..8 {
+        let running = Arc::clone(&running);
+        let peak = Arc::clone(&peak);
+        let done_tx = done_tx.clone();
+        pool.spawn(move || {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(20));
+            running.fetch_sub(1, Ordering::SeqCst);
+            let _ = done_tx.send(());
+        });
+    }
+
+    for _ in 0..8 {
+        done_rx.recv_timeout(Duration::from_secs(5)).expect("job did not finish");
+    }
+    assert!(peak.load(Ordering::SeqCst) <= 2);
+
+    // Permits drop just after each job reports done
+    for _ in 0..100 {
+        if pool.active() == 0 {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(pool.active(), 0);
+}
+
+#[test]
+fn test_permit_released_on_drop() {
+    let pool = FetchPool::new(1);
+    let permit = pool.acquire();
+    assert_eq!(pool.active(), 1);
+    drop(permit);
+    assert_eq!(pool.active(), 0);
+    let _again = pool.acquire();
+    assert_eq!(pool.active(), 1);
+}
+
+#[test]
+fn test_raising_max_wakes_waiters() {
+    let pool = FetchPool::new(1);
+    let _held = pool.acquire();
+    let (tx, rx) = channel();
+    pool.spawn(move || {
+        let _ = tx.send(());
+    });
+
+    // Blocked behind the held permit
+    assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+
+    pool.set_max(2);
+    rx.recv_timeout(Duration::from_secs(5)).expect("waiter was not woken");
+}
+
+#[test]
+fn test_zero_max_is_clamped() {
+    let pool = FetchPool::new(0);
+    assert_eq!(pool.max(), 1);
+    pool.set_max(0);
+    assert_eq!(pool.max(), 1);
+}
@@ -111,7 +111,7 @@ mod player_impl {
         }
 
         /// Play a stream URL
-        pub fn play(&mut self, name: &str, url: &str, _buffer_secs: u32, user_agent: &str) {
+        pub fn play(&mut self, name: &str, url: &str, _buffer_secs: u32, user_agent: &str, referrer: Option<&str>) {
             self.stop();
             self.url = url.to_string();
             self.channel_name = name.to_string();
@@ -126,17 +126,19 @@ mod player_impl {
             
             let url = url.to_string();
             let user_agent = user_agent.to_string();
+            let referrer = referrer.map(str::to_string);
             let state = Arc::clone(&self.state);
             let current_frame = Arc::clone(&self.current_frame);
             
             thread::spawn(move || {
-                Self::decode_thread(url, user_agent, state, current_frame, cmd_rx, msg_tx);
+                Self::decode_thread(url, user_agent, referrer, state, current_frame, cmd_rx, msg_tx);
             });
         }
 
         fn decode_thread(
             url: String,
             user_agent: String,
+            referrer: Option<String>,
             state: Arc<Mutex<PlayerState>>,
             current_frame: Arc<Mutex<Option<DecodedFrame>>>,
             cmd_rx: Receiver<PlayerCommand>,
@@ -145,6 +147,10 @@ mod player_impl {
             // Set options for network streams
             let mut options = ffmpeg::Dictionary::new();
             options.set("user_agent", &user_agent);
+            if let Some(referrer) = &referrer {
+                // #EXTVLCOPT:http-referrer - some providers refuse streams without it
+                options.set("headers", &format!("Referer: {}\r\n", referrer));
+            }
             options.set("reconnect", "1");
             options.set("reconnect_streamed", "1");
             options.set("reconnect_delay_max", "5");
@@ -405,7 +411,7 @@ mod player_impl {
             Vec::new()
         }
 
-        pub fn play(&mut self, name: &str, _url: &str, _buffer_secs: u32, _user_agent: &str) {
+        pub fn play(&mut self, name: &str, _url: &str, _buffer_secs: u32, _user_agent: &str, _referrer: Option<&str>) {
             self.channel_name = name.to_string();
             self.state = PlayerState::Error("Internal player not enabled. Build with --features internal-player".to_string());
         }
@@ -426,12 +432,36 @@ mod player_impl {
 // Re-export
 pub use player_impl::*;
 
+use std::time::{Duration, Instant};
+
+/// Called with the URL of a VOD stream that played to its end
+pub type FinishedHook = Box<dyn FnMut(&str) + Send>;
+
+/// Delay before each reconnect attempt
+const RECONNECT_DELAY: Duration = Duration::from_secs(3);
+
+/// What was last played, kept so a dropped stream can be reopened
+struct PlayRequest {
+    name: String,
+    url: String,
+    buffer_secs: u32,
+    user_agent: String,
+    referrer: Option<String>,
+    live: bool,
+}
+
 /// Player window that can be embedded in egui
 pub struct PlayerWindow {
     pub player: InternalPlayer,
     pub texture: Option<egui::TextureHandle>,
     pub show_controls: bool,
     last_error: Option<String>,
+    // Reconnect supervisor
+    request: Option<PlayRequest>,
+    max_retries: u32,
+    retry_count: u32,
+    retry_at: Option<Instant>,
+    on_finished: Option<FinishedHook>,
 }
 
 impl PlayerWindow {
@@ -441,20 +471,76 @@ impl PlayerWindow {
             texture: None,
             show_controls: true,
             last_error: None,
+            request: None,
+            max_retries: 0,
+            retry_count: 0,
+            retry_at: None,
+            on_finished: None,
         }
     }
 
-    /// Play a channel
-    pub fn play(&mut self, name: &str, url: &str, buffer_secs: u32, user_agent: &str) {
+    /// Hook run when a VOD stream reaches its end (e.g. to queue the next episode).
+    /// Stays set for later plays until replaced or cleared with `None`.
+    pub fn set_on_finished(&mut self, hook: Option<FinishedHook>) {
+        self.on_finished = hook;
+    }
+
+    /// Play a channel with the given (user agent, referrer) headers, reconnecting up to
+    /// `max_retries` times if the stream fails.
+    /// Live streams are also reopened when they end; VOD ending is treated as finished.
+    pub fn play(&mut self, name: &str, url: &str, buffer_secs: u32, headers: (&str, Option<&str>), max_retries: u32, live: bool) {
+        let (user_agent, referrer) = headers;
         self.last_error = None;
         self.texture = None;
-        self.player.play(name, url, buffer_secs, user_agent);
+        self.max_retries = max_retries;
+        self.retry_count = 0;
+        self.retry_at = None;
+        self.request = Some(PlayRequest {
+            name: name.to_string(),
+            url: url.to_string(),
+            buffer_secs,
+            user_agent: user_agent.to_string(),
+            referrer: referrer.map(str::to_string),
+            live,
+        });
+        self.player.play(name, url, buffer_secs, user_agent, referrer);
     }
 
     /// Stop playback
     pub fn stop(&mut self) {
         self.player.stop();
         self.texture = None;
+        self.request = None;
+        self.retry_at = None;
+    }
+
+    /// Queue a reconnect after a failure, or give up once retries are used up
+    fn schedule_reconnect(&mut self) {
+        if self.request.is_none() {
+            return;
+        }
+        if self.retry_count < self.max_retries {
+            self.retry_count += 1;
+            self.retry_at = Some(Instant::now() + RECONNECT_DELAY);
+        } else if self.max_retries > 0 {
+            self.last_error = Some(format!(
+                "Gave up after {} reconnect attempt{}",
+                self.max_retries,
+                if self.max_retries == 1 { "" } else { "s" }
+            ));
+        }
+    }
+
+    /// Reconnect status for display, e.g. "Reconnecting in 2s (attempt 1/3)"
+    fn reconnect_status(&self) -> Option<String> {
+        if let Some(at) = self.retry_at {
+            let secs = at.saturating_duration_since(Instant::now()).as_secs_f32().ceil() as u32;
+            Some(format!("🔁 Reconnecting in {}s (attempt {}/{})", secs, self.retry_count, self.max_retries))
+        } else if self.retry_count > 0 && matches!(self.player.state(), PlayerState::Loading) {
+            Some(format!("🔁 Reconnecting (attempt {}/{})...", self.retry_count, self.max_retries))
+        } else {
+            None
+        }
     }
 
     /// Render the player UI
@@ -464,11 +550,34 @@ impl PlayerWindow {
             match msg {
                 PlayerMessage::Error(e) => {
                     self.last_error = Some(e);
+                    self.schedule_reconnect();
+                }
+                PlayerMessage::Finished if self.request.as_ref().is_some_and(|r| r.live) => {
+                    self.last_error = Some("Stream ended unexpectedly".to_string());
+                    self.schedule_reconnect();
+                }
+                PlayerMessage::Finished => {
+                    if let (Some(hook), Some(req)) = (self.on_finished.as_mut(), self.request.as_ref()) {
+                        hook(&req.url);
+                    }
+                }
+                PlayerMessage::StateChanged(PlayerState::Playing) if self.retry_count > 0 => {
+                    // Connected again - a later drop gets a fresh set of retries
+                    self.retry_count = 0;
+                    self.last_error = None;
                 }
                 _ => {}
             }
         }
 
+        // Reopen the stream once the reconnect delay has passed
+        if self.retry_at.is_some_and(|at| Instant::now() >= at) {
+            self.retry_at = None;
+            if let Some(req) = &self.request {
+                self.player.play(&req.name, &req.url, req.buffer_secs, &req.user_agent, req.referrer.as_deref());
+            }
+        }
+
         // Check for new frames
         if let Some(frame) = self.player.take_frame() {
             let image = egui::ColorImage::from_rgb(
@@ -519,6 +628,11 @@ impl PlayerWindow {
                 }
             }
             
+            if let Some(status) = self.reconnect_status() {
+                ui.add_space(5.0);
+                ui.colored_label(egui::Color32::YELLOW, status);
+            }
+            
             // Show any errors even while playing
             if matches!(self.player.state(), PlayerState::Playing) {
                 if let Some(ref error) = self.last_error {
@@ -551,14 +665,14 @@ impl PlayerWindow {
             });
         }
 
-        // Request continuous repaint while playing
-        if matches!(self.player.state(), PlayerState::Playing | PlayerState::Loading) {
+        // Request continuous repaint while playing or waiting to reconnect
+        if self.retry_at.is_some() || matches!(self.player.state(), PlayerState::Playing | PlayerState::Loading) {
             ctx.request_repaint();
         }
     }
 
-    /// Check if currently playing
+    /// Check if currently playing (or about to reconnect)
     pub fn is_playing(&self) -> bool {
-        matches!(self.player.state(), PlayerState::Playing | PlayerState::Loading)
+        self.retry_at.is_some() || matches!(self.player.state(), PlayerState::Playing | PlayerState::Loading)
     }
 }
@@ -0,0 +1,198 @@
+//! Async image cache for posters and logos
+//!
+//! Images are downloaded and decoded on background threads, then uploaded
+//! as egui textures on the UI thread. Failed URLs are remembered so they
+//! are not retried every frame. Only the most recently drawn textures are
+//! kept, so scrolling through thousands of channel logos doesn't pile up
+//! GPU memory.
+
+use eframe::egui;
+use std::collections::{HashMap, VecDeque};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+/// Maximum number of concurrent downloads
+const MAX_CONCURRENT: usize = 4;
+/// Maximum image download size (10 MB)
+const MAX_IMAGE_BYTES: u64 = 10 * 1024 * 1024;
+/// Decoded images are downscaled to fit within this size
+const MAX_DIMENSION: u32 = 512;
+/// Textures kept before the least recently drawn ones are dropped
+const MAX_TEXTURES: usize = 400;
+
+/// State of a cached image
+#[derive(Clone)]
+pub enum ImageState {
+    Loading,
+    Ready(egui::TextureHandle),
+    Failed,
+}
+
+pub struct ImageCache {
+    entries: HashMap<String, ImageState>,
+    last_used: HashMap<String, u64>, // Ready textures -> `clock` when last drawn
+    clock: u64,
+    queue: VecDeque<String>,
+    in_flight: usize,
+    user_agent: String,
+    enabled: bool,
+    sender: Sender<(String, Result<egui::ColorImage, String>)>,
+    receiver: Receiver<(String, Result<egui::ColorImage, String>)>,
+}
+
+impl Default for ImageCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ImageCache {
+    pub fn new() -> Self {
+        let (sender, receiver) = channel();
+        Self {
+            entries: HashMap::new(),
+            last_used: HashMap::new(),
+            clock: 0,
+            queue: VecDeque::new(),
+            in_flight: 0,
+            user_agent: String::new(),
+            enabled: true,
+            sender,
+            receiver,
+        }
+    }
+
+    /// Set the user agent used for image requests
+    pub fn set_user_agent(&mut self, user_agent: &str) {
+        self.user_agent = user_agent.to_string();
+    }
+
+    /// Enable or pause new downloads (already loaded images stay available)
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Get an image, queueing a download if it hasn't been requested yet
+    pub fn get(&mut self, ctx: &egui::Context, url: &str) -> ImageState {
+        let url = url.trim();
+        if url.is_empty() || !(url.starts_with("http://") || url.starts_with("https://")) {
+            return ImageState::Failed;
+        }
+
+        if let Some(state) = self.entries.get(url) {
+            if let ImageState::Ready(_) = state {
+                self.clock += 1;
+                self.last_used.insert(url.to_string(), self.clock);
+            }
+            return state.clone();
+        }
+
+        // Not remembered as failed, so it loads once downloads are enabled again
+        if !self.enabled {
+            return ImageState::Failed;
+        }
+
+        self.entries.insert(url.to_string(), ImageState::Loading);
+        self.queue.push_back(url.to_string());
+        self.start_downloads(ctx);
+        ImageState::Loading
+    }
+
+    /// Upload finished downloads as textures - call once per frame
+    pub fn poll(&mut self, ctx: &egui::Context) {
+        while let Ok((url, result)) = self.receiver.try_recv() {
+            self.in_flight = self.in_flight.saturating_sub(1);
+            let state = match result {
+                Ok(image) => {
+                    self.clock += 1;
+                    self.last_used.insert(url.clone(), self.clock);
+                    ImageState::Ready(ctx.load_texture(
+                        &url,
+                        image,
+                        egui::TextureOptions::LINEAR,
+                    ))
+                }
+                Err(_) => ImageState::Failed,
+            };
+            self.entries.insert(url, state);
+        }
+        self.evict();
+        self.start_downloads(ctx);
+    }
+
+    /// Drop the least recently drawn textures beyond `MAX_TEXTURES` - they are
+    /// downloaded again if scrolled back into view
+    fn evict(&mut self) {
+        if self.last_used.len() <= MAX_TEXTURES {
+            return;
+        }
+        let mut by_age: Vec<(u64, String)> = self.last_used.drain().map(|(url, used)| (used, url)).collect();
+        by_age.sort_unstable();
+        let keep_from = by_age.len() - MAX_TEXTURES;
+        for (i, (used, url)) in by_age.into_iter().enumerate() {
+            if i < keep_from {
+                self.entries.remove(&url);
+            } else {
+                self.last_used.insert(url, used);
+            }
+        }
+    }
+
+    /// Drop all cached textures and failures
+    pub fn clear(&mut self) {
+        self.entries.retain(|_, state| matches!(state, ImageState::Loading));
+        self.last_used.clear();
+    }
+
+    fn start_downloads(&mut self, ctx: &egui::Context) {
+        while self.in_flight < MAX_CONCURRENT {
+            let Some(url) = self.queue.pop_front() else { break };
+            self.in_flight += 1;
+
+            let sender = self.sender.clone();
+            let user_agent = self.user_agent.clone();
+            let ctx = ctx.clone();
+            thread::spawn(move || {
+                let result = download_image(&url, &user_agent);
+                let _ = sender.send((url, result));
+                ctx.request_repaint();
+            });
+        }
+    }
+}
+
+/// Download and decode an image into an egui ColorImage
+fn download_image(url: &str, user_agent: &str) -> Result<egui::ColorImage, String> {
+    let agent = ureq::Agent::config_builder()
+        .timeout_global(Some(Duration::from_secs(20)))
+        .timeout_connect(Some(Duration::from_secs(10)))
+        .build()
+        .new_agent();
+
+    let mut request = agent.get(url);
+    if !user_agent.is_empty() {
+        request = request.header("User-Agent", user_agent);
+    }
+
+    let mut response = request.call().map_err(|e| format!("HTTP error: {}", e))?;
+
+    let bytes = response.body_mut()
+        .with_config()
+        .limit(MAX_IMAGE_BYTES)
+        .read_to_vec()
+        .map_err(|e| format!("Read error: {}", e))?;
+
+    let image = image::load_from_memory(&bytes)
+        .map_err(|e| format!("Decode error: {}", e))?;
+
+    let image = if image.width() > MAX_DIMENSION || image.height() > MAX_DIMENSION {
+        image.thumbnail(MAX_DIMENSION, MAX_DIMENSION)
+    } else {
+        image
+    };
+
+    let rgba = image.to_rgba8();
+    let size = [rgba.width() as usize, rgba.height() as usize];
+    Ok(egui::ColorImage::from_rgba_unmultiplied(size, rgba.as_raw()))
+}
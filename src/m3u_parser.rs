@@ -18,6 +18,11 @@ pub struct M3uChannel {
     pub channel_number: Option<u32>,    // Channel number (channel-number)
     pub catchup: Option<String>,        // Catchup type (default, shift, etc.)
     pub catchup_days: Option<u32>,      // Days of catchup available
+    pub catchup_source: Option<String>, // Catchup URL template (catchup-source)
+    pub tvg_shift: Option<f32>,         // EPG offset in hours (tvg-shift)
+    pub group_logo: Option<String>,     // Category logo (group-logo)
+    pub user_agent: Option<String>,     // #EXTVLCOPT:http-user-agent
+    pub referrer: Option<String>,       // #EXTVLCOPT:http-referrer
 }
 
 #[derive(Debug, Clone, Default)]
@@ -68,10 +73,11 @@ pub fn download_and_parse_playlist(url: &str, user_agent: &str) -> Result<M3uPla
         return Err(format!("HTTP error: {}", response.status()));
     }
 
-    let content = response
+    let bytes = response
         .body_mut()
-        .read_to_string()
+        .read_to_vec()
         .map_err(|e| format!("Read failed: {}", e))?;
+    let (content, _) = decode_playlist_bytes(&bytes)?;
 
     let mut playlist = parse_m3u_playlist(&content);
     
@@ -102,6 +108,44 @@ pub fn download_and_parse_playlist(url: &str, user_agent: &str) -> Result<M3uPla
     Ok(playlist)
 }
 
+/// Playlist bytes as text, transparently decompressing gzip (detected by magic bytes,
+/// since .m3u.gz files are often served without a Content-Encoding header).
+/// Returns the text and whether it was compressed.
+pub fn decode_playlist_bytes(bytes: &[u8]) -> Result<(String, bool), String> {
+    use std::io::Read;
+
+    if bytes.starts_with(&[0x1f, 0x8b]) {
+        let mut decoded = Vec::new();
+        flate2::read::MultiGzDecoder::new(bytes)
+            .read_to_end(&mut decoded)
+            .map_err(|e| format!("Failed to decompress gzip playlist: {}", e))?;
+        return Ok((String::from_utf8_lossy(&decoded).into_owned(), true));
+    }
+    Ok((String::from_utf8_lossy(bytes).into_owned(), false))
+}
+
+/// File name suffixes recognised as local playlists
+pub const PLAYLIST_FILE_EXTENSIONS: [&str; 5] = [".m3u", ".m3u8", ".xspf", ".m3u.gz", ".m3u8.gz"];
+
+pub fn is_playlist_file_name(name: &str) -> bool {
+    let name = name.to_lowercase();
+    PLAYLIST_FILE_EXTENSIONS.iter().any(|ext| name.ends_with(ext))
+}
+
+/// Playlist files directly inside `dir` (not recursive), sorted by file name
+pub fn scan_playlist_dir(dir: &std::path::Path) -> Result<Vec<std::path::PathBuf>, String> {
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+    let mut files: Vec<_> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .filter(|p| p.file_name().is_some_and(|n| is_playlist_file_name(&n.to_string_lossy())))
+        .collect();
+    files.sort_by_cached_key(|p| p.file_name().map(|n| n.to_string_lossy().to_lowercase()));
+    Ok(files)
+}
+
 /// Get base URL for resolving relative paths
 fn get_base_url(url: &str) -> String {
     if let Some(pos) = url.rfind('/') {
@@ -202,6 +246,11 @@ fn parse_m3u8_hls(content: &str) -> Vec<M3uChannel> {
                         channel_number: None,
                         catchup: None,
                         catchup_days: None,
+                        catchup_source: None,
+                        tvg_shift: None,
+                        group_logo: None,
+                        user_agent: None,
+                        referrer: None,
                     });
                     i += 1;
                 }
@@ -244,6 +293,11 @@ fn parse_m3u8_hls(content: &str) -> Vec<M3uChannel> {
                         channel_number: None,
                         catchup: None,
                         catchup_days: None,
+                        catchup_source: None,
+                        tvg_shift: None,
+                        group_logo: None,
+                        user_agent: None,
+                        referrer: None,
                     });
                 }
             }
@@ -264,6 +318,11 @@ fn parse_m3u8_hls(content: &str) -> Vec<M3uChannel> {
                 channel_number: None,
                 catchup: None,
                 catchup_days: None,
+                catchup_source: None,
+                tvg_shift: None,
+                group_logo: None,
+                user_agent: None,
+                referrer: None,
             });
             // For media playlists, the original URL is the stream URL
             break;
@@ -275,6 +334,61 @@ fn parse_m3u8_hls(content: &str) -> Vec<M3uChannel> {
     channels
 }
 
+/// One quality of an HLS master playlist
+#[derive(Debug, Clone, PartialEq)]
+pub struct HlsVariant {
+    /// "1280x720 - 2.6 Mbps" (or the variant's NAME)
+    pub label: String,
+    pub bandwidth: Option<u64>,
+    /// Absolute URL of the variant's media playlist
+    pub url: String,
+}
+
+/// `#EXT-X-STREAM-INF` variants of an HLS master playlist, highest bandwidth first.
+/// Relative URIs are resolved against `playlist_url`. Empty for media playlists.
+pub fn hls_variants(content: &str, playlist_url: &str) -> Vec<HlsVariant> {
+    let mut variants = Vec::new();
+    let mut lines = content.lines().map(str::trim);
+    while let Some(line) = lines.next() {
+        let Some(attrs) = line.strip_prefix("#EXT-X-STREAM-INF:") else { continue };
+        // The URI is the next non-tag line
+        let Some(uri) = lines.by_ref().find(|l| !l.is_empty() && !l.starts_with('#')) else { break };
+        
+        // Leading comma so BANDWIDTH= doesn't match AVERAGE-BANDWIDTH=
+        let attrs = format!(",{}", attrs);
+        let bandwidth = extract_hls_attr(&attrs, ",BANDWIDTH");
+        let label = extract_hls_attr(&attrs, "NAME").unwrap_or_else(|| {
+            let parts: Vec<String> = extract_hls_attr(&attrs, "RESOLUTION").into_iter()
+                .chain(bandwidth.as_deref().map(format_bandwidth))
+                .collect();
+            if parts.is_empty() { format!("Variant {}", variants.len() + 1) } else { parts.join(" - ") }
+        });
+        variants.push(HlsVariant {
+            label,
+            bandwidth: bandwidth.and_then(|b| b.parse().ok()),
+            url: resolve_hls_uri(playlist_url, uri),
+        });
+    }
+    variants.sort_by_key(|v| std::cmp::Reverse(v.bandwidth));
+    variants
+}
+
+/// Resolve a URI from an HLS playlist against the playlist's own URL
+fn resolve_hls_uri(playlist_url: &str, uri: &str) -> String {
+    if uri.contains("://") {
+        return uri.to_string();
+    }
+    let base = playlist_url.split(['?', '#']).next().unwrap_or(playlist_url);
+    if uri.starts_with('/') {
+        // Keep scheme and host
+        let host_end = base.find("://")
+            .and_then(|s| base[s + 3..].find('/').map(|p| s + 3 + p))
+            .unwrap_or(base.len());
+        return format!("{}{}", &base[..host_end], uri);
+    }
+    format!("{}/{}", get_base_url(base), uri)
+}
+
 /// Extract attribute from HLS tag (e.g., BANDWIDTH=1280000)
 fn extract_hls_attr(line: &str, attr: &str) -> Option<String> {
     let search = format!("{}=", attr);
@@ -329,6 +443,9 @@ pub fn parse_m3u(content: &str) -> Vec<M3uChannel> {
     // Reuse buffer to avoid allocations
     let mut current_attrs = AttrBuffer::new();
     let mut current_name: Option<&str> = None;
+    // #EXTVLCOPT lines can sit before or after #EXTINF - they apply to the next URL
+    let mut current_user_agent: Option<&str> = None;
+    let mut current_referrer: Option<&str> = None;
     
     for line in content.lines() {
         let line = line.trim();
@@ -384,25 +501,41 @@ pub fn parse_m3u(content: &str) -> Vec<M3uChannel> {
             } else {
                 extract_attrs_fast(info_part, &mut current_attrs);
             }
+        } else if let Some(option) = line.strip_prefix("#EXTVLCOPT:") {
+            if let Some((key, value)) = option.split_once('=') {
+                let value = value.trim().trim_matches('"');
+                match key.trim().to_ascii_lowercase().as_str() {
+                    "http-user-agent" if !value.is_empty() => current_user_agent = Some(value),
+                    // Both spellings are found in the wild
+                    "http-referrer" | "http-referer" if !value.is_empty() => current_referrer = Some(value),
+                    _ => {}
+                }
+            }
         } else if !bytes.is_empty() && bytes[0] != b'#' && !bytes.starts_with(b"EXTM3U") {
             // URL line
+            let user_agent = current_user_agent.take();
+            let referrer = current_referrer.take();
             if let Some(name) = current_name.take() {
                 // Extract all attrs in one pass using indices
-                let (group, tvg_id, tvg_logo, tvg_name, tvg_chno, channel_id, channel_number, catchup, catchup_days) = 
-                    current_attrs.get_all();
+                let attrs = current_attrs.get_all();
                 
                 channels.push(M3uChannel {
                     name: name.to_string(),
                     url: line.to_string(),
-                    group: group.map(|s| s.to_string()),
-                    tvg_id: tvg_id.map(|s| s.to_string()),
-                    tvg_logo: tvg_logo.map(|s| s.to_string()),
-                    tvg_name: tvg_name.map(|s| s.to_string()),
-                    tvg_chno: tvg_chno.and_then(|s| s.parse().ok()),
-                    channel_id: channel_id.map(|s| s.to_string()),
-                    channel_number: channel_number.and_then(|s| s.parse().ok()),
-                    catchup: catchup.map(|s| s.to_string()),
-                    catchup_days: catchup_days.and_then(|s| s.parse().ok()),
+                    group: attrs.group.map(|s| s.to_string()),
+                    tvg_id: attrs.tvg_id.map(|s| s.to_string()),
+                    tvg_logo: attrs.tvg_logo.map(|s| s.to_string()),
+                    tvg_name: attrs.tvg_name.map(|s| s.to_string()),
+                    tvg_chno: attrs.tvg_chno.and_then(|s| s.parse().ok()),
+                    channel_id: attrs.channel_id.map(|s| s.to_string()),
+                    channel_number: attrs.channel_number.and_then(|s| s.parse().ok()),
+                    catchup: attrs.catchup.map(|s| s.to_string()),
+                    catchup_days: attrs.catchup_days.and_then(|s| s.parse().ok()),
+                    catchup_source: attrs.catchup_source.map(|s| s.to_string()),
+                    tvg_shift: attrs.tvg_shift.and_then(|s| s.trim().parse().ok()),
+                    group_logo: attrs.group_logo.map(|s| s.to_string()),
+                    user_agent: user_agent.map(|s| s.to_string()),
+                    referrer: referrer.map(|s| s.to_string()),
                 });
             }
         }
@@ -411,16 +544,74 @@ pub fn parse_m3u(content: &str) -> Vec<M3uChannel> {
     channels
 }
 
+/// Serialize channels as an M3U Plus playlist (the inverse of [`parse_m3u_playlist`])
+pub fn write_m3u(channels: &[M3uChannel], epg_url: Option<&str>) -> String {
+    // Quotes would end the attribute value and line breaks (or other control
+    // characters) the entry
+    fn clean(value: &str) -> String {
+        value.chars()
+            .map(|c| match c {
+                '"' => '\'',
+                c if c.is_control() => ' ',
+                c => c,
+            })
+            .collect()
+    }
+
+    let mut out = String::with_capacity(channels.len() * 200 + 64);
+    out.push_str("#EXTM3U");
+    if let Some(epg_url) = epg_url {
+        out.push_str(&format!(" x-tvg-url=\"{}\"", clean(epg_url)));
+    }
+    out.push('\n');
+
+    for ch in channels {
+        out.push_str("#EXTINF:-1");
+        let numbers = [("tvg-chno", ch.tvg_chno), ("channel-number", ch.channel_number), ("catchup-days", ch.catchup_days)];
+        let attrs = [
+            ("tvg-id", ch.tvg_id.clone()),
+            ("tvg-name", ch.tvg_name.clone()),
+            ("tvg-logo", ch.tvg_logo.clone()),
+            ("channel-id", ch.channel_id.clone()),
+            ("tvg-shift", ch.tvg_shift.map(|s| s.to_string())),
+            ("catchup", ch.catchup.clone()),
+            ("catchup-source", ch.catchup_source.clone()),
+            ("group-title", ch.group.clone()),
+            ("group-logo", ch.group_logo.clone()),
+        ];
+        let numbers = numbers.iter().filter_map(|(key, n)| Some((*key, n.as_ref()?.to_string())));
+        for (key, value) in attrs.into_iter().filter_map(|(key, v)| Some((key, v?))).chain(numbers) {
+            out.push_str(&format!(" {}=\"{}\"", key, clean(&value)));
+        }
+        // Readers split the name off at a comma, so it can't contain one
+        let name = clean(&ch.name).replace(',', " ");
+        out.push(',');
+        out.push_str(&name.split_whitespace().collect::<Vec<_>>().join(" "));
+        out.push('\n');
+        for (option, value) in [("http-user-agent", &ch.user_agent), ("http-referrer", &ch.referrer)] {
+            if let Some(value) = value {
+                out.push_str(&format!("#EXTVLCOPT:{}={}\n", option, clean(value).trim()));
+            }
+        }
+        out.push_str(clean(&ch.url).trim());
+        out.push('\n');
+    }
+    out
+}
+
+/// Maximum attributes kept per EXTINF line
+const MAX_ATTRS: usize = 16;
+
 /// Lightweight attribute buffer - avoids HashMap overhead
 struct AttrBuffer<'a> {
-    attrs: [Option<(&'a str, &'a str)>; 12], // Increased for more attrs
+    attrs: [Option<(&'a str, &'a str)>; MAX_ATTRS],
     len: usize,
 }
 
 impl<'a> AttrBuffer<'a> {
     fn new() -> Self {
         Self {
-            attrs: [None; 12],
+            attrs: [None; MAX_ATTRS],
             len: 0,
         }
     }
@@ -430,7 +621,7 @@ impl<'a> AttrBuffer<'a> {
     }
     
     fn push(&mut self, key: &'a str, value: &'a str) {
-        if self.len < 12 {
+        if self.len < MAX_ATTRS {
             self.attrs[self.len] = Some((key, value));
             self.len += 1;
         }
@@ -449,27 +640,10 @@ impl<'a> AttrBuffer<'a> {
         None
     }
     
-    /// Get all known attributes in single pass - avoids repeated linear searches
-    fn get_all(&self) -> (
-        Option<&'a str>, // group-title
-        Option<&'a str>, // tvg-id
-        Option<&'a str>, // tvg-logo
-        Option<&'a str>, // tvg-name
-        Option<&'a str>, // tvg-chno
-        Option<&'a str>, // channel-id
-        Option<&'a str>, // channel-number
-        Option<&'a str>, // catchup
-        Option<&'a str>, // catchup-days
-    ) {
-        let mut group = None;
-        let mut tvg_id = None;
-        let mut tvg_logo = None;
-        let mut tvg_name = None;
-        let mut tvg_chno = None;
-        let mut channel_id = None;
-        let mut channel_number = None;
-        let mut catchup = None;
-        let mut catchup_days = None;
+    /// Get all known attributes in single pass - avoids repeated linear searches.
+    /// Unknown attributes are skipped.
+    fn get_all(&self) -> KnownAttrs<'a> {
+        let mut known = KnownAttrs::default();
         
         for i in 0..self.len {
             if let Some((k, v)) = self.attrs[i] {
@@ -478,28 +652,50 @@ impl<'a> AttrBuffer<'a> {
                 if k_bytes.is_empty() { continue; }
                 
                 match k_bytes[0].to_ascii_lowercase() {
-                    b'g' => if k.eq_ignore_ascii_case("group-title") { group = Some(v); }
+                    b'g' => {
+                        if k.eq_ignore_ascii_case("group-title") { known.group = Some(v); }
+                        else if k.eq_ignore_ascii_case("group-logo") { known.group_logo = Some(v); }
+                    }
                     b't' => {
-                        if k.eq_ignore_ascii_case("tvg-id") { tvg_id = Some(v); }
-                        else if k.eq_ignore_ascii_case("tvg-logo") { tvg_logo = Some(v); }
-                        else if k.eq_ignore_ascii_case("tvg-name") { tvg_name = Some(v); }
-                        else if k.eq_ignore_ascii_case("tvg-chno") { tvg_chno = Some(v); }
+                        if k.eq_ignore_ascii_case("tvg-id") { known.tvg_id = Some(v); }
+                        else if k.eq_ignore_ascii_case("tvg-logo") { known.tvg_logo = Some(v); }
+                        else if k.eq_ignore_ascii_case("tvg-name") { known.tvg_name = Some(v); }
+                        else if k.eq_ignore_ascii_case("tvg-chno") { known.tvg_chno = Some(v); }
+                        else if k.eq_ignore_ascii_case("tvg-shift") { known.tvg_shift = Some(v); }
                     }
                     b'c' => {
-                        if k.eq_ignore_ascii_case("channel-id") { channel_id = Some(v); }
-                        else if k.eq_ignore_ascii_case("channel-number") { channel_number = Some(v); }
-                        else if k.eq_ignore_ascii_case("catchup") { catchup = Some(v); }
-                        else if k.eq_ignore_ascii_case("catchup-days") { catchup_days = Some(v); }
+                        if k.eq_ignore_ascii_case("channel-id") { known.channel_id = Some(v); }
+                        else if k.eq_ignore_ascii_case("channel-number") { known.channel_number = Some(v); }
+                        else if k.eq_ignore_ascii_case("catchup") { known.catchup = Some(v); }
+                        else if k.eq_ignore_ascii_case("catchup-days") { known.catchup_days = Some(v); }
+                        else if k.eq_ignore_ascii_case("catchup-source") { known.catchup_source = Some(v); }
                     }
                     _ => {}
                 }
             }
         }
         
-        (group, tvg_id, tvg_logo, tvg_name, tvg_chno, channel_id, channel_number, catchup, catchup_days)
+        known
     }
 }
 
+/// Known attribute values borrowed from an EXTINF line
+#[derive(Default)]
+struct KnownAttrs<'a> {
+    group: Option<&'a str>,          // group-title
+    group_logo: Option<&'a str>,     // group-logo
+    tvg_id: Option<&'a str>,         // tvg-id
+    tvg_logo: Option<&'a str>,       // tvg-logo
+    tvg_name: Option<&'a str>,       // tvg-name
+    tvg_chno: Option<&'a str>,       // tvg-chno
+    tvg_shift: Option<&'a str>,      // tvg-shift
+    channel_id: Option<&'a str>,     // channel-id
+    channel_number: Option<&'a str>, // channel-number
+    catchup: Option<&'a str>,        // catchup
+    catchup_days: Option<&'a str>,   // catchup-days
+    catchup_source: Option<&'a str>, // catchup-source
+}
+
 /// Fast attribute extraction using byte scanning
 fn extract_attrs_fast<'a>(info: &'a str, attrs: &mut AttrBuffer<'a>) {
     let bytes = info.as_bytes();
@@ -572,6 +768,95 @@ fn extract_attrs_fast<'a>(info: &'a str, attrs: &mut AttrBuffer<'a>) {
     }
 }
 
+/// For each channel, the URLs of other entries that carry the same stream
+/// (matched by tvg-id, or by name when there is no tvg-id). Playlists often
+/// list backup feeds this way; they are used as playback failover.
+pub fn alternate_urls(channels: &[M3uChannel]) -> Vec<Vec<String>> {
+    use std::collections::HashMap;
+    
+    let identity = |c: &M3uChannel| match c.tvg_id.as_deref().map(str::trim) {
+        Some(id) if !id.is_empty() => format!("id:{}", id.to_lowercase()),
+        _ => format!("name:{}", c.name.trim().to_lowercase()),
+    };
+    
+    let mut groups: HashMap<String, Vec<usize>> = HashMap::new();
+    for (i, c) in channels.iter().enumerate() {
+        groups.entry(identity(c)).or_default().push(i);
+    }
+    
+    let mut result = vec![Vec::new(); channels.len()];
+    for members in groups.values().filter(|m| m.len() > 1) {
+        for &i in members {
+            let mut seen = vec![channels[i].url.as_str()];
+            for &j in members {
+                let url = channels[j].url.as_str();
+                if !seen.contains(&url) {
+                    seen.push(url);
+                    result[i].push(url.to_string());
+                }
+            }
+        }
+    }
+    result
+}
+
+/// Build a catchup (archive) URL for a program from the channel's M3U
+/// `catchup` mode and `catchup-source` template.
+///
+/// Supported modes:
+/// - `default` - `catchup-source` is a full URL template
+/// - `append`  - `catchup-source` is appended to the channel URL
+/// - `shift`/`timeshift` - `?utc=<start>&lutc=<now>` is appended
+///
+/// Template placeholders: `{utc}`/`{start}`/`${start}`, `{utcend}`/`{end}`/`${end}`,
+/// `{duration}`, `{lutc}`/`{now}`/`${now}`/`{timestamp}`/`${timestamp}`, and
+/// `{Y}` `{m}` `{d}` `{H}` `{M}` `{S}` (UTC start time parts).
+/// Returns None when the channel has no usable catchup information.
+pub fn build_catchup_url(
+    channel_url: &str,
+    catchup: Option<&str>,
+    catchup_source: Option<&str>,
+    start: i64,
+    stop: i64,
+    now: i64,
+) -> Option<String> {
+    let mode = catchup.map(|c| c.trim().to_ascii_lowercase()).unwrap_or_default();
+    let source = catchup_source.map(str::trim).filter(|s| !s.is_empty());
+    
+    let expand = |template: &str| {
+        let start_time = chrono::DateTime::from_timestamp(start, 0).unwrap_or_default();
+        let mut url = template.to_string();
+        for (keys, value) in [
+            (&["${start}", "{utc}", "{start}"][..], start.to_string()),
+            (&["${end}", "{utcend}", "{end}"][..], stop.to_string()),
+            (&["${timestamp}", "${now}", "{lutc}", "{now}", "{timestamp}"][..], now.to_string()),
+            (&["{duration}"][..], (stop - start).max(0).to_string()),
+            (&["{Y}"][..], start_time.format("%Y").to_string()),
+            (&["{m}"][..], start_time.format("%m").to_string()),
+            (&["{d}"][..], start_time.format("%d").to_string()),
+            (&["{H}"][..], start_time.format("%H").to_string()),
+            (&["{M}"][..], start_time.format("%M").to_string()),
+            (&["{S}"][..], start_time.format("%S").to_string()),
+        ] {
+            for key in keys {
+                url = url.replace(key, &value);
+            }
+        }
+        url
+    };
+    
+    match mode.as_str() {
+        "append" => source.map(|s| format!("{}{}", channel_url, expand(s))),
+        "shift" | "timeshift" => {
+            let sep = if channel_url.contains('?') { '&' } else { '?' };
+            Some(format!("{}{}utc={}&lutc={}", channel_url, sep, start, now))
+        }
+        // "default", or no mode but a template was given
+        "default" | "" => source.map(expand),
+        _ => None,
+    }
+}
+
 /// Extract credentials from M3U Plus URL
 /// Format: http://server/get.php?username=XXX&password=YYY&type=m3u_plus
 pub fn extract_credentials(url: &str) -> Option<M3uCredentials> {
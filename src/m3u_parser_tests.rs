@@ -498,6 +498,93 @@ http://server.com/ch1.ts"#;
         assert_eq!(channels[0].catchup_days, Some(3));
     }
 
+    #[test]
+    fn test_m3u_catchup_source_shift_and_group_logo() {
+        let content = r#"#EXTM3U
+#EXTINF:-1 tvg-id="CH1" tvg-shift="-1.5" group-title="News" group-logo="http://logo/news.png" catchup="append" catchup-source="?utc={utc}&lutc={lutc}" x-unknown="foo",Channel 1
+http://server.com/ch1.ts"#;
+
+        let channels = parse_m3u(content);
+        assert_eq!(channels.len(), 1);
+        assert_eq!(channels[0].tvg_shift, Some(-1.5));
+        assert_eq!(channels[0].group_logo.as_deref(), Some("http://logo/news.png"));
+        assert_eq!(channels[0].catchup.as_deref(), Some("append"));
+        assert_eq!(channels[0].catchup_source.as_deref(), Some("?utc={utc}&lutc={lutc}"));
+        assert_eq!(channels[0].name, "Channel 1");
+    }
+
+    #[test]
+    fn test_m3u_extvlcopt_headers() {
+        let content = r#"#EXTM3U
+#EXTINF:-1 tvg-id="CH1",Channel 1
+#EXTVLCOPT:http-user-agent=Mozilla/5.0 (SMART-TV; Linux)
+#EXTVLCOPT:http-referrer=https://example.com/
+http://server.com/ch1.ts
+#EXTVLCOPT:http-referer=https://other.example/
+#EXTINF:-1,Channel 2
+http://server.com/ch2.ts
+#EXTINF:-1,Channel 3
+http://server.com/ch3.ts"#;
+
+        let channels = parse_m3u(content);
+        assert_eq!(channels.len(), 3);
+        assert_eq!(channels[0].user_agent.as_deref(), Some("Mozilla/5.0 (SMART-TV; Linux)"));
+        assert_eq!(channels[0].referrer.as_deref(), Some("https://example.com/"));
+        // Options before #EXTINF apply too, and don't leak into the next channel
+        assert_eq!(channels[1].user_agent, None);
+        assert_eq!(channels[1].referrer.as_deref(), Some("https://other.example/"));
+        assert_eq!(channels[2].referrer, None);
+    }
+
+    #[test]
+    fn test_build_catchup_url() {
+        let url = "http://server.com/ch1.ts";
+        // 2024-01-02 03:04:05 UTC
+        let start = 1704164645;
+
+        assert_eq!(
+            build_catchup_url(url, Some("append"), Some("?utc={utc}&lutc={lutc}"), start, start + 3600, 1704200000).as_deref(),
+            Some("http://server.com/ch1.ts?utc=1704164645&lutc=1704200000")
+        );
+        assert_eq!(
+            build_catchup_url(url, Some("default"), Some("http://arc/{Y}/{m}/{d}/{H}-{M}-{S}.ts?d={duration}"), start, start + 3600, 0).as_deref(),
+            Some("http://arc/2024/01/02/03-04-05.ts?d=3600")
+        );
+        assert_eq!(
+            build_catchup_url("http://s/ch?x=1", Some("shift"), None, 10, 20, 30).as_deref(),
+            Some("http://s/ch?x=1&utc=10&lutc=30")
+        );
+        assert_eq!(build_catchup_url(url, Some("default"), None, 10, 20, 30), None);
+        assert_eq!(build_catchup_url(url, None, None, 10, 20, 30), None);
+        assert_eq!(build_catchup_url(url, Some("flussonic"), Some("x"), 10, 20, 30), None);
+    }
+
+    #[test]
+    fn test_alternate_urls() {
+        let content = r#"#EXTM3U
+#EXTINF:-1 tvg-id="news.uk",News
+http://a.com/news.ts
+#EXTINF:-1 tvg-id="NEWS.UK",News Backup
+http://b.com/news.ts
+#EXTINF:-1,Sports
+http://a.com/sports.ts
+#EXTINF:-1,sports
+http://b.com/sports.ts
+#EXTINF:-1,sports
+http://b.com/sports.ts
+#EXTINF:-1,Movies
+http://a.com/movies.ts"#;
+
+        let channels = parse_m3u(content);
+        let alts = alternate_urls(&channels);
+        assert_eq!(alts[0], vec!["http://b.com/news.ts"]);
+        assert_eq!(alts[1], vec!["http://a.com/news.ts"]);
+        // Duplicate URLs are listed once and never point back at the channel itself
+        assert_eq!(alts[2], vec!["http://b.com/sports.ts"]);
+        assert_eq!(alts[3], vec!["http://a.com/sports.ts"]);
+        assert!(alts[5].is_empty());
+    }
+
     #[test]
     fn test_m3u_duration_values() {
         let content = r#"#EXTM3U
@@ -712,4 +799,123 @@ v9/prog_index.m3u8
         // Verify no i-frame streams
         assert!(!urls.iter().any(|u| u.contains("iframe")));
     }
+
+    #[test]
+    fn test_decode_gzip_playlist() {
+        use std::io::Write;
+
+        let m3u = "#EXTM3U\n#EXTINF:-1,News\nhttp://example.com/news.ts\n";
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(m3u.as_bytes()).unwrap();
+        let gz = encoder.finish().unwrap();
+
+        let (text, compressed) = decode_playlist_bytes(&gz).unwrap();
+        assert!(compressed);
+        assert_eq!(text, m3u);
+        assert_eq!(parse_m3u(&text).len(), 1);
+
+        let (text, compressed) = decode_playlist_bytes(m3u.as_bytes()).unwrap();
+        assert!(!compressed);
+        assert_eq!(text, m3u);
+
+        assert!(decode_playlist_bytes(&[0x1f, 0x8b, 0x00]).is_err());
+    }
+
+    #[test]
+    fn test_write_m3u_round_trip() {
+        let content = r#"#EXTM3U x-tvg-url="http://example.com/epg.xml"
+#EXTINF:-1 tvg-id="news.uk" tvg-name="News HD" tvg-logo="http://example.com/news.png" tvg-chno="101" tvg-shift="-1.5" catchup="shift" catchup-days="7" group-title="UK",News HD
+http://example.com/news.ts
+#EXTINF:-1 group-title="Movies",Film "Classics"
+#EXTVLCOPT:http-referrer=http://example.com/
+http://example.com/film.ts
+"#;
+        let playlist = parse_m3u_playlist(content);
+        let written = write_m3u(&playlist.channels, playlist.epg_url.as_deref());
+        let reparsed = parse_m3u_playlist(&written);
+
+        assert_eq!(reparsed.epg_url.as_deref(), Some("http://example.com/epg.xml"));
+        assert_eq!(reparsed.channels.len(), 2);
+        let news = &reparsed.channels[0];
+        assert_eq!(news.name, "News HD");
+        assert_eq!(news.url, "http://example.com/news.ts");
+        assert_eq!(news.tvg_id.as_deref(), Some("news.uk"));
+        assert_eq!(news.tvg_logo.as_deref(), Some("http://example.com/news.png"));
+        assert_eq!(news.tvg_chno, Some(101));
+        assert_eq!(news.tvg_shift, Some(-1.5));
+        assert_eq!(news.catchup.as_deref(), Some("shift"));
+        assert_eq!(news.catchup_days, Some(7));
+        assert_eq!(news.group.as_deref(), Some("UK"));
+        assert_eq!(reparsed.channels[1].group.as_deref(), Some("Movies"));
+        assert_eq!(reparsed.channels[1].referrer.as_deref(), Some("http://example.com/"));
+    }
+
+    #[test]
+    fn test_write_m3u_special_characters() {
+        let mut playlist = parse_m3u_playlist("#EXTM3U\n#EXTINF:-1,x\nhttp://example.com/1.ts\n");
+        let channel = &mut playlist.channels[0];
+        channel.name = "News, \"Live\"\r\n#EXTINF:-1,Injected\thttp://evil.example/".to_string();
+        channel.group = Some("UK \"Main\"\n".to_string());
+        channel.tvg_logo = Some("http://example.com/a\".png".to_string());
+
+        let written = write_m3u(&playlist.channels, None);
+        assert_eq!(written.lines().count(), 3);
+        let reparsed = parse_m3u_playlist(&written);
+        assert_eq!(reparsed.channels.len(), 1);
+        let channel = &reparsed.channels[0];
+        assert_eq!(channel.name, "News 'Live' #EXTINF:-1 Injected http://evil.example/");
+        assert_eq!(channel.url, "http://example.com/1.ts");
+        assert_eq!(channel.group.as_deref(), Some("UK 'Main' "));
+        assert_eq!(channel.tvg_logo.as_deref(), Some("http://example.com/a'.png"));
+    }
+
+    #[test]
+    fn test_scan_playlist_dir() {
+        let dir = std::env::temp_dir().join(format!("xtreme_iptv_scan_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("nested.m3u")).unwrap();
+        for name in ["b.m3u8", "A.M3U", "c.xspf", "d.m3u.gz", "notes.txt", "e.xml"] {
+            std::fs::write(dir.join(name), "#EXTM3U\n").unwrap();
+        }
+
+        let names: Vec<String> = scan_playlist_dir(&dir).unwrap().iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, ["A.M3U", "b.m3u8", "c.xspf", "d.m3u.gz"]);
+
+        // New files are picked up on rescan
+        std::fs::write(dir.join("f.m3u"), "#EXTM3U\n").unwrap();
+        assert_eq!(scan_playlist_dir(&dir).unwrap().len(), 5);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(scan_playlist_dir(&dir).is_err());
+    }
+
+    #[test]
+    fn test_hls_variants() {
+        let content = r#"#EXTM3U
+#EXT-X-VERSION:3
+#EXT-X-STREAM-INF:AVERAGE-BANDWIDTH=900000,BANDWIDTH=1280000,RESOLUTION=640x360
+low/index.m3u8
+#EXT-X-STREAM-INF:BANDWIDTH=5120000,RESOLUTION=1280x720,CODECS="avc1.4d401f,mp4a.40.2"
+
+/hd/index.m3u8?token=abc
+#EXT-X-STREAM-INF:BANDWIDTH=8192000,NAME="Full HD"
+http://cdn.example/fhd.m3u8"#;
+
+        let variants = hls_variants(content, "http://tv.example:8080/live/u/p/42.m3u8?session=1");
+        assert_eq!(variants.len(), 3);
+        assert_eq!(variants[0], HlsVariant {
+            label: "Full HD".to_string(),
+            bandwidth: Some(8192000),
+            url: "http://cdn.example/fhd.m3u8".to_string(),
+        });
+        assert_eq!(variants[1].label, "1280x720 - 5.1 Mbps");
+        assert_eq!(variants[1].url, "http://tv.example:8080/hd/index.m3u8?token=abc");
+        assert_eq!(variants[2].bandwidth, Some(1280000));
+        assert_eq!(variants[2].url, "http://tv.example:8080/live/u/p/low/index.m3u8");
+
+        // Media playlists have no variants
+        let media = "#EXTM3U\n#EXT-X-TARGETDURATION:10\n#EXTINF:10,\nseg1.ts\n#EXT-X-ENDLIST";
+        assert!(hls_variants(media, "http://tv.example/a.m3u8").is_empty());
+    }
 }
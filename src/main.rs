@@ -10,7 +10,7 @@
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
 use eframe::egui;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::process::{Command, Stdio};
 use std::sync::mpsc::{channel, Receiver, Sender};
 use std::thread;
@@ -23,15 +23,72 @@ mod m3u_parser;
 mod xspf_parser;
 mod epg;
 mod ffmpeg_player;
+mod player_args;
+mod image_cache;
+mod fetch_pool;
+mod probe;
+mod cli;
+mod playlist_server;
+mod stalker;
+mod app_lock;
+mod single_instance;
+mod mpv_ipc;
+mod search;
+mod vault;
 
 use api::*;
 use config::*;
 use models::*;
 use ffmpeg_player::PlayerWindow;
+use image_cache::{ImageCache, ImageState};
+use fetch_pool::FetchPool;
+use probe::ProbeResult;
+use cli::LaunchIntent;
+use playlist_server::PlaylistServer;
+use stalker::StalkerClient;
+use single_instance::{Instance, InstanceGuard};
 use epg::{EpgData, EpgAutoUpdate, EpgDownloader, DownloadConfig, Program};
 
 // Re-export ConnectionQuality for use in main
 
+/// Selectable visible time spans of the EPG timeline grid (hours)
+const EPG_GRID_WINDOW_CHOICES: [u32; 4] = [2, 3, 4, 6];
+/// Selectable spacings of time axis ticks in the EPG timeline grid (minutes)
+const EPG_GRID_STEP_CHOICES: [u32; 3] = [15, 30, 60];
+/// Timeline scale of the full-day EPG grid
+const EPG_FULL_DAY_PX_PER_HOUR: f32 = 240.0;
+/// Show a program reminder this long before it starts
+const REMINDER_LEAD_SECS: i64 = 5 * 60;
+/// Drop reminders this long after the program started
+const REMINDER_EXPIRE_SECS: i64 = 10 * 60;
+/// A player that exits with an error this soon after launch counts as a
+/// failed stream and triggers failover to the channel's backup URLs
+const PLAYER_QUICK_FAIL: std::time::Duration = std::time::Duration::from_secs(10);
+/// Quiet period before a deferred save is written, so bursts of changes
+/// (favorite toggles, typing in a settings field) become a single write
+const DEFERRED_SAVE_DELAY: std::time::Duration = std::time::Duration::from_millis(750);
+/// How many entries the Info tab's "Recent failures" list keeps
+const MAX_RECENT_FAILURES: usize = 50;
+
+/// URL prefix for Stalker channels whose stream link is created at playback time
+const STALKER_LINK_PREFIX: &str = "stalker:";
+
+/// Error returned by a playlist download that was cancelled from the status bar
+const PLAYLIST_CANCELLED: &str = "Playlist loading cancelled";
+/// Bytes between playlist download progress updates
+const PLAYLIST_PROGRESS_STEP: u64 = 512 * 1024;
+
+/// Items in the "Continue Watching" band on the Movies/Series tabs
+const CONTINUE_WATCHING_LIMIT: usize = 10;
+
+/// Main window size on first launch (and after "Reset window layout")
+const DEFAULT_WINDOW_SIZE: [f32; 2] = [1250.0, 700.0];
+const MIN_WINDOW_SIZE: [f32; 2] = [1000.0, 550.0];
+
+/// Poster size in the Movies/Series poster grid
+const POSTER_TILE_WIDTH: f32 = 120.0;
+const POSTER_TILE_HEIGHT: f32 = 180.0;
+
 /// Case-insensitive substring check without allocation
 fn contains_ignore_case(haystack: &str, needle: &str) -> bool {
     if needle.is_empty() { return true; }
@@ -42,6 +99,18 @@ fn contains_ignore_case(haystack: &str, needle: &str) -> bool {
         .any(|window| window.eq_ignore_ascii_case(needle.as_bytes()))
 }
 
+/// Return the filesystem path if `url` refers to a local file rather than a web URL
+fn local_file_path(url: &str) -> Option<&str> {
+    let url = url.trim();
+    if let Some(path) = url.strip_prefix("file://") {
+        return Some(path);
+    }
+    if url.starts_with("http://") || url.starts_with("https://") || url.is_empty() {
+        return None;
+    }
+    std::path::Path::new(url).is_file().then_some(url)
+}
+
 /// Get current Unix timestamp in seconds
 fn unix_timestamp() -> i64 {
     std::time::SystemTime::now()
@@ -177,9 +246,16 @@ enum TaskResult {
         server_info: ServerInfo,
     },
     ChannelsLoaded(Vec<Channel>),
+    CategoryCountsLoaded {
+        stream_type: String,
+        counts: HashMap<String, usize>,
+        complete: bool, // true when counts cover every category of this type
+    },
     SeriesListLoaded(Vec<SeriesInfo>),
     SeasonsLoaded(Vec<i32>),
     EpisodesLoaded(Vec<Episode>),
+    // All episodes of a series, for searching across seasons
+    SeriesEpisodesCached { series_id: i64, episodes: Vec<Episode> },
     PlaylistLoaded {
         channels: Vec<Channel>,
         playlist_name: Option<String>,
@@ -188,18 +264,47 @@ enum TaskResult {
         channels: Vec<Channel>,
         playlist_name: String,
     },
+    // Stalker portal session, kept for creating stream links
+    StalkerConnected(String, Box<StalkerClient>), // Playlist name, session
+    StalkerLinkResolved(Box<Channel>),
+    // Variants of an HLS master playlist about to be played (empty = play as is)
+    HlsVariants(Box<Channel>, Vec<m3u_parser::HlsVariant>),
+    // Bytes received so far for a foreground playlist download
+    PlaylistProgress { downloaded: u64, total: Option<u64> },
     // Favorites series viewing
     FavSeasonsLoaded(Vec<i32>),
     FavEpisodesLoaded(Vec<Episode>),
-    Error(String),
+    // Movie/series detail panel
+    ContentDetailLoaded(Box<ContentDetail>),
+    StreamProbed { url: String, result: ProbeResult },
+    Error { kind: Option<TaskKind>, msg: String }, // The fetch that failed, if it's a tracked one
     PlayerLog(String),
-    PlayerExited { code: Option<i32>, stderr: String },
+    PlayerExited { pid: u32, code: Option<i32>, stderr: String },
+    // Last mpv position of a movie/episode, read when the player closed
+    PlaybackPosition { url: String, position: f64, duration: Option<f64> },
+    // Internal player reached the end of this episode URL
+    EpisodeFinished(String),
+    PlayerTested { player: String, result: Result<String, String> },
     // EPG loading results
     EpgLoading { progress: String },
     EpgLoaded { data: Box<EpgData> },
     EpgError(String),
 }
 
+/// Kinds of foreground fetches tracked in the status bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TaskKind {
+    Login,
+    Channels,
+    Series,
+    Seasons,
+    Episodes,
+    FavSeasons,
+    FavEpisodes,
+    Detail,
+    Playlist,
+}
+
 /// Context for background fetch operations - avoids cloning credentials repeatedly
 struct FetchContext {
     server: String,
@@ -207,6 +312,11 @@ struct FetchContext {
     password: String,
     user_agent: String,
     use_post: bool,
+    connect_timeout_secs: u64,
+    read_timeout_secs: u64,
+    resolution: HostResolution,
+    live_hls: bool,
+    logo_url_template: String,
     sender: std::sync::mpsc::Sender<TaskResult>,
 }
 
@@ -214,10 +324,41 @@ impl FetchContext {
     fn client(&self) -> XtreamClient {
         XtreamClient::new(&self.server, &self.username, &self.password)
             .with_user_agent(&self.user_agent)
+            .with_timeouts(self.connect_timeout_secs, self.read_timeout_secs)
             .with_post_method(self.use_post)
+            .with_host_resolution(self.resolution.clone())
     }
 }
 
+/// Row of the channel list
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ChannelRow {
+    Source(usize),  // Playlist source header (index into playlist_sources)
+    Channel(usize), // Index into current_channels
+}
+
+/// Sorted and filtered channel list, rebuilt only when its signature changes
+/// so large playlists are not cloned and re-sorted every frame
+#[derive(Default)]
+struct ChannelListView {
+    signature: u64,
+    sorted: Vec<usize>, // Every channel, in the chosen sort order
+    rows: Vec<ChannelRow>, // What the list shows after search and filters
+    genres: BTreeSet<String>,
+    hidden_count: usize,
+}
+
+/// Live channels airing a starred program - EPG ids are resolved once per channel
+/// list / EPG load and the airing set is recomputed at most once a minute
+#[derive(Default)]
+struct AiringFavorites {
+    signature: u64, // Channel list and EPG the ids were resolved for
+    epg_ids: Vec<(String, String, i64)>, // (url, EPG id, tvg-shift secs) of channels with guide data
+    titles: u64, // Starred titles the set was computed for
+    computed_at: i64,
+    urls: HashSet<String>,
+}
+
 // Predefined user agents
 const USER_AGENTS: &[(&str, &str)] = &[
     ("Chrome (Windows)", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Safari/537.36"),
@@ -269,14 +410,42 @@ fn main() -> Result<(), eframe::Error> {
         std::env::remove_var("WAYLAND_DISPLAY");
     }
 
+    // --play / --playlist launch options
+    let launch_intent = cli::parse_launch_args(std::env::args().skip(1));
+    
+    // Only one instance writes the config - a second launch hands its options over and exits
+    let instance = match single_instance::acquire(&instance_lock_path(), launch_intent.as_ref()) {
+        Ok(Instance::Secondary) => {
+            println!("Xtreme IPTV is already running - switched to the open window");
+            return Ok(());
+        }
+        Ok(Instance::Primary(guard)) => Some(guard),
+        Err(e) => {
+            eprintln!("Single-instance check failed, continuing anyway: {}", e);
+            None
+        }
+    };
+    
     // Load icon from embedded bytes
     let icon = load_icon();
 
+    // Window size and position from the last session
+    let config = AppConfig::load();
+    let window_size = config.window_size
+        .filter(|s| s.iter().all(|v| v.is_finite()))
+        .map_or(DEFAULT_WINDOW_SIZE, |[w, h]| [w.clamp(MIN_WINDOW_SIZE[0], 16384.0), h.clamp(MIN_WINDOW_SIZE[1], 16384.0)]);
+    let mut viewport = egui::ViewportBuilder::default()
+        .with_inner_size(window_size)
+        .with_min_inner_size(MIN_WINDOW_SIZE)
+        .with_icon(icon);
+    // Nonsense coordinates are dropped; a position on a monitor that's no longer
+    // connected is moved back on screen once the window exists
+    if let Some(position) = config.window_position.filter(|p| p.iter().all(|v| v.is_finite() && v.abs() < 32768.0)) {
+        viewport = viewport.with_position(position);
+    }
+
     let options = eframe::NativeOptions {
-    viewport: egui::ViewportBuilder::default()
-        .with_inner_size([1250.0, 700.0])
-        .with_min_inner_size([1000.0, 550.0])
-        .with_icon(icon),
+    viewport,
     vsync: true,
     hardware_acceleration: eframe::HardwareAcceleration::Preferred,
     ..Default::default()
@@ -349,7 +518,14 @@ fn main() -> Result<(), eframe::Error> {
             
             // Enable dark mode by default
             cc.egui_ctx.set_visuals(egui::Visuals::dark());
-            Ok(Box::new(IPTVApp::new()))
+            let mut app = IPTVApp::new(config);
+            app.launch_intent = launch_intent;
+            if let Some(guard) = &instance {
+                let ctx = cc.egui_ctx.clone();
+                guard.set_notify(move || ctx.request_repaint());
+            }
+            app.instance = instance;
+            Ok(Box::new(app))
         }),
     )
 }
@@ -379,11 +555,17 @@ struct IPTVApp {
     current_series: Vec<SeriesInfo>,
     current_seasons: Vec<i32>,
     current_episodes: Vec<Episode>,
+    // Every episode of the open series (series_id, episodes) - lets search span all seasons
+    series_episodes: Option<(i64, Vec<Episode>)>,
     
     // Sort settings (persisted)
     live_sort_order: SortOrder,
     movie_sort_order: SortOrder,
     series_sort_order: SortOrder,
+    favorites_sort_order: SortOrder,
+    episodes_by_air_date: bool,
+    remember_sort_per_category: bool,
+    current_category_id: Option<String>, // Category whose channels/series are listed
     
     // Favorites
     favorites: Vec<FavoriteItem>,
@@ -396,6 +578,7 @@ struct IPTVApp {
     
     // Recently watched (last 20)
     recent_watched: Vec<FavoriteItem>,
+    reminders: Vec<ProgramReminder>,
     
     navigation_stack: Vec<NavigationLevel>,
     scroll_positions: Vec<f32>,  // Store scroll Y position for each navigation level
@@ -408,33 +591,90 @@ struct IPTVApp {
     
     // Search
     search_query: String,
+    search_regex: bool, // Session-only regex mode for the search box
+    search_filter: search::SearchQuery, // Compiled search_query, see search_filter()
+    quality_filter: Option<Quality>, // Session-only, applies to live and movie lists
+    movie_genre_filter: Option<String>, // Session-only genre filters
+    series_genre_filter: Option<String>,
+    hidden_channels: HashMap<String, String>, // URL -> name, filtered out of lists
+    show_hidden: bool, // Include hidden streams in lists so they can be unhidden
+    favorite_programs: Vec<String>, // Starred program titles
+    my_shows_only: bool, // Live list filter: only channels airing a starred program
+    show_my_shows: bool,
+    my_show_input: String,
+    
+    // Pinned categories for the current playlist ("{stream_type}:{category_id}")
+    favorite_categories: Vec<String>,
+    // Per-category sort orders for the current playlist (same keys)
+    category_sort_orders: HashMap<String, SortOrder>,
+    
+    // Multi-select (channel list)
+    selection_mode: bool,
+    // Local channel renames (url -> alias) and the rename dialog state (url, original name, input)
+    channel_aliases: HashMap<String, String>,
+    // Play counts per stream URL for the "Most watched" sort
+    play_counts: HashMap<String, u32>,
+    alias_edit: Option<(String, String, String)>,
+    // Item counts per "{stream_type}:{category_id}" (session only)
+    category_counts: HashMap<String, usize>,
+    category_counts_prefetching: bool,
+    // ffprobe result popup (name, url, result - None while probing)
+    stream_probe: Option<(String, String, Option<ProbeResult>)>,
+    selected_urls: HashSet<String>,
     
     // Settings
     external_player: String,
+    player_extra_args: String,
     buffer_seconds: u32,
+    connect_timeout_secs: u64,
+    read_timeout_secs: u64,
+    force_ipv4: bool,
+    ip_override: String,
+    live_hls: bool, // Build live stream URLs with .m3u8 instead of .ts
+    logo_url_template: String, // Fallback logo URL for streams without a stream_icon
+    audio_language: String, // Preferred audio languages passed to external players
+    subtitle_language: String, // Preferred subtitle languages passed to external players
     connection_quality: ConnectionQuality,
     dark_mode: bool,
     use_post_method: bool,
     save_state: bool,
     auto_login: bool,
     auto_login_triggered: bool,
+    startup_action: StartupAction,
     
     // User Agent
     selected_user_agent: usize,
     custom_user_agent: String,
     use_custom_user_agent: bool,
     pass_user_agent_to_player: bool,
+    // Separate player (playback) user agent
+    separate_player_user_agent: bool,
+    player_selected_user_agent: usize,
+    player_custom_user_agent: String,
+    player_use_custom_user_agent: bool,
+    ua_dialog_editing_player: bool,
     show_user_agent_dialog: bool,
+    show_settings_dialog: bool,
     
     // Config
     config: AppConfig,
     address_book: Vec<SavedCredential>, // Legacy - kept for migration
     playlist_entries: Vec<PlaylistEntry>, // New unified playlist manager
+    playlists_dir: String, // Folder of local playlist files offered in the manager
+    playlists_dir_files: Option<Vec<std::path::PathBuf>>, // None until scanned
+    recording_dir: String, // Where ⏺ writes ffmpeg recordings
+    recordings: Vec<(String, std::process::Child)>, // Running ffmpeg recordings with their channel name
     current_playlist_idx: Option<usize>, // Cached index of current Xtream playlist (avoids repeated lookups)
+    active_playlist_name: Option<String>, // Last playlist opened from the manager
+    previous_playlist_name: Option<String>, // The one before it - target of "↔ Switch"
     show_playlist_manager: bool,
     playlist_name_input: String,
     playlist_url_input: String,
+    stalker_portal_input: String,
+    stalker_mac_input: String,
+    stalker_sessions: HashMap<String, StalkerClient>, // Portal session per loaded Stalker playlist (by playlist name)
     show_reset_confirm: bool,
+    show_merge_favorites_confirm: bool, // Lists the duplicate favorites before they are removed
     
     // Playlist loading state (M3U/M3U8/XSPF)
     playlist_mode: bool,
@@ -442,18 +682,73 @@ struct IPTVApp {
     
     // Console log
     console_log: Vec<String>,
+    recent_failures: Vec<FailureRecord>, // Newest last, capped at MAX_RECENT_FAILURES
+    epg_coverage: Option<EpgCoverage>, // Last "Check coverage" report in the EPG dialog
     
     // Player process management
     single_window_mode: bool,
+    always_on_top: bool,
+    low_data_mode: bool, // Pauses logos, probing and auto-updates without changing those settings
+    show_container_labels: bool, // TS / HLS / MP4 ... next to streams in lists
+    show_channel_logos: bool, // Logo column in channel lists
+    choose_hls_quality: bool, // Ask which variant to play for multi-bitrate .m3u8 streams
+    hls_variant_choice: Option<(Channel, Vec<m3u_parser::HlsVariant>)>, // Quality chooser being shown
+    hls_checked_url: Option<String>, // Next playback of this URL skips the variant check
+    applied_always_on_top: Option<bool>, // Last window level sent to the viewport
+    window_layout: Option<([f32; 2], [f32; 2])>, // Inner size and outer position, saved on exit
+    window_position_checked: bool, // Restored position verified to be on a monitor
     current_player: Option<std::process::Child>,
+    playlist_server: Option<PlaylistServer>, // LAN /playlist.m3u, when enabled
+    serve_playlist: bool,
+    serve_playlist_addr: String,
+    served_playlist_signature: u64, // Re-render the served playlist when this changes
+    channel_list_view: ChannelListView, // Cached row order of the channel list
+    airing_favorites: AiringFavorites,
+    app_locked: bool, // Startup PIN not entered yet - nothing else runs
+    pin_input: String,
+    pin_attempts: u32,
+    new_pin_input: String,
+    confirm_pin_input: String,
+    vault_locked: bool, // Saved passwords are encrypted and the passphrase wasn't entered yet
+    vault_passphrase_input: String,
+    vault_unlock_failed: bool,
+    new_vault_passphrase: String,
+    confirm_vault_passphrase: String,
+    detached_players: std::sync::Arc<std::sync::Mutex<HashMap<u32, std::process::Child>>>, // Multi-window players, by PID
+    launch_intent: Option<LaunchIntent>, // From the command line, handled on the first frame
+    instance: Option<InstanceGuard>, // Single-instance lock, receives options from later launches
+    config_save_due: Option<std::time::Instant>, // Deferred config.save()
+    playlists_save_due: Option<std::time::Instant>, // Deferred save_playlist_entries()
+    active_tasks: Vec<(TaskKind, String)>, // Running fetches shown next to the spinner
+    playlist_progress: Option<(u64, Option<u64>)>, // (downloaded, total) bytes of the playlist being loaded
+    playlist_cancel: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>, // Set to abort in-flight playlist loads
+    last_launch: Option<(u32, std::time::Instant, Channel)>, // (pid, launched at, channel) for failover
     
     // Hardware acceleration
     hw_accel: bool,
     
     // Internal player
     use_internal_player: bool,
-    internal_player: PlayerWindow,
+    internal_player: std::sync::Arc<std::sync::Mutex<PlayerWindow>>, // Shared with the pop-out viewport
+    internal_player_closed: std::sync::Arc<std::sync::atomic::AtomicBool>, // Set when the pop-out window is closed
     show_internal_player: bool,
+    internal_player_viewport: bool, // Separate OS window instead of in-app window
+    internal_player_max_retries: u32,
+    autoplay_next_episode: bool,
+    playing_episode: Option<(i64, i64, String)>, // Series id, episode id and URL of the last episode started
+    
+    // Subscription expiry warning
+    expiry_warning_days: u32,
+    expiry_banner_dismissed: bool,
+    
+    // Movie/series detail panel
+    image_cache: ImageCache,
+    fetch_pool: FetchPool,
+    epg_pool: FetchPool, // EPG downloads run long - kept out of fetch_pool so browsing isn't starved
+    max_concurrent_fetches: usize,
+    content_detail: Option<ContentDetail>,
+    content_detail_channel: Option<Channel>, // Playable movie for the detail panel
+    show_content_detail: bool,
     
     // EPG state
     show_epg_dialog: bool,
@@ -467,26 +762,41 @@ struct IPTVApp {
     epg_last_update: Option<i64>,
     epg_last_ui_refresh: i64,
     epg_show_actual_time: bool, // false = offset mode (Now, +30m), true = actual time (8:00 PM)
+    epg_show_next_inline: bool, // Append the next program to inline EPG info in lists
+    epg_category_colors: Vec<EpgCategoryColor>, // Grid tint per program category
+    epg_grid_window_hours: u32, // Visible span of the timeline grid
+    epg_grid_step_mins: u32, // Time axis tick spacing (also the ◀/▶ step)
+    epg_grid_full_day: bool, // Scroll through the whole day instead of a fixed window from now
     epg_load_on_startup: bool,
     epg_panel_visible: bool, // Show/hide EPG panel in main window
     selected_epg_channel: Option<String>,
+    selected_epg_program: Option<i64>, // Start of the clicked program on that channel (None = the one airing now)
+    epg_grid_offset_secs: i64, // Horizontal scroll of the EPG timeline (relative to now)
+    epg_grid_scroll_to_top: bool, // Reset the EPG grid's vertical scroll on the next frame
+    epg_preview_time: Option<i64>, // Pretend "now" for the EPG grid only (playback and reminders stay live)
+    epg_preview_input: String,
+    zap_index: Option<usize>, // Position in the live favorites while zapping with PageUp/PageDown
+    playing_url: Option<String>, // Last stream started with play_channel - zapping continues from it
     // Auto-update throttling (check once per minute instead of every frame)
     last_auto_update_check: i64,
     // UI settings
     channel_name_width: f32,
     list_layout: ListLayout,
+    vod_view_mode: ViewMode,
     font_size_setting: FontSize,
+    ui_scale: UiScale,
+    applied_ui_scale: Option<UiScale>, // Last zoom sent to egui
 }
 
 impl Default for IPTVApp {
     fn default() -> Self {
-        Self::new()
+        Self::new(AppConfig::load())
     }
 }
 
 impl IPTVApp {
-    fn new() -> Self {
-        let config = AppConfig::load();
+    fn new(config: AppConfig) -> Self {
+        let window_position_checked = config.window_position.is_none();
         let address_book = load_address_book(); // Legacy
         let playlist_entries = load_playlist_entries();
         let (task_sender, task_receiver) = channel();
@@ -523,9 +833,46 @@ impl IPTVApp {
             Vec::new()
         };
         
+        // Load EPG reminders from JSON
+        let reminders: Vec<ProgramReminder> = if !config.reminders_json.is_empty() {
+            serde_json::from_str(&config.reminders_json).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        
         // Extract values - prefer playlist-specific settings over global config
         let single_window_mode = config.single_window_mode;
+        let always_on_top = config.always_on_top;
+        let low_data_mode = config.low_data_mode;
+        let show_container_labels = config.show_container_labels;
+        let show_channel_logos = config.show_channel_logos;
+        let choose_hls_quality = config.choose_hls_quality;
+        let channel_aliases = config.channel_aliases.clone();
+        let hidden_channels = config.hidden_channels.clone();
+        let favorite_programs = config.favorite_programs.clone();
+        let playlists_dir = config.playlists_dir.clone();
+        let recording_dir = config.recording_dir.clone();
+        let serve_playlist_addr = config.serve_playlist_addr.clone();
+        let app_locked = !config.app_pin_hash.is_empty();
+        let vault_locked = !config.password_vault.is_empty()
+            || has_sealed_passwords(&playlist_entries)
+            || vault::is_sealed(&config.saved_password);
+        let (playlist_server, playlist_server_error) = if config.serve_playlist {
+            match PlaylistServer::start(&config.serve_playlist_addr) {
+                Ok(server) => (Some(server), None),
+                Err(e) => (None, Some(e)),
+            }
+        } else {
+            (None, None)
+        };
+        let serve_playlist = playlist_server.is_some();
+        let play_counts = config.play_counts.clone();
+        let max_concurrent_fetches = config.max_concurrent_fetches;
         let hw_accel = config.hw_accel;
+        let internal_player_viewport = config.internal_player_viewport;
+        let internal_player_max_retries = config.internal_player_max_retries;
+        let autoplay_next_episode = config.autoplay_next_episode;
+        let expiry_warning_days = config.expiry_warning_days;
         
         // Use per-playlist EPG settings if available, otherwise fall back to global config
         let (epg_url, epg_auto_update_index, epg_time_offset, epg_show_actual_time) = 
@@ -540,22 +887,48 @@ impl IPTVApp {
                 (config.epg_url.clone(), config.epg_auto_update_index, config.epg_time_offset, config.epg_show_actual_time)
             };
         let epg_load_on_startup = config.epg_load_on_startup;
+        let epg_show_next_inline = config.epg_show_next_inline;
+        let epg_category_colors = config.epg_category_colors.clone();
+        let epg_grid_window_hours = config.epg_grid_window_hours;
+        let epg_grid_step_mins = config.epg_grid_step_mins;
+        let epg_grid_full_day = config.epg_grid_full_day;
         let channel_name_width = config.channel_name_width;
         let list_layout = config.list_layout;
+        let vod_view_mode = config.vod_view_mode;
         let font_size_setting = config.font_size_setting;
+        let ui_scale = config.ui_scale;
         
         // Use per-playlist player settings if available
-        let (external_player, buffer_seconds, connection_quality) = 
+        let (external_player, player_extra_args, buffer_seconds, connection_quality) = 
             if let Some(ref ps) = playlist_settings {
                 (
                     if ps.external_player.is_empty() { config.external_player.clone() } else { ps.external_player.clone() },
+                    if ps.player_extra_args.trim().is_empty() { config.player_extra_args.clone() } else { ps.player_extra_args.clone() },
                     ps.buffer_seconds,
                     ps.connection_quality,
                 )
             } else {
-                (config.external_player.clone(), config.buffer_seconds, config.connection_quality)
+                (config.external_player.clone(), config.player_extra_args.clone(), config.buffer_seconds, config.connection_quality)
             };
         
+        // Use per-playlist network timeouts if available
+        let (connect_timeout_secs, read_timeout_secs) = match playlist_settings {
+            Some(ref ps) => (ps.connect_timeout_secs, ps.read_timeout_secs),
+            None => (config.connect_timeout_secs, config.read_timeout_secs),
+        };
+        let (force_ipv4, ip_override) = match playlist_settings {
+            Some(ref ps) => (ps.force_ipv4, ps.ip_override.clone()),
+            None => (config.force_ipv4, config.ip_override.clone()),
+        };
+        let live_hls = playlist_settings.as_ref().map_or(config.live_hls, |ps| ps.live_hls);
+        let logo_url_template = playlist_settings.as_ref()
+            .map_or_else(|| config.logo_url_template.clone(), |ps| ps.logo_url_template.clone());
+        let (audio_language, subtitle_language) = match playlist_settings {
+            Some(ref ps) => (ps.audio_language.clone(), ps.subtitle_language.clone()),
+            None => (config.audio_language.clone(), config.subtitle_language.clone()),
+        };
+        let use_internal_player = playlist_settings.as_ref().is_some_and(|ps| ps.use_internal_player);
+        
         // Use per-playlist user agent settings if available
         let (selected_user_agent, custom_user_agent, use_custom_user_agent, pass_user_agent_to_player) = 
             if let Some(ref ps) = playlist_settings {
@@ -568,8 +941,19 @@ impl IPTVApp {
             } else {
                 (config.selected_user_agent, config.custom_user_agent.clone(), config.use_custom_user_agent, config.pass_user_agent_to_player)
             };
+        let (separate_player_user_agent, player_selected_user_agent, player_custom_user_agent, player_use_custom_user_agent) =
+            if let Some(ref ps) = playlist_settings {
+                (
+                    ps.separate_player_user_agent,
+                    ps.player_selected_user_agent,
+                    ps.player_custom_user_agent.clone(),
+                    ps.player_use_custom_user_agent,
+                )
+            } else {
+                (config.separate_player_user_agent, config.player_selected_user_agent, config.player_custom_user_agent.clone(), config.player_use_custom_user_agent)
+            };
         
-        Self {
+        let mut app = Self {
             server,
             username,
             password,
@@ -590,15 +974,21 @@ impl IPTVApp {
             current_series: Vec::new(),
             current_seasons: Vec::new(),
             current_episodes: Vec::new(),
+            series_episodes: None,
             live_sort_order: config.live_sort_order,
             movie_sort_order: config.movie_sort_order,
             series_sort_order: config.series_sort_order,
+            favorites_sort_order: config.favorites_sort_order,
+            episodes_by_air_date: config.episodes_by_air_date,
+            remember_sort_per_category: config.remember_sort_per_category,
+            current_category_id: None,
             favorites,
             fav_viewing_series: None,
             fav_series_seasons: Vec::new(),
             fav_series_episodes: Vec::new(),
             fav_viewing_season: None,
             recent_watched,
+            reminders,
             navigation_stack: Vec::new(),
             scroll_positions: Vec::new(),
             pending_scroll_restore: None,
@@ -606,36 +996,138 @@ impl IPTVApp {
             user_info: UserInfo::default(),
             server_info: ServerInfo::default(),
             search_query: String::new(),
+            search_regex: false,
+            search_filter: search::SearchQuery::default(),
+            movie_genre_filter: None,
+            quality_filter: None,
+            series_genre_filter: None,
+            hidden_channels,
+            show_hidden: false,
+            favorite_programs,
+            playlists_dir,
+            playlists_dir_files: None,
+            recording_dir,
+            recordings: Vec::new(),
+            my_shows_only: false,
+            show_my_shows: false,
+            my_show_input: String::new(),
+            favorite_categories: Vec::new(),
+            category_sort_orders: HashMap::new(),
+            selection_mode: false,
+            channel_aliases,
+            play_counts,
+            alias_edit: None,
+            category_counts: HashMap::new(),
+            category_counts_prefetching: false,
+            stream_probe: None,
+            selected_urls: HashSet::new(),
             external_player,
+            player_extra_args,
             buffer_seconds,
+            connect_timeout_secs,
+            read_timeout_secs,
+            force_ipv4,
+            ip_override,
+            live_hls,
+            logo_url_template,
+            audio_language,
+            subtitle_language,
             connection_quality,
             dark_mode: config.dark_mode,
             use_post_method: false,
             save_state: config.save_state,
             auto_login: config.auto_login,
             auto_login_triggered: false,
+            startup_action: config.startup_action,
             selected_user_agent,
             custom_user_agent,
             use_custom_user_agent,
             pass_user_agent_to_player,
+            separate_player_user_agent,
+            player_selected_user_agent,
+            player_custom_user_agent,
+            player_use_custom_user_agent,
+            ua_dialog_editing_player: false,
             show_user_agent_dialog: false,
+            show_settings_dialog: false,
             config,
             address_book,
             playlist_entries,
             current_playlist_idx: None,
+            active_playlist_name: None,
+            previous_playlist_name: None,
             show_playlist_manager: false,
             playlist_name_input: String::new(),
             playlist_url_input: String::new(),
+            stalker_portal_input: String::new(),
+            stalker_mac_input: String::new(),
+            stalker_sessions: HashMap::new(),
             show_reset_confirm: false,
+            show_merge_favorites_confirm: false,
             playlist_mode: false,
             playlist_sources: Vec::new(),
             console_log: vec!["[INFO] Xtreme IPTV Player started".to_string()],
+            recent_failures: Vec::new(),
+            epg_coverage: None,
             single_window_mode,
+            always_on_top,
+            low_data_mode,
+            show_container_labels,
+            show_channel_logos,
+            choose_hls_quality,
+            hls_variant_choice: None,
+            hls_checked_url: None,
+            applied_always_on_top: None,
+            window_layout: None,
+            window_position_checked,
             current_player: None,
+            playlist_server,
+            serve_playlist,
+            serve_playlist_addr,
+            served_playlist_signature: 0,
+            channel_list_view: ChannelListView::default(),
+            airing_favorites: AiringFavorites::default(),
+            app_locked,
+            pin_input: String::new(),
+            pin_attempts: 0,
+            new_pin_input: String::new(),
+            confirm_pin_input: String::new(),
+            vault_locked,
+            vault_passphrase_input: String::new(),
+            vault_unlock_failed: false,
+            new_vault_passphrase: String::new(),
+            confirm_vault_passphrase: String::new(),
+            detached_players: Default::default(),
+            launch_intent: None,
+            instance: None,
+            config_save_due: None,
+            playlists_save_due: None,
+            active_tasks: Vec::new(),
+            playlist_progress: None,
+            playlist_cancel: None,
+            last_launch: None,
             hw_accel,
-            use_internal_player: false,
-            internal_player: PlayerWindow::new(),
+            use_internal_player,
+            internal_player: std::sync::Arc::new(std::sync::Mutex::new(PlayerWindow::new())),
+            internal_player_closed: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
             show_internal_player: false,
+            internal_player_viewport,
+            internal_player_max_retries,
+            autoplay_next_episode,
+            playing_episode: None,
+            expiry_warning_days,
+            expiry_banner_dismissed: false,
+            image_cache: {
+                let mut cache = ImageCache::new();
+                cache.set_enabled(!low_data_mode);
+                cache
+            },
+            fetch_pool: FetchPool::new(max_concurrent_fetches),
+            epg_pool: FetchPool::new(1),
+            max_concurrent_fetches,
+            content_detail: None,
+            content_detail_channel: None,
+            show_content_detail: false,
             
             // EPG state
             show_epg_dialog: false,
@@ -649,14 +1141,35 @@ impl IPTVApp {
             epg_last_update: None,
             epg_last_ui_refresh: 0,
             epg_show_actual_time: epg_show_actual_time,
+            epg_show_next_inline,
+            epg_category_colors,
+            epg_grid_window_hours,
+            epg_grid_step_mins,
+            epg_grid_full_day,
             epg_load_on_startup: epg_load_on_startup,
             epg_panel_visible: true, // Show EPG panel by default
             selected_epg_channel: None,
+            selected_epg_program: None,
+            epg_grid_offset_secs: 0,
+            epg_grid_scroll_to_top: false,
+            epg_preview_time: None,
+            epg_preview_input: String::new(),
+            zap_index: None,
+            playing_url: None,
             last_auto_update_check: 0,
             channel_name_width,
             list_layout,
+            vod_view_mode,
             font_size_setting,
+            ui_scale,
+            applied_ui_scale: None,
+        };
+        
+        if let Some(e) = playlist_server_error {
+            app.log(&format!("[ERROR] Playlist server: {}", e));
+            app.status_message = format!("Playlist server: {}", e);
         }
+        app
     }
     
     fn log(&mut self, message: &str) {
@@ -668,6 +1181,23 @@ impl IPTVApp {
         }
     }
     
+    /// Remember a failure for the Info tab, tagged with the open playlist
+    fn record_failure(&mut self, what: &str, message: &str) {
+        let playlist = self.find_current_playlist_idx()
+            .and_then(|idx| self.playlist_entries.get(idx))
+            .map(|e| e.name.clone())
+            .or_else(|| self.playlist_sources.last().map(|(_, name)| name.clone()));
+        self.recent_failures.push(FailureRecord {
+            time: unix_timestamp(),
+            what: what.to_string(),
+            message: message.trim().to_string(),
+            playlist,
+        });
+        if self.recent_failures.len() > MAX_RECENT_FAILURES {
+            self.recent_failures.remove(0);
+        }
+    }
+    
     /// Find the index of the current Xtream playlist entry (caches result)
     fn find_current_playlist_idx(&mut self) -> Option<usize> {
         // Return cached index if still valid
@@ -704,10 +1234,11 @@ impl IPTVApp {
     /// Create an Xtream PlaylistEntry from current app state
     fn create_xtream_entry_from_state(&self) -> PlaylistEntry {
         let now = unix_timestamp();
+        let server = normalize_server_url(&self.server);
         PlaylistEntry {
-            name: format!("{}@{}", self.username, self.server.split('/').nth(2).unwrap_or(&self.server)),
+            name: format!("{}@{}", self.username, server.split('/').nth(2).unwrap_or(&server)),
             entry_type: PlaylistType::Xtream {
-                server: self.server.clone(),
+                server,
                 username: self.username.clone(),
                 password: self.password.clone(),
             },
@@ -723,39 +1254,85 @@ impl IPTVApp {
             epg_last_updated: 0,
             external_player: self.external_player.clone(),
             buffer_seconds: self.buffer_seconds,
+            connect_timeout_secs: self.connect_timeout_secs,
+            read_timeout_secs: self.read_timeout_secs,
+            force_ipv4: self.force_ipv4,
+            ip_override: self.ip_override.clone(),
+            live_hls: self.live_hls,
+            logo_url_template: self.logo_url_template.clone(),
+            audio_language: self.audio_language.clone(),
+            subtitle_language: self.subtitle_language.clone(),
             connection_quality: self.connection_quality,
+            player_extra_args: self.player_extra_args.clone(),
+            use_internal_player: self.use_internal_player,
             selected_user_agent: self.selected_user_agent,
             custom_user_agent: self.custom_user_agent.clone(),
             use_custom_user_agent: self.use_custom_user_agent,
             pass_user_agent_to_player: self.pass_user_agent_to_player,
+            separate_player_user_agent: self.separate_player_user_agent,
+            player_selected_user_agent: self.player_selected_user_agent,
+            player_custom_user_agent: self.player_custom_user_agent.clone(),
+            player_use_custom_user_agent: self.player_use_custom_user_agent,
+            favorite_categories: self.favorite_categories.clone(),
+            category_sort_orders: self.category_sort_orders.clone(),
         }
     }
     
     fn save_current_state(&mut self) {
         self.config.save_state = self.save_state;
         self.config.auto_login = self.auto_login;
+        self.config.startup_action = self.startup_action;
         self.config.external_player = self.external_player.clone();
+        self.config.player_extra_args = self.player_extra_args.clone();
         self.config.buffer_seconds = self.buffer_seconds;
+        self.config.connect_timeout_secs = self.connect_timeout_secs;
+        self.config.read_timeout_secs = self.read_timeout_secs;
+        self.config.force_ipv4 = self.force_ipv4;
+        self.config.ip_override = self.ip_override.clone();
+        self.config.live_hls = self.live_hls;
+        self.config.logo_url_template = self.logo_url_template.clone();
+        self.config.audio_language = self.audio_language.clone();
+        self.config.subtitle_language = self.subtitle_language.clone();
         self.config.connection_quality = self.connection_quality;
         self.config.dark_mode = self.dark_mode;
         self.config.single_window_mode = self.single_window_mode;
+        self.config.always_on_top = self.always_on_top;
+        self.config.low_data_mode = self.low_data_mode;
+        self.config.show_container_labels = self.show_container_labels;
+        self.config.show_channel_logos = self.show_channel_logos;
+        self.config.choose_hls_quality = self.choose_hls_quality;
         self.config.hw_accel = self.hw_accel;
+        self.config.internal_player_viewport = self.internal_player_viewport;
+        self.config.internal_player_max_retries = self.internal_player_max_retries;
+        self.config.autoplay_next_episode = self.autoplay_next_episode;
+        self.config.expiry_warning_days = self.expiry_warning_days;
         self.config.selected_user_agent = self.selected_user_agent;
         self.config.custom_user_agent = self.custom_user_agent.clone();
         self.config.use_custom_user_agent = self.use_custom_user_agent;
         self.config.pass_user_agent_to_player = self.pass_user_agent_to_player;
+        self.config.separate_player_user_agent = self.separate_player_user_agent;
+        self.config.player_selected_user_agent = self.player_selected_user_agent;
+        self.config.player_custom_user_agent = self.player_custom_user_agent.clone();
+        self.config.player_use_custom_user_agent = self.player_use_custom_user_agent;
         
         // Save EPG settings
         self.config.epg_url = self.epg_url_input.clone();
         self.config.epg_auto_update_index = self.epg_auto_update.to_index();
         self.config.epg_time_offset = self.epg_time_offset;
         self.config.epg_show_actual_time = self.epg_show_actual_time;
+        self.config.epg_show_next_inline = self.epg_show_next_inline;
+        self.config.epg_category_colors = self.epg_category_colors.clone();
+        self.config.epg_grid_window_hours = self.epg_grid_window_hours;
+        self.config.epg_grid_step_mins = self.epg_grid_step_mins;
+        self.config.epg_grid_full_day = self.epg_grid_full_day;
         self.config.epg_load_on_startup = self.epg_load_on_startup;
         
         // Save UI settings
         self.config.channel_name_width = self.channel_name_width;
         self.config.list_layout = self.list_layout;
+        self.config.vod_view_mode = self.vod_view_mode;
         self.config.font_size_setting = self.font_size_setting;
+        self.config.ui_scale = self.ui_scale;
         
         // Save favorites
         self.config.favorites_json = serde_json::to_string(&self.favorites).unwrap_or_default();
@@ -825,6 +1402,15 @@ impl IPTVApp {
         // Clear favorites and recent
         self.favorites.clear();
         self.recent_watched.clear();
+        self.reminders.clear();
+        self.favorite_categories.clear();
+        self.category_sort_orders.clear();
+        self.hidden_channels.clear();
+        self.favorite_programs.clear();
+        self.my_shows_only = false;
+        self.playlists_dir.clear();
+        self.playlists_dir_files = None;
+        self.recording_dir.clear();
         
         // Clear EPG
         self.epg_data = None;
@@ -833,22 +1419,55 @@ impl IPTVApp {
         self.epg_time_offset = 0.0;
         self.epg_auto_update = EpgAutoUpdate::Day1;
         self.epg_show_actual_time = false;
+        self.epg_show_next_inline = false;
+        self.epg_category_colors = default_epg_category_colors();
+        self.epg_grid_window_hours = 4;
+        self.epg_grid_step_mins = 30;
+        self.epg_grid_full_day = false;
         self.selected_epg_channel = None;
+        self.selected_epg_program = None;
         
         // Reset player settings to defaults
         self.external_player.clear();
+        self.player_extra_args.clear();
         self.buffer_seconds = 5;
+        self.connect_timeout_secs = 30;
+        self.read_timeout_secs = 60;
+        self.force_ipv4 = false;
+        self.ip_override.clear();
+        self.set_playlist_server(false);
+        self.serve_playlist_addr = default_serve_playlist_addr();
+        self.live_hls = false;
+        self.logo_url_template.clear();
+        self.audio_language.clear();
+        self.subtitle_language.clear();
         self.connection_quality = ConnectionQuality::Normal;
         self.hw_accel = true;
         self.single_window_mode = true;
+        self.always_on_top = false;
+        self.show_container_labels = true;
+        self.show_channel_logos = false;
+        self.choose_hls_quality = false;
+        self.set_low_data_mode(false);
+        self.internal_player_viewport = false;
+        self.internal_player_max_retries = 3;
+        self.autoplay_next_episode = true;
+        self.expiry_warning_days = 7;
         
         // Reset user agent to defaults
         self.selected_user_agent = 0;
         self.custom_user_agent.clear();
         self.use_custom_user_agent = false;
         self.pass_user_agent_to_player = true;
+        self.separate_player_user_agent = false;
+        self.player_selected_user_agent = 0;
+        self.player_custom_user_agent.clear();
+        self.player_use_custom_user_agent = false;
         self.use_post_method = false;
         
+        // Reset startup behavior
+        self.startup_action = StartupAction::default();
+        
         // Clear current state
         self.live_categories.clear();
         self.movie_categories.clear();
@@ -857,19 +1476,52 @@ impl IPTVApp {
         self.current_series.clear();
         self.current_seasons.clear();
         self.current_episodes.clear();
+        self.series_episodes = None;
         self.navigation_stack.clear();
         self.scroll_positions.clear();
         self.playlist_sources.clear();
         self.playlist_mode = false;
         self.logged_in = false;
         
-        // Reset config and save
+        // Reset config and save (the startup PIN and password encryption are only removed explicitly)
+        let app_pin_hash = std::mem::take(&mut self.config.app_pin_hash);
+        let password_vault = std::mem::take(&mut self.config.password_vault);
         self.config = AppConfig::default();
+        self.config.app_pin_hash = app_pin_hash;
+        self.config.password_vault = password_vault;
         self.config.save();
         
         self.log("All settings reset to defaults");
     }
     
+    /// Save the config after a short quiet period instead of right away
+    fn save_config_soon(&mut self) {
+        self.config_save_due = Some(std::time::Instant::now() + DEFERRED_SAVE_DELAY);
+    }
+    
+    /// Save the playlist entries after a short quiet period instead of right away
+    fn save_playlists_soon(&mut self) {
+        self.playlists_save_due = Some(std::time::Instant::now() + DEFERRED_SAVE_DELAY);
+    }
+    
+    /// Write deferred saves that are due (all of them when `force`).
+    /// Returns how long until the next one is due.
+    fn flush_deferred_saves(&mut self, force: bool) -> Option<std::time::Duration> {
+        let now = std::time::Instant::now();
+        if self.config_save_due.is_some_and(|due| force || due <= now) {
+            self.config_save_due = None;
+            self.config.save();
+        }
+        if self.playlists_save_due.is_some_and(|due| force || due <= now) {
+            self.playlists_save_due = None;
+            save_playlist_entries(&self.playlist_entries);
+        }
+        [self.config_save_due, self.playlists_save_due].into_iter()
+            .flatten()
+            .min()
+            .map(|due| due.saturating_duration_since(now))
+    }
+    
     fn is_favorite(&self, url: &str) -> bool {
         self.favorites.iter().any(|f| f.url == url)
     }
@@ -883,9 +1535,128 @@ impl IPTVApp {
             self.status_message = format!("Added '{}' to favorites", item.name);
             self.favorites.push(item);
         }
-        // Auto-save favorites
+        // Auto-save favorites (rapid toggles are written once)
+        self.config.favorites_json = serde_json::to_string(&self.favorites).unwrap_or_default();
+        self.save_config_soon();
+    }
+    
+    /// Check if a category is pinned to the top of the list
+    fn is_favorite_category(&self, stream_type: &str, category_id: &str) -> bool {
+        self.favorite_categories.iter().any(|k| {
+            k.split_once(':') == Some((stream_type, category_id))
+        })
+    }
+    
+    /// Pin/unpin a category and persist it to the current playlist entry
+    fn toggle_favorite_category(&mut self, stream_type: &str, category_id: &str, category_name: &str) {
+        let key = format!("{}:{}", stream_type, category_id);
+        if let Some(pos) = self.favorite_categories.iter().position(|k| *k == key) {
+            self.favorite_categories.remove(pos);
+            self.status_message = format!("Unpinned category '{}'", category_name);
+        } else {
+            self.favorite_categories.push(key);
+            self.status_message = format!("Pinned category '{}'", category_name);
+        }
+        
+        if let Some(idx) = self.find_current_playlist_idx() {
+            self.playlist_entries[idx].favorite_categories = self.favorite_categories.clone();
+            save_playlist_entries(&self.playlist_entries);
+        }
+    }
+    
+    /// Key into `category_sort_orders` for the category being browsed, if sorting is remembered per category
+    fn category_sort_key(&self, stream_type: &str) -> Option<String> {
+        if !self.remember_sort_per_category || self.playlist_mode {
+            return None;
+        }
+        let in_category = match stream_type {
+            "series" => matches!(self.navigation_stack.last(), Some(NavigationLevel::Series(_))),
+            _ => matches!(self.navigation_stack.last(), Some(NavigationLevel::Channels(_))),
+        };
+        let category_id = self.current_category_id.as_ref().filter(|_| in_category)?;
+        Some(format!("{}:{}", stream_type, category_id))
+    }
+    
+    /// Sort order for the list being shown - the category's own when remembered, else the tab default
+    fn effective_sort_order(&self, stream_type: &str) -> SortOrder {
+        let default = match stream_type {
+            "live" => self.live_sort_order,
+            "movie" => self.movie_sort_order,
+            "series" => self.series_sort_order,
+            _ => SortOrder::Default,
+        };
+        self.category_sort_key(stream_type)
+            .and_then(|key| self.category_sort_orders.get(&key).copied())
+            .unwrap_or(default)
+    }
+    
+    /// Store a sort order chosen from the top bar - per category when remembered, else for the whole tab
+    fn set_sort_order(&mut self, stream_type: &str, order: SortOrder) {
+        if let Some(key) = self.category_sort_key(stream_type) {
+            self.category_sort_orders.insert(key, order);
+            if let Some(idx) = self.find_current_playlist_idx() {
+                self.playlist_entries[idx].category_sort_orders = self.category_sort_orders.clone();
+                save_playlist_entries(&self.playlist_entries);
+            }
+            return;
+        }
+        match stream_type {
+            "live" => {
+                self.live_sort_order = order;
+                self.config.live_sort_order = order;
+            }
+            "movie" => {
+                self.movie_sort_order = order;
+                self.config.movie_sort_order = order;
+            }
+            "series" => {
+                self.series_sort_order = order;
+                self.config.series_sort_order = order;
+            }
+            _ => return,
+        }
+        self.config.save();
+    }
+    
+    /// Add multiple favorites with a single save
+    fn add_favorites_bulk(&mut self, items: Vec<FavoriteItem>) {
+        let mut added = 0;
+        for item in items {
+            if !self.is_favorite(&item.url) {
+                self.favorites.push(item);
+                added += 1;
+            }
+        }
+        self.config.favorites_json = serde_json::to_string(&self.favorites).unwrap_or_default();
+        self.config.save();
+        self.status_message = format!("Added {} items to favorites", added);
+        self.log(&format!("[INFO] Added {} items to favorites", added));
+    }
+    
+    /// Remove multiple favorites (by URL) with a single save
+    fn remove_favorites_bulk(&mut self, urls: &HashSet<String>) {
+        let before = self.favorites.len();
+        self.favorites.retain(|f| !urls.contains(&f.url));
+        let removed = before - self.favorites.len();
         self.config.favorites_json = serde_json::to_string(&self.favorites).unwrap_or_default();
         self.config.save();
+        self.status_message = format!("Removed {} items from favorites", removed);
+        self.log(&format!("[INFO] Removed {} items from favorites", removed));
+    }
+    
+    /// Remove near-duplicate favorites, keeping the newest of each
+    fn merge_duplicate_favorites(&mut self) {
+        let removed = merge_duplicate_favorites(&mut self.favorites);
+        if removed > 0 {
+            self.config.favorites_json = serde_json::to_string(&self.favorites).unwrap_or_default();
+            self.config.save();
+            self.log(&format!("[INFO] Merged {} duplicate favorites", removed));
+        }
+        self.status_message = match removed {
+            0 => "No duplicate favorites found".to_string(),
+            1 => "Cleaned 1 duplicate favorite".to_string(),
+            n => format!("Cleaned {} duplicate favorites", n),
+        };
     }
     
     fn play_favorite(&mut self, fav: &FavoriteItem) {
@@ -899,7 +1670,7 @@ impl IPTVApp {
             if let (Some(series_id), Some(stream_id), Some(_season), Some(_ep_num)) = 
                 (fav.series_id, fav.stream_id, fav.season_num, fav.episode_num) {
                 // Build episode URL directly to avoid navigation dependency
-                let container = fav.container_extension.clone().unwrap_or_else(|| "mp4".to_string());
+                let container = clean_container_extension(fav.container_extension.as_deref()).unwrap_or("mp4").to_string();
                 let url = format!(
                     "{}/series/{}/{}/{}.{}",
                     self.server, self.username, self.password,
@@ -917,6 +1688,14 @@ impl IPTVApp {
                     series_id: Some(series_id),
                     container_extension: Some(container),
                     playlist_source: fav.playlist_source.clone(),
+                    catchup: None,
+                    catchup_source: None,
+                    tvg_shift: None,
+                    alternate_urls: Vec::new(),
+                    archive_days: None,
+                    genre: None,
+                    user_agent: None,
+                    referrer: None,
                 };
                 
                 self.play_channel(&channel);
@@ -925,9 +1704,14 @@ impl IPTVApp {
         }
         
         // Handle live/movie favorites - play directly
+        let url = if fav.stream_type == "live" {
+            live_url_with_format(&fav.url, self.live_hls)
+        } else {
+            fav.url.clone()
+        };
         let channel = Channel {
             name: fav.name.clone(),
-            url: fav.url.clone(),
+            url,
             stream_id: fav.stream_id,
             category_id: None,
             epg_channel_id: None,
@@ -935,6 +1719,14 @@ impl IPTVApp {
             series_id: fav.series_id,
             container_extension: fav.container_extension.clone(),
             playlist_source: fav.playlist_source.clone(),
+            catchup: None,
+            catchup_source: None,
+            tvg_shift: None,
+            alternate_urls: Vec::new(),
+            archive_days: None,
+            genre: None,
+            user_agent: None,
+            referrer: None,
         };
         self.play_channel(&channel);
     }
@@ -975,50 +1767,472 @@ impl IPTVApp {
     }
     
     /// Display a fixed-width channel name with truncation and hover tooltip
-    fn show_channel_name(&self, ui: &mut egui::Ui, name: &str, width: f32, strong: bool) {
-        let display_name = Self::sanitize_text(name);
+    fn show_channel_name(&self, ui: &mut egui::Ui, url: &str, name: &str, width: f32, strong: bool) -> egui::Response {
+        let display_name = self.display_name(url, name);
         let truncated_name = Self::truncate_to_width(&display_name, width);
         let label = if strong {
-            egui::Label::new(egui::RichText::new(&truncated_name).strong()).sense(egui::Sense::hover())
+            egui::Label::new(egui::RichText::new(&truncated_name).strong()).sense(egui::Sense::click())
         } else {
-            egui::Label::new(&truncated_name).sense(egui::Sense::hover())
+            egui::Label::new(&truncated_name).sense(egui::Sense::click())
         };
         let name_response = ui.add_sized([width, 18.0], label);
         if truncated_name != display_name {
-            name_response.on_hover_text(&display_name);
+            name_response.on_hover_text(&display_name)
+        } else {
+            name_response
+        }
+    }
+    
+    /// Muted container label (TS / HLS / MP4 ...) for a stream, if enabled and recognised
+    fn show_container_label(&self, ui: &mut egui::Ui, url: &str, container_extension: Option<&str>) {
+        if !self.show_container_labels {
+            return;
+        }
+        if let Some(label) = stream_container_label(url, container_extension) {
+            ui.label(egui::RichText::new(label).small().weak());
+        }
+    }
+    
+    /// Square logo slot in a channel row - the logo is only requested once the
+    /// row is on screen, and a placeholder is drawn when there is none
+    fn show_channel_logo(ui: &mut egui::Ui, image_cache: &mut ImageCache, url: Option<&str>, size: f32) {
+        let (rect, _) = ui.allocate_exact_size(egui::vec2(size, size), egui::Sense::hover());
+        if !ui.is_rect_visible(rect) {
+            return;
+        }
+        let state = url.map_or(ImageState::Failed, |url| image_cache.get(ui.ctx(), url));
+        match state {
+            ImageState::Ready(texture) => {
+                let tex_size = texture.size_vec2();
+                let scale = (size / tex_size.x).min(size / tex_size.y);
+                egui::Image::new(&texture).paint_at(ui, egui::Rect::from_center_size(rect.center(), tex_size * scale));
+            }
+            ImageState::Loading => {}
+            ImageState::Failed => {
+                ui.painter().text(rect.center(), egui::Align2::CENTER_CENTER, "📺",
+                    egui::FontId::proportional(size * 0.7), ui.visuals().weak_text_color());
+            }
+        }
+    }
+    
+    /// "⏪" marker for live channels the provider keeps an archive of
+    fn show_archive_badge(ui: &mut egui::Ui, archive_days: Option<u32>) {
+        if let Some(days) = archive_days.filter(|d| *d > 0) {
+            ui.label(egui::RichText::new("⏪").small().weak())
+                .on_hover_text(format!("Archive available ({} day{})\nRight-click a past program in the EPG grid to replay it", days, if days == 1 { "" } else { "s" }));
+        }
+    }
+    
+    /// Colored SD/HD/FHD/4K tag for a quality found in the channel name
+    fn show_quality_badge(ui: &mut egui::Ui, quality: Quality) {
+        let color = match quality {
+            Quality::Unknown => return,
+            Quality::Sd => egui::Color32::GRAY,
+            Quality::Hd => egui::Color32::from_rgb(100, 149, 237),
+            Quality::Fhd => egui::Color32::from_rgb(60, 179, 113),
+            Quality::Uhd => egui::Color32::from_rgb(218, 165, 32),
+        };
+        ui.label(egui::RichText::new(quality.label()).small().strong().color(color).background_color(color.gamma_multiply(0.15)));
+    }
+    
+    /// Display name for a stream - the local alias if one is set, sanitized
+    fn display_name(&self, url: &str, name: &str) -> String {
+        Self::sanitize_text(self.channel_aliases.get(url).map(String::as_str).unwrap_or(name))
+    }
+    
+    /// Right-click menu on a channel name offering to rename it locally
+    fn rename_context_menu(response: &egui::Response, url: &str, name: &str, to_rename: &mut Option<(String, String)>) {
+        response.context_menu(|ui| {
+            if ui.button("✏ Rename...").clicked() {
+                *to_rename = Some((url.to_string(), name.to_string()));
+                ui.close();
+            }
+        });
+    }
+    
+    /// Right-click menu for streams in the category lists (rename + hide/unhide)
+    fn channel_context_menu(response: &egui::Response, url: &str, name: &str, is_hidden: bool,
+                            to_rename: &mut Option<(String, String)>, to_toggle_hidden: &mut Option<(String, String)>) {
+        response.context_menu(|ui| {
+            if ui.button("✏ Rename...").clicked() {
+                *to_rename = Some((url.to_string(), name.to_string()));
+                ui.close();
+            }
+            if ui.button(if is_hidden { "👁 Unhide" } else { "🚫 Hide" }).clicked() {
+                *to_toggle_hidden = Some((url.to_string(), name.to_string()));
+                ui.close();
+            }
+        });
+    }
+    
+    /// Right-click menu with just hide/unhide (series rows have nothing to rename)
+    fn hide_context_menu(response: &egui::Response, url: &str, name: &str, is_hidden: bool, to_toggle_hidden: &mut Option<(String, String)>) {
+        response.context_menu(|ui| {
+            if ui.button(if is_hidden { "👁 Unhide" } else { "🚫 Hide" }).clicked() {
+                *to_toggle_hidden = Some((url.to_string(), name.to_string()));
+                ui.close();
+            }
+        });
+    }
+    
+    fn is_hidden(&self, url: &str) -> bool {
+        self.hidden_channels.contains_key(url)
+    }
+    
+    /// Hidden-list key for a series ("series://host/user/id") - series ids are only unique per provider
+    fn series_hide_key(&self, series_id: i64) -> String {
+        let host = self.server.split("://").last().unwrap_or_default().trim_end_matches('/');
+        format!("series://{}/{}/{}", host, self.username, series_id)
+    }
+    
+    /// Hide a stream from all lists, or bring it back
+    fn toggle_hidden(&mut self, url: &str, name: &str) {
+        if self.hidden_channels.remove(url).is_some() {
+            self.status_message = format!("Unhid '{}'", Self::sanitize_text(name));
+        } else {
+            self.hidden_channels.insert(url.to_string(), name.to_string());
+            self.status_message = format!("Hid '{}' - use \"Show hidden\" to bring it back", Self::sanitize_text(name));
+        }
+        self.config.hidden_channels = self.hidden_channels.clone();
+        self.config.save();
+    }
+    
+    /// "Show hidden (N)" toggle - only drawn when the list has hidden items
+    fn show_hidden_toggle(ui: &mut egui::Ui, hidden_count: usize, show_hidden: &mut bool) {
+        if hidden_count == 0 && !*show_hidden {
+            return;
+        }
+        if ui.selectable_label(*show_hidden, format!("🚫 Show hidden ({})", hidden_count))
+            .on_hover_text("Include hidden streams so they can be unhidden (right-click → Unhide)")
+            .clicked()
+        {
+            *show_hidden = !*show_hidden;
+        }
+    }
+    
+    /// Open the rename dialog for a stream
+    fn start_rename(&mut self, url: String, name: String) {
+        let current = self.channel_aliases.get(&url).cloned().unwrap_or_else(|| name.clone());
+        self.alias_edit = Some((url, name, current));
+    }
+    
+    /// Set or clear (when empty or equal to the original) the alias for a stream
+    fn set_channel_alias(&mut self, url: &str, original: &str, alias: &str) {
+        let alias = alias.trim();
+        if alias.is_empty() || alias == original {
+            self.channel_aliases.remove(url);
+            self.status_message = format!("Restored original name '{}'", Self::sanitize_text(original));
+        } else {
+            self.channel_aliases.insert(url.to_string(), alias.to_string());
+            self.status_message = format!("Renamed '{}' to '{}'", Self::sanitize_text(original), Self::sanitize_text(alias));
+        }
+        self.channel_list_view.signature = 0;
+        self.config.channel_aliases = self.channel_aliases.clone();
+        self.config.save();
+    }
+
+    /// Draw a poster tile (image + title) for the poster grid view
+    fn show_poster_tile(&mut self, ui: &mut egui::Ui, title: &str, image_url: Option<&str>, is_fav: bool) -> egui::Response {
+        let title_height = 20.0;
+        let (rect, response) = ui.allocate_exact_size(
+            egui::vec2(POSTER_TILE_WIDTH, POSTER_TILE_HEIGHT + title_height),
+            egui::Sense::click(),
+        );
+        
+        // Don't request images for tiles scrolled out of view
+        if !ui.is_rect_visible(rect) {
+            return response;
+        }
+        
+        let poster_rect = egui::Rect::from_min_size(rect.min, egui::vec2(POSTER_TILE_WIDTH, POSTER_TILE_HEIGHT));
+        let state = match image_url {
+            Some(url) => self.image_cache.get(ui.ctx(), url),
+            None => ImageState::Failed,
+        };
+        
+        let painter = ui.painter();
+        painter.rect_filled(poster_rect, 4.0, ui.visuals().extreme_bg_color);
+        match state {
+            ImageState::Ready(texture) => {
+                // Fit inside the tile keeping aspect ratio
+                let tex_size = texture.size_vec2();
+                let scale = (poster_rect.width() / tex_size.x).min(poster_rect.height() / tex_size.y);
+                let image_rect = egui::Rect::from_center_size(poster_rect.center(), tex_size * scale);
+                egui::Image::new(&texture)
+                    .corner_radius(4.0)
+                    .paint_at(ui, image_rect);
+            }
+            ImageState::Loading => {
+                painter.text(poster_rect.center(), egui::Align2::CENTER_CENTER, "⏳",
+                    egui::FontId::proportional(24.0), egui::Color32::GRAY);
+            }
+            ImageState::Failed => {
+                painter.text(poster_rect.center(), egui::Align2::CENTER_CENTER, "🎬",
+                    egui::FontId::proportional(40.0), egui::Color32::GRAY);
+            }
+        }
+        
+        if is_fav {
+            painter.text(poster_rect.right_top() + egui::vec2(-6.0, 4.0), egui::Align2::RIGHT_TOP, "★",
+                egui::FontId::proportional(18.0), egui::Color32::GOLD);
+        }
+        
+        if response.hovered() {
+            painter.rect_stroke(poster_rect, 4.0,
+                egui::Stroke::new(2.0, ui.visuals().selection.stroke.color), egui::StrokeKind::Outside);
+        }
+        
+        let display_name = Self::sanitize_text(title);
+        let truncated = Self::truncate_to_width(&display_name, POSTER_TILE_WIDTH);
+        painter.text(
+            egui::pos2(poster_rect.center().x, poster_rect.bottom() + 3.0),
+            egui::Align2::CENTER_TOP,
+            &truncated,
+            egui::FontId::proportional(12.0),
+            ui.visuals().text_color(),
+        );
+        
+        response.on_hover_text(display_name)
+    }
+
+    fn window_level(&self) -> egui::WindowLevel {
+        if self.always_on_top {
+            egui::WindowLevel::AlwaysOnTop
+        } else {
+            egui::WindowLevel::Normal
         }
     }
 
+    fn player_window(&self) -> std::sync::MutexGuard<'_, PlayerWindow> {
+        self.internal_player.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
     fn get_effective_buffer(&self) -> u32 {
         self.connection_quality.buffer_seconds(self.buffer_seconds)
     }
 
-    fn get_user_agent(&self) -> String {
-        if self.use_custom_user_agent && !self.custom_user_agent.is_empty() {
-            self.custom_user_agent.clone()
-        } else if self.selected_user_agent < USER_AGENTS.len() {
-            USER_AGENTS[self.selected_user_agent].1.to_string()
+    fn resolve_user_agent(selected: usize, custom: &str, use_custom: bool) -> String {
+        if use_custom && !custom.is_empty() {
+            custom.to_string()
+        } else if selected < USER_AGENTS.len() {
+            USER_AGENTS[selected].1.to_string()
         } else {
             USER_AGENTS[0].1.to_string()
         }
     }
 
+    /// User agent for API requests (login, categories, EPG, images)
+    fn get_user_agent(&self) -> String {
+        Self::resolve_user_agent(self.selected_user_agent, &self.custom_user_agent, self.use_custom_user_agent)
+    }
+
+    /// User agent passed to the media player - falls back to the API user agent
+    fn get_player_user_agent(&self) -> String {
+        if self.separate_player_user_agent {
+            Self::resolve_user_agent(self.player_selected_user_agent, &self.player_custom_user_agent, self.player_use_custom_user_agent)
+        } else {
+            self.get_user_agent()
+        }
+    }
+    
+    /// `#EXTVLCOPT` user agent and referrer of an M3U channel. Favorites and
+    /// history entries don't carry them, so they're looked up in the loaded list.
+    fn stream_headers(&self, channel: &Channel) -> (Option<String>, Option<String>) {
+        if channel.user_agent.is_some() || channel.referrer.is_some() {
+            return (channel.user_agent.clone(), channel.referrer.clone());
+        }
+        self.current_channels.iter()
+            .find(|c| c.url == channel.url)
+            .map(|c| (c.user_agent.clone(), c.referrer.clone()))
+            .unwrap_or_default()
+    }
+
+    /// Perform the configured startup action (runs once per launch)
+    fn run_startup_action(&mut self) {
+        match self.startup_action {
+            StartupAction::OpenPlaylistManager => {
+                self.show_playlist_manager = true;
+            }
+            StartupAction::AutoLoginFirstEnabled => {
+                // Find first entry with auto_login enabled AND playlist enabled
+                let auto_login_idx = self.playlist_entries.iter().position(|e| {
+                    e.enabled && e.auto_login && matches!(e.entry_type, PlaylistType::Xtream { .. })
+                });
+                
+                if let Some(idx) = auto_login_idx {
+                    self.open_playlist_entry(idx);
+                } else if self.save_state && self.auto_login
+                    && !self.server.is_empty() && !self.username.is_empty() && !self.password.is_empty()
+                {
+                    // Fall back to legacy auto_login behavior
+                    self.invalidate_playlist_cache(); // Will be found on first lookup
+                    self.login();
+                }
+            }
+            StartupAction::LoadLastUsed => {
+                let last_name = self.config.last_playlist_name.clone();
+                let idx = self.playlist_entries.iter().position(|e| e.enabled && e.name == last_name);
+                if let Some(idx) = idx {
+                    self.open_playlist_entry(idx);
+                } else if !last_name.is_empty() {
+                    self.log(&format!("[WARN] Last used playlist '{}' not found or disabled", last_name));
+                }
+            }
+            StartupAction::DoNothing => {}
+        }
+    }
+    
+    /// Load a saved entry's player, connection and user agent settings into the app
+    fn apply_entry_player_settings(&mut self, idx: usize) {
+        let Some(entry) = self.playlist_entries.get(idx) else { return };
+        // Player settings
+        if !entry.external_player.is_empty() {
+            self.external_player = entry.external_player.clone();
+        }
+        // Empty means inherit the global arguments
+        self.player_extra_args = if entry.player_extra_args.trim().is_empty() {
+            self.config.player_extra_args.clone()
+        } else {
+            entry.player_extra_args.clone()
+        };
+        self.use_internal_player = entry.use_internal_player;
+        self.buffer_seconds = entry.buffer_seconds;
+        self.connection_quality = entry.connection_quality;
+        self.connect_timeout_secs = entry.connect_timeout_secs;
+        self.read_timeout_secs = entry.read_timeout_secs;
+        self.force_ipv4 = entry.force_ipv4;
+        self.ip_override = entry.ip_override.clone();
+        self.live_hls = entry.live_hls;
+        self.logo_url_template = entry.logo_url_template.clone();
+        self.audio_language = entry.audio_language.clone();
+        self.subtitle_language = entry.subtitle_language.clone();
+        // User agent settings
+        self.selected_user_agent = entry.selected_user_agent;
+        self.custom_user_agent = entry.custom_user_agent.clone();
+        self.use_custom_user_agent = entry.use_custom_user_agent;
+        self.pass_user_agent_to_player = entry.pass_user_agent_to_player;
+        self.separate_player_user_agent = entry.separate_player_user_agent;
+        self.player_selected_user_agent = entry.player_selected_user_agent;
+        self.player_custom_user_agent = entry.player_custom_user_agent.clone();
+        self.player_use_custom_user_agent = entry.player_use_custom_user_agent;
+    }
+    
+    /// Load a saved playlist entry - applies its settings and logs in (Xtream) or downloads it (M3U)
+    fn open_playlist_entry(&mut self, idx: usize) {
+        let Some(entry) = self.playlist_entries.get(idx).cloned() else {
+            return;
+        };
+        
+        match &entry.entry_type {
+            PlaylistType::Xtream { server, username, password } => {
+                self.current_playlist_idx = Some(idx); // Cache the index
+                // Server credentials - entries saved before URL normalization are fixed up so they still match
+                self.server = normalize_server_url(server);
+                if self.server != *server {
+                    self.playlist_entries[idx].entry_type = PlaylistType::Xtream {
+                        server: self.server.clone(),
+                        username: username.clone(),
+                        password: password.clone(),
+                    };
+                    save_playlist_entries(&self.playlist_entries);
+                }
+                self.username = username.clone();
+                self.password = password.clone();
+                // EPG settings
+                if !entry.epg_url.is_empty() {
+                    self.epg_url_input = entry.epg_url.clone();
+                }
+                self.epg_time_offset = entry.epg_time_offset;
+                self.epg_auto_update = EpgAutoUpdate::from_index(entry.epg_auto_update_index);
+                self.epg_show_actual_time = entry.epg_show_actual_time;
+                self.apply_entry_player_settings(idx);
+                self.login();
+            }
+            PlaylistType::M3U { url } => {
+                self.connect_timeout_secs = entry.connect_timeout_secs;
+                self.read_timeout_secs = entry.read_timeout_secs;
+                self.force_ipv4 = entry.force_ipv4;
+                self.ip_override = entry.ip_override.clone();
+                self.load_playlist_with_name(url, &entry.name);
+            }
+            PlaylistType::Stalker { portal, mac } => {
+                self.apply_entry_player_settings(idx);
+                self.login_stalker(portal, mac, &entry.name);
+            }
+        }
+        
+        if self.active_playlist_name.as_deref() != Some(entry.name.as_str()) {
+            self.previous_playlist_name = self.active_playlist_name.replace(entry.name.clone());
+        }
+        
+        // Remember for "Load last used playlist" on next startup
+        if self.config.last_playlist_name != entry.name {
+            self.config.last_playlist_name = entry.name.clone();
+            self.config.save();
+        }
+    }
+
+    /// Index of the previously opened playlist, if it still exists and is enabled
+    fn previous_playlist_idx(&self) -> Option<usize> {
+        let name = self.previous_playlist_name.as_deref()?;
+        self.playlist_entries.iter().position(|e| e.enabled && e.name == name)
+    }
+    
+    /// Open the previously active playlist (the current one becomes "previous").
+    /// Switching replaces the active playlist's channels instead of adding to them.
+    fn switch_to_previous_playlist(&mut self) {
+        if let Some(idx) = self.previous_playlist_idx() {
+            let target = self.playlist_entries[idx].name.clone();
+            self.log(&format!("[INFO] Switching to playlist '{}'", target));
+            let active = self.active_playlist_name.clone();
+            // The target is loaded again below, so drop a copy that is still loaded too
+            while let Some(source_idx) = self.playlist_sources.iter()
+                .position(|(_, name)| Some(name) == active.as_ref() || *name == target)
+            {
+                remove_playlist_source(&mut self.current_channels, &mut self.playlist_sources, source_idx);
+            }
+            if self.playlist_sources.is_empty() {
+                self.playlist_mode = false;
+            }
+            self.open_playlist_entry(idx);
+        }
+    }
+
     fn login(&mut self) {
         if self.server.is_empty() || self.username.is_empty() || self.password.is_empty() {
             self.status_message = "Please fill all fields".to_string();
             return;
         }
 
+        // Add a missing protocol, strip pasted endpoints (/player_api.php, /get.php?...) and trailing slashes
+        let normalized = normalize_server_url(&self.server);
+        if normalized != self.server {
+            self.log(&format!("[INFO] Server URL normalized to {}", normalized));
+            self.server = normalized;
+        }
+
         self.status_message = "Logging in...".to_string();
-        self.loading = true;
+        self.begin_task(TaskKind::Login, format!("Logging in to {}", self.server));
+        
+        // Show the categories from the last visit right away - the login below refreshes them
+        if let Some(cached) = load_category_cache::<CategoryLists>(&self.server, &self.username) {
+            self.log(&format!("[INFO] Loaded cached categories - Live: {}, Movies: {}, Series: {} (refreshing)",
+                cached.live.len(), cached.movies.len(), cached.series.len()));
+            self.live_categories = cached.live;
+            self.movie_categories = cached.movies;
+            self.series_categories = cached.series;
+            self.category_counts.clear();
+            self.logged_in = true;
+            let entry = self.find_current_playlist_idx()
+                .and_then(|idx| self.playlist_entries.get(idx));
+            self.favorite_categories = entry.map(|e| e.favorite_categories.clone()).unwrap_or_default();
+            self.category_sort_orders = entry.map(|e| e.category_sort_orders.clone()).unwrap_or_default();
+            self.status_message = "Showing cached categories - refreshing...".to_string();
+        }
         
         self.log(&format!("[INFO] Attempting login to {}", self.server));
         self.log(&format!("[INFO] User Agent: {}", self.get_user_agent()));
-
-        // Ensure server has protocol
-        if !self.server.starts_with("http://") && !self.server.starts_with("https://") {
-            self.server = format!("http://{}", self.server);
-        }
+        self.image_cache.set_user_agent(&self.get_user_agent());
 
         // Spawn background thread for login
         let server = self.server.clone();
@@ -1026,33 +2240,57 @@ impl IPTVApp {
         let password = self.password.clone();
         let user_agent = self.get_user_agent();
         let use_post = self.use_post_method;
+        let (connect_timeout, read_timeout) = (self.connect_timeout_secs, self.read_timeout_secs);
+        let resolution = self.host_resolution(&server);
         let sender = self.task_sender.clone();
+        let pool = self.fetch_pool.clone();
 
+        // Coordinator thread only waits on the category fetches, so it doesn't take a permit itself
         thread::spawn(move || {
             let client = XtreamClient::new(&server, &username, &password)
                 .with_user_agent(&user_agent)
-                .with_post_method(use_post);
+                .with_post_method(use_post)
+                .with_timeouts(connect_timeout, read_timeout)
+                .with_host_resolution(resolution.clone());
 
             // Fetch categories in parallel
             let live_handle = {
                 let client = XtreamClient::new(&server, &username, &password)
                     .with_user_agent(&user_agent)
-                    .with_post_method(use_post);
-                thread::spawn(move || client.get_live_categories())
+                    .with_post_method(use_post)
+                    .with_timeouts(connect_timeout, read_timeout)
+                    .with_host_resolution(resolution.clone());
+                let pool = pool.clone();
+                thread::spawn(move || {
+                    let _permit = pool.acquire();
+                    client.get_live_categories()
+                })
             };
             
             let movies_handle = {
                 let client = XtreamClient::new(&server, &username, &password)
                     .with_user_agent(&user_agent)
-                    .with_post_method(use_post);
-                thread::spawn(move || client.get_vod_categories())
+                    .with_post_method(use_post)
+                    .with_timeouts(connect_timeout, read_timeout)
+                    .with_host_resolution(resolution.clone());
+                let pool = pool.clone();
+                thread::spawn(move || {
+                    let _permit = pool.acquire();
+                    client.get_vod_categories()
+                })
             };
             
             let series_handle = {
                 let client = XtreamClient::new(&server, &username, &password)
                     .with_user_agent(&user_agent)
-                    .with_post_method(use_post);
-                thread::spawn(move || client.get_series_categories())
+                    .with_post_method(use_post)
+                    .with_timeouts(connect_timeout, read_timeout)
+                    .with_host_resolution(resolution.clone());
+                let pool = pool.clone();
+                thread::spawn(move || {
+                    let _permit = pool.acquire();
+                    client.get_series_categories()
+                })
             };
 
             // Wait for all to complete and collect errors
@@ -1064,11 +2302,11 @@ impl IPTVApp {
             let live = match live_result {
                 Ok(Ok(data)) => Some(data),
                 Ok(Err(e)) => {
-                    let _ = sender.send(TaskResult::Error(format!("Live categories: {}", e)));
+                    let _ = sender.send(TaskResult::Error { kind: Some(TaskKind::Login), msg: format!("Live categories: {}", e) });
                     return;
                 }
                 Err(_) => {
-                    let _ = sender.send(TaskResult::Error("Live categories thread panicked".to_string()));
+                    let _ = sender.send(TaskResult::Error { kind: Some(TaskKind::Login), msg: "Live categories thread panicked".to_string() });
                     return;
                 }
             };
@@ -1076,11 +2314,11 @@ impl IPTVApp {
             let movies = match movies_result {
                 Ok(Ok(data)) => Some(data),
                 Ok(Err(e)) => {
-                    let _ = sender.send(TaskResult::Error(format!("Movie categories: {}", e)));
+                    let _ = sender.send(TaskResult::Error { kind: Some(TaskKind::Login), msg: format!("Movie categories: {}", e) });
                     return;
                 }
                 Err(_) => {
-                    let _ = sender.send(TaskResult::Error("Movie categories thread panicked".to_string()));
+                    let _ = sender.send(TaskResult::Error { kind: Some(TaskKind::Login), msg: "Movie categories thread panicked".to_string() });
                     return;
                 }
             };
@@ -1088,11 +2326,11 @@ impl IPTVApp {
             let series = match series_result {
                 Ok(Ok(data)) => Some(data),
                 Ok(Err(e)) => {
-                    let _ = sender.send(TaskResult::Error(format!("Series categories: {}", e)));
+                    let _ = sender.send(TaskResult::Error { kind: Some(TaskKind::Login), msg: format!("Series categories: {}", e) });
                     return;
                 }
                 Err(_) => {
-                    let _ = sender.send(TaskResult::Error("Series categories thread panicked".to_string()));
+                    let _ = sender.send(TaskResult::Error { kind: Some(TaskKind::Login), msg: "Series categories thread panicked".to_string() });
                     return;
                 }
             };
@@ -1101,7 +2339,11 @@ impl IPTVApp {
                 let _ = sender.send(TaskResult::CategoriesLoaded { live, movies, series });
                 
                 // Also fetch user info
-                if let Ok(info) = client.get_account_info() {
+                let account_info = {
+                    let _permit = pool.acquire();
+                    client.get_account_info()
+                };
+                if let Ok(info) = account_info {
                     let mut user_info = UserInfo::default();
                     let mut server_info = ServerInfo::default();
                     
@@ -1134,6 +2376,7 @@ impl IPTVApp {
                         if let Some(exp) = user.get("exp_date").and_then(|v| v.as_str()) {
                             if let Ok(ts) = exp.parse::<i64>() {
                                 user_info.expiry = format_timestamp(ts);
+                                user_info.expiry_ts = Some(ts);
                             } else {
                                 user_info.expiry = "Unlimited".to_string();
                             }
@@ -1153,6 +2396,12 @@ impl IPTVApp {
                             .and_then(|v| v.as_str())
                             .unwrap_or("Unknown")
                             .to_string();
+                        let timestamp_now = srv.get("timestamp_now")
+                            .and_then(|v| v.as_i64().or_else(|| v.as_str()?.parse().ok()));
+                        server_info.utc_offset_secs = srv.get("time_now")
+                            .and_then(|v| v.as_str())
+                            .zip(timestamp_now)
+                            .and_then(|(time_now, ts)| server_utc_offset(time_now, ts));
                     }
                     
                     let _ = sender.send(TaskResult::UserInfoLoaded { user_info, server_info });
@@ -1161,6 +2410,24 @@ impl IPTVApp {
         });
     }
 
+    /// Host resolution rules for requests to `provider_url`
+    fn host_resolution(&self, provider_url: &str) -> HostResolution {
+        HostResolution::new(self.force_ipv4, &self.ip_override, provider_url)
+    }
+    
+    /// Mark a background fetch as running, replacing any earlier one of the same kind
+    fn begin_task(&mut self, kind: TaskKind, label: String) {
+        self.active_tasks.retain(|(k, _)| *k != kind);
+        self.active_tasks.push((kind, label));
+        self.loading = true;
+    }
+    
+    /// Clear a finished background fetch
+    fn end_task(&mut self, kind: TaskKind) {
+        self.active_tasks.retain(|(k, _)| *k != kind);
+        self.loading = !self.active_tasks.is_empty();
+    }
+    
     /// Helper to create fetch context with all credentials
     fn fetch_context(&self) -> FetchContext {
         FetchContext {
@@ -1169,19 +2436,30 @@ impl IPTVApp {
             password: self.password.clone(),
             user_agent: self.get_user_agent(),
             use_post: self.use_post_method,
+            connect_timeout_secs: self.connect_timeout_secs,
+            read_timeout_secs: self.read_timeout_secs,
+            resolution: self.host_resolution(&self.server),
+            live_hls: self.live_hls,
+            logo_url_template: self.logo_url_template.clone(),
             sender: self.task_sender.clone(),
         }
     }
 
     fn fetch_channels(&mut self, category_id: &str, stream_type: &str) {
-        self.loading = true;
+        self.selected_urls.clear();
+        self.current_category_id = Some(category_id.to_string());
+        let category_name = match stream_type {
+            "live" => &self.live_categories,
+            _ => &self.movie_categories,
+        }.iter().find(|c| c.category_id == category_id).map(|c| c.category_name.clone()).unwrap_or_default();
+        self.begin_task(TaskKind::Channels, format!("Loading channels: {}", category_name));
         self.status_message = "Loading channels...".to_string();
         
         let ctx = self.fetch_context();
         let category_id = category_id.to_string();
         let stream_type = stream_type.to_string();
 
-        thread::spawn(move || {
+        self.fetch_pool.spawn(move || {
             let client = ctx.client();
             
             let result = match stream_type.as_str() {
@@ -1190,62 +2468,140 @@ impl IPTVApp {
                 _ => return,
             };
 
-            if let Ok(streams) = result {
-                let channels: Vec<Channel> = streams.into_iter().map(|s| {
-                    let ext = s.container_extension.as_deref().unwrap_or(
-                        if stream_type == "live" { "ts" } else { "mp4" }
-                    );
-                    let url = format!(
-                        "{}/{}/{}/{}/{}.{}",
-                        ctx.server, stream_type, ctx.username, ctx.password,
-                        s.stream_id, ext
-                    );
+            match result {
+                Ok(streams) => {
+                    let channels: Vec<Channel> = streams.into_iter().map(|s| {
+                        let archive_days = s.archive_days();
+                        // Malformed extensions ("", "mp4?x=1") would produce unplayable URLs
+                        let container_extension = clean_container_extension(s.container_extension.as_deref()).map(str::to_string);
+                        let ext = if stream_type == "live" && ctx.live_hls {
+                            "m3u8"
+                        } else {
+                            container_extension.as_deref().unwrap_or(
+                                if stream_type == "live" { "ts" } else { "mp4" }
+                            )
+                        };
+                        let url = format!(
+                            "{}/{}/{}/{}/{}.{}",
+                            ctx.server, stream_type, ctx.username, ctx.password,
+                            s.stream_id, ext
+                        );
                     
-                    Channel {
-                        name: s.name,
-                        url,
-                        stream_id: Some(s.stream_id),
-                        category_id: s.category_id,
-                        epg_channel_id: s.epg_channel_id,
-                        stream_icon: s.stream_icon,
-                        series_id: None,
-                        container_extension: s.container_extension,
-                        playlist_source: None, // From Xtream API, not playlist
-                    }
-                }).collect();
+                        let stream_icon = s.stream_icon
+                            .filter(|icon| !icon.trim().is_empty())
+                            .or_else(|| logo_from_template(
+                                &ctx.logo_url_template, &ctx.server, s.stream_id, &s.name, s.epg_channel_id.as_deref(),
+                            ));
+                    
+                        Channel {
+                            name: s.name,
+                            url,
+                            stream_id: Some(s.stream_id),
+                            category_id: s.category_id,
+                            epg_channel_id: s.epg_channel_id,
+                            stream_icon,
+                            series_id: None,
+                            container_extension,
+                            playlist_source: None, // From Xtream API, not playlist
+                            catchup: None,
+                            catchup_source: None,
+                            tvg_shift: None,
+                            alternate_urls: Vec::new(),
+                            archive_days,
+                            genre: s.genre,
+                            user_agent: None,
+                            referrer: None,
+                        }
+                    }).collect();
                 
-                let _ = ctx.sender.send(TaskResult::ChannelsLoaded(channels));
-            } else {
-                let _ = ctx.sender.send(TaskResult::Error("Failed to load channels".to_string()));
+                    let counts = HashMap::from([(category_id, channels.len())]);
+                    let _ = ctx.sender.send(TaskResult::CategoryCountsLoaded { stream_type, counts, complete: false });
+                    let _ = ctx.sender.send(TaskResult::ChannelsLoaded(channels));
+                }
+                Err(e) => {
+                    let _ = ctx.sender.send(TaskResult::Error { kind: Some(TaskKind::Channels), msg: format!("Failed to load channels: {}", e) });
+                }
             }
         });
     }
 
     fn fetch_series_list(&mut self, category_id: &str) {
-        self.loading = true;
+        self.current_category_id = Some(category_id.to_string());
+        let category_name = self.series_categories.iter()
+            .find(|c| c.category_id == category_id)
+            .map(|c| c.category_name.clone())
+            .unwrap_or_default();
+        self.begin_task(TaskKind::Series, format!("Loading series: {}", category_name));
         self.status_message = "Loading series...".to_string();
         
         let ctx = self.fetch_context();
         let category_id = category_id.to_string();
 
-        thread::spawn(move || {
+        self.fetch_pool.spawn(move || {
             let client = ctx.client();
             
-            if let Ok(series) = client.get_series(&category_id) {
-                let _ = ctx.sender.send(TaskResult::SeriesListLoaded(series));
-            } else {
-                let _ = ctx.sender.send(TaskResult::Error("Failed to load series".to_string()));
+            match client.get_series(&category_id) {
+                Ok(series) => {
+                    let counts = HashMap::from([(category_id, series.len())]);
+                    let _ = ctx.sender.send(TaskResult::CategoryCountsLoaded { stream_type: "series".to_string(), counts, complete: false });
+                    let _ = ctx.sender.send(TaskResult::SeriesListLoaded(series));
+                }
+                Err(e) => {
+                    let _ = ctx.sender.send(TaskResult::Error { kind: Some(TaskKind::Series), msg: format!("Failed to load series: {}", e) });
+                }
+            }
+        });
+    }
+
+    /// Fetch item counts for every category in the background (one request per stream type)
+    fn prefetch_category_counts(&mut self) {
+        self.category_counts_prefetching = true;
+        self.status_message = "Counting category items...".to_string();
+        
+        let ctx = self.fetch_context();
+        
+        self.fetch_pool.spawn(move || {
+            let client = ctx.client();
+            
+            for (stream_type, action) in [("live", "get_live_streams"), ("movie", "get_vod_streams"), ("series", "get_series")] {
+                match client.get_category_counts(action) {
+                    Ok(counts) => {
+                        let _ = ctx.sender.send(TaskResult::CategoryCountsLoaded {
+                            stream_type: stream_type.to_string(),
+                            counts,
+                            complete: true,
+                        });
+                    }
+                    Err(e) => {
+                        let _ = ctx.sender.send(TaskResult::PlayerLog(format!("[WARN] Failed to count {} categories: {}", stream_type, e)));
+                    }
+                }
             }
+            let _ = ctx.sender.send(TaskResult::CategoryCountsLoaded {
+                stream_type: String::new(),
+                counts: HashMap::new(),
+                complete: true,
+            });
         });
     }
+    
+    /// Category button text with the cached item count, if known
+    fn category_button_text(&self, stream_type: &str, category: &Category) -> egui::RichText {
+        let display_name = Self::sanitize_text(&category.category_name);
+        match self.category_counts.get(&format!("{}:{}", stream_type, category.category_id)) {
+            Some(0) => egui::RichText::new(format!("{} (0)", display_name)).weak(),
+            Some(n) => egui::RichText::new(format!("{} ({})", display_name, n)),
+            None => egui::RichText::new(display_name),
+        }
+    }
 
     fn fetch_series_info(&mut self, series_id: i64) {
-        self.loading = true;
+        self.begin_task(TaskKind::Seasons, "Loading seasons".to_string());
         self.status_message = "Loading seasons...".to_string();
         
         let ctx = self.fetch_context();
 
-        thread::spawn(move || {
+        self.fetch_pool.spawn(move || {
             let client = ctx.client();
             
             if let Ok(info) = client.get_series_info(series_id) {
@@ -1255,73 +2611,66 @@ impl IPTVApp {
                             .filter_map(|k| k.parse::<i32>().ok())
                             .collect();
                         seasons.sort();
+                        let _ = ctx.sender.send(TaskResult::SeriesEpisodesCached {
+                            series_id,
+                            episodes: parse_series_episodes(&info),
+                        });
                         let _ = ctx.sender.send(TaskResult::SeasonsLoaded(seasons));
                         return;
                     }
                 }
-                let _ = ctx.sender.send(TaskResult::Error("No seasons found".to_string()));
+                let _ = ctx.sender.send(TaskResult::Error { kind: Some(TaskKind::Seasons), msg: "No seasons found".to_string() });
             } else {
-                let _ = ctx.sender.send(TaskResult::Error("Failed to load series info".to_string()));
+                let _ = ctx.sender.send(TaskResult::Error { kind: Some(TaskKind::Seasons), msg: "Failed to load series info".to_string() });
             }
         });
     }
 
     fn fetch_episodes(&mut self, series_id: i64, season: i32) {
-        self.loading = true;
+        // Already have the whole series from the seasons fetch
+        if let Some((cached_id, episodes)) = &self.series_episodes {
+            if *cached_id == series_id {
+                self.current_episodes = episodes.iter().filter(|ep| ep.season == season).cloned().collect();
+                if !self.current_episodes.is_empty() {
+                    self.status_message = format!("Loaded {} episodes", self.current_episodes.len());
+                    return;
+                }
+            }
+        }
+        
+        self.begin_task(TaskKind::Episodes, format!("Loading episodes: season {}", season));
         self.status_message = "Loading episodes...".to_string();
         
         let ctx = self.fetch_context();
 
-        thread::spawn(move || {
+        self.fetch_pool.spawn(move || {
             let client = ctx.client();
             
             if let Ok(info) = client.get_series_info(series_id) {
-                if let Some(episodes) = info.get("episodes") {
-                    if let Some(season_eps) = episodes.get(&season.to_string()) {
-                        if let Some(arr) = season_eps.as_array() {
-                            let eps: Vec<Episode> = arr.iter().filter_map(|ep| {
-                                let id = ep.get("id")?.as_str()?.parse().ok()?;
-                                let title = ep.get("title")
-                                    .and_then(|v| v.as_str())
-                                    .unwrap_or("Unknown")
-                                    .to_string();
-                                let episode_num = ep.get("episode_num")
-                                    .and_then(|v| v.as_i64())
-                                    .unwrap_or(0) as i32;
-                                let container = ep.get("container_extension")
-                                    .and_then(|v| v.as_str())
-                                    .unwrap_or("mp4")
-                                    .to_string();
-                                
-                                Some(Episode {
-                                    id,
-                                    title,
-                                    episode_num,
-                                    season,
-                                    container_extension: container,
-                                })
-                            }).collect();
-                            
-                            let _ = ctx.sender.send(TaskResult::EpisodesLoaded(eps));
-                            return;
-                        }
-                    }
+                let all = parse_series_episodes(&info);
+                let eps: Vec<Episode> = all.iter().filter(|ep| ep.season == season).cloned().collect();
+                if !all.is_empty() {
+                    let _ = ctx.sender.send(TaskResult::SeriesEpisodesCached { series_id, episodes: all });
                 }
-                let _ = ctx.sender.send(TaskResult::Error("No episodes found".to_string()));
+                if !eps.is_empty() {
+                    let _ = ctx.sender.send(TaskResult::EpisodesLoaded(eps));
+                    return;
+                }
+                let _ = ctx.sender.send(TaskResult::Error { kind: Some(TaskKind::Episodes), msg: "No episodes found".to_string() });
             } else {
-                let _ = ctx.sender.send(TaskResult::Error("Failed to load episodes".to_string()));
+                let _ = ctx.sender.send(TaskResult::Error { kind: Some(TaskKind::Episodes), msg: "Failed to load episodes".to_string() });
             }
         });
     }
 
     // Fetch series info for favorites tab (doesn't change main navigation)
     fn fetch_fav_series_info(&mut self, series_id: i64) {
-        self.loading = true;
+        self.begin_task(TaskKind::FavSeasons, "Loading seasons".to_string());
         self.status_message = "Loading seasons...".to_string();
         
         let ctx = self.fetch_context();
 
-        thread::spawn(move || {
+        self.fetch_pool.spawn(move || {
             let client = ctx.client();
             
             if let Ok(info) = client.get_series_info(series_id) {
@@ -1335,64 +2684,124 @@ impl IPTVApp {
                         return;
                     }
                 }
-                let _ = ctx.sender.send(TaskResult::Error("No seasons found".to_string()));
+                let _ = ctx.sender.send(TaskResult::Error { kind: Some(TaskKind::FavSeasons), msg: "No seasons found".to_string() });
             } else {
-                let _ = ctx.sender.send(TaskResult::Error("Failed to load series".to_string()));
+                let _ = ctx.sender.send(TaskResult::Error { kind: Some(TaskKind::FavSeasons), msg: "Failed to load series".to_string() });
             }
         });
     }
 
     fn fetch_fav_episodes(&mut self, series_id: i64, season: i32) {
-        self.loading = true;
+        self.begin_task(TaskKind::FavEpisodes, format!("Loading episodes: season {}", season));
         self.status_message = "Loading episodes...".to_string();
         
         let ctx = self.fetch_context();
 
-        thread::spawn(move || {
+        self.fetch_pool.spawn(move || {
             let client = ctx.client();
             
             if let Ok(info) = client.get_series_info(series_id) {
-                if let Some(episodes) = info.get("episodes") {
-                    if let Some(season_eps) = episodes.get(&season.to_string()) {
-                        if let Some(arr) = season_eps.as_array() {
-                            let eps: Vec<Episode> = arr.iter().filter_map(|ep| {
-                                let id = ep.get("id")?.as_str()?.parse().ok()?;
-                                let title = ep.get("title")
-                                    .and_then(|v| v.as_str())
-                                    .unwrap_or("Unknown")
-                                    .to_string();
-                                let episode_num = ep.get("episode_num")
-                                    .and_then(|v| v.as_i64())
-                                    .unwrap_or(0) as i32;
-                                let container = ep.get("container_extension")
-                                    .and_then(|v| v.as_str())
-                                    .unwrap_or("mp4")
-                                    .to_string();
-                                
-                                Some(Episode {
-                                    id,
-                                    title,
-                                    episode_num,
-                                    season,
-                                    container_extension: container,
-                                })
-                            }).collect();
-                            
-                            let _ = ctx.sender.send(TaskResult::FavEpisodesLoaded(eps));
-                            return;
-                        }
-                    }
+                let eps: Vec<Episode> = parse_series_episodes(&info).into_iter()
+                    .filter(|ep| ep.season == season)
+                    .collect();
+                if !eps.is_empty() {
+                    let _ = ctx.sender.send(TaskResult::FavEpisodesLoaded(eps));
+                    return;
                 }
-                let _ = ctx.sender.send(TaskResult::Error("No episodes found".to_string()));
+                let _ = ctx.sender.send(TaskResult::Error { kind: Some(TaskKind::FavEpisodes), msg: "No episodes found".to_string() });
             } else {
-                let _ = ctx.sender.send(TaskResult::Error("Failed to load episodes".to_string()));
+                let _ = ctx.sender.send(TaskResult::Error { kind: Some(TaskKind::FavEpisodes), msg: "Failed to load episodes".to_string() });
+            }
+        });
+    }
+
+    /// Fetch movie metadata for the detail panel
+    fn fetch_vod_detail(&mut self, channel: &Channel) {
+        let Some(stream_id) = channel.stream_id else {
+            return;
+        };
+        self.begin_task(TaskKind::Detail, format!("Loading movie info: {}", channel.name));
+        self.status_message = "Loading movie info...".to_string();
+        self.content_detail = None;
+        self.content_detail_channel = Some(channel.clone());
+        self.show_content_detail = true;
+        let user_agent = self.get_user_agent();
+        self.image_cache.set_user_agent(&user_agent);
+        
+        let ctx = self.fetch_context();
+        let name = channel.name.clone();
+        let icon = channel.stream_icon.clone();
+
+        self.fetch_pool.spawn(move || {
+            let client = ctx.client();
+            
+            match client.get_vod_info(stream_id) {
+                Ok(info) => {
+                    let detail = ContentDetail::from_vod_info(&name, &info, icon.as_deref());
+                    let _ = ctx.sender.send(TaskResult::ContentDetailLoaded(Box::new(detail)));
+                }
+                Err(e) => {
+                    // Still show what we know from the list
+                    let detail = ContentDetail::from_vod_info(&name, &VodInfo::default(), icon.as_deref());
+                    let _ = ctx.sender.send(TaskResult::ContentDetailLoaded(Box::new(detail)));
+                    let _ = ctx.sender.send(TaskResult::Error { kind: Some(TaskKind::Detail), msg: format!("Failed to load movie info: {}", e) });
+                }
+            }
+        });
+    }
+    
+    /// Probe a stream with ffprobe and show its codecs/resolution in a popup
+    fn probe_stream(&mut self, channel: &Channel) {
+        if self.low_data_mode {
+            self.status_message = "Stream info is disabled in low data mode".to_string();
+            return;
+        }
+        let url = channel.url.clone();
+        let user_agent = self.get_player_user_agent();
+        let sender = self.task_sender.clone();
+        self.stream_probe = Some((channel.name.clone(), url.clone(), None));
+
+        self.fetch_pool.spawn(move || {
+            let result = probe::probe_stream(&url, &user_agent);
+            let _ = sender.send(TaskResult::StreamProbed { url, result });
+        });
+    }
+    
+    /// Fetch series metadata for the detail panel
+    fn fetch_series_detail(&mut self, series: &SeriesInfo) {
+        self.begin_task(TaskKind::Detail, format!("Loading series info: {}", series.name));
+        self.status_message = "Loading series info...".to_string();
+        self.content_detail = None;
+        self.content_detail_channel = None;
+        self.show_content_detail = true;
+        let user_agent = self.get_user_agent();
+        self.image_cache.set_user_agent(&user_agent);
+        
+        let ctx = self.fetch_context();
+        let series_id = series.series_id;
+        let name = series.name.clone();
+        let cover = series.cover.clone();
+
+        self.fetch_pool.spawn(move || {
+            let client = ctx.client();
+            
+            match client.get_series_info(series_id) {
+                Ok(info) => {
+                    let detail = ContentDetail::from_series_info(&name, &info, cover.as_deref());
+                    let _ = ctx.sender.send(TaskResult::ContentDetailLoaded(Box::new(detail)));
+                }
+                Err(e) => {
+                    let detail = ContentDetail::from_series_info(&name, &serde_json::Value::Null, cover.as_deref());
+                    let _ = ctx.sender.send(TaskResult::ContentDetailLoaded(Box::new(detail)));
+                    let _ = ctx.sender.send(TaskResult::Error { kind: Some(TaskKind::Detail), msg: format!("Failed to load series info: {}", e) });
+                }
             }
         });
     }
 
     fn load_epg(&mut self) {
-        let url = self.epg_url_input.trim().to_string();
-        if url.is_empty() {
+        let sources = epg::split_epg_sources(&self.epg_url_input);
+        if sources.is_empty() {
             self.epg_status = "Please enter an EPG URL".to_string();
             return;
         }
@@ -1400,53 +2809,100 @@ impl IPTVApp {
         self.epg_loading = true;
         self.epg_progress = 0.0;
         self.epg_status = "Starting download...".to_string();
-        self.log(&format!("[INFO] Loading EPG from: {}", url));
+        self.log(&format!("[INFO] Loading EPG from: {}", sources.join(", ")));
         
         let sender = self.task_sender.clone();
         let user_agent = self.get_user_agent();
+        let (connect_timeout_secs, read_timeout_secs) = (self.connect_timeout_secs, self.read_timeout_secs);
         
-        thread::spawn(move || {
+        self.epg_pool.spawn(move || {
             let config = DownloadConfig {
                 max_retries: 3,
                 retry_delay_ms: 2000,
-                connect_timeout_secs: 30,
-                read_timeout_secs: 180,
+                connect_timeout_secs,
+                read_timeout_secs,
                 chunk_size: 64 * 1024,
                 user_agent,
+                partial_dir: Some(epg_cache_dir()),
             };
             
-            // Progress callback sends updates to UI
-            let progress_sender = sender.clone();
-            let progress_callback: Option<epg::ProgressCallback> = Some(Box::new(move |downloaded, total| {
-                let msg = if let Some(total) = total {
-                    let pct = (downloaded as f64 / total as f64 * 100.0) as u32;
-                    let dl_mb = downloaded as f64 / 1_048_576.0;
-                    let total_mb = total as f64 / 1_048_576.0;
-                    format!("Downloading: {:.1} / {:.1} MB ({}%)", dl_mb, total_mb, pct)
-                } else {
-                    let dl_mb = downloaded as f64 / 1_048_576.0;
-                    format!("Downloading: {:.1} MB", dl_mb)
-                };
-                let _ = progress_sender.send(TaskResult::EpgLoading { progress: msg });
-            }));
+            // Several sources are loaded one after another and merged into one guide
+            let total = sources.len();
+            let mut merged: Option<EpgData> = None;
+            let mut stats = Vec::new();
+            let mut errors = Vec::new();
+            for (i, source) in sources.into_iter().enumerate() {
+                let prefix = if total > 1 { format!("[{}/{}] ", i + 1, total) } else { String::new() };
+                match Self::load_epg_source(&source, &prefix, &config, &sender) {
+                    Ok(epg) => {
+                        stats.push(epg::EpgSourceStats {
+                            source,
+                            channels: epg.channels.len(),
+                            programs: epg.program_count(),
+                            error: None,
+                        });
+                        match merged.as_mut() {
+                            Some(merged) => merged.merge(epg),
+                            None => merged = Some(epg),
+                        }
+                    }
+                    Err(e) => {
+                        errors.push(if total > 1 { format!("{}: {}", source, e) } else { e.clone() });
+                        stats.push(epg::EpgSourceStats { source, error: Some(e), ..Default::default() });
+                    }
+                }
+            }
             
-            // Download and parse with retry/resume support
-            match EpgDownloader::download_and_parse(&url, &config, progress_callback) {
-                Ok(epg) => {
-                    let _ = sender.send(TaskResult::EpgLoaded { 
-                        data: Box::new(epg)
-                    });
+            match merged {
+                Some(mut epg) => {
+                    if total > 1 {
+                        epg.sources = stats;
+                    }
+                    let _ = sender.send(TaskResult::EpgLoaded { data: Box::new(epg) });
                 }
-                Err(e) => {
-                    let _ = sender.send(TaskResult::EpgError(e));
+                None => {
+                    let _ = sender.send(TaskResult::EpgError(errors.join("; ")));
                 }
             }
         });
     }
     
+    /// Download (or read from disk) and parse a single XMLTV source.
+    /// `prefix` tags progress messages when several sources are loading.
+    fn load_epg_source(source: &str, prefix: &str, config: &DownloadConfig, sender: &Sender<TaskResult>) -> Result<EpgData, String> {
+        // Local XMLTV file (.xml, .xml.gz or .xml.xz) - parse directly from disk
+        if let Some(path) = local_file_path(source) {
+            let _ = sender.send(TaskResult::EpgLoading { progress: format!("{}Parsing local file...", prefix) });
+            return epg::EpgParser::parse_file(path).map_err(|e| format!("Failed to read EPG file: {}", e));
+        }
+        
+        // Progress callback sends updates to UI
+        let progress_sender = sender.clone();
+        let prefix = prefix.to_string();
+        let progress_callback: Option<epg::ProgressCallback> = Some(Box::new(move |downloaded, total| {
+            let msg = if let Some(total) = total {
+                let pct = (downloaded as f64 / total as f64 * 100.0) as u32;
+                let dl_mb = downloaded as f64 / 1_048_576.0;
+                let total_mb = total as f64 / 1_048_576.0;
+                format!("{}Downloading: {:.1} / {:.1} MB ({}%)", prefix, dl_mb, total_mb, pct)
+            } else {
+                let dl_mb = downloaded as f64 / 1_048_576.0;
+                format!("{}Downloading: {:.1} MB", prefix, dl_mb)
+            };
+            let _ = progress_sender.send(TaskResult::EpgLoading { progress: msg });
+        }));
+        
+        // Download and parse with retry/resume support
+        EpgDownloader::download_and_parse(source, config, progress_callback)
+    }
+    
     fn get_current_program(&self, epg_channel_id: &str) -> Option<&Program> {
+        self.get_current_program_at(epg_channel_id, self.get_adjusted_now())
+    }
+    
+    /// Get the program airing at an EPG-adjusted timestamp
+    fn get_current_program_at(&self, epg_channel_id: &str, adjusted_now: i64) -> Option<&Program> {
         let epg = self.epg_data.as_ref()?;
-        let adjusted_now = self.get_adjusted_now();
         
         let programs = epg.programs.get(epg_channel_id)?;
         
@@ -1460,8 +2916,12 @@ impl IPTVApp {
     
     /// Get current and next N programs for a channel (with time offset applied)
     fn get_upcoming_programs(&self, epg_channel_id: &str, count: usize) -> Vec<&Program> {
+        self.get_upcoming_programs_at(epg_channel_id, count, self.get_adjusted_now())
+    }
+    
+    /// Get current and next N programs relative to an EPG-adjusted timestamp
+    fn get_upcoming_programs_at(&self, epg_channel_id: &str, count: usize, adjusted_now: i64) -> Vec<&Program> {
         let Some(epg) = self.epg_data.as_ref() else { return Vec::new() };
-        let adjusted_now = self.get_adjusted_now();
         
         let Some(programs) = epg.programs.get(epg_channel_id) else { 
             return Vec::new() 
@@ -1474,6 +2934,22 @@ impl IPTVApp {
         programs[start_idx..].iter().take(count).collect()
     }
     
+    /// Get programs overlapping a time window [start, end) for a channel
+    fn get_programs_in_window(&self, epg_channel_id: &str, start: i64, end: i64) -> Vec<&Program> {
+        let Some(epg) = self.epg_data.as_ref() else { return Vec::new() };
+        
+        let Some(programs) = epg.programs.get(epg_channel_id) else { 
+            return Vec::new() 
+        };
+        
+        // Programs are sorted by start time - skip those that ended before the window
+        let start_idx = programs.partition_point(|p| p.stop <= start);
+        
+        programs[start_idx..].iter()
+            .take_while(|p| p.start < end)
+            .collect()
+    }
+    
     /// Get adjusted "now" timestamp accounting for EPG time offset
     fn get_adjusted_now(&self) -> i64 {
         let offset_secs = (self.epg_time_offset * 3600.0) as i64;
@@ -1481,13 +2957,119 @@ impl IPTVApp {
         now - offset_secs
     }
     
+    /// Per-channel EPG shift from the M3U `tvg-shift` attribute, in seconds
+    fn tvg_shift_secs(tvg_shift: Option<f32>) -> i64 {
+        (tvg_shift.unwrap_or(0.0) * 3600.0) as i64
+    }
+    
     /// Display EPG info inline for a channel (used in Live/Favorites/Recent tabs)
     /// If epg_channel_id is provided, uses it directly. Otherwise looks up by channel name.
-    fn show_epg_inline(&self, ui: &mut egui::Ui, channel_name: &str, epg_channel_id: Option<&str>) {
-        let Some(ref epg) = self.epg_data else { return };
+    /// `tvg_shift` is the channel's M3U EPG offset in hours, applied on top of the global offset.
+    fn show_epg_inline(&self, ui: &mut egui::Ui, channel_name: &str, epg_channel_id: Option<&str>, tvg_shift: Option<f32>) {
+        let Some(epg_id) = self.resolve_epg_id(channel_name, epg_channel_id) else { return };
+        let adjusted_now = self.get_adjusted_now() - Self::tvg_shift_secs(tvg_shift);
+        
+        // Truncate title
+        let truncate = |title: &str| {
+            let short_title: String = title.chars().take(20).collect();
+            if title.chars().count() > 20 {
+                format!("{}…", short_title)
+            } else {
+                short_title
+            }
+        };
+        
+        if let Some(program) = self.get_current_program_at(&epg_id, adjusted_now) {
+            ui.label(" | ");
+            if self.is_favorite_program(&program.title) {
+                ui.label(egui::RichText::new(format!("⭐ {}", truncate(&program.title)))
+                    .color(egui::Color32::GOLD)
+                    .italics())
+                    .on_hover_text("One of your starred shows");
+            } else {
+                ui.label(egui::RichText::new(truncate(&program.title))
+                    .color(egui::Color32::LIGHT_BLUE)
+                    .italics());
+            }
+            
+            let remaining = (program.stop - adjusted_now) / 60;
+            if remaining > 0 {
+                ui.label(egui::RichText::new(format!("({}m left)", remaining))
+                    .small()
+                    .color(egui::Color32::GRAY));
+            }
+        } else if !self.epg_show_next_inline {
+            return;
+        }
+        
+        if self.epg_show_next_inline {
+            let next = self.get_upcoming_programs_at(&epg_id, 2, adjusted_now)
+                .into_iter()
+                .find(|p| p.start > adjusted_now);
+            if let Some(next) = next {
+                let when = if self.epg_show_actual_time {
+                    format!("at {}", Self::format_time(next.start))
+                } else {
+                    format!("({})", Self::format_relative_offset(next.start - adjusted_now))
+                };
+                ui.label(egui::RichText::new(format!("→ Next: {} {}", truncate(&next.title), when))
+                    .small()
+                    .color(egui::Color32::GRAY));
+            }
+        }
+    }
+    
+    /// Attach a hover popup with the current program (title, time, description) and the next two.
+    /// The response is returned unchanged when there's no EPG match for the channel.
+    fn with_mini_epg(&self, response: egui::Response, channel_name: &str, epg_channel_id: Option<&str>, tvg_shift: Option<f32>) -> egui::Response {
+        // The guide lookups are only worth doing for the row under the pointer
+        if !response.hovered() {
+            return response;
+        }
+        let Some(epg_id) = self.resolve_epg_id(channel_name, epg_channel_id) else { return response };
+        let adjusted_now = self.get_adjusted_now() - Self::tvg_shift_secs(tvg_shift);
+        let programs = self.get_upcoming_programs_at(&epg_id, 3, adjusted_now);
+        let current = programs.first().filter(|p| p.start <= adjusted_now);
+        let next: Vec<_> = programs.iter().filter(|p| p.start > adjusted_now).take(2).collect();
+        if current.is_none() && next.is_empty() {
+            return response;
+        }
+        
+        response.on_hover_ui(|ui| {
+            ui.set_max_width(320.0);
+            if let Some(prog) = current {
+                let starred = self.is_favorite_program(&prog.title);
+                let title = egui::RichText::new(format!("{}{}", if starred { "⭐ " } else { "" }, prog.title)).strong();
+                ui.label(if starred { title.color(egui::Color32::GOLD) } else { title });
+                ui.label(egui::RichText::new(format!(
+                    "{} - {} ({}m left)",
+                    Self::format_time(prog.start),
+                    Self::format_time(prog.stop),
+                    (prog.stop - adjusted_now).max(0) / 60
+                )).small().weak());
+                if let Some(desc) = prog.description.as_deref().filter(|d| !d.trim().is_empty()) {
+                    let short: String = desc.chars().take(300).collect();
+                    let ellipsis = if desc.chars().count() > 300 { "…" } else { "" };
+                    ui.label(format!("{}{}", Self::sanitize_text(&short), ellipsis));
+                }
+            }
+            if !next.is_empty() {
+                if current.is_some() {
+                    ui.separator();
+                }
+                for prog in next {
+                    ui.label(egui::RichText::new(format!("{}  {}", Self::format_time(prog.start), prog.title)).small());
+                }
+            }
+        })
+    }
+    
+    /// EPG channel ID for a stream - the provided ID, or a close match on the channel name
+    fn resolve_epg_id(&self, channel_name: &str, epg_channel_id: Option<&str>) -> Option<String> {
+        let epg = self.epg_data.as_ref()?;
         
         // Use provided ID or find by name match
-        let epg_id: Option<String> = epg_channel_id
+        epg_channel_id
             .map(|id| id.to_string())
             .or_else(|| {
                 // Clean up channel name for matching (remove common prefixes like "US:", "UK:", etc.)
@@ -1532,93 +3114,288 @@ impl IPTVApp {
                         }
                     })
                     .map(|(id, _)| id.clone())
-            });
-        
-        let Some(epg_id) = epg_id else { return };
-        let Some(program) = self.get_current_program(&epg_id) else { return };
-        
-        // Truncate title
-        let short_title: String = program.title.chars().take(20).collect();
-        let display_title = if program.title.len() > 20 {
-            format!("{}…", short_title)
-        } else {
-            short_title
-        };
-        
-        ui.label(" | ");
-        ui.label(egui::RichText::new(&display_title)
-            .color(egui::Color32::LIGHT_BLUE)
-            .italics());
-        
-        let remaining = (program.stop - self.get_adjusted_now()) / 60;
-        if remaining > 0 {
-            ui.label(egui::RichText::new(format!("({}m left)", remaining))
-                .small()
-                .color(egui::Color32::GRAY));
+            })
+    }
+    
+    /// Run the inline EPG matching over every loaded channel
+    fn check_epg_coverage(&self) -> EpgCoverage {
+        let mut coverage = EpgCoverage { total: self.current_channels.len(), ..Default::default() };
+        let Some(epg) = &self.epg_data else { return coverage };
+        for channel in &self.current_channels {
+            let name = self.display_name(&channel.url, &channel.name);
+            match self.resolve_epg_id(&channel.name, channel.epg_channel_id.as_deref()) {
+                Some(id) if epg.programs.get(&id).is_some_and(|p| !p.is_empty()) => coverage.matched += 1,
+                Some(id) => coverage.unmatched.push((name, format!("no programmes for \"{}\"", id))),
+                None => coverage.unmatched.push((name, "no matching guide channel".to_string())),
+            }
         }
+        coverage
     }
-
-    fn play_channel(&mut self, channel: &Channel) {
-        // Add to recently watched
-        let category_name = self.navigation_stack.iter().find_map(|n| {
-            match n {
-                NavigationLevel::Channels(name) => Some(name.clone()),
-                NavigationLevel::Series(name) => Some(name.clone()),
-                _ => None,
+    
+    /// Guide ids of the loaded channels and live favorites that have programmes
+    fn matched_epg_ids(&self) -> Vec<String> {
+        let Some(epg) = &self.epg_data else { return Vec::new() };
+        let loaded = self.current_channels.iter()
+            .map(|c| self.resolve_epg_id(&c.name, c.epg_channel_id.as_deref()));
+        let favorites = self.favorites.iter()
+            .filter(|f| f.stream_type == "live")
+            .map(|f| self.resolve_epg_id(&f.name, None));
+        let mut ids: Vec<String> = Vec::new();
+        for id in loaded.chain(favorites).flatten() {
+            if epg.programs.get(&id).is_some_and(|p| !p.is_empty()) && !ids.contains(&id) {
+                ids.push(id);
             }
-        }).unwrap_or_default();
+        }
+        ids
+    }
+    
+    /// Save a trimmed XMLTV file with only the matched channels' programmes
+    fn export_epg_xmltv(&mut self) {
+        let ids = self.matched_epg_ids();
+        let Some(epg) = &self.epg_data else { return };
+        if ids.is_empty() {
+            self.status_message = "No guide data matches the loaded channels".to_string();
+            return;
+        }
+        let Some(path) = rfd::FileDialog::new()
+            .set_title("Export EPG")
+            .set_file_name("guide.xml")
+            .add_filter("XMLTV", &["xml"])
+            .save_file()
+        else { return };
+        
+        let xml = epg.to_xmltv(&ids);
+        let programs: usize = ids.iter().filter_map(|id| epg.programs.get(id)).map(Vec::len).sum();
+        match std::fs::write(&path, xml) {
+            Ok(()) => {
+                self.status_message = format!("Exported {} channels ({} programmes)", ids.len(), programs);
+                self.log(&format!("[INFO] Exported XMLTV for {} channels to {}", ids.len(), path.display()));
+            }
+            Err(e) => {
+                self.status_message = format!("EPG export failed: {}", e);
+                self.log(&format!("[ERROR] EPG export to {} failed: {}", path.display(), e));
+            }
+        }
+    }
+    
+    /// "EPG coverage" section of the EPG dialog
+    fn show_epg_coverage(&mut self, ui: &mut egui::Ui) {
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.strong("Coverage:");
+            let has_channels = !self.current_channels.is_empty();
+            if ui.add_enabled(has_channels, egui::Button::new("🔍 Check coverage"))
+                .on_hover_text("How many of the loaded channels have guide data")
+                .on_disabled_hover_text("Open a channel category or load a playlist first")
+                .clicked()
+            {
+                let coverage = self.check_epg_coverage();
+                self.log(&format!("[INFO] EPG coverage: {}/{} channels ({:.0}%)",
+                    coverage.matched, coverage.total, coverage.percent()));
+                self.epg_coverage = Some(coverage);
+            }
+            let can_export = self.epg_data.is_some() && (has_channels || !self.favorites.is_empty());
+            if ui.add_enabled(can_export, egui::Button::new("💾 Export XMLTV"))
+                .on_hover_text("Save the guide for the loaded channels and live favorites only")
+                .on_disabled_hover_text("Load EPG data and a channel list first")
+                .clicked()
+            {
+                self.export_epg_xmltv();
+            }
+        });
         
-        // Determine stream type based on URL pattern and channel properties
-        let stream_type = if channel.series_id.is_some() {
-            "series"
-        } else if channel.url.contains("/live/") {
-            "live"
-        } else if channel.url.contains("/movie/") {
-            "movie"
-        } else if self.current_tab == Tab::Live {
-            "live"
-        } else if self.current_tab == Tab::Movies {
-            "movie"
-        } else {
-            // Default to live for ambiguous cases (M3U playlists, etc.)
-            "live"
+        let Some(coverage) = &self.epg_coverage else { return };
+        let color = match coverage.percent() {
+            p if p >= 80.0 => egui::Color32::GREEN,
+            p if p >= 40.0 => egui::Color32::YELLOW,
+            _ => egui::Color32::from_rgb(220, 80, 80),
         };
+        ui.label(egui::RichText::new(format!(
+            "{} of {} channels have guide data ({:.0}%)",
+            coverage.matched, coverage.total, coverage.percent()
+        )).color(color));
+        
+        if !coverage.unmatched.is_empty() {
+            egui::CollapsingHeader::new(format!("Unmatched channels ({})", coverage.unmatched.len()))
+                .id_salt("epg_unmatched")
+                .show(ui, |ui| {
+                    egui::ScrollArea::vertical()
+                        .id_salt("epg_unmatched_scroll")
+                        .max_height(150.0)
+                        .show(ui, |ui| {
+                            for (name, reason) in &coverage.unmatched {
+                                ui.horizontal(|ui| {
+                                    ui.label(name);
+                                    ui.label(egui::RichText::new(reason).weak().small());
+                                });
+                            }
+                        });
+                });
+        }
+    }
+    
+    fn is_favorite_program(&self, title: &str) -> bool {
+        let title = title.trim();
+        self.favorite_programs.iter().any(|t| t.eq_ignore_ascii_case(title))
+    }
+    
+    /// Star a program title, or unstar it
+    fn toggle_favorite_program(&mut self, title: &str) {
+        let title = title.trim();
+        if title.is_empty() {
+            return;
+        }
+        if self.is_favorite_program(title) {
+            self.favorite_programs.retain(|t| !t.eq_ignore_ascii_case(title));
+            self.status_message = format!("Unstarred '{}'", title);
+        } else {
+            self.favorite_programs.push(title.to_string());
+            self.status_message = format!("Starred '{}' - channels airing it get a ⭐", title);
+        }
+        self.config.favorite_programs = self.favorite_programs.clone();
+        self.config.save();
+    }
+    
+    /// URLs of the loaded live channels airing a starred program right now
+    fn airing_favorites(&mut self) -> HashSet<String> {
+        use std::hash::{Hash, Hasher};
         
-        // Don't reorder if playing from Recent tab
-        let reorder = self.current_tab != Tab::Recent;
+        if self.favorite_programs.is_empty() {
+            return HashSet::new();
+        }
         
-        self.add_to_recent(FavoriteItem {
-            name: channel.name.clone(),
-            url: channel.url.clone(),
-            stream_type: stream_type.to_string(),
-            stream_id: channel.stream_id,
-            series_id: channel.series_id,
-            category_name,
-            container_extension: channel.container_extension.clone(),
-            season_num: None,
-            episode_num: None,
-            series_name: None,
-            playlist_source: channel.playlist_source.clone(),
-        }, reorder);
-        
-        // Use internal player if enabled OR if user typed "internal" in player field
-        let player_lower = self.external_player.to_lowercase();
-        let use_internal = self.use_internal_player || player_lower == "internal";
-        
-        if use_internal {
-            return self.play_channel_internal(channel);
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.current_channels.len().hash(&mut hasher);
+        self.current_channels.first().map(|c| &c.url).hash(&mut hasher);
+        self.current_channels.last().map(|c| &c.url).hash(&mut hasher);
+        self.epg_data.as_deref().map(|epg| epg as *const EpgData as usize).hash(&mut hasher);
+        self.epg_last_update.hash(&mut hasher);
+        let signature = hasher.finish().max(1);
+        if signature != self.airing_favorites.signature {
+            // Resolving an EPG id can be a fuzzy scan over the whole guide
+            let epg_ids = self.current_channels.iter()
+                .filter_map(|c| {
+                    let id = self.resolve_epg_id(&c.name, c.epg_channel_id.as_deref())?;
+                    Some((c.url.clone(), id, Self::tvg_shift_secs(c.tvg_shift)))
+                })
+                .collect();
+            self.airing_favorites = AiringFavorites { signature, epg_ids, ..Default::default() };
         }
         
-        // Kill existing player if in single window mode
-        if self.single_window_mode {
-            if let Some(ref mut child) = self.current_player {
-                let _ = child.kill();
-                let _ = child.wait(); // Reap the process
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.favorite_programs.hash(&mut hasher);
+        let titles = hasher.finish();
+        let now = unix_timestamp();
+        let cache = &self.airing_favorites;
+        if cache.titles != titles || cache.computed_at == 0 || now - cache.computed_at >= 60 {
+            let adjusted_now = self.get_adjusted_now();
+            let urls = cache.epg_ids.iter()
+                .filter(|(_, id, shift_secs)| {
+                    self.get_current_program_at(id, adjusted_now - shift_secs)
+                        .is_some_and(|p| self.is_favorite_program(&p.title))
+                })
+                .map(|(url, ..)| url.clone())
+                .collect();
+            self.airing_favorites.urls = urls;
+            self.airing_favorites.titles = titles;
+            self.airing_favorites.computed_at = now;
+        }
+        self.airing_favorites.urls.clone()
+    }
+    
+    /// "⭐ My shows" window - starred titles and every guide channel airing one now
+    fn show_my_shows_window(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+        let mut to_unstar: Option<String> = None;
+        let mut to_play: Option<Channel> = None;
+        
+        // Guide channels currently airing a starred title: (channel name, title, minutes left, loaded channel)
+        let mut on_now: Vec<(String, String, i64, Option<Channel>)> = Vec::new();
+        if let Some(epg) = &self.epg_data {
+            let adjusted_now = self.get_adjusted_now();
+            for (id, programs) in &epg.programs {
+                let idx = programs.partition_point(|p| p.stop <= adjusted_now);
+                let Some(prog) = programs.get(idx).filter(|p| p.start <= adjusted_now) else { continue };
+                if !self.is_favorite_program(&prog.title) {
+                    continue;
+                }
+                let name = epg.channels.get(id).map(|c| c.name.clone()).unwrap_or_else(|| id.clone());
+                let loaded = self.current_channels.iter()
+                    .find(|c| c.epg_channel_id.as_deref() == Some(id.as_str()))
+                    .cloned();
+                on_now.push((name, prog.title.clone(), (prog.stop - adjusted_now) / 60, loaded));
             }
-            self.current_player = None;
-            self.log("[PLAY] Single window mode - closing previous player");
         }
+        on_now.sort_by_cached_key(|(name, ..)| name.to_lowercase());
+        
+        egui::Window::new("⭐ My shows")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(&mut self.my_show_input)
+                        .hint_text("Program title...")
+                        .desired_width(260.0));
+                    if ui.button("⭐ Star").clicked() && !self.my_show_input.trim().is_empty() {
+                        let title = std::mem::take(&mut self.my_show_input);
+                        if !self.is_favorite_program(&title) {
+                            self.toggle_favorite_program(&title);
+                        }
+                    }
+                });
+                ui.label(egui::RichText::new("Tip: right-click a program in the EPG guide to star it").weak().small());
+                
+                ui.add_space(4.0);
+                ui.strong(format!("On now ({})", on_now.len()));
+                if self.epg_data.is_none() {
+                    ui.label(egui::RichText::new("Load EPG data to see what's on").weak());
+                } else if on_now.is_empty() {
+                    ui.label(egui::RichText::new("None of your shows are airing right now").weak());
+                }
+                egui::ScrollArea::vertical().id_salt("my_shows_on_now").max_height(240.0).show(ui, |ui| {
+                    for (name, title, left, loaded) in &on_now {
+                        ui.horizontal(|ui| {
+                            if let Some(channel) = loaded {
+                                if ui.button("▶").on_hover_text("Play").clicked() {
+                                    to_play = Some(channel.clone());
+                                }
+                            }
+                            ui.label(Self::sanitize_text(name));
+                            ui.label(egui::RichText::new(title).color(egui::Color32::GOLD).italics());
+                            ui.label(egui::RichText::new(format!("({}m left)", left)).small().color(egui::Color32::GRAY));
+                        });
+                    }
+                });
+                
+                ui.add_space(4.0);
+                ui.separator();
+                ui.strong(format!("Starred ({})", self.favorite_programs.len()));
+                for title in &self.favorite_programs {
+                    ui.horizontal(|ui| {
+                        if ui.small_button("✖").on_hover_text("Unstar").clicked() {
+                            to_unstar = Some(title.clone());
+                        }
+                        ui.label(title);
+                    });
+                }
+            });
         
+        if let Some(title) = to_unstar {
+            self.toggle_favorite_program(&title);
+        }
+        if let Some(channel) = to_play {
+            self.play_channel(&channel);
+        }
+        if !open {
+            self.show_my_shows = false;
+        }
+    }
+
+    /// Configured external player command (ffplay when empty), with common
+    /// install locations auto-detected on Windows
+    fn resolved_player(&self) -> String {
         let player = if self.external_player.is_empty() {
             "ffplay".to_string()
         } else {
@@ -1668,12 +3445,160 @@ impl IPTVApp {
         #[cfg(not(target_os = "windows"))]
         let player = player;
         
+        player
+    }
+    
+    /// Launch the configured player with a version flag to check the command works
+    fn test_player(&mut self) {
+        let player = self.resolved_player();
+        let player_lower = player.to_lowercase();
+        // FFmpeg tools use a single dash
+        let version_arg = if player_lower.contains("ffplay") || player_lower.contains("ffmpeg") {
+            "-version"
+        } else {
+            "--version"
+        };
+        
+        self.log(&format!("[INFO] Testing player: {} {}", player, version_arg));
+        self.status_message = format!("Testing player '{}'...", player);
+        
+        let mut cmd = Command::new(&player);
+        cmd.arg(version_arg)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        
+        #[cfg(target_os = "windows")]
+        {
+            use std::os::windows::process::CommandExt;
+            const CREATE_NO_WINDOW: u32 = 0x08000000;
+            cmd.creation_flags(CREATE_NO_WINDOW);
+        }
+        
+        let sender = self.task_sender.clone();
+        thread::spawn(move || {
+            let result = match cmd.output() {
+                Ok(output) => {
+                    let stdout = String::from_utf8_lossy(&output.stdout);
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    let first_line = |text: &str| text.lines().map(str::trim).find(|l| !l.is_empty()).map(str::to_string);
+                    if output.status.success() {
+                        Ok(first_line(&stdout).or_else(|| first_line(&stderr)).unwrap_or_else(|| "no version output".to_string()))
+                    } else {
+                        Err(format!("exited with {} - {}", output.status,
+                            first_line(&stderr).or_else(|| first_line(&stdout)).unwrap_or_default()))
+                    }
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err("not found - check the path or PATH".to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            let _ = sender.send(TaskResult::PlayerTested { player, result });
+        });
+    }
+
+    fn play_channel(&mut self, channel: &Channel) {
+        // Add to recently watched
+        let category_name = self.navigation_stack.iter().find_map(|n| {
+            match n {
+                NavigationLevel::Channels(name) => Some(name.clone()),
+                NavigationLevel::Series(name) => Some(name.clone()),
+                _ => None,
+            }
+        }).unwrap_or_default();
+        
+        // Determine stream type based on URL pattern and channel properties
+        let stream_type = if channel.series_id.is_some() {
+            "series"
+        } else if channel.url.contains("/live/") {
+            "live"
+        } else if channel.url.contains("/movie/") {
+            "movie"
+        } else if self.current_tab == Tab::Live {
+            "live"
+        } else if self.current_tab == Tab::Movies {
+            "movie"
+        } else {
+            // Default to live for ambiguous cases (M3U playlists, etc.)
+            "live"
+        };
+        
+        // Don't reorder if playing from Recent tab
+        let reorder = self.current_tab != Tab::Recent;
+        
+        self.add_to_recent(FavoriteItem {
+            name: channel.name.clone(),
+            url: channel.url.clone(),
+            stream_type: stream_type.to_string(),
+            stream_id: channel.stream_id,
+            series_id: channel.series_id,
+            category_name,
+            container_extension: channel.container_extension.clone(),
+            season_num: None,
+            episode_num: None,
+            series_name: None,
+            playlist_source: channel.playlist_source.clone(),
+            resume_secs: None,
+            duration_secs: None,
+        }, reorder);
+        
+        record_play(&mut self.play_counts, &channel.url);
+        self.config.play_counts = self.play_counts.clone();
+        self.save_config_soon();
+        
+        self.playing_url = Some(channel.url.clone());
+        self.start_playback(channel);
+    }
+    
+    /// Launch a channel in the configured player without touching history
+    fn start_playback(&mut self, channel: &Channel) {
+        if let Some(cmd) = channel.url.strip_prefix(STALKER_LINK_PREFIX) {
+            return self.resolve_stalker_link(channel, cmd);
+        }
+        
+        let checked = self.hls_checked_url.take().is_some_and(|url| url == channel.url);
+        if self.choose_hls_quality && !checked && stream_container_label(&channel.url, None) == Some("HLS") {
+            return self.fetch_hls_variants(channel);
+        }
+        
+        // Use internal player if enabled OR if user typed "internal" in player field
+        let player_lower = self.external_player.to_lowercase();
+        let use_internal = self.use_internal_player || player_lower == "internal";
+        
+        if use_internal {
+            return self.play_channel_internal(channel);
+        }
+        
+        // Kill existing player if in single window mode
+        if self.single_window_mode {
+            if let Some(ref mut child) = self.current_player {
+                let _ = child.kill();
+                let _ = child.wait(); // Reap the process
+            }
+            self.current_player = None;
+            self.log("[PLAY] Single window mode - closing previous player");
+        }
+        
+        let player = self.resolved_player();
+        
         self.log(&format!("[PLAY] {} | Player: {}", Self::sanitize_text(&channel.name), player));
         self.log(&format!("[PLAY] URL: {}", channel.url));
 
         let player_lower = player.to_lowercase();
         let mut cmd = Command::new(&player);
         
+        // Movies and episodes pick up where they were stopped
+        let resume_secs = self.resume_secs_for(&channel.url);
+        let mut mpv_ipc_path: Option<String> = None;
+        
+        // A user agent set by the playlist for this channel wins over the global one,
+        // and is passed even when passing the user agent is turned off
+        let (channel_user_agent, referrer) = self.stream_headers(channel);
+        let pass_user_agent = self.pass_user_agent_to_player || channel_user_agent.is_some();
+        let user_agent = channel_user_agent.unwrap_or_else(|| self.get_player_user_agent());
+        if let Some(referrer) = &referrer {
+            self.log(&format!("[PLAY] Referrer: {}", referrer));
+        }
+        
         // On Windows, hide the console window for ffplay/ffmpeg
         #[cfg(target_os = "windows")]
         {
@@ -1715,13 +3640,7 @@ impl IPTVApp {
             args.extend(["-window_title".to_string(), title]);
             
             // Add reconnect options for HTTP streams
-            if channel.url.starts_with("http") {
-                args.extend([
-                    "-reconnect".to_string(), "1".to_string(),
-                    "-reconnect_streamed".to_string(), "1".to_string(),
-                    "-reconnect_delay_max".to_string(), if is_slow { "30".to_string() } else { "10".to_string() },
-                ]);
-            }
+            args.extend(player_args::reconnect_args(&channel.url, is_slow));
             
             // Infinite buffer for slow connections
             if is_slow {
@@ -1729,11 +3648,14 @@ impl IPTVApp {
             }
             
             // User agent (optional)
-            if self.pass_user_agent_to_player {
+            if pass_user_agent {
                 args.extend([
-                    "-user_agent".to_string(), self.get_user_agent(),
+                    "-user_agent".to_string(), user_agent.clone(),
                 ]);
             }
+            if let Some(referrer) = &referrer {
+                args.extend(["-headers".to_string(), format!("Referer: {}\r\n", referrer)]);
+            }
             
             // Hardware acceleration - disabled on Windows (black screen with Vulkan renderer)
             // Works on Linux/Mac
@@ -1813,8 +3735,18 @@ impl IPTVApp {
             }
             
             // User agent
-            if self.pass_user_agent_to_player {
-                args.push(format!("--user-agent={}", self.get_user_agent()));
+            if pass_user_agent {
+                args.push(format!("--user-agent={}", user_agent));
+            }
+            if let Some(referrer) = &referrer {
+                args.push(format!("--referrer={}", referrer));
+            }
+            
+            // Movies and episodes report their position back for resuming
+            if self.is_vod_history_url(&channel.url) {
+                let path = mpv_ipc::ipc_path(&format!("{}_{}", std::process::id(), unix_timestamp()));
+                args.push(format!("--input-ipc-server={}", path));
+                mpv_ipc_path = Some(path);
             }
             
             // Slow connection optimizations
@@ -1859,8 +3791,11 @@ impl IPTVApp {
             }
             
             // User agent
-            if self.pass_user_agent_to_player {
-                args.push(format!("--http-user-agent={}", self.get_user_agent()));
+            if pass_user_agent {
+                args.push(format!("--http-user-agent={}", user_agent));
+            }
+            if let Some(referrer) = &referrer {
+                args.push(format!("--http-referrer={}", referrer));
             }
             
             for arg in args {
@@ -1888,8 +3823,8 @@ impl IPTVApp {
                 "-title".to_string(), title,
             ];
             
-            if self.pass_user_agent_to_player {
-                args.extend(["-user-agent".to_string(), self.get_user_agent()]);
+            if pass_user_agent {
+                args.extend(["-user-agent".to_string(), user_agent.clone()]);
             }
             
             for arg in args {
@@ -1908,9 +3843,31 @@ impl IPTVApp {
             // Generic player - just pass URL
             cmd.arg(&channel.url);
         }
+        
+        if let Some(secs) = resume_secs {
+            let start = player_args::start_args(&player_lower, secs);
+            if !start.is_empty() {
+                self.log(&format!("[PLAY] Resuming at {}", mpv_ipc::format_position(secs)));
+                cmd.args(start);
+            }
+        }
+        
+        // Preferred audio/subtitle languages
+        let language_args = player_args::language_args(&player_lower, &self.audio_language, &self.subtitle_language);
+        if !language_args.is_empty() {
+            self.log(&format!("[PLAY] Languages: {}", language_args.join(" ")));
+            cmd.args(language_args);
+        }
+        
+        // User-defined extra arguments (appended for any player)
+        if !self.player_extra_args.trim().is_empty() {
+            let extra = player_args::expand_args(&self.player_extra_args, &channel.url, &channel.name);
+            self.log(&format!("[PLAY] Extra args: {}", extra.join(" ")));
+            cmd.args(extra);
+        }
 
         // Set user agent environment variable for some players
-        cmd.env("USER_AGENT", self.get_user_agent());
+        cmd.env("USER_AGENT", &user_agent);
         
         // Capture stderr for error logging
         cmd.stderr(Stdio::piped());
@@ -1920,6 +3877,17 @@ impl IPTVApp {
             Ok(mut child) => {
                 let pid = child.id();
                 self.log(&format!("[PLAY] Player launched successfully (PID: {})", pid));
+                self.last_launch = Some((pid, std::time::Instant::now(), channel.clone()));
+                
+                if let Some(path) = mpv_ipc_path {
+                    let sender = self.task_sender.clone();
+                    let url = channel.url.clone();
+                    mpv_ipc::watch_position(path, move |last| {
+                        if let Some((position, duration)) = last {
+                            let _ = sender.send(TaskResult::PlaybackPosition { url, position, duration });
+                        }
+                    });
+                }
                 
                 // Take stderr before potentially moving child
                 let stderr = child.stderr.take();
@@ -1942,24 +3910,35 @@ impl IPTVApp {
                 if self.single_window_mode {
                     self.current_player = Some(child);
                 } else {
-                    // Spawn monitoring thread for non-single-window mode to track exit
+                    // Spawn monitoring thread for non-single-window mode to track exit.
+                    // The child stays in the shared list so it can be closed on app exit.
                     let sender = self.task_sender.clone();
                     let channel_name = channel.name.clone();
-                    thread::spawn(move || {
-                        // Wait for process and get exit code
-                        match child.wait() {
-                            Ok(status) => {
+                    let players = self.detached_players.clone();
+                    players.lock().unwrap_or_else(|e| e.into_inner()).insert(pid, child);
+                    thread::spawn(move || loop {
+                        thread::sleep(std::time::Duration::from_millis(500));
+                        let mut players = players.lock().unwrap_or_else(|e| e.into_inner());
+                        // Gone from the list - already killed on shutdown
+                        let Some(child) = players.get_mut(&pid) else { return };
+                        match child.try_wait() {
+                            Ok(None) => continue,
+                            Ok(Some(status)) => {
+                                players.remove(&pid);
                                 if !status.success() {
                                     let _ = sender.send(TaskResult::PlayerExited {
+                                        pid,
                                         code: status.code(),
                                         stderr: format!("Player exited with error for '{}'", channel_name),
                                     });
                                 }
                             }
                             Err(e) => {
+                                players.remove(&pid);
                                 let _ = sender.send(TaskResult::PlayerLog(format!("[ERROR] Failed to wait for player: {}", e)));
                             }
                         }
+                        return;
                     });
                 }
             }
@@ -1970,87 +3949,625 @@ impl IPTVApp {
         }
     }
     
-    /// Play using internal FFmpeg player
-    fn play_channel_internal(&mut self, channel: &Channel) {
-        self.log(&format!("[PLAY] {} | Internal Player", Self::sanitize_text(&channel.name)));
-        self.log(&format!("[PLAY] URL: {}", channel.url));
-        
-        let buffer_secs = self.get_effective_buffer();
-        let user_agent = self.get_user_agent();
-        
-        self.internal_player.play(&channel.name, &channel.url, buffer_secs, &user_agent);
-        self.show_internal_player = true;
+    /// External players launched by this app that are still open
+    fn running_player_count(&self) -> usize {
+        usize::from(self.current_player.is_some())
+            + self.detached_players.lock().unwrap_or_else(|e| e.into_inner()).len()
     }
-
-    fn play_episode(&mut self, episode: &Episode, series_id: i64) {
-        // Get series name from navigation or use generic name
-        let series_name = self.navigation_stack.iter().find_map(|n| {
-            if let NavigationLevel::Series(name) = n { Some(name.clone()) } else { None }
-        }).or_else(|| {
-            // Check if viewing from favorites
-            self.fav_viewing_series.as_ref().map(|(_, name)| name.clone())
-        }).unwrap_or_else(|| "Series".to_string());
-        
-        let url = format!(
-            "{}/series/{}/{}/{}.{}",
-            self.server, self.username, self.password,
-            episode.id, episode.container_extension
-        );
-        
-        let channel = Channel {
-            name: format!("{} - {}", series_name, episode.title),
-            url,
-            stream_id: Some(episode.id),
-            category_id: None,
-            epg_channel_id: None,
-            stream_icon: None,
-            series_id: Some(series_id),
-            container_extension: Some(episode.container_extension.clone()),
-            playlist_source: None,
-        };
-        
-        self.play_channel(&channel);
+    
+    /// Kill the single-window player and every multi-window player - returns their PIDs
+    fn kill_external_players(&mut self) -> Vec<u32> {
+        let mut children: Vec<_> = self.current_player.take().into_iter().collect();
+        // Monitoring threads notice their PID is gone from the list and stop
+        children.extend(self.detached_players.lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .drain()
+            .map(|(_, child)| child));
+        children.into_iter()
+            .map(|mut child| {
+                let _ = child.kill();
+                let _ = child.wait(); // Reap the process
+                child.id()
+            })
+            .collect()
     }
-
-    fn go_back(&mut self) {
-        if self.navigation_stack.pop().is_some() {
-            // Restore scroll position for the previous level
-            if let Some(scroll_y) = self.scroll_positions.pop() {
-                self.pending_scroll_restore = Some(scroll_y);
-            }
-            
-            // Handle what to show based on remaining stack
-            if let Some(level) = self.navigation_stack.last() {
-                match level {
-                    NavigationLevel::Categories => {
-                        self.current_channels.clear();
-                        self.current_series.clear();
-                    }
-                    NavigationLevel::Channels(_cat) => {
-                        // Refetch channels
-                    }
-                    _ => {}
-                }
-            } else {
-                self.current_channels.clear();
-                self.current_series.clear();
-                self.current_seasons.clear();
-                self.current_episodes.clear();
-            }
+    
+    /// "Stop Player" button / X shortcut
+    fn stop_external_players(&mut self) {
+        let pids = self.kill_external_players();
+        for pid in &pids {
+            self.log(&format!("[INFO] Stopped player (PID: {})", pid));
         }
-        self.search_query.clear();
+        self.status_message = match pids.len() {
+            0 => "No player running".to_string(),
+            1 => "Stopped player".to_string(),
+            n => format!("Stopped {} players", n),
+        };
     }
     
-    /// Save current scroll position before navigating into a folder
-    fn save_scroll_position(&mut self, _ctx: &egui::Context) {
-        // Save the current scroll offset tracked from the scroll area
-        self.scroll_positions.push(self.current_scroll_offset);
+    /// Ask for the recordings folder - false when the dialog was cancelled
+    fn choose_recording_dir(&mut self) -> bool {
+        let Some(dir) = rfd::FileDialog::new()
+            .set_title("Select Recordings Folder")
+            .pick_folder()
+        else {
+            return false;
+        };
+        self.recording_dir = dir.display().to_string();
+        self.config.recording_dir = self.recording_dir.clone();
+        self.config.save();
+        true
     }
-
-    fn extract_m3u_credentials(&mut self, url: &str) {
-        // Parse m3u_plus URL format:
-        // http://server.com/get.php?username=XXX&password=YYY&type=m3u_plus
-        if let Some(query_start) = url.find('?') {
+    
+    /// Copy a stream to a .ts file in the recordings folder with ffmpeg (no re-encoding)
+    fn start_recording(&mut self, channel: &Channel) {
+        if channel.url.starts_with(STALKER_LINK_PREFIX) {
+            self.status_message = "Portal channels can't be recorded".to_string();
+            return;
+        }
+        if self.recording_dir.is_empty() && !self.choose_recording_dir() {
+            return;
+        }
+        
+        let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+        let output = std::path::Path::new(&self.recording_dir)
+            .join(player_args::recording_file_name(&channel.name, &timestamp));
+        let is_slow = matches!(self.connection_quality, ConnectionQuality::Slow | ConnectionQuality::VerySlow);
+        let user_agent = self.get_player_user_agent();
+        let args = player_args::record_args(&channel.url, Some(&user_agent), is_slow, &output.display().to_string());
+        
+        let mut cmd = Command::new("ffmpeg");
+        #[cfg(target_os = "windows")]
+        {
+            use std::os::windows::process::CommandExt;
+            const CREATE_NO_WINDOW: u32 = 0x08000000;
+            cmd.creation_flags(CREATE_NO_WINDOW);
+        }
+        cmd.args(&args).stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::piped());
+        
+        match cmd.spawn() {
+            Ok(mut child) => {
+                self.log(&format!("[RECORD] {} -> {} (PID: {})", Self::sanitize_text(&channel.name), output.display(), child.id()));
+                self.status_message = format!("Recording '{}'", Self::sanitize_text(&channel.name));
+                
+                if let Some(stderr) = child.stderr.take() {
+                    let sender = self.task_sender.clone();
+                    thread::spawn(move || {
+                        // Progress lines end in \r and update constantly - pass one on every 10s
+                        let mut last_progress: Option<std::time::Instant> = None;
+                        for chunk in BufReader::new(stderr).split(b'\r') {
+                            let Ok(chunk) = chunk else { break };
+                            for line in String::from_utf8_lossy(&chunk).lines().map(str::trim).filter(|l| !l.is_empty()) {
+                                if line.starts_with("frame=") || line.starts_with("size=") {
+                                    if last_progress.is_some_and(|t| t.elapsed().as_secs() < 10) {
+                                        continue;
+                                    }
+                                    last_progress = Some(std::time::Instant::now());
+                                }
+                                let _ = sender.send(TaskResult::PlayerLog(format!("[RECORD] {}", line)));
+                            }
+                        }
+                    });
+                }
+                self.recordings.push((channel.name.clone(), child));
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                self.log("[ERROR] ffmpeg not found - install FFmpeg and make sure it is on your PATH");
+                self.status_message = "Recording needs ffmpeg on your PATH".to_string();
+            }
+            Err(e) => {
+                self.log(&format!("[ERROR] Failed to start recording: {}", e));
+                self.status_message = format!("Failed to start recording: {}", e);
+            }
+        }
+    }
+    
+    /// Stop every running recording (ffmpeg is killed - MPEG-TS files stay playable)
+    fn stop_recordings(&mut self) {
+        for (name, mut child) in std::mem::take(&mut self.recordings) {
+            let _ = child.kill();
+            let _ = child.wait();
+            self.log(&format!("[RECORD] Stopped '{}'", Self::sanitize_text(&name)));
+        }
+    }
+    
+    /// Log recordings whose ffmpeg has exited (stream ended or failed)
+    fn poll_recordings(&mut self) {
+        let mut finished = Vec::new();
+        self.recordings.retain_mut(|(name, child)| match child.try_wait() {
+            Ok(None) => true,
+            Ok(Some(status)) => {
+                finished.push((name.clone(), status.code()));
+                false
+            }
+            Err(_) => {
+                finished.push((name.clone(), None));
+                false
+            }
+        });
+        for (name, code) in finished {
+            match code {
+                Some(0) => self.log(&format!("[RECORD] Finished '{}'", Self::sanitize_text(&name))),
+                _ => self.log(&format!("[WARN] Recording of '{}' ended with an error (code {:?})", Self::sanitize_text(&name), code)),
+            }
+        }
+    }
+    
+    /// Close every player this app launched (called on exit so none are left orphaned)
+    fn shutdown_players(&mut self) {
+        for pid in self.kill_external_players() {
+            self.log(&format!("[INFO] Closed player (PID: {}) on exit", pid));
+        }
+        self.stop_recordings();
+        
+        self.player_window().stop();
+        self.show_internal_player = false;
+    }
+    
+    /// Start or stop the LAN playlist server
+    fn set_playlist_server(&mut self, enabled: bool) {
+        self.playlist_server = None;
+        self.served_playlist_signature = 0;
+        if enabled {
+            match PlaylistServer::start(&self.serve_playlist_addr) {
+                Ok(server) => {
+                    self.log(&format!("[INFO] Serving playlist at {}", server.playlist_url()));
+                    self.playlist_server = Some(server);
+                }
+                Err(e) => {
+                    self.log(&format!("[ERROR] {}", e));
+                    self.status_message = e;
+                }
+            }
+        }
+        self.serve_playlist = self.playlist_server.is_some();
+        self.config.serve_playlist = self.serve_playlist;
+        self.config.serve_playlist_addr = self.serve_playlist_addr.clone();
+        self.config.save();
+    }
+    
+    /// Loaded channels as M3U entries (served on the LAN and exported) - hidden
+    /// channels are left out, the group is the playlist source or open category
+    fn current_channels_as_m3u(&self) -> Vec<m3u_parser::M3uChannel> {
+        let group = match self.navigation_stack.last() {
+            Some(NavigationLevel::Channels(name)) => Some(name.clone()),
+            _ => None,
+        };
+        // Stalker placeholders only become playable through this app's portal session
+        self.current_channels.iter()
+            .filter(|c| !self.is_hidden(&c.url) && !c.url.starts_with(STALKER_LINK_PREFIX))
+            .map(|c| m3u_parser::M3uChannel {
+                name: self.display_name(&c.url, &c.name),
+                url: c.url.clone(),
+                group: c.playlist_source.clone().or_else(|| group.clone()),
+                tvg_id: c.epg_channel_id.clone(),
+                tvg_logo: c.stream_icon.clone(),
+                tvg_name: None,
+                tvg_chno: None,
+                channel_id: None,
+                channel_number: None,
+                catchup: c.catchup.clone(),
+                catchup_days: None,
+                catchup_source: c.catchup_source.clone(),
+                tvg_shift: c.tvg_shift,
+                group_logo: None,
+                user_agent: c.user_agent.clone(),
+                referrer: c.referrer.clone(),
+            })
+            .collect()
+    }
+    
+    /// First web EPG source - url-tvg holds a single guide
+    fn m3u_epg_url(&self) -> Option<String> {
+        epg::split_epg_sources(&self.epg_url_input).into_iter()
+            .find(|u| u.starts_with("http://") || u.starts_with("https://"))
+    }
+    
+    /// Save the loaded channel list as an .m3u file for use in other apps
+    fn export_m3u(&mut self) {
+        let channels = self.current_channels_as_m3u();
+        if channels.is_empty() {
+            self.status_message = "Open a channel category or load a playlist first".to_string();
+            return;
+        }
+        let Some(path) = rfd::FileDialog::new()
+            .set_title("Export M3U")
+            .set_file_name("channels.m3u")
+            .add_filter("M3U playlist", &["m3u", "m3u8"])
+            .save_file()
+        else { return };
+        
+        let m3u = m3u_parser::write_m3u(&channels, self.m3u_epg_url().as_deref());
+        match std::fs::write(&path, m3u) {
+            Ok(()) => {
+                self.status_message = format!("Exported {} channels", channels.len());
+                self.log(&format!("[INFO] Exported {} channels to {}", channels.len(), path.display()));
+            }
+            Err(e) => {
+                self.status_message = format!("M3U export failed: {}", e);
+                self.log(&format!("[ERROR] M3U export to {} failed: {}", path.display(), e));
+            }
+        }
+    }
+    
+    /// Re-render the served playlist when the loaded channels, hidden list or names change
+    fn sync_served_playlist(&mut self) {
+        use std::hash::{Hash, Hasher};
+        
+        let Some(server) = &self.playlist_server else { return };
+        let group = match self.navigation_stack.last() {
+            Some(NavigationLevel::Channels(name)) => Some(name.clone()),
+            _ => None,
+        };
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.current_channels.len().hash(&mut hasher);
+        self.current_channels.first().map(|c| &c.url).hash(&mut hasher);
+        self.current_channels.last().map(|c| &c.url).hash(&mut hasher);
+        self.hidden_channels.len().hash(&mut hasher);
+        self.channel_aliases.len().hash(&mut hasher);
+        group.hash(&mut hasher);
+        let signature = hasher.finish();
+        if signature == self.served_playlist_signature {
+            return;
+        }
+        self.served_playlist_signature = signature;
+        
+        let m3u = m3u_parser::write_m3u(&self.current_channels_as_m3u(), self.m3u_epg_url().as_deref());
+        server.set_playlist(m3u);
+    }
+    
+    /// Switch low data mode, pausing or resuming the subsystems it covers
+    fn set_low_data_mode(&mut self, enabled: bool) {
+        self.low_data_mode = enabled;
+        self.image_cache.set_enabled(!enabled);
+        self.log(&format!("[INFO] Low data mode {}", if enabled { "on" } else { "off" }));
+    }
+    
+    /// Show a folder in the system file manager
+    fn open_folder(&mut self, path: &std::path::Path) {
+        #[cfg(target_os = "windows")]
+        let opener = "explorer";
+        #[cfg(target_os = "macos")]
+        let opener = "open";
+        #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+        let opener = "xdg-open";
+        
+        match Command::new(opener).arg(path).stdout(Stdio::null()).stderr(Stdio::null()).spawn() {
+            Ok(_) => self.status_message = format!("Opened {}", path.display()),
+            Err(e) => {
+                self.status_message = format!("Could not open {}: {}", path.display(), e);
+                self.log(&format!("[ERROR] {} {}: {}", opener, path.display(), e));
+            }
+        }
+    }
+    
+    /// Act on a --play / --playlist command-line option
+    fn handle_launch_intent(&mut self, intent: LaunchIntent) {
+        match intent {
+            LaunchIntent::Play(url) => {
+                self.log(&format!("[INFO] Command line: play {}", url));
+                let name = url.trim_end_matches('/')
+                    .rsplit('/')
+                    .next()
+                    .filter(|s| !s.is_empty())
+                    .unwrap_or(&url)
+                    .to_string();
+                let channel = Channel {
+                    name,
+                    url,
+                    stream_id: None,
+                    category_id: None,
+                    epg_channel_id: None,
+                    stream_icon: None,
+                    series_id: None,
+                    container_extension: None,
+                    playlist_source: None,
+                    catchup: None,
+                    catchup_source: None,
+                    tvg_shift: None,
+                    alternate_urls: Vec::new(),
+                    archive_days: None,
+                    genre: None,
+                    user_agent: None,
+                    referrer: None,
+                };
+                self.play_channel(&channel);
+            }
+            LaunchIntent::Playlist(url) => {
+                self.log(&format!("[INFO] Command line: load playlist {}", url));
+                self.load_playlist(&url);
+            }
+        }
+    }
+    
+    /// Log a failed player exit, and if the stream died right after launch
+    /// move on to the channel's next backup URL
+    fn handle_player_exit(&mut self, pid: u32, code: Option<i32>, stderr: String) {
+        let exit_msg = match code {
+            Some(c) => format!("[WARN] Player exited with code {}: {}", c, stderr),
+            None => format!("[WARN] Player terminated by signal: {}", stderr),
+        };
+        self.log(&exit_msg);
+        let what = match &self.last_launch {
+            Some((launch_pid, _, channel)) if *launch_pid == pid => format!("Playback: {}", Self::sanitize_text(&channel.name)),
+            _ => "Playback".to_string(),
+        };
+        let reason = match code {
+            Some(c) => format!("exit code {}: {}", c, stderr),
+            None => format!("terminated by signal: {}", stderr),
+        };
+        self.record_failure(&what, &reason);
+        
+        // An older detached player exiting must not touch the current stream
+        if !matches!(&self.last_launch, Some((p, ..)) if *p == pid) {
+            return;
+        }
+        self.status_message = stderr;
+        let Some((_, started, channel)) = self.last_launch.take() else { return };
+        if started.elapsed() > PLAYER_QUICK_FAIL {
+            return;
+        }
+        let Some((next_url, rest)) = channel.alternate_urls.split_first() else { return };
+        
+        self.log(&format!("[PLAY] Stream failed after {:.1}s - trying backup URL ({} left)",
+            started.elapsed().as_secs_f32(), rest.len()));
+        self.status_message = format!("Trying backup stream for {}...", channel.name);
+        let backup = Channel {
+            url: next_url.clone(),
+            alternate_urls: rest.to_vec(),
+            archive_days: None,
+            ..channel
+        };
+        self.start_playback(&backup);
+    }
+    
+    /// Play using internal FFmpeg player
+    fn play_channel_internal(&mut self, channel: &Channel) {
+        self.log(&format!("[PLAY] {} | Internal Player", Self::sanitize_text(&channel.name)));
+        self.log(&format!("[PLAY] URL: {}", channel.url));
+        
+        let buffer_secs = self.get_effective_buffer();
+        let (channel_user_agent, referrer) = self.stream_headers(channel);
+        let user_agent = channel_user_agent.unwrap_or_else(|| self.get_player_user_agent());
+        if let Some(referrer) = &referrer {
+            self.log(&format!("[PLAY] Referrer: {}", referrer));
+        }
+        
+        // Only live streams are reopened when they end - a finished movie stays finished
+        let live = channel.is_live() && !matches!(self.current_tab, Tab::Movies | Tab::Series);
+        
+        // Episodes queue the next one of the season when they end
+        let is_episode = self.playing_episode.as_ref().is_some_and(|(_, _, url)| *url == channel.url);
+        let on_finished: Option<ffmpeg_player::FinishedHook> = if self.autoplay_next_episode && is_episode {
+            let sender = self.task_sender.clone();
+            Some(Box::new(move |url: &str| {
+                let _ = sender.send(TaskResult::EpisodeFinished(url.to_string()));
+            }))
+        } else {
+            None
+        };
+        self.player_window().set_on_finished(on_finished);
+        
+        self.player_window().play(&channel.name, &channel.url, buffer_secs, (&user_agent, referrer.as_deref()),
+            self.internal_player_max_retries, live);
+        self.internal_player_closed.store(false, std::sync::atomic::Ordering::Relaxed);
+        self.show_internal_player = true;
+    }
+
+    fn play_episode(&mut self, episode: &Episode, series_id: i64) {
+        // Get series name from navigation or use generic name
+        let series_name = self.navigation_stack.iter().find_map(|n| {
+            if let NavigationLevel::Series(name) = n { Some(name.clone()) } else { None }
+        }).or_else(|| {
+            // Check if viewing from favorites
+            self.fav_viewing_series.as_ref().map(|(_, name)| name.clone())
+        }).unwrap_or_else(|| "Series".to_string());
+        
+        let container = clean_container_extension(Some(&episode.container_extension)).unwrap_or("mp4").to_string();
+        let url = format!(
+            "{}/series/{}/{}/{}.{}",
+            self.server, self.username, self.password,
+            episode.id, container
+        );
+        
+        let channel = Channel {
+            name: format!("{} - {}", series_name, episode.title),
+            url,
+            stream_id: Some(episode.id),
+            category_id: None,
+            epg_channel_id: None,
+            stream_icon: None,
+            series_id: Some(series_id),
+            container_extension: Some(container),
+            playlist_source: None,
+            catchup: None,
+            catchup_source: None,
+            tvg_shift: None,
+            alternate_urls: Vec::new(),
+            archive_days: None,
+            genre: None,
+            user_agent: None,
+            referrer: None,
+        };
+        
+        self.playing_episode = Some((series_id, episode.id, channel.url.clone()));
+        self.play_channel(&channel);
+    }
+    
+    /// Internal player finished an episode - start the next one, stopping at the end of the season
+    fn play_next_episode(&mut self, finished_url: &str) {
+        let Some((series_id, episode_id, url)) = self.playing_episode.clone() else { return };
+        if url != finished_url || !self.autoplay_next_episode {
+            return;
+        }
+        
+        // The list the episode was started from, in the order it is shown
+        let episodes = [&self.current_episodes, &self.fav_series_episodes].into_iter()
+            .find(|list| list.iter().any(|ep| ep.id == episode_id))
+            .cloned()
+            .or_else(|| self.series_episodes.as_ref()
+                .filter(|(id, _)| *id == series_id)
+                .map(|(_, episodes)| episodes.clone()))
+            .unwrap_or_default();
+        let episodes = self.ordered_episodes(episodes);
+        
+        match next_episode(&episodes, episode_id).cloned() {
+            Some(next) => {
+                self.log(&format!("[PLAY] Autoplay: S{:02}E{:02} {}", next.season, next.episode_num, Self::sanitize_text(&next.title)));
+                self.play_episode(&next, series_id);
+            }
+            None => {
+                self.playing_episode = None;
+                self.status_message = "Finished the last episode of the season".to_string();
+            }
+        }
+    }
+
+    /// Global keyboard shortcuts - single-key shortcuts are ignored while typing in a text field
+    fn handle_keyboard_shortcuts(&mut self, ctx: &egui::Context) {
+        use egui::{Key, Modifiers};
+        
+        let typing = ctx.wants_keyboard_input();
+        let (escape, focus_search, save, epg, playlists, user_agent, zap_next, zap_prev, stop_players) = ctx.input_mut(|i| {
+            (
+                i.consume_key(Modifiers::NONE, Key::Escape),
+                i.consume_key(Modifiers::COMMAND, Key::F) || (!typing && i.consume_key(Modifiers::NONE, Key::Slash)),
+                i.consume_key(Modifiers::COMMAND, Key::S),
+                !typing && i.consume_key(Modifiers::NONE, Key::E),
+                !typing && i.consume_key(Modifiers::NONE, Key::P),
+                !typing && i.consume_key(Modifiers::NONE, Key::U),
+                !typing && i.consume_key(Modifiers::NONE, Key::PageDown),
+                !typing && i.consume_key(Modifiers::NONE, Key::PageUp),
+                !typing && i.consume_key(Modifiers::NONE, Key::X),
+            )
+        });
+        
+        if escape {
+            // Close the topmost dialog, otherwise leave the text field or go back a level
+            if self.alias_edit.is_some() {
+                self.alias_edit = None;
+            } else if self.show_content_detail {
+                self.show_content_detail = false;
+            } else if self.show_reset_confirm {
+                self.show_reset_confirm = false;
+            } else if self.show_merge_favorites_confirm {
+                self.show_merge_favorites_confirm = false;
+            } else if self.show_user_agent_dialog {
+                self.show_user_agent_dialog = false;
+            } else if self.show_settings_dialog {
+                self.show_settings_dialog = false;
+            } else if self.show_my_shows {
+                self.show_my_shows = false;
+            } else if self.show_epg_dialog {
+                self.show_epg_dialog = false;
+            } else if self.show_playlist_manager {
+                self.show_playlist_manager = false;
+            } else if typing {
+                ctx.memory_mut(|m| {
+                    if let Some(id) = m.focused() {
+                        m.surrender_focus(id);
+                    }
+                });
+            } else if !self.navigation_stack.is_empty() {
+                self.go_back();
+            }
+        }
+        
+        if focus_search {
+            ctx.memory_mut(|m| m.request_focus(egui::Id::new("search_field")));
+        }
+        if save {
+            self.save_current_state();
+        }
+        if epg {
+            self.show_epg_dialog = !self.show_epg_dialog;
+        }
+        if playlists {
+            self.show_playlist_manager = !self.show_playlist_manager;
+        }
+        if user_agent {
+            self.show_user_agent_dialog = !self.show_user_agent_dialog;
+        }
+        if zap_next {
+            self.zap_favorite(1);
+        }
+        if zap_prev {
+            self.zap_favorite(-1);
+        }
+        if stop_players {
+            self.stop_external_players();
+        }
+    }
+    
+    /// Play the next (`step` = 1) or previous (-1) live favorite, like channel up/down on a remote.
+    /// Follows the Favorites tab's sort order and wraps around at either end.
+    fn zap_favorite(&mut self, step: isize) {
+        let mut live: Vec<FavoriteItem> = self.favorites.iter()
+            .filter(|f| f.stream_type == "live")
+            .cloned()
+            .collect();
+        if live.is_empty() {
+            self.status_message = "No live favorites to switch between".to_string();
+            return;
+        }
+        self.sort_favorites(&mut live);
+        
+        let len = live.len() as isize;
+        // Step from the channel playing now if it's a favorite, even when it was picked by hand
+        let playing = self.playing_url.as_ref().and_then(|url| live.iter().position(|f| f.url == *url));
+        let idx = match playing.or(self.zap_index) {
+            Some(idx) => (idx as isize + step).rem_euclid(len),
+            None if step < 0 => len - 1,
+            None => 0,
+        } as usize;
+        self.zap_index = Some(idx);
+        
+        let fav = &live[idx];
+        self.play_favorite(fav);
+        self.status_message = format!("📺 Favorite {}/{}: {}", idx + 1, len, self.display_name(&fav.url, &fav.name));
+    }
+    
+    fn go_back(&mut self) {
+        self.selected_urls.clear();
+        if self.navigation_stack.pop().is_some() {
+            // Restore scroll position for the previous level
+            if let Some(scroll_y) = self.scroll_positions.pop() {
+                self.pending_scroll_restore = Some(scroll_y);
+            }
+            
+            // Handle what to show based on remaining stack
+            if let Some(level) = self.navigation_stack.last() {
+                match level {
+                    NavigationLevel::Categories => {
+                        self.current_channels.clear();
+                        self.current_series.clear();
+                    }
+                    NavigationLevel::Channels(_cat) => {
+                        // Refetch channels
+                    }
+                    _ => {}
+                }
+            } else {
+                self.current_channels.clear();
+                self.current_series.clear();
+                self.current_seasons.clear();
+                self.current_episodes.clear();
+                self.series_episodes = None;
+            }
+        }
+        self.search_query.clear();
+    }
+    
+    /// Save current scroll position before navigating into a folder
+    fn save_scroll_position(&mut self, _ctx: &egui::Context) {
+        // Save the current scroll offset tracked from the scroll area
+        self.scroll_positions.push(self.current_scroll_offset);
+    }
+
+    fn extract_m3u_credentials(&mut self, url: &str) {
+        // Parse m3u_plus URL format:
+        // http://server.com/get.php?username=XXX&password=YYY&type=m3u_plus
+        if let Some(query_start) = url.find('?') {
             let query = &url[query_start + 1..];
             let mut params: HashMap<&str, &str> = HashMap::new();
             
@@ -2092,7 +4609,7 @@ impl IPTVApp {
                 if let Some(proto_end) = url.find("://") {
                     let rest = &url[proto_end + 3..];
                     if let Some(path_start) = rest.find('/') {
-                        let server = url[..proto_end + 3 + path_start].to_string();
+                        let server = normalize_server_url(&url[..proto_end + 3 + path_start]);
                         return Some((server, user.to_string(), pass.to_string()));
                     }
                 }
@@ -2102,153 +4619,554 @@ impl IPTVApp {
     }
     
     /// Load playlist with a specific name (for saved playlists)
-    fn load_playlist_with_name(&mut self, url: &str, name: &str) {
-        let url = url.to_string();
-        let name = name.to_string();
-        let sender = self.task_sender.clone();
-        let user_agent = self.get_user_agent().to_string();
-        
-        self.loading = true;
-        self.status_message = format!("Loading {}...", name);
-        self.log(&format!("[INFO] Loading playlist: {} ({})", name, url));
-        
-        std::thread::spawn(move || {
-            let agent = ureq::Agent::config_builder()
-                .timeout_global(Some(std::time::Duration::from_secs(60)))
-                .build()
-                .new_agent();
-            
-            let result = agent.get(&url)
-                .header("User-Agent", &user_agent)
-                .call();
+    /// Handle a file dropped onto the window - playlists are added and loaded, XMLTV files load as EPG
+    fn handle_dropped_file(&mut self, path: &std::path::Path) {
+        let file_name = path.file_name()
+            .map(|n| n.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let path_str = path.display().to_string();
+        
+        if m3u_parser::is_playlist_file_name(&file_name) {
+            let name = Self::playlist_name_from_path(path);
+            self.add_local_playlist_entry(&path_str, &name);
             
-            match result {
-                Ok(mut response) => {
-                    if let Ok(content) = response.body_mut().read_to_string() {
-                        let (channels, playlist_name) = if xspf_parser::is_xspf(&content) {
-                            match xspf_parser::parse_xspf(&content) {
-                                Ok(playlist) => {
-                                    let pname = playlist.title.clone().unwrap_or_else(|| name.clone());
-                                    let m3u_channels = xspf_parser::to_m3u_channels(&playlist);
-                                    let channels: Vec<Channel> = m3u_channels.into_iter().map(|c| {
-                                        Channel {
-                                            stream_id: None,
-                                            name: c.name,
-                                            url: c.url,
-                                            epg_channel_id: c.tvg_id,
-                                            stream_icon: c.tvg_logo,
-                                            category_id: None,
-                                            series_id: None,
-                                            container_extension: None,
-                                            playlist_source: Some(name.clone()),
-                                        }
-                                    }).collect();
-                                    (channels, Some(pname))
-                                }
-                                Err(e) => {
-                                    let _ = sender.send(TaskResult::Error(format!("XSPF parse error: {}", e)));
-                                    return;
-                                }
-                            }
-                        } else {
-                            let playlist = m3u_parser::parse_m3u_playlist(&content);
-                            let channels: Vec<Channel> = playlist.channels.into_iter().map(|c| {
-                                Channel {
-                                    stream_id: None,
-                                    name: c.name,
-                                    url: c.url,
-                                    epg_channel_id: c.tvg_id,
-                                    stream_icon: c.tvg_logo,
-                                    category_id: None,
-                                    series_id: None,
-                                    container_extension: None,
-                                    playlist_source: Some(name.clone()),
-                                }
-                            }).collect();
-                            (channels, Some(name.clone()))
-                        };
-                        
-                        let _ = sender.send(TaskResult::PlaylistLoaded { channels, playlist_name });
-                    } else {
-                        let _ = sender.send(TaskResult::Error("Failed to read playlist content".to_string()));
-                    }
-                }
-                Err(e) => {
-                    let _ = sender.send(TaskResult::Error(format!("Failed to fetch playlist: {}", e)));
-                }
-            }
-        });
+            self.log(&format!("[INFO] Dropped playlist file: {}", path_str));
+            self.load_playlist_with_name(&path_str, &name);
+        } else if file_name.ends_with(".xml") || file_name.ends_with(".xml.gz") || file_name.ends_with(".xml.xz") {
+            self.log(&format!("[INFO] Dropped EPG file: {}", path_str));
+            self.epg_url_input = path_str;
+            self.load_epg();
+            self.status_message = "Loading EPG from dropped file...".to_string();
+        } else {
+            self.status_message = format!("Unsupported file type: {}", file_name);
+        }
     }
     
-    /// Reload a playlist in background (for auto-update)
+    /// Playlist name from a file name, without the playlist extensions
+    fn playlist_name_from_path(path: &std::path::Path) -> String {
+        path.file_stem()
+            .map(|s| s.to_string_lossy().trim_end_matches(".m3u8").trim_end_matches(".m3u").to_string())
+            .unwrap_or_else(|| "Playlist".to_string())
+    }
+    
+    /// Save a local playlist file as an M3U entry, unless it's already there
+    fn add_local_playlist_entry(&mut self, path_str: &str, name: &str) -> bool {
+        if self.playlist_entries.iter().any(|e| {
+            matches!(&e.entry_type, PlaylistType::M3U { url } if url == path_str)
+        }) {
+            return false;
+        }
+        self.playlist_entries.push(PlaylistEntry::new_m3u(name.to_string(), path_str.to_string()));
+        save_playlist_entries(&self.playlist_entries);
+        true
+    }
+    
+    /// Rescan the playlists folder for playlist files
+    fn scan_playlists_dir(&mut self) {
+        if self.playlists_dir.is_empty() {
+            self.playlists_dir_files = None;
+            return;
+        }
+        match m3u_parser::scan_playlist_dir(std::path::Path::new(&self.playlists_dir)) {
+            Ok(files) => {
+                self.log(&format!("[INFO] Found {} playlist file(s) in {}", files.len(), self.playlists_dir));
+                self.playlists_dir_files = Some(files);
+            }
+            Err(e) => {
+                self.log(&format!("[WARN] {}", e));
+                self.status_message = e;
+                self.playlists_dir_files = Some(Vec::new());
+            }
+        }
+    }
+    
+    /// "Playlists Folder" section of the Playlist Manager
+    fn show_playlists_dir_section(&mut self, ui: &mut egui::Ui) {
+        if !self.playlists_dir.is_empty() && self.playlists_dir_files.is_none() {
+            self.scan_playlists_dir();
+        }
+        let file_count = self.playlists_dir_files.as_ref().map_or(0, |f| f.len());
+        
+        egui::CollapsingHeader::new(format!("📁 Playlists Folder ({})", file_count))
+            .id_salt("playlists_dir_section")
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    if self.playlists_dir.is_empty() {
+                        ui.label(egui::RichText::new("No folder chosen").weak());
+                    } else {
+                        ui.label(egui::RichText::new(&self.playlists_dir).monospace());
+                    }
+                    if ui.button("📂 Choose...").on_hover_text("Pick a folder of .m3u/.m3u8/.xspf files").clicked() {
+                        if let Some(dir) = rfd::FileDialog::new()
+                            .set_title("Select Playlists Folder")
+                            .pick_folder()
+                        {
+                            self.playlists_dir = dir.display().to_string();
+                            self.config.playlists_dir = self.playlists_dir.clone();
+                            self.config.save();
+                            self.scan_playlists_dir();
+                        }
+                    }
+                    if !self.playlists_dir.is_empty() {
+                        if ui.button("🔄 Rescan").on_hover_text("Pick up newly added files").clicked() {
+                            self.scan_playlists_dir();
+                        }
+                        if ui.small_button("✖").on_hover_text("Forget this folder").clicked() {
+                            self.playlists_dir.clear();
+                            self.config.playlists_dir.clear();
+                            self.config.save();
+                            self.playlists_dir_files = None;
+                        }
+                    }
+                });
+                
+                let files = self.playlists_dir_files.clone().unwrap_or_default();
+                if !self.playlists_dir.is_empty() && files.is_empty() {
+                    ui.label(egui::RichText::new("No playlist files found").weak());
+                }
+                let mut to_load: Option<(String, String)> = None;
+                let mut to_add: Option<(String, String)> = None;
+                egui::ScrollArea::vertical().id_salt("playlists_dir_files").max_height(150.0).show(ui, |ui| {
+                    for path in &files {
+                        let path_str = path.display().to_string();
+                        let name = Self::playlist_name_from_path(path);
+                        let saved = self.playlist_entries.iter().any(|e| {
+                            matches!(&e.entry_type, PlaylistType::M3U { url } if url == &path_str)
+                        });
+                        ui.horizontal(|ui| {
+                            if ui.button("▶ Load").clicked() {
+                                to_load = Some((path_str.clone(), name.clone()));
+                            }
+                            if ui.add_enabled(!saved, egui::Button::new("➕"))
+                                .on_hover_text("Add to Saved Playlists")
+                                .on_disabled_hover_text("Already saved")
+                                .clicked()
+                            {
+                                to_add = Some((path_str.clone(), name.clone()));
+                            }
+                            ui.label(path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default())
+                                .on_hover_text(&path_str);
+                        });
+                    }
+                });
+                
+                if let Some((path_str, name)) = to_add {
+                    if self.add_local_playlist_entry(&path_str, &name) {
+                        self.status_message = format!("Added playlist '{}'", name);
+                    }
+                }
+                if let Some((path_str, name)) = to_load {
+                    self.log(&format!("[INFO] Loading playlist from folder: {}", path_str));
+                    self.load_playlist_with_name(&path_str, &name);
+                }
+            });
+    }
+    
+    /// Read playlist content from a local file path (or file:// URL) or download it.
+    /// Foreground loads pass a `cancel` flag: download progress is reported and the
+    /// download stops once the flag is set.
+    fn fetch_playlist_content(url: &str, user_agent: &str, timeouts: (u64, u64), resolution: &HostResolution, sender: &Sender<TaskResult>, cancel: Option<&std::sync::atomic::AtomicBool>) -> Result<String, String> {
+        let bytes = Self::fetch_playlist_bytes(url, user_agent, timeouts, resolution, sender, cancel)?;
+        let (content, compressed) = m3u_parser::decode_playlist_bytes(&bytes)?;
+        if compressed {
+            let _ = sender.send(TaskResult::PlayerLog(format!(
+                "[INFO] Playlist is gzip-compressed ({} KB -> {} KB)", bytes.len() / 1024, content.len() / 1024
+            )));
+        }
+        Ok(content)
+    }
+    
+    fn fetch_playlist_bytes(url: &str, user_agent: &str, timeouts: (u64, u64), resolution: &HostResolution, sender: &Sender<TaskResult>, cancel: Option<&std::sync::atomic::AtomicBool>) -> Result<Vec<u8>, String> {
+        use std::io::Read;
+        use std::sync::atomic::Ordering;
+        
+        if let Some(path) = local_file_path(url) {
+            return std::fs::read(path)
+                .map_err(|e| format!("Failed to read playlist file: {}", e));
+        }
+        
+        // Read timeout covers waiting for the response and downloading the body
+        let (connect_secs, read_secs) = timeouts;
+        let agent = resolution.agent(ureq::Agent::config_builder()
+            .timeout_connect(Some(std::time::Duration::from_secs(connect_secs.max(1))))
+            .timeout_recv_response(Some(std::time::Duration::from_secs(read_secs.max(1))))
+            .timeout_recv_body(Some(std::time::Duration::from_secs(read_secs.max(1)))));
+        
+        let mut response = agent.get(url)
+            .header("User-Agent", user_agent)
+            .call()
+            .map_err(|e| format!("Failed to fetch playlist: {}", e))?;
+        
+        let Some(cancel) = cancel else {
+            return response.body_mut()
+                .read_to_vec()
+                .map_err(|_| "Failed to read playlist content".to_string());
+        };
+        
+        // Stream the body so progress can be shown and the download cancelled
+        let total = response.body().content_length();
+        let mut reader = response.body_mut().as_reader();
+        let mut bytes = Vec::with_capacity(total.unwrap_or(0).min(256 * 1024 * 1024) as usize);
+        let mut buf = vec![0u8; 64 * 1024];
+        let mut last_report = 0u64;
+        loop {
+            if cancel.load(Ordering::Relaxed) {
+                return Err(PLAYLIST_CANCELLED.to_string());
+            }
+            let n = reader.read(&mut buf).map_err(|_| "Failed to read playlist content".to_string())?;
+            if n == 0 {
+                break;
+            }
+            bytes.extend_from_slice(&buf[..n]);
+            let downloaded = bytes.len() as u64;
+            if downloaded - last_report >= PLAYLIST_PROGRESS_STEP {
+                last_report = downloaded;
+                let _ = sender.send(TaskResult::PlaylistProgress { downloaded, total });
+            }
+        }
+        let _ = sender.send(TaskResult::PlaylistProgress { downloaded: bytes.len() as u64, total });
+        Ok(bytes)
+    }
+    
+    /// Cancel flag shared by foreground playlist loads, so one Cancel stops all of them
+    fn playlist_cancel_flag(&mut self) -> std::sync::Arc<std::sync::atomic::AtomicBool> {
+        self.playlist_cancel
+            .get_or_insert_with(|| std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)))
+            .clone()
+    }
+    
+    /// Abort the playlist loads in progress - their results are discarded
+    fn cancel_playlist_load(&mut self) {
+        if let Some(cancel) = self.playlist_cancel.take() {
+            cancel.store(true, std::sync::atomic::Ordering::Relaxed);
+        }
+        self.playlist_progress = None;
+        self.end_task(TaskKind::Playlist);
+        self.status_message = "Playlist loading cancelled".to_string();
+        self.log("[INFO] Playlist loading cancelled");
+    }
+    
+    fn load_playlist_with_name(&mut self, url: &str, name: &str) {
+        let url = url.to_string();
+        let name = name.to_string();
+        let sender = self.task_sender.clone();
+        let user_agent = self.get_user_agent().to_string();
+        let timeouts = (self.connect_timeout_secs, self.read_timeout_secs);
+        let resolution = self.host_resolution(&url);
+        
+        self.begin_task(TaskKind::Playlist, format!("Loading playlist: {}", name));
+        self.status_message = format!("Loading {}...", name);
+        self.log(&format!("[INFO] Loading playlist: {} ({})", name, url));
+        
+        let cancel = self.playlist_cancel_flag();
+        self.playlist_progress = None;
+        
+        self.fetch_pool.spawn(move || {
+            match Self::fetch_playlist_content(&url, &user_agent, timeouts, &resolution, &sender, Some(&cancel)) {
+                Ok(content) => {
+                    let (channels, playlist_name) = if xspf_parser::is_xspf(&content) {
+                        match xspf_parser::parse_xspf(&content) {
+                            Ok(playlist) => {
+                                let pname = playlist.title.clone().unwrap_or_else(|| name.clone());
+                                let m3u_channels = xspf_parser::to_m3u_channels(&playlist);
+                                let channels: Vec<Channel> = m3u_channels.into_iter().map(|c| {
+                                    Channel {
+                                        stream_id: None,
+                                        name: c.name,
+                                        url: c.url,
+                                        epg_channel_id: c.tvg_id,
+                                        stream_icon: c.tvg_logo,
+                                        category_id: None,
+                                        series_id: None,
+                                        container_extension: None,
+                                        playlist_source: Some(name.clone()),
+                                        catchup: None,
+                                        catchup_source: None,
+                                        tvg_shift: None,
+                                        alternate_urls: Vec::new(),
+                                        archive_days: None,
+                                        genre: None,
+                                        user_agent: None,
+                                        referrer: None,
+                                    }
+                                }).collect();
+                                (channels, Some(pname))
+                            }
+                            Err(e) => {
+                                let _ = sender.send(TaskResult::Error { kind: Some(TaskKind::Playlist), msg: format!("XSPF parse error: {}", e) });
+                                return;
+                            }
+                        }
+                    } else {
+                        let playlist = m3u_parser::parse_m3u_playlist(&content);
+                        let alternates = m3u_parser::alternate_urls(&playlist.channels);
+                        let channels: Vec<Channel> = playlist.channels.into_iter().zip(alternates).map(|(c, alternate_urls)| {
+                            Channel {
+                                stream_id: None,
+                                name: c.name,
+                                url: c.url,
+                                epg_channel_id: c.tvg_id,
+                                stream_icon: c.tvg_logo,
+                                category_id: None,
+                                series_id: None,
+                                container_extension: None,
+                                playlist_source: Some(name.clone()),
+                                catchup: c.catchup,
+                                catchup_source: c.catchup_source,
+                                tvg_shift: c.tvg_shift,
+                                alternate_urls,
+                                archive_days: None,
+                                genre: None,
+                                user_agent: c.user_agent,
+                                referrer: c.referrer,
+                            }
+                        }).collect();
+                        (channels, Some(name.clone()))
+                    };
+                    
+                    if !cancel.load(std::sync::atomic::Ordering::Relaxed) {
+                        let _ = sender.send(TaskResult::PlaylistLoaded { channels, playlist_name });
+                    }
+                }
+                Err(e) if e == PLAYLIST_CANCELLED => {}
+                Err(e) => {
+                    let _ = sender.send(TaskResult::Error { kind: Some(TaskKind::Playlist), msg: e });
+                }
+            }
+        });
+    }
+    
+    /// Reload a playlist in background (for auto-update)
     fn reload_playlist(&mut self, url: &str, name: &str) {
         let url = url.to_string();
         let name = name.to_string();
         let sender = self.task_sender.clone();
         let user_agent = self.get_user_agent().to_string();
+        let timeouts = (self.connect_timeout_secs, self.read_timeout_secs);
+        let resolution = self.host_resolution(&url);
         
         self.status_message = format!("Updating {}...", name);
         
-        std::thread::spawn(move || {
-            let agent = ureq::Agent::config_builder()
-                .timeout_global(Some(std::time::Duration::from_secs(60)))
-                .build()
-                .new_agent();
-            
-            let result = agent.get(&url)
-                .header("User-Agent", &user_agent)
-                .call();
-            
-            match result {
-                Ok(mut response) => {
-                    if let Ok(content) = response.body_mut().read_to_string() {
-                        let channels = if xspf_parser::is_xspf(&content) {
-                            match xspf_parser::parse_xspf(&content) {
-                                Ok(playlist) => {
-                                    let m3u_channels = xspf_parser::to_m3u_channels(&playlist);
-                                    m3u_channels.into_iter().map(|c| {
-                                        Channel {
-                                            stream_id: None,
-                                            name: c.name,
-                                            url: c.url,
-                                            epg_channel_id: c.tvg_id,
-                                            stream_icon: c.tvg_logo,
-                                            category_id: None,
-                                            series_id: None,
-                                            container_extension: None,
-                                            playlist_source: Some(name.clone()),
-                                        }
-                                    }).collect()
-                                }
-                                Err(e) => {
-                                    let _ = sender.send(TaskResult::Error(format!("XSPF parse error: {}", e)));
-                                    return;
-                                }
+        self.fetch_pool.spawn(move || {
+            match Self::fetch_playlist_content(&url, &user_agent, timeouts, &resolution, &sender, None) {
+                Ok(content) => {
+                    let channels = if xspf_parser::is_xspf(&content) {
+                        match xspf_parser::parse_xspf(&content) {
+                            Ok(playlist) => {
+                                let m3u_channels = xspf_parser::to_m3u_channels(&playlist);
+                                m3u_channels.into_iter().map(|c| {
+                                    Channel {
+                                        stream_id: None,
+                                        name: c.name,
+                                        url: c.url,
+                                        epg_channel_id: c.tvg_id,
+                                        stream_icon: c.tvg_logo,
+                                        category_id: None,
+                                        series_id: None,
+                                        container_extension: None,
+                                        playlist_source: Some(name.clone()),
+                                        catchup: None,
+                                        catchup_source: None,
+                                        tvg_shift: None,
+                                        alternate_urls: Vec::new(),
+                                        archive_days: None,
+                                        genre: None,
+                                        user_agent: None,
+                                        referrer: None,
+                                    }
+                                }).collect()
                             }
-                        } else {
-                            let playlist = m3u_parser::parse_m3u_playlist(&content);
-                            playlist.channels.into_iter().map(|c| {
-                                Channel {
-                                    stream_id: None,
-                                    name: c.name,
-                                    url: c.url,
-                                    epg_channel_id: c.tvg_id,
-                                    stream_icon: c.tvg_logo,
-                                    category_id: None,
-                                    series_id: None,
-                                    container_extension: None,
-                                    playlist_source: Some(name.clone()),
-                                }
-                            }).collect()
-                        };
-                        
-                        let _ = sender.send(TaskResult::PlaylistReloaded { channels, playlist_name: name });
+                            Err(e) => {
+                                let _ = sender.send(TaskResult::Error { kind: None, msg: format!("XSPF parse error: {}", e) });
+                                return;
+                            }
+                        }
                     } else {
-                        let _ = sender.send(TaskResult::Error("Failed to read playlist content".to_string()));
+                        let playlist = m3u_parser::parse_m3u_playlist(&content);
+                        let alternates = m3u_parser::alternate_urls(&playlist.channels);
+                        playlist.channels.into_iter().zip(alternates).map(|(c, alternate_urls)| {
+                            Channel {
+                                stream_id: None,
+                                name: c.name,
+                                url: c.url,
+                                epg_channel_id: c.tvg_id,
+                                stream_icon: c.tvg_logo,
+                                category_id: None,
+                                series_id: None,
+                                container_extension: None,
+                                playlist_source: Some(name.clone()),
+                                catchup: c.catchup,
+                                catchup_source: c.catchup_source,
+                                tvg_shift: c.tvg_shift,
+                                alternate_urls,
+                                archive_days: None,
+                                genre: None,
+                                user_agent: c.user_agent,
+                                referrer: c.referrer,
+                            }
+                        }).collect()
+                    };
+                    
+                    let _ = sender.send(TaskResult::PlaylistReloaded { channels, playlist_name: name });
+                }
+                Err(e) => {
+                    let _ = sender.send(TaskResult::Error { kind: None, msg: e });
+                }
+            }
+        });
+    }
+    
+    /// Connect to a Stalker portal and load its channels like a playlist
+    fn login_stalker(&mut self, portal: &str, mac: &str, name: &str) {
+        let client = StalkerClient::new(portal, mac)
+            .with_timeouts(self.connect_timeout_secs, self.read_timeout_secs)
+            .with_host_resolution(self.host_resolution(portal));
+        let name = name.to_string();
+        let sender = self.task_sender.clone();
+        
+        self.begin_task(TaskKind::Playlist, format!("Connecting to portal: {}", name));
+        self.status_message = format!("Connecting to {}...", name);
+        self.log(&format!("[INFO] Stalker login: {} ({})", name, portal));
+        
+        self.fetch_pool.spawn(move || {
+            let mut client = client;
+            let result = client.handshake()
+                .and_then(|_| Ok((client.get_genres()?, client.get_channels()?)));
+            match result {
+                Ok((genres, stalker_channels)) => {
+                    let genre_names: HashMap<String, String> = genres.into_iter()
+                        .map(|g| (g.category_id, g.category_name))
+                        .collect();
+                    let channels: Vec<Channel> = stalker_channels.into_iter().map(|c| {
+                        // Direct links play as is; placeholders are resolved when played
+                        let url = stalker::stream_url_from_cmd(&c.cmd)
+                            .unwrap_or_else(|| format!("{}{}", STALKER_LINK_PREFIX, c.cmd));
+                        Channel {
+                            name: c.name,
+                            url,
+                            stream_id: c.id.parse().ok(),
+                            category_id: c.genre_id.clone(),
+                            epg_channel_id: c.xmltv_id,
+                            stream_icon: c.logo,
+                            series_id: None,
+                            container_extension: None,
+                            playlist_source: Some(name.clone()),
+                            catchup: None,
+                            catchup_source: None,
+                            tvg_shift: None,
+                            alternate_urls: Vec::new(),
+                            archive_days: None,
+                            genre: c.genre_id.and_then(|id| genre_names.get(&id).cloned()),
+                            user_agent: None,
+                            referrer: None,
+                        }
+                    }).collect();
+                    let _ = sender.send(TaskResult::StalkerConnected(name.clone(), Box::new(client)));
+                    let _ = sender.send(TaskResult::PlaylistLoaded { channels, playlist_name: Some(name) });
+                }
+                Err(e) => {
+                    let _ = sender.send(TaskResult::Error { kind: Some(TaskKind::Playlist), msg: format!("Stalker login failed: {}", e) });
+                }
+            }
+        });
+    }
+    
+    /// Download an .m3u8 before playback to see whether it offers several qualities.
+    /// Any failure just plays the stream as is.
+    fn fetch_hls_variants(&mut self, channel: &Channel) {
+        let channel = channel.clone();
+        let user_agent = self.get_player_user_agent();
+        let (connect_secs, read_secs) = (self.connect_timeout_secs.clamp(1, 10), self.read_timeout_secs.clamp(1, 10));
+        let resolution = self.host_resolution(&channel.url);
+        let sender = self.task_sender.clone();
+        self.status_message = format!("Checking stream qualities for {}...", Self::sanitize_text(&channel.name));
+        
+        self.fetch_pool.spawn(move || {
+            let agent = resolution.agent(ureq::Agent::config_builder()
+                .timeout_connect(Some(std::time::Duration::from_secs(connect_secs)))
+                .timeout_recv_response(Some(std::time::Duration::from_secs(read_secs)))
+                .timeout_recv_body(Some(std::time::Duration::from_secs(read_secs))));
+            let variants = agent.get(&channel.url)
+                .header("User-Agent", &user_agent)
+                .call()
+                .ok()
+                .and_then(|mut response| {
+                    // Relative variant URIs are relative to where redirects ended up (load balancers)
+                    use ureq::ResponseExt;
+                    let final_url = response.get_uri().to_string();
+                    let content = response.body_mut().read_to_string().ok()?;
+                    Some(m3u_parser::hls_variants(&content, &final_url))
+                })
+                .unwrap_or_default();
+            let _ = sender.send(TaskResult::HlsVariants(Box::new(channel), variants));
+        });
+    }
+    
+    /// Play a channel (optionally at a chosen variant URL) without asking for a quality again
+    fn play_hls_variant(&mut self, mut channel: Channel, url: Option<String>) {
+        if let Some(url) = url {
+            channel.url = url;
+        }
+        self.hls_checked_url = Some(channel.url.clone());
+        self.start_playback(&channel);
+    }
+    
+    /// Quality chooser for multi-bitrate HLS streams
+    fn show_hls_quality_window(&mut self, ctx: &egui::Context) {
+        let Some((channel, variants)) = &self.hls_variant_choice else { return };
+        let mut open = true;
+        let mut chosen: Option<Option<String>> = None;
+        
+        egui::Window::new("📶 Choose quality")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(egui::RichText::new(Self::sanitize_text(&channel.name)).strong());
+                ui.add_space(4.0);
+                if ui.button("✨ Auto").on_hover_text("Let the player pick and switch qualities").clicked() {
+                    chosen = Some(None);
+                }
+                for variant in variants {
+                    if ui.button(&variant.label).clicked() {
+                        chosen = Some(Some(variant.url.clone()));
                     }
                 }
+            });
+        
+        if let Some(url) = chosen {
+            if let Some((channel, _)) = self.hls_variant_choice.take() {
+                self.play_hls_variant(channel, url);
+            }
+        } else if !open {
+            self.hls_variant_choice = None;
+        }
+    }
+    
+    /// Ask the portal for a playable link, then start playback with it
+    fn resolve_stalker_link(&mut self, channel: &Channel, cmd: &str) {
+        let session = channel.playlist_source.as_ref()
+            .and_then(|name| Some((name.clone(), self.stalker_sessions.get(name)?.clone())));
+        let Some((name, mut client)) = session else {
+            self.status_message = "Reconnect to the Stalker portal to play this channel".to_string();
+            return;
+        };
+        let mut channel = channel.clone();
+        let cmd = cmd.to_string();
+        let sender = self.task_sender.clone();
+        self.status_message = format!("Requesting stream link for {}...", Self::sanitize_text(&channel.name));
+        
+        self.fetch_pool.spawn(move || {
+            match client.create_link_renewing(&cmd) {
+                Ok((url, renewed)) => {
+                    if renewed {
+                        let _ = sender.send(TaskResult::StalkerConnected(name, Box::new(client)));
+                    }
+                    channel.url = url;
+                    let _ = sender.send(TaskResult::StalkerLinkResolved(Box::new(channel)));
+                }
                 Err(e) => {
-                    let _ = sender.send(TaskResult::Error(format!("Failed to fetch playlist: {}", e)));
+                    let _ = sender.send(TaskResult::Error { kind: None, msg: format!("Stalker link failed: {}", e) });
                 }
             }
         });
@@ -2260,26 +5178,11 @@ impl IPTVApp {
             return;
         }
         
-        let (start_idx, name) = self.playlist_sources[idx].clone();
-        let end_idx = self.playlist_sources.get(idx + 1)
-            .map(|(next_start, _)| *next_start)
-            .unwrap_or(self.current_channels.len());
-        
-        let channels_to_remove = end_idx - start_idx;
-        
-        // Remove channels
-        if start_idx < self.current_channels.len() {
-            let actual_end = end_idx.min(self.current_channels.len());
-            self.current_channels.drain(start_idx..actual_end);
-        }
-        
-        // Remove from sources
-        self.playlist_sources.remove(idx);
+        let Some((name, channels_to_remove)) = remove_playlist_source(&mut self.current_channels, &mut self.playlist_sources, idx) else {
+            return;
+        };
         
-        // Update indices
-        for (start, _) in self.playlist_sources.iter_mut().skip(idx) {
-            *start = start.saturating_sub(channels_to_remove);
-        }
+        self.stalker_sessions.remove(&name);
         
         // Remove related favorites/recent
         self.favorites.retain(|f| f.playlist_source.as_ref() != Some(&name));
@@ -2299,6 +5202,8 @@ impl IPTVApp {
         let url = url.to_string();
         let sender = self.task_sender.clone();
         let user_agent = self.get_user_agent().to_string();
+        let timeouts = (self.connect_timeout_secs, self.read_timeout_secs);
+        let resolution = self.host_resolution(&url);
         
         // Extract a short name from URL for source tracking
         let url_for_name = url.split('/').last()
@@ -2307,77 +5212,87 @@ impl IPTVApp {
             .unwrap_or(&url)
             .to_string();
         
-        self.loading = true;
+        self.begin_task(TaskKind::Playlist, format!("Loading playlist: {}", url_for_name));
         self.status_message = "Loading playlist...".to_string();
         self.log(&format!("[INFO] Loading playlist: {}", url));
         
-        std::thread::spawn(move || {
-            let agent = ureq::Agent::config_builder()
-                .timeout_global(Some(std::time::Duration::from_secs(60)))
-                .build()
-                .new_agent();
-            
-            let result = agent.get(&url)
-                .header("User-Agent", &user_agent)
-                .call();
-            
-            match result {
-                Ok(mut response) => {
-                    if let Ok(content) = response.body_mut().read_to_string() {
-                        let (channels, playlist_name) = if xspf_parser::is_xspf(&content) {
-                            // Parse as XSPF
-                            match xspf_parser::parse_xspf(&content) {
-                                Ok(playlist) => {
-                                    let name = playlist.title.clone();
-                                    let source_name = name.clone().unwrap_or_else(|| url_for_name.clone());
-                                    let m3u_channels = xspf_parser::to_m3u_channels(&playlist);
-                                    let channels: Vec<Channel> = m3u_channels.into_iter().map(|c| {
-                                        Channel {
-                                            stream_id: None,
-                                            name: c.name,
-                                            url: c.url,
-                                            epg_channel_id: c.tvg_id,
-                                            stream_icon: c.tvg_logo,
-                                            category_id: None,
-                                            series_id: None,
-                                            container_extension: None,
-                                            playlist_source: Some(source_name.clone()),
-                                        }
-                                    }).collect();
-                                    (channels, name)
-                                }
-                                Err(e) => {
-                                    let _ = sender.send(TaskResult::Error(format!("XSPF parse error: {}", e)));
-                                    return;
-                                }
+        let cancel = self.playlist_cancel_flag();
+        self.playlist_progress = None;
+        
+        self.fetch_pool.spawn(move || {
+            match Self::fetch_playlist_content(&url, &user_agent, timeouts, &resolution, &sender, Some(&cancel)) {
+                Ok(content) => {
+                    let (channels, playlist_name) = if xspf_parser::is_xspf(&content) {
+                        // Parse as XSPF
+                        match xspf_parser::parse_xspf(&content) {
+                            Ok(playlist) => {
+                                let name = playlist.title.clone();
+                                let source_name = name.clone().unwrap_or_else(|| url_for_name.clone());
+                                let m3u_channels = xspf_parser::to_m3u_channels(&playlist);
+                                let channels: Vec<Channel> = m3u_channels.into_iter().map(|c| {
+                                    Channel {
+                                        stream_id: None,
+                                        name: c.name,
+                                        url: c.url,
+                                        epg_channel_id: c.tvg_id,
+                                        stream_icon: c.tvg_logo,
+                                        category_id: None,
+                                        series_id: None,
+                                        container_extension: None,
+                                        playlist_source: Some(source_name.clone()),
+                                        catchup: None,
+                                        catchup_source: None,
+                                        tvg_shift: None,
+                                        alternate_urls: Vec::new(),
+                                        archive_days: None,
+                                        genre: None,
+                                        user_agent: None,
+                                        referrer: None,
+                                    }
+                                }).collect();
+                                (channels, name)
                             }
-                        } else {
-                            // Parse as M3U/M3U8
-                            let playlist = m3u_parser::parse_m3u_playlist(&content);
-                            let source_name = url_for_name.clone();
-                            let channels: Vec<Channel> = playlist.channels.into_iter().map(|c| {
-                                Channel {
-                                    stream_id: None,
-                                    name: c.name,
-                                    url: c.url,
-                                    epg_channel_id: c.tvg_id,
-                                    stream_icon: c.tvg_logo,
-                                    category_id: None,
-                                    series_id: None,
-                                    container_extension: None,
-                                    playlist_source: Some(source_name.clone()),
-                                }
-                            }).collect();
-                            (channels, None)
-                        };
-                        
-                        let _ = sender.send(TaskResult::PlaylistLoaded { channels, playlist_name });
+                            Err(e) => {
+                                let _ = sender.send(TaskResult::Error { kind: Some(TaskKind::Playlist), msg: format!("XSPF parse error: {}", e) });
+                                return;
+                            }
+                        }
                     } else {
-                        let _ = sender.send(TaskResult::Error("Failed to read playlist content".to_string()));
+                        // Parse as M3U/M3U8
+                        let playlist = m3u_parser::parse_m3u_playlist(&content);
+                        let alternates = m3u_parser::alternate_urls(&playlist.channels);
+                        let source_name = url_for_name.clone();
+                        let channels: Vec<Channel> = playlist.channels.into_iter().zip(alternates).map(|(c, alternate_urls)| {
+                            Channel {
+                                stream_id: None,
+                                name: c.name,
+                                url: c.url,
+                                epg_channel_id: c.tvg_id,
+                                stream_icon: c.tvg_logo,
+                                category_id: None,
+                                series_id: None,
+                                container_extension: None,
+                                playlist_source: Some(source_name.clone()),
+                                catchup: c.catchup,
+                                catchup_source: c.catchup_source,
+                                tvg_shift: c.tvg_shift,
+                                alternate_urls,
+                                archive_days: None,
+                                genre: None,
+                                user_agent: c.user_agent,
+                                referrer: c.referrer,
+                            }
+                        }).collect();
+                        (channels, None)
+                    };
+                    
+                    if !cancel.load(std::sync::atomic::Ordering::Relaxed) {
+                        let _ = sender.send(TaskResult::PlaylistLoaded { channels, playlist_name });
                     }
                 }
+                Err(e) if e == PLAYLIST_CANCELLED => {}
                 Err(e) => {
-                    let _ = sender.send(TaskResult::Error(format!("Failed to fetch playlist: {}", e)));
+                    let _ = sender.send(TaskResult::Error { kind: Some(TaskKind::Playlist), msg: e });
                 }
             }
         });
@@ -2385,20 +5300,69 @@ impl IPTVApp {
 }
 
 impl eframe::App for IPTVApp {
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        if let Some((size, position)) = self.window_layout {
+            self.config.window_size = Some(size);
+            self.config.window_position = Some(position);
+            self.save_config_soon();
+        }
+        self.flush_deferred_saves(true);
+        self.shutdown_players();
+    }
+    
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Another launch of the app: come to the front and take over its --play / --playlist
+        while let Some(intent) = self.instance.as_ref().and_then(InstanceGuard::try_recv) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+            ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+            if intent.is_some() {
+                self.launch_intent = intent;
+            }
+        }
+        
+        // Nothing loads or plays until the startup PIN is entered
+        if self.app_locked {
+            self.show_lock_screen(ctx);
+            return;
+        }
+        
+        // Encrypted passwords are needed before any playlist logs in
+        if self.vault_locked {
+            self.show_vault_screen(ctx);
+            return;
+        }
+        
+        if let Some(wait) = self.flush_deferred_saves(false) {
+            ctx.request_repaint_after(wait);
+        }
+        
+        if let Some(intent) = self.launch_intent.take() {
+            self.handle_launch_intent(intent);
+        }
+        
         // Process background task results (non-blocking)
         while let Ok(result) = self.task_receiver.try_recv() {
             match result {
                 TaskResult::CategoriesLoaded { live, movies, series } => {
                     self.log(&format!("[INFO] Login successful - Live: {}, Movies: {}, Series: {} categories", 
                         live.len(), movies.len(), series.len()));
+                    let lists = CategoryLists { live, movies, series };
+                    save_category_cache(&self.server, &self.username, &lists);
+                    let CategoryLists { live, movies, series } = lists;
                     self.live_categories = live;
                     self.movie_categories = movies;
                     self.series_categories = series;
+                    self.category_counts.clear();
                     self.logged_in = true;
-                    self.loading = false;
+                    self.end_task(TaskKind::Login);
                     self.status_message = "Logged in successfully".to_string();
                     
+                    // Restore pinned categories and per-category sorting for this playlist
+                    let entry = self.find_current_playlist_idx()
+                        .and_then(|idx| self.playlist_entries.get(idx));
+                    self.favorite_categories = entry.map(|e| e.favorite_categories.clone()).unwrap_or_default();
+                    self.category_sort_orders = entry.map(|e| e.category_sort_orders.clone()).unwrap_or_default();
+                    
                     // Auto-save to playlist_entries if save_state is enabled
                     if self.save_state && !self.server.is_empty() && !self.username.is_empty() {
                         let entry = self.create_xtream_entry_from_state();
@@ -2471,63 +5435,160 @@ impl eframe::App for IPTVApp {
                         user_info.username, user_info.status, user_info.expiry));
                     self.user_info = user_info;
                     self.server_info = server_info;
+                    self.expiry_banner_dismissed = false;
+                    if let Some(days) = self.expiry_days_left().filter(|_| self.expiry_warning_due()) {
+                        self.log(&format!("[WARN] Subscription expires in {} day(s)", days.max(0)));
+                    }
+                }
+                TaskResult::CategoryCountsLoaded { stream_type, counts, complete } => {
+                    if stream_type.is_empty() {
+                        // End of prefetch
+                        self.category_counts_prefetching = false;
+                        self.status_message = "Category counts loaded".to_string();
+                        continue;
+                    }
+                    if complete {
+                        // Categories missing from a full count are empty
+                        let categories = match stream_type.as_str() {
+                            "live" => &self.live_categories,
+                            "movie" => &self.movie_categories,
+                            _ => &self.series_categories,
+                        };
+                        for cat in categories {
+                            self.category_counts.insert(format!("{}:{}", stream_type, cat.category_id), 0);
+                        }
+                    }
+                    for (category_id, count) in counts {
+                        self.category_counts.insert(format!("{}:{}", stream_type, category_id), count);
+                    }
                 }
                 TaskResult::ChannelsLoaded(channels) => {
                     self.log(&format!("[INFO] Loaded {} channels", channels.len()));
                     self.current_channels = channels;
-                    self.loading = false;
+                    self.end_task(TaskKind::Channels);
                     self.status_message = format!("Loaded {} channels", self.current_channels.len());
                 }
                 TaskResult::SeriesListLoaded(series) => {
                     self.log(&format!("[INFO] Loaded {} series", series.len()));
                     self.current_series = series;
-                    self.loading = false;
+                    self.end_task(TaskKind::Series);
                     self.status_message = format!("Loaded {} series", self.current_series.len());
                 }
                 TaskResult::SeasonsLoaded(seasons) => {
                     self.log(&format!("[INFO] Loaded {} seasons", seasons.len()));
                     self.current_seasons = seasons;
-                    self.loading = false;
+                    self.end_task(TaskKind::Seasons);
                     self.status_message = format!("Loaded {} seasons", self.current_seasons.len());
                 }
+                TaskResult::SeriesEpisodesCached { series_id, episodes } => {
+                    self.series_episodes = Some((series_id, episodes));
+                }
                 TaskResult::EpisodesLoaded(episodes) => {
                     self.log(&format!("[INFO] Loaded {} episodes", episodes.len()));
                     self.current_episodes = episodes;
-                    self.loading = false;
+                    self.end_task(TaskKind::Episodes);
                     self.status_message = format!("Loaded {} episodes", self.current_episodes.len());
                 }
                 TaskResult::FavSeasonsLoaded(seasons) => {
                     self.log(&format!("[INFO] Loaded {} seasons for favorite", seasons.len()));
                     self.fav_series_seasons = seasons;
-                    self.loading = false;
+                    self.end_task(TaskKind::FavSeasons);
                     self.status_message = format!("Loaded {} seasons", self.fav_series_seasons.len());
                 }
                 TaskResult::FavEpisodesLoaded(episodes) => {
                     self.log(&format!("[INFO] Loaded {} episodes for favorite", episodes.len()));
                     self.fav_series_episodes = episodes;
-                    self.loading = false;
+                    self.end_task(TaskKind::FavEpisodes);
                     self.status_message = format!("Loaded {} episodes", self.fav_series_episodes.len());
                 }
-                TaskResult::Error(msg) => {
+                TaskResult::ContentDetailLoaded(detail) => {
+                    self.log(&format!("[INFO] Loaded details for '{}'", Self::sanitize_text(&detail.name)));
+                    // Remember genres from detail info so the genre filter can use them
+                    if let Some(genre) = detail.genre.clone() {
+                        if detail.stream_type == "movie" {
+                            for c in self.current_channels.iter_mut().filter(|c| c.genre.is_none() && c.name == detail.name) {
+                                c.genre = Some(genre.clone());
+                            }
+                            self.channel_list_view.signature = 0;
+                        } else {
+                            for s in self.current_series.iter_mut().filter(|s| s.genre.is_none() && s.name == detail.name) {
+                                s.genre = Some(genre.clone());
+                            }
+                        }
+                    }
+                    self.content_detail = Some(*detail);
+                    self.end_task(TaskKind::Detail);
+                    self.status_message = "Details loaded".to_string();
+                }
+                TaskResult::StreamProbed { url, result } => {
+                    match &result {
+                        Ok(_) => self.log(&format!("[INFO] Probed {}", url)),
+                        Err(e) => self.log(&format!("[WARN] Probe failed: {}", e)),
+                    }
+                    // Ignore results for a popup that was closed or replaced
+                    if let Some((_, probe_url, pending)) = &mut self.stream_probe {
+                        if *probe_url == url {
+                            *pending = Some(result);
+                        }
+                    }
+                }
+                TaskResult::Error { kind, msg } => {
                     self.log(&format!("[ERROR] {}", msg));
-                    self.loading = false;
+                    // Most errors read "<what failed>: <reason>"
+                    match msg.split_once(": ") {
+                        Some((what, reason)) => self.record_failure(what, reason),
+                        None => self.record_failure("Request", &msg),
+                    }
+                    // Only the fetch that failed ends - others may still succeed
+                    if let Some(kind) = kind {
+                        // A failed login must not leave the cached categories looking logged in
+                        if kind == TaskKind::Login && !self.playlist_mode {
+                            self.logged_in = false;
+                            self.live_categories.clear();
+                            self.movie_categories.clear();
+                            self.series_categories.clear();
+                        }
+                        if kind == TaskKind::Playlist {
+                            self.playlist_progress = None;
+                            self.playlist_cancel = None;
+                        }
+                        self.end_task(kind);
+                    }
                     self.status_message = format!("Error: {}", msg);
                 }
                 TaskResult::PlayerLog(msg) => {
                     self.log(&msg);
                 }
-                TaskResult::PlayerExited { code, stderr } => {
-                    let exit_msg = match code {
-                        Some(c) => format!("[WARN] Player exited with code {}: {}", c, stderr),
-                        None => format!("[WARN] Player terminated by signal: {}", stderr),
-                    };
-                    self.log(&exit_msg);
-                    self.status_message = stderr;
+                TaskResult::PlayerExited { pid, code, stderr } => {
+                    self.handle_player_exit(pid, code, stderr);
                 }
-                TaskResult::EpgLoading { progress } => {
-                    self.epg_status = progress.clone();
-                    // Extract percentage from status like "Downloading: 45.2 / 80.0 MB (56%)"
-                    if let Some(start) = progress.rfind('(') {
+                TaskResult::EpisodeFinished(url) => {
+                    self.play_next_episode(&url);
+                }
+                TaskResult::PlaybackPosition { url, position, duration } => {
+                    let resume = mpv_ipc::resume_position(position, duration);
+                    match resume {
+                        Some(secs) => self.log(&format!("[PLAY] Stopped at {} - will resume from there", mpv_ipc::format_position(secs))),
+                        None => self.log("[PLAY] Watched to the end (or barely started) - next play starts over"),
+                    }
+                    self.store_resume_position(&url, resume, duration.filter(|d| *d > 0.0).map(|d| d as u64));
+                }
+                TaskResult::PlayerTested { player, result } => {
+                    match result {
+                        Ok(version) => {
+                            self.log(&format!("[INFO] Player OK: {}", version));
+                            self.status_message = format!("✔ Player '{}' works: {}", player, version);
+                        }
+                        Err(e) => {
+                            self.log(&format!("[ERROR] Player test failed for '{}': {}", player, e));
+                            self.status_message = format!("✖ Player '{}' failed: {}", player, e);
+                        }
+                    }
+                }
+                TaskResult::EpgLoading { progress } => {
+                    self.epg_status = progress.clone();
+                    // Extract percentage from status like "Downloading: 45.2 / 80.0 MB (56%)"
+                    if let Some(start) = progress.rfind('(') {
                         if let Some(end) = progress.rfind('%') {
                             if let Ok(pct) = progress[start+1..end].parse::<f32>() {
                                 self.epg_progress = pct / 100.0;
@@ -2538,6 +5599,7 @@ impl eframe::App for IPTVApp {
                     }
                 }
                 TaskResult::EpgLoaded { data } => {
+                    self.epg_coverage = None;
                     let channel_count = data.channels.len();
                     let program_count = data.program_count();
                     
@@ -2584,25 +5646,44 @@ impl eframe::App for IPTVApp {
                 }
                 TaskResult::EpgError(msg) => {
                     self.log(&format!("[ERROR] EPG: {}", msg));
+                    self.record_failure("EPG", &msg);
                     self.epg_loading = false;
                     self.epg_progress = 0.0;
                     self.epg_status = format!("Error: {}", msg);
                 }
+                TaskResult::StalkerConnected(name, client) => {
+                    self.stalker_sessions.insert(name, *client);
+                }
+                TaskResult::StalkerLinkResolved(channel) => {
+                    self.start_playback(&channel);
+                }
+                TaskResult::HlsVariants(channel, variants) => {
+                    if variants.len() > 1 {
+                        self.status_message = format!("{} qualities available", variants.len());
+                        self.hls_variant_choice = Some((*channel, variants));
+                    } else {
+                        // Media playlist, single variant or fetch failed - play as is
+                        self.play_hls_variant(*channel, None);
+                    }
+                }
+                TaskResult::PlaylistProgress { downloaded, total } => {
+                    if self.playlist_cancel.is_some() {
+                        self.playlist_progress = Some((downloaded, total));
+                    }
+                }
                 TaskResult::PlaylistLoaded { channels, playlist_name } => {
+                    self.playlist_progress = None;
+                    self.playlist_cancel = None;
                     let count = channels.len();
                     let source_name = playlist_name.clone().unwrap_or_else(|| "Playlist".to_string());
                     self.log(&format!("[INFO] Loaded {} with {} channels", source_name, count));
                     
-                    // Track source for separator display
-                    let start_idx = self.current_channels.len();
-                    self.playlist_sources.push((start_idx, source_name.clone()));
-                    
-                    // Append channels (don't replace)
-                    self.current_channels.extend(channels);
+                    // Append channels (don't replace), tracking the source for separator display
+                    add_playlist_source(&mut self.current_channels, &mut self.playlist_sources, &source_name, channels);
                     
                     self.playlist_mode = true;
                     self.logged_in = true;
-                    self.loading = false;
+                    self.end_task(TaskKind::Playlist);
                     
                     // Set navigation to show channels (only on first playlist load)
                     if self.playlist_sources.len() == 1 {
@@ -2673,9 +5754,40 @@ impl eframe::App for IPTVApp {
             }
         }
         
+        // Upload any finished poster/logo downloads
+        self.image_cache.poll(ctx);
+        
+        // Keep the LAN playlist in step with the channel list
+        self.sync_served_playlist();
+        
+        self.poll_recordings();
+        
+        // Single window mode keeps the child - notice when it exits
+        if let Some(child) = self.current_player.as_mut() {
+            if let Ok(Some(status)) = child.try_wait() {
+                let pid = child.id();
+                self.current_player = None;
+                if !status.success() {
+                    let name = self.last_launch.as_ref()
+                        .filter(|(launch_pid, _, _)| *launch_pid == pid)
+                        .map(|(_, _, channel)| channel.name.clone())
+                        .unwrap_or_default();
+                    self.handle_player_exit(pid, status.code(), format!("Player exited with error for '{}'", name));
+                }
+            }
+        }
+        
         // Request repaint while loading or when player might be outputting
-        if self.loading || self.epg_loading || self.current_player.is_some() {
+        let probing = matches!(self.stream_probe, Some((_, _, None)));
+        if self.loading || self.epg_loading || probing {
             ctx.request_repaint();
+        } else if self.current_player.is_some() || !self.recordings.is_empty() {
+            // Only watching for the player or a recording to exit - low data mode polls once a second
+            if self.low_data_mode {
+                ctx.request_repaint_after(std::time::Duration::from_secs(1));
+            } else {
+                ctx.request_repaint();
+            }
         }
         
         // EPG UI refresh every 5 minutes (to update current program, time remaining, etc.)
@@ -2688,55 +5800,41 @@ impl eframe::App for IPTVApp {
             }
         }
         
-        // Auto-login on startup - check playlist_entries for auto_login flag (must be enabled)
+        // Startup action (auto-login, last used playlist, etc.) - runs once
         if !self.auto_login_triggered && !self.logged_in && !self.loading {
-            // Find first entry with auto_login enabled AND playlist enabled
-            let auto_login_idx = self.playlist_entries.iter().position(|e| {
-                e.enabled && e.auto_login && matches!(e.entry_type, PlaylistType::Xtream { .. })
-            });
-            
-            if let Some(idx) = auto_login_idx {
-                self.auto_login_triggered = true;
-                self.current_playlist_idx = Some(idx); // Cache the index
-                let entry = &self.playlist_entries[idx];
-                if let PlaylistType::Xtream { server, username, password } = &entry.entry_type {
-                    // Load settings from entry
-                    self.server = server.clone();
-                    self.username = username.clone();
-                    self.password = password.clone();
-                    if !entry.epg_url.is_empty() {
-                        self.epg_url_input = entry.epg_url.clone();
-                    }
-                    self.epg_time_offset = entry.epg_time_offset;
-                    self.epg_auto_update = EpgAutoUpdate::from_index(entry.epg_auto_update_index);
-                    self.epg_show_actual_time = entry.epg_show_actual_time;
-                    if !entry.external_player.is_empty() {
-                        self.external_player = entry.external_player.clone();
-                    }
-                    self.buffer_seconds = entry.buffer_seconds;
-                    self.connection_quality = entry.connection_quality;
-                    self.selected_user_agent = entry.selected_user_agent;
-                    self.custom_user_agent = entry.custom_user_agent.clone();
-                    self.use_custom_user_agent = entry.use_custom_user_agent;
-                    self.pass_user_agent_to_player = entry.pass_user_agent_to_player;
-                    self.login();
-                }
-            } else {
-                // Fall back to legacy auto_login behavior
-                if self.save_state && self.auto_login {
-                    if !self.server.is_empty() && !self.username.is_empty() && !self.password.is_empty() {
-                        self.auto_login_triggered = true;
-                        self.invalidate_playlist_cache(); // Will be found on first lookup
-                        self.login();
-                    } else {
-                        self.auto_login_triggered = true;
-                    }
-                } else {
-                    self.auto_login_triggered = true;
-                }
+            self.auto_login_triggered = true;
+            self.run_startup_action();
+        }
+        
+        // === Drag-and-drop of playlist / EPG files ===
+        let dropped_files = ctx.input(|i| i.raw.dropped_files.clone());
+        for file in dropped_files {
+            if let Some(path) = file.path {
+                self.handle_dropped_file(&path);
             }
         }
         
+        self.handle_keyboard_shortcuts(ctx);
+        self.check_reminders(ctx);
+        self.show_reminder_banner(ctx);
+        self.show_expiry_banner(ctx);
+        
+        if ctx.input(|i| !i.raw.hovered_files.is_empty()) {
+            let painter = ctx.layer_painter(egui::LayerId::new(
+                egui::Order::Foreground,
+                egui::Id::new("file_drop_overlay"),
+            ));
+            let screen_rect = ctx.content_rect();
+            painter.rect_filled(screen_rect, 0.0, egui::Color32::from_black_alpha(180));
+            painter.text(
+                screen_rect.center(),
+                egui::Align2::CENTER_CENTER,
+                "📂 Drop playlist (.m3u, .m3u8, .xspf, .m3u.gz) or EPG (.xml, .xml.gz, .xml.xz)",
+                egui::FontId::proportional(22.0),
+                egui::Color32::WHITE,
+            );
+        }
+        
         // === Auto-update checks (EPG and Playlist) ===
         // Throttle to once per minute - no need to check "has 4 hours passed?" 60 times/second
         let now = unix_timestamp();
@@ -2745,7 +5843,7 @@ impl eframe::App for IPTVApp {
             self.last_auto_update_check = now;
             
             // EPG auto-update check (periodic refresh) - only when logged in and interval elapsed
-            if self.logged_in && !self.epg_loading && !self.epg_url_input.is_empty() {
+            if self.logged_in && !self.low_data_mode && !self.epg_loading && !self.epg_url_input.is_empty() {
                 if let Some(interval_secs) = self.epg_auto_update.as_secs() {
                     // Get persistent epg_last_updated from cached playlist entry
                     let persistent_last_update = self.find_current_playlist_idx()
@@ -2766,7 +5864,7 @@ impl eframe::App for IPTVApp {
             
             // Playlist auto-update check (periodic refresh for M3U playlists)
             // Skip if loading/EPG loading, and add 30min stagger after EPG updates
-            if !self.loading && !self.epg_loading {
+            if !self.loading && !self.epg_loading && !self.low_data_mode {
                 // 30 minute stagger after EPG update to avoid simultaneous requests
                 let stagger_ok = self.epg_last_update.map_or(true, |epg_last| (now - epg_last) >= 1800);
                 
@@ -2798,6 +5896,14 @@ impl eframe::App for IPTVApp {
             }
         }
 
+        self.track_window_layout(ctx);
+        
+        // Apply window level (only when changed - viewport commands aren't free)
+        if self.applied_always_on_top != Some(self.always_on_top) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(self.window_level()));
+            self.applied_always_on_top = Some(self.always_on_top);
+        }
+        
         // Apply theme
         if self.dark_mode {
             ctx.set_visuals(egui::Visuals::dark());
@@ -2805,6 +5911,12 @@ impl eframe::App for IPTVApp {
             ctx.set_visuals(egui::Visuals::light());
         }
         
+        // Apply UI scale (only when it changes, so Ctrl +/- zoom still works in between)
+        if self.applied_ui_scale != Some(self.ui_scale) {
+            ctx.set_zoom_factor(self.ui_scale.zoom_factor());
+            self.applied_ui_scale = Some(self.ui_scale);
+        }
+        
         // Apply font size
         let font_size = self.font_size_setting.size();
         let mut style = (*ctx.style()).clone();
@@ -2866,12 +5978,15 @@ impl eframe::App for IPTVApp {
                     }
                 }
                 
-                ui.separator();
-                
-                if ui.button("🌐 User Agent").on_hover_text("Configure User Agent string sent to server").clicked() {
-                    self.show_user_agent_dialog = true;
+                if let Some(idx) = self.previous_playlist_idx() {
+                    let name = &self.playlist_entries[idx].name;
+                    if ui.button("↔ Switch").on_hover_text(format!("Switch back to '{}'", name)).clicked() {
+                        self.switch_to_previous_playlist();
+                    }
                 }
                 
+                ui.separator();
+                
                 if ui.button("📡 EPG").on_hover_text("Load Electronic Program Guide").clicked() {
                     self.show_epg_dialog = true;
                 }
@@ -2886,13 +6001,9 @@ impl eframe::App for IPTVApp {
                 
                 ui.separator();
                 
-                ui.checkbox(&mut self.single_window_mode, "Single Window")
-                    .on_hover_text("Close previous player when opening new stream");
-                
-                ui.separator();
-                
-                ui.checkbox(&mut self.save_state, "💾 Auto-Save")
-                    .on_hover_text("Auto-save logins to Playlist Manager");
+                if ui.button("⚙ Settings").on_hover_text("Player, appearance and other settings").clicked() {
+                    self.show_settings_dialog = true;
+                }
                 
                 if ui.button("💾 Save").on_hover_text("Save current settings").clicked() {
                     self.save_current_state();
@@ -2982,6 +6093,26 @@ impl eframe::App for IPTVApp {
                     }
                 }
                 
+                let internal_selected = self.use_internal_player || self.external_player.eq_ignore_ascii_case("internal");
+                if !internal_selected && ui.button("🧪 Test").on_hover_text("Check that the player command launches").clicked() {
+                    self.test_player();
+                }
+                let running = self.running_player_count();
+                if running > 0 {
+                    let label = if running == 1 { "⏹ Stop Player".to_string() } else { format!("⏹ Stop Players ({})", running) };
+                    if ui.button(label).on_hover_text("Close the external players opened from here (X)").clicked() {
+                        self.stop_external_players();
+                    }
+                }
+                if !self.recordings.is_empty() {
+                    let names: Vec<_> = self.recordings.iter().map(|(name, _)| Self::sanitize_text(name)).collect();
+                    if ui.button(egui::RichText::new(format!("⏹ Stop Recording ({})", self.recordings.len())).color(egui::Color32::from_rgb(220, 60, 60)))
+                        .on_hover_text(format!("Recording to {}:\n{}", self.recording_dir, names.join("\n")))
+                        .clicked()
+                    {
+                        self.stop_recordings();
+                    }
+                }
                 ui.separator();
                 
                 ui.label("📶 Connection:");
@@ -3013,40 +6144,20 @@ impl eframe::App for IPTVApp {
                 
                 ui.separator();
                 
-                ui.checkbox(&mut self.hw_accel, "HW Acceleration")
-                    .on_hover_text("GPU Decoding\n\nEnable GPU hardware acceleration for video decoding\nDisable if you experience playback issues");
-                
-                ui.separator();
-                
-                ui.checkbox(&mut self.dark_mode, "🌙 Dark");
-                
-                ui.separator();
-                
-                // Font size dropdown
-                ui.label("Font Size:");
-                egui::ComboBox::from_id_salt("font_size_selector")
-                    .selected_text(self.font_size_setting.label())
-                    .show_ui(ui, |ui| {
-                        if ui.selectable_value(&mut self.font_size_setting, FontSize::Default, "Default (13px)").changed() {
-                            self.config.font_size_setting = self.font_size_setting;
-                            self.config.save();
-                        }
-                        ui.separator();
-                        if ui.selectable_value(&mut self.font_size_setting, FontSize::Medium, "Medium (15px)").changed() {
-                            self.config.font_size_setting = self.font_size_setting;
-                            self.config.save();
-                        }
-                        ui.separator();
-                        if ui.selectable_value(&mut self.font_size_setting, FontSize::Large, "Large (16px)").changed() {
-                            self.config.font_size_setting = self.font_size_setting;
-                            self.config.save();
-                        }
-                        ui.separator();
-                        if ui.selectable_value(&mut self.font_size_setting, FontSize::XLarge, "X-Large (18px)").changed() {
-                            self.config.font_size_setting = self.font_size_setting;
-                            self.config.save();
-                        }
-                    });
+                let mut low_data = self.low_data_mode;
+                let low_data_text = if low_data {
+                    egui::RichText::new("📉 Low data").color(egui::Color32::from_rgb(230, 180, 60)).strong()
+                } else {
+                    egui::RichText::new("📉 Low data")
+                };
+                if ui.checkbox(&mut low_data, low_data_text)
+                    .on_hover_text("Save bandwidth on metered connections:\n• No logo/poster downloads\n• No stream probing\n• No EPG or playlist auto-updates\n• Fewer screen refreshes\n\nYour individual settings are kept and apply again when turned off")
+                    .changed()
+                {
+                    self.set_low_data_mode(low_data);
+                    self.config.low_data_mode = low_data;
+                    self.config.save();
+                }
             });
             
             ui.add_space(5.0);
@@ -3057,6 +6168,40 @@ impl eframe::App for IPTVApp {
             ui.horizontal(|ui| {
                 if self.loading {
                     ui.spinner();
+                    // Name the running fetches; several can be in flight at once
+                    let label = match self.active_tasks.as_slice() {
+                        [] => String::new(),
+                        [(_, single)] => single.clone(),
+                        tasks => format!("{} tasks running", tasks.len()),
+                    };
+                    if !label.is_empty() {
+                        let response = ui.label(egui::RichText::new(label).small().weak());
+                        if self.active_tasks.len() > 1 {
+                            let list = self.active_tasks.iter().map(|(_, l)| l.as_str()).collect::<Vec<_>>().join("\n");
+                            response.on_hover_text(list);
+                        }
+                        ui.separator();
+                    }
+                }
+                if self.playlist_cancel.is_some() && self.active_tasks.iter().any(|(k, _)| *k == TaskKind::Playlist) {
+                    if let Some((downloaded, total)) = self.playlist_progress {
+                        let mb = |b: u64| b as f64 / (1024.0 * 1024.0);
+                        match total.filter(|t| *t > 0) {
+                            Some(total) => {
+                                let fraction = (downloaded as f32 / total as f32).clamp(0.0, 1.0);
+                                ui.add(egui::ProgressBar::new(fraction)
+                                    .desired_width(160.0)
+                                    .text(format!("{:.1} / {:.1} MB ({:.0}%)", mb(downloaded), mb(total), fraction * 100.0)));
+                            }
+                            None => {
+                                ui.label(egui::RichText::new(format!("{:.1} MB", mb(downloaded))).small());
+                            }
+                        }
+                    }
+                    if ui.small_button("✖ Cancel").on_hover_text("Stop loading the playlist").clicked() {
+                        self.cancel_playlist_load();
+                    }
+                    ui.separator();
                 }
                 ui.label(&self.status_message);
             });
@@ -3094,6 +6239,7 @@ impl eframe::App for IPTVApp {
                             let btn_text = match &entry.entry_type {
                                 PlaylistType::Xtream { .. } => format!("🔑 {}", entry.name),
                                 PlaylistType::M3U { .. } => format!("📺 {}", entry.name),
+                                PlaylistType::Stalker { .. } => format!("📡 {}", entry.name),
                             };
                             if ui.button(&btn_text).clicked() {
                                 to_load_idx = Some(i);
@@ -3101,36 +6247,7 @@ impl eframe::App for IPTVApp {
                         }
                         
                         if let Some(idx) = to_load_idx {
-                            let entry = &self.playlist_entries[idx];
-                            match &entry.entry_type {
-                                PlaylistType::Xtream { server, username, password } => {
-                                    self.server = server.clone();
-                                    self.username = username.clone();
-                                    self.password = password.clone();
-                                    if !entry.epg_url.is_empty() {
-                                        self.epg_url_input = entry.epg_url.clone();
-                                    }
-                                    self.epg_time_offset = entry.epg_time_offset;
-                                    self.epg_auto_update = EpgAutoUpdate::from_index(entry.epg_auto_update_index);
-                                    self.epg_show_actual_time = entry.epg_show_actual_time;
-                                    if !entry.external_player.is_empty() {
-                                        self.external_player = entry.external_player.clone();
-                                    }
-                                    self.buffer_seconds = entry.buffer_seconds;
-                                    self.connection_quality = entry.connection_quality;
-                                    self.selected_user_agent = entry.selected_user_agent;
-                                    self.custom_user_agent = entry.custom_user_agent.clone();
-                                    self.use_custom_user_agent = entry.use_custom_user_agent;
-                                    self.pass_user_agent_to_player = entry.pass_user_agent_to_player;
-                                    self.login();
-                                }
-                                PlaylistType::M3U { url } => {
-                                    // Clone before calling mutable method
-                                    let url = url.clone();
-                                    let name = entry.name.clone();
-                                    self.load_playlist_with_name(&url, &name);
-                                }
-                            }
+                            self.open_playlist_entry(idx);
                         }
                         
                         ui.add_space(20.0);
@@ -3143,12 +6260,24 @@ impl eframe::App for IPTVApp {
             }
 
             // Tab bar
+            // Counts come from what's already loaded: channels in playlist mode, categories otherwise
+            let with_count = |label: &str, count: usize| {
+                if count > 0 { format!("{} ({})", label, count) } else { label.to_string() }
+            };
+            let (live_count, live_unit) = if self.playlist_mode {
+                (self.current_channels.len(), "channels")
+            } else {
+                (self.live_categories.len(), "categories")
+            };
             ui.horizontal(|ui| {
-                ui.selectable_value(&mut self.current_tab, Tab::Live, "📺 LIVE");
-                ui.selectable_value(&mut self.current_tab, Tab::Movies, "🎬 MOVIES");
-                ui.selectable_value(&mut self.current_tab, Tab::Series, "📺 SERIES");
-                ui.selectable_value(&mut self.current_tab, Tab::Favorites, "⭐ FAVORITES");
-                ui.selectable_value(&mut self.current_tab, Tab::Recent, "🕐 RECENT");
+                ui.selectable_value(&mut self.current_tab, Tab::Live, with_count("📺 LIVE", live_count))
+                    .on_hover_text(format!("{} {}", live_count, live_unit));
+                ui.selectable_value(&mut self.current_tab, Tab::Movies, with_count("🎬 MOVIES", self.movie_categories.len()))
+                    .on_hover_text(format!("{} categories", self.movie_categories.len()));
+                ui.selectable_value(&mut self.current_tab, Tab::Series, with_count("📺 SERIES", self.series_categories.len()))
+                    .on_hover_text(format!("{} categories", self.series_categories.len()));
+                ui.selectable_value(&mut self.current_tab, Tab::Favorites, with_count("⭐ FAVORITES", self.favorites.len()));
+                ui.selectable_value(&mut self.current_tab, Tab::Recent, with_count("🕐 RECENT", self.recent_watched.len()));
                 ui.selectable_value(&mut self.current_tab, Tab::Info, "ℹ️ INFO");
                 
                 // Push Console to the right
@@ -3169,9 +6298,21 @@ impl eframe::App for IPTVApp {
                     }
                     
                     ui.label("");
-                    ui.add(egui::TextEdit::singleline(&mut self.search_query)
-                        .hint_text("Search...")
-                        .desired_width(150.0));
+                    let search_response = ui.add(egui::TextEdit::singleline(&mut self.search_query)
+                        .id(egui::Id::new("search_field"))
+                        .hint_text("Search... ( / )")
+                        .desired_width(150.0))
+                        .on_hover_text("Prefix with tvg: or url: to search EPG ids or stream URLs\n\nKeyboard shortcuts:\n• / or Ctrl+F - focus search\n• Esc - close dialog / go back\n• E - EPG dialog\n• P - Playlist Manager\n• U - User Agent settings\n• PageDown / PageUp - next / previous live favorite\n• X - stop external players\n• Ctrl+S - save settings");
+                    
+                    // Regex mode - only the Live and Movies lists use it
+                    if matches!(self.current_tab, Tab::Live | Tab::Movies) {
+                        if self.search_regex && self.search_filter().is_invalid() {
+                            ui.painter().rect_stroke(search_response.rect, 2.0,
+                                egui::Stroke::new(1.0, egui::Color32::from_rgb(200, 80, 80)), egui::StrokeKind::Outside);
+                        }
+                        ui.toggle_value(&mut self.search_regex, ".*")
+                            .on_hover_text("Regular expression search (an invalid pattern is matched as plain text)");
+                    }
                     
                     // Sort dropdown - show for Live, Movies, Series tabs
                     match self.current_tab {
@@ -3184,22 +6325,19 @@ impl eframe::App for IPTVApp {
                             };
                             if item_count > 0 {
                                 ui.separator();
-                                egui::ComboBox::from_id_salt("live_sort_top")
-                                    .selected_text(format!("{} {}", self.live_sort_order.icon(), self.live_sort_order.label()))
+                                let mut sort_order = self.effective_sort_order("live");
+                                let sort_changed = egui::ComboBox::from_id_salt("live_sort_top")
+                                    .selected_text(format!("{} {}", sort_order.icon(), sort_order.label()))
                                     .show_ui(ui, |ui| {
-                                        if ui.selectable_value(&mut self.live_sort_order, SortOrder::Default, "⇅ Default").changed() {
-                                            self.config.live_sort_order = self.live_sort_order;
-                                            self.config.save();
-                                        }
-                                        if ui.selectable_value(&mut self.live_sort_order, SortOrder::NameAsc, "↑ Name A-Z").changed() {
-                                            self.config.live_sort_order = self.live_sort_order;
-                                            self.config.save();
-                                        }
-                                        if ui.selectable_value(&mut self.live_sort_order, SortOrder::NameDesc, "↓ Name Z-A").changed() {
-                                            self.config.live_sort_order = self.live_sort_order;
-                                            self.config.save();
-                                        }
-                                    });
+                                        ui.selectable_value(&mut sort_order, SortOrder::Default, "⇅ Default").changed()
+                                            | ui.selectable_value(&mut sort_order, SortOrder::NameAsc, "↑ Name A-Z").changed()
+                                            | ui.selectable_value(&mut sort_order, SortOrder::NameDesc, "↓ Name Z-A").changed()
+                                            | ui.selectable_value(&mut sort_order, SortOrder::MostWatched, "🔥 Most watched").changed()
+                                    }).inner.unwrap_or(false);
+                                if sort_changed {
+                                    self.set_sort_order("live", sort_order);
+                                }
+                                Self::quality_filter_combo(ui, "live_quality_filter", &mut self.quality_filter);
                                 // Show layout dropdown when EPG panel is hidden
                                 let epg_panel_visible = self.epg_data.is_some() && self.epg_panel_visible;
                                 if !epg_panel_visible {
@@ -3236,43 +6374,55 @@ impl eframe::App for IPTVApp {
                             };
                             if item_count > 0 {
                                 ui.separator();
-                                egui::ComboBox::from_id_salt("movie_sort_top")
-                                    .selected_text(format!("{} {}", self.movie_sort_order.icon(), self.movie_sort_order.label()))
+                                let mut sort_order = self.effective_sort_order("movie");
+                                let sort_changed = egui::ComboBox::from_id_salt("movie_sort_top")
+                                    .selected_text(format!("{} {}", sort_order.icon(), sort_order.label()))
                                     .show_ui(ui, |ui| {
-                                        if ui.selectable_value(&mut self.movie_sort_order, SortOrder::Default, "⇅ Default").changed() {
-                                            self.config.movie_sort_order = self.movie_sort_order;
-                                            self.config.save();
-                                        }
-                                        if ui.selectable_value(&mut self.movie_sort_order, SortOrder::NameAsc, "↑ Name A-Z").changed() {
-                                            self.config.movie_sort_order = self.movie_sort_order;
-                                            self.config.save();
-                                        }
-                                        if ui.selectable_value(&mut self.movie_sort_order, SortOrder::NameDesc, "↓ Name Z-A").changed() {
-                                            self.config.movie_sort_order = self.movie_sort_order;
-                                            self.config.save();
-                                        }
-                                    });
-                                // Layout dropdown
-                                egui::ComboBox::from_id_salt("movie_layout")
-                                    .selected_text(format!("{} {}", self.list_layout.icon(), self.list_layout.label()))
+                                        ui.selectable_value(&mut sort_order, SortOrder::Default, "⇅ Default").changed()
+                                            | ui.selectable_value(&mut sort_order, SortOrder::NameAsc, "↑ Name A-Z").changed()
+                                            | ui.selectable_value(&mut sort_order, SortOrder::NameDesc, "↓ Name Z-A").changed()
+                                            | ui.selectable_value(&mut sort_order, SortOrder::MostWatched, "🔥 Most watched").changed()
+                                    }).inner.unwrap_or(false);
+                                if sort_changed {
+                                    self.set_sort_order("movie", sort_order);
+                                }
+                                Self::quality_filter_combo(ui, "movie_quality_filter", &mut self.quality_filter);
+                                // View mode (list / poster wall)
+                                egui::ComboBox::from_id_salt("movie_view_mode")
+                                    .selected_text(format!("{} {}", self.vod_view_mode.icon(), self.vod_view_mode.label()))
                                     .show_ui(ui, |ui| {
-                                        if ui.selectable_value(&mut self.list_layout, ListLayout::Single, "▤ Single").changed() {
-                                            self.config.list_layout = self.list_layout;
+                                        if ui.selectable_value(&mut self.vod_view_mode, ViewMode::List, "☰ List").changed() {
+                                            self.config.vod_view_mode = self.vod_view_mode;
                                             self.config.save();
                                         }
-                                        if ui.selectable_value(&mut self.list_layout, ListLayout::Double, "▥ 2 Columns").changed() {
-                                            self.config.list_layout = self.list_layout;
+                                        if ui.selectable_value(&mut self.vod_view_mode, ViewMode::Grid, "🖼 Posters").changed() {
+                                            self.config.vod_view_mode = self.vod_view_mode;
                                             self.config.save();
                                         }
-                                        if ui.selectable_value(&mut self.list_layout, ListLayout::Triple, "▦ 3 Columns").changed() {
-                                            self.config.list_layout = self.list_layout;
-                                            self.config.save();
-                                        }
-                                        if ui.selectable_value(&mut self.list_layout, ListLayout::Quad, "▩ 4 Columns").changed() {
-                                            self.config.list_layout = self.list_layout;
-                                            self.config.save();
-                                        }
-                                    });
+                                    }).response.on_hover_text("Text list or poster grid (posters use more bandwidth)");
+                                // Layout dropdown (columns only apply to the list view)
+                                if self.vod_view_mode == ViewMode::List {
+                                    egui::ComboBox::from_id_salt("movie_layout")
+                                        .selected_text(format!("{} {}", self.list_layout.icon(), self.list_layout.label()))
+                                        .show_ui(ui, |ui| {
+                                            if ui.selectable_value(&mut self.list_layout, ListLayout::Single, "▤ Single").changed() {
+                                                self.config.list_layout = self.list_layout;
+                                                self.config.save();
+                                            }
+                                            if ui.selectable_value(&mut self.list_layout, ListLayout::Double, "▥ 2 Columns").changed() {
+                                                self.config.list_layout = self.list_layout;
+                                                self.config.save();
+                                            }
+                                            if ui.selectable_value(&mut self.list_layout, ListLayout::Triple, "▦ 3 Columns").changed() {
+                                                self.config.list_layout = self.list_layout;
+                                                self.config.save();
+                                            }
+                                            if ui.selectable_value(&mut self.list_layout, ListLayout::Quad, "▩ 4 Columns").changed() {
+                                                self.config.list_layout = self.list_layout;
+                                                self.config.save();
+                                            }
+                                        });
+                                }
                                 ui.label(format!("({})", item_count));
                             }
                         }
@@ -3286,43 +6436,53 @@ impl eframe::App for IPTVApp {
                             let show_sort = self.current_episodes.is_empty() && self.current_seasons.is_empty() && item_count > 0;
                             if show_sort {
                                 ui.separator();
-                                egui::ComboBox::from_id_salt("series_sort_top")
-                                    .selected_text(format!("{} {}", self.series_sort_order.icon(), self.series_sort_order.label()))
+                                let mut sort_order = self.effective_sort_order("series");
+                                let sort_changed = egui::ComboBox::from_id_salt("series_sort_top")
+                                    .selected_text(format!("{} {}", sort_order.icon(), sort_order.label()))
                                     .show_ui(ui, |ui| {
-                                        if ui.selectable_value(&mut self.series_sort_order, SortOrder::Default, "⇅ Default").changed() {
-                                            self.config.series_sort_order = self.series_sort_order;
-                                            self.config.save();
-                                        }
-                                        if ui.selectable_value(&mut self.series_sort_order, SortOrder::NameAsc, "↑ Name A-Z").changed() {
-                                            self.config.series_sort_order = self.series_sort_order;
-                                            self.config.save();
-                                        }
-                                        if ui.selectable_value(&mut self.series_sort_order, SortOrder::NameDesc, "↓ Name Z-A").changed() {
-                                            self.config.series_sort_order = self.series_sort_order;
-                                            self.config.save();
-                                        }
-                                    });
-                                // Layout dropdown
-                                egui::ComboBox::from_id_salt("series_layout")
-                                    .selected_text(format!("{} {}", self.list_layout.icon(), self.list_layout.label()))
+                                        ui.selectable_value(&mut sort_order, SortOrder::Default, "⇅ Default").changed()
+                                            | ui.selectable_value(&mut sort_order, SortOrder::NameAsc, "↑ Name A-Z").changed()
+                                            | ui.selectable_value(&mut sort_order, SortOrder::NameDesc, "↓ Name Z-A").changed()
+                                    }).inner.unwrap_or(false);
+                                if sort_changed {
+                                    self.set_sort_order("series", sort_order);
+                                }
+                                // View mode (list / poster wall)
+                                egui::ComboBox::from_id_salt("series_view_mode")
+                                    .selected_text(format!("{} {}", self.vod_view_mode.icon(), self.vod_view_mode.label()))
                                     .show_ui(ui, |ui| {
-                                        if ui.selectable_value(&mut self.list_layout, ListLayout::Single, "▤ Single").changed() {
-                                            self.config.list_layout = self.list_layout;
+                                        if ui.selectable_value(&mut self.vod_view_mode, ViewMode::List, "☰ List").changed() {
+                                            self.config.vod_view_mode = self.vod_view_mode;
                                             self.config.save();
                                         }
-                                        if ui.selectable_value(&mut self.list_layout, ListLayout::Double, "▥ 2 Columns").changed() {
-                                            self.config.list_layout = self.list_layout;
+                                        if ui.selectable_value(&mut self.vod_view_mode, ViewMode::Grid, "🖼 Posters").changed() {
+                                            self.config.vod_view_mode = self.vod_view_mode;
                                             self.config.save();
                                         }
-                                        if ui.selectable_value(&mut self.list_layout, ListLayout::Triple, "▦ 3 Columns").changed() {
-                                            self.config.list_layout = self.list_layout;
-                                            self.config.save();
-                                        }
-                                        if ui.selectable_value(&mut self.list_layout, ListLayout::Quad, "▩ 4 Columns").changed() {
-                                            self.config.list_layout = self.list_layout;
-                                            self.config.save();
-                                        }
-                                    });
+                                    }).response.on_hover_text("Text list or poster grid (posters use more bandwidth)");
+                                // Layout dropdown (columns only apply to the list view)
+                                if self.vod_view_mode == ViewMode::List {
+                                    egui::ComboBox::from_id_salt("series_layout")
+                                        .selected_text(format!("{} {}", self.list_layout.icon(), self.list_layout.label()))
+                                        .show_ui(ui, |ui| {
+                                            if ui.selectable_value(&mut self.list_layout, ListLayout::Single, "▤ Single").changed() {
+                                                self.config.list_layout = self.list_layout;
+                                                self.config.save();
+                                            }
+                                            if ui.selectable_value(&mut self.list_layout, ListLayout::Double, "▥ 2 Columns").changed() {
+                                                self.config.list_layout = self.list_layout;
+                                                self.config.save();
+                                            }
+                                            if ui.selectable_value(&mut self.list_layout, ListLayout::Triple, "▦ 3 Columns").changed() {
+                                                self.config.list_layout = self.list_layout;
+                                                self.config.save();
+                                            }
+                                            if ui.selectable_value(&mut self.list_layout, ListLayout::Quad, "▩ 4 Columns").changed() {
+                                                self.config.list_layout = self.list_layout;
+                                                self.config.save();
+                                            }
+                                        });
+                                }
                                 ui.label(format!("({})", item_count));
                             }
                         }
@@ -3435,6 +6595,47 @@ impl eframe::App for IPTVApp {
                             .desired_width(400.0));
                     });
                     
+                    ui.horizontal(|ui| {
+                        ui.label("Portal:");
+                        ui.add(egui::TextEdit::singleline(&mut self.stalker_portal_input)
+                            .hint_text("http://portal.com/c/")
+                            .desired_width(220.0));
+                        ui.label("MAC:");
+                        let mac_valid = self.stalker_mac_input.trim().is_empty()
+                            || stalker::normalize_mac(&self.stalker_mac_input).is_some();
+                        let mut mac_edit = egui::TextEdit::singleline(&mut self.stalker_mac_input)
+                            .hint_text("00:1A:79:XX:XX:XX")
+                            .desired_width(130.0);
+                        if !mac_valid {
+                            mac_edit = mac_edit.text_color(egui::Color32::from_rgb(220, 80, 80));
+                        }
+                        ui.add(mac_edit);
+                        if ui.button("➕ Add Stalker").on_hover_text("Add a Stalker / Ministra (MAG) portal").clicked() {
+                            match stalker::normalize_mac(&self.stalker_mac_input) {
+                                Some(mac) if !self.stalker_portal_input.trim().is_empty() => {
+                                    let portal = self.stalker_portal_input.trim().to_string();
+                                    let name = if self.playlist_name_input.is_empty() {
+                                        portal.split('/').nth(2).unwrap_or(&portal).to_string()
+                                    } else {
+                                        self.playlist_name_input.clone()
+                                    };
+                                    let is_duplicate = self.playlist_entries.iter().any(|e| {
+                                        matches!(&e.entry_type, PlaylistType::Stalker { portal: p, mac: m } if p == &portal && m == &mac)
+                                    });
+                                    if !is_duplicate {
+                                        self.playlist_entries.push(PlaylistEntry::new_stalker(name.clone(), portal, mac));
+                                        save_playlist_entries(&self.playlist_entries);
+                                        self.status_message = format!("Added portal '{}'", name);
+                                    }
+                                    self.playlist_name_input.clear();
+                                    self.stalker_portal_input.clear();
+                                    self.stalker_mac_input.clear();
+                                }
+                                _ => self.status_message = "Enter a portal URL and a MAC address like 00:1A:79:12:34:56".to_string(),
+                            }
+                        }
+                    });
+                    
                     ui.horizontal(|ui| {
                         // Add as M3U playlist
                         if ui.button("➕ Add M3U/XSPF").on_hover_text("Add as M3U/M3U8/XSPF playlist").clicked() {
@@ -3548,19 +6749,52 @@ impl eframe::App for IPTVApp {
                     
                     ui.separator();
                     
+                    self.show_playlists_dir_section(ui);
+                    
+                    ui.separator();
+                    
                     // Saved playlists section
-                    ui.heading("Saved Playlists");
+                    let mut set_all_enabled: Option<bool> = None;
+                    ui.horizontal(|ui| {
+                        ui.heading("Saved Playlists");
+                        if !self.playlist_entries.is_empty() {
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                if ui.small_button("○ Disable all").on_hover_text("Disable every playlist (also turns off auto-login)").clicked() {
+                                    set_all_enabled = Some(false);
+                                }
+                                if ui.small_button("✓ Enable all").clicked() {
+                                    set_all_enabled = Some(true);
+                                }
+                            });
+                        }
+                    });
+                    
+                    if let Some(enabled) = set_all_enabled {
+                        for entry in &mut self.playlist_entries {
+                            entry.enabled = enabled;
+                            // Matches the single toggle - disabled playlists can't auto-login
+                            if !enabled {
+                                entry.auto_login = false;
+                            }
+                        }
+                        save_playlist_entries(&self.playlist_entries);
+                        self.status_message = format!("{} all playlists", if enabled { "Enabled" } else { "Disabled" });
+                    }
                     
                     if self.playlist_entries.is_empty() {
                         ui.label(egui::RichText::new("No saved playlists").weak());
                     } else {
                         let mut to_delete: Option<usize> = None;
+                        let mut to_duplicate: Option<usize> = None;
                         let mut to_load_xtream_idx: Option<usize> = None;
-                        let mut to_load_m3u: Option<(String, String)> = None; // url, name
+                        let mut to_load_m3u_idx: Option<usize> = None;
                         let mut to_toggle_auto_login: Option<usize> = None;
+                        let mut to_toggle_internal_player: Option<usize> = None;
                         let mut to_toggle_enabled: Option<usize> = None;
                         let mut to_change_auto_update: Option<(usize, u8)> = None; // (index, new_days)
                         let mut to_reload: Option<usize> = None; // index of playlist to reload
+                        let mut to_copy_settings: Option<(usize, usize)> = None; // (target, source)
+                        let entry_names: Vec<String> = self.playlist_entries.iter().map(|e| e.name.clone()).collect();
                         
                         egui::ScrollArea::vertical()
                             .max_height(250.0)
@@ -3588,14 +6822,20 @@ impl eframe::App for IPTVApp {
                                                     }
                                                     ui.label("🔑");
                                                 }
-                                                PlaylistType::M3U { url } => {
+                                                PlaylistType::M3U { .. } => {
                                                     if entry.enabled {
                                                         if ui.button("▶").on_hover_text("Load this playlist").clicked() {
-                                                            to_load_m3u = Some((url.clone(), entry.name.clone()));
+                                                            to_load_m3u_idx = Some(i);
                                                         }
                                                     }
                                                     ui.label("📺");
                                                 }
+                                                PlaylistType::Stalker { mac, .. } => {
+                                                    if entry.enabled && ui.button("▶").on_hover_text("Connect to this portal").clicked() {
+                                                        to_load_m3u_idx = Some(i);
+                                                    }
+                                                    ui.label("📡").on_hover_text(format!("Stalker portal, MAC {}", mac));
+                                                }
                                             }
                                             
                                             let name_text = if entry.enabled {
@@ -3609,6 +6849,9 @@ impl eframe::App for IPTVApp {
                                                 if ui.button("🗑").on_hover_text("Delete").clicked() {
                                                     to_delete = Some(i);
                                                 }
+                                                if ui.button("📋").on_hover_text("Duplicate").clicked() {
+                                                    to_duplicate = Some(i);
+                                                }
                                                 if ui.button("🔄").on_hover_text("Reload playlist data from server").clicked() {
                                                     to_reload = Some(i);
                                                 }
@@ -3627,6 +6870,14 @@ impl eframe::App for IPTVApp {
                                                     if ui.button(auto_text).on_hover_text(hover).clicked() {
                                                         to_toggle_auto_login = Some(i);
                                                     }
+                                                    
+                                                    let internal_text = if entry.use_internal_player { "🎞 Internal player" } else { "○ Internal player" };
+                                                    if ui.button(internal_text)
+                                                        .on_hover_text("Play this provider's streams in the built-in player instead of the external one")
+                                                        .clicked()
+                                                    {
+                                                        to_toggle_internal_player = Some(i);
+                                                    }
                                                 }
                                                 
                                                 // Auto-update dropdown
@@ -3664,6 +6915,20 @@ impl eframe::App for IPTVApp {
                                                         }
                                                     }).response.on_hover_text("Auto-update interval - automatically refresh playlist data");
                                                 
+                                                // Copy player/connection/user agent settings from another entry
+                                                if entry_names.len() > 1 {
+                                                    egui::ComboBox::from_id_salt(format!("copy_settings_{}", i))
+                                                        .selected_text("Copy settings from…")
+                                                        .width(130.0)
+                                                        .show_ui(ui, |ui| {
+                                                            for (j, name) in entry_names.iter().enumerate() {
+                                                                if j != i && ui.selectable_label(false, name).clicked() {
+                                                                    to_copy_settings = Some((i, j));
+                                                                }
+                                                            }
+                                                        }).response.on_hover_text("Copy player, buffer, connection and user agent settings\n(credentials and EPG settings are kept)");
+                                                }
+                                                
                                                 // Saved date
                                                 if entry.saved_at > 0 {
                                                     ui.label(egui::RichText::new(format!("Saved: {}", Self::format_datetime(entry.saved_at))).weak());
@@ -3691,6 +6956,15 @@ impl eframe::App for IPTVApp {
                             save_playlist_entries(&self.playlist_entries);
                         }
                         
+                        // Handle internal player toggle - applies now if this provider is open
+                        if let Some(i) = to_toggle_internal_player {
+                            self.playlist_entries[i].use_internal_player = !self.playlist_entries[i].use_internal_player;
+                            save_playlist_entries(&self.playlist_entries);
+                            if self.find_current_playlist_idx() == Some(i) {
+                                self.use_internal_player = self.playlist_entries[i].use_internal_player;
+                            }
+                        }
+                        
                         // Handle auto-update change
                         if let Some((i, days)) = to_change_auto_update {
                             self.playlist_entries[i].auto_update_days = days;
@@ -3701,44 +6975,39 @@ impl eframe::App for IPTVApp {
                             save_playlist_entries(&self.playlist_entries);
                         }
                         
-                        // Handle actions
-                        if let Some(idx) = to_load_xtream_idx {
-                            self.current_playlist_idx = Some(idx); // Cache the index
-                            let entry = &self.playlist_entries[idx];
-                            if let PlaylistType::Xtream { server, username, password } = &entry.entry_type {
-                                // Server credentials
-                                self.server = server.clone();
-                                self.username = username.clone();
-                                self.password = password.clone();
-                                // EPG settings
-                                if !entry.epg_url.is_empty() {
-                                    self.epg_url_input = entry.epg_url.clone();
-                                }
-                                self.epg_time_offset = entry.epg_time_offset;
-                                self.epg_auto_update = EpgAutoUpdate::from_index(entry.epg_auto_update_index);
-                                self.epg_show_actual_time = entry.epg_show_actual_time;
-                                // Clear EPG data for new provider
-                                self.epg_data = None;
-                                self.epg_last_update = None;
-                                // Player settings
-                                if !entry.external_player.is_empty() {
-                                    self.external_player = entry.external_player.clone();
-                                }
-                                self.buffer_seconds = entry.buffer_seconds;
-                                self.connection_quality = entry.connection_quality;
-                                // User agent settings
-                                self.selected_user_agent = entry.selected_user_agent;
-                                self.custom_user_agent = entry.custom_user_agent.clone();
-                                self.use_custom_user_agent = entry.use_custom_user_agent;
-                                self.pass_user_agent_to_player = entry.pass_user_agent_to_player;
-                                
-                                self.show_playlist_manager = false;
-                                self.login();
+                        // Handle copy settings
+                        if let Some((target, source)) = to_copy_settings {
+                            let source_entry = self.playlist_entries[source].clone();
+                            self.playlist_entries[target].copy_settings_from(&source_entry);
+                            save_playlist_entries(&self.playlist_entries);
+                            // Apply right away if the target is the open provider
+                            if self.find_current_playlist_idx() == Some(target) {
+                                self.apply_entry_player_settings(target);
                             }
+                            self.status_message = format!("Copied settings from '{}' to '{}'",
+                                source_entry.name, self.playlist_entries[target].name);
+                        }
+                        
+                        // Handle duplicate - insert the copy right after the original
+                        if let Some(i) = to_duplicate {
+                            let copy = self.playlist_entries[i].duplicate();
+                            self.status_message = format!("Duplicated '{}'", self.playlist_entries[i].name);
+                            self.playlist_entries.insert(i + 1, copy);
+                            self.invalidate_playlist_cache(); // Index may have shifted
+                            save_playlist_entries(&self.playlist_entries);
                         }
                         
-                        if let Some((url, name)) = to_load_m3u {
-                            self.load_playlist_with_name(&url, &name);
+                        // Handle actions
+                        if let Some(idx) = to_load_xtream_idx {
+                            // Clear EPG data for new provider
+                            self.epg_data = None;
+                            self.epg_last_update = None;
+                            self.show_playlist_manager = false;
+                            self.open_playlist_entry(idx);
+                        }
+                        
+                        if let Some(idx) = to_load_m3u_idx {
+                            self.open_playlist_entry(idx);
                             self.show_playlist_manager = false;
                         }
                         
@@ -3764,6 +7033,7 @@ impl eframe::App for IPTVApp {
                                 }
                                 PlaylistType::Xtream { server, username, password } => {
                                     // For Xtream, reload means re-login to refresh categories
+                                    clear_category_cache(server, username);
                                     self.server = server.clone();
                                     self.username = username.clone();
                                     self.password = password.clone();
@@ -3774,6 +7044,16 @@ impl eframe::App for IPTVApp {
                                     self.login();
                                     self.show_playlist_manager = false;
                                 }
+                                PlaylistType::Stalker { .. } => {
+                                    // Portals have no incremental reload - reconnect
+                                    if let Some(source_idx) = self.playlist_sources.iter().position(|(_, n)| n == &name) {
+                                        self.unload_playlist(source_idx);
+                                    }
+                                    self.log(&format!("[INFO] Manual reload triggered for '{}'", name));
+                                    self.playlist_entries[idx].last_updated = now;
+                                    save_playlist_entries(&self.playlist_entries);
+                                    self.open_playlist_entry(idx);
+                                }
                             }
                         }
                         
@@ -3834,6 +7114,59 @@ impl eframe::App for IPTVApp {
                             self.show_playlist_manager = false;
                         }
                         
+                        if ui.add_enabled(!self.current_channels.is_empty(), egui::Button::new("💾 Export M3U"))
+                            .on_hover_text("Save the loaded channel list as an .m3u file")
+                            .on_disabled_hover_text("Open a channel category or load a playlist first")
+                            .clicked()
+                        {
+                            self.export_m3u();
+                        }
+                        
+                        ui.separator();
+                        
+                        ui.label("🚀 On startup:");
+                        egui::ComboBox::from_id_salt("startup_action")
+                            .selected_text(self.startup_action.label())
+                            .show_ui(ui, |ui| {
+                                for action in StartupAction::all() {
+                                    if ui.selectable_value(&mut self.startup_action, action, action.label()).changed() {
+                                        self.config.startup_action = self.startup_action;
+                                        self.config.save();
+                                    }
+                                }
+                            }).response.on_hover_text("What to do when the app starts");
+                        
+                        ui.separator();
+                        
+                        ui.label("🔌 Max fetches:");
+                        if ui.add(egui::DragValue::new(&mut self.max_concurrent_fetches).range(1..=16))
+                            .on_hover_text("Maximum simultaneous downloads from the provider\n(lower this if your provider limits connections)")
+                            .changed()
+                        {
+                            self.fetch_pool.set_max(self.max_concurrent_fetches);
+                            self.config.max_concurrent_fetches = self.max_concurrent_fetches;
+                            self.config.save();
+                        }
+                        
+                        ui.label("⏱ Timeouts:");
+                        let connect_changed = ui.add(egui::DragValue::new(&mut self.connect_timeout_secs).range(1..=300).prefix("connect ").suffix("s"))
+                            .on_hover_text("Time allowed to connect to the provider")
+                            .changed();
+                        let read_changed = ui.add(egui::DragValue::new(&mut self.read_timeout_secs).range(1..=900).prefix("read ").suffix("s"))
+                            .on_hover_text("Time allowed for a response (API, playlist and EPG downloads)\nRaise this for providers with huge playlists")
+                            .changed();
+                        if connect_changed || read_changed {
+                            self.config.connect_timeout_secs = self.connect_timeout_secs;
+                            self.config.read_timeout_secs = self.read_timeout_secs;
+                            self.config.save();
+                            // Also remember them for the current provider
+                            if let Some(idx) = self.find_current_playlist_idx() {
+                                self.playlist_entries[idx].connect_timeout_secs = self.connect_timeout_secs;
+                                self.playlist_entries[idx].read_timeout_secs = self.read_timeout_secs;
+                                save_playlist_entries(&self.playlist_entries);
+                            }
+                        }
+                        
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                             if ui.button(egui::RichText::new("⚠ Reset All").color(egui::Color32::from_rgb(200, 80, 80)))
                                 .on_hover_text("Clear all settings, playlists, favorites")
@@ -3843,6 +7176,73 @@ impl eframe::App for IPTVApp {
                             }
                         });
                     });
+                    
+                    ui.horizontal(|ui| {
+                        ui.label("🌐 Network:");
+                        let ipv4_changed = ui.checkbox(&mut self.force_ipv4, "IPv4 only")
+                            .on_hover_text("Never connect to the provider over IPv6")
+                            .changed();
+                        ui.label("IP override:");
+                        let override_valid = self.ip_override.trim().is_empty()
+                            || self.ip_override.trim().parse::<std::net::IpAddr>().is_ok();
+                        let mut override_edit = egui::TextEdit::singleline(&mut self.ip_override)
+                            .hint_text("system DNS")
+                            .desired_width(120.0);
+                        if !override_valid {
+                            override_edit = override_edit.text_color(egui::Color32::from_rgb(220, 80, 80));
+                        }
+                        let override_changed = ui.add(override_edit)
+                            .on_hover_text("Connect to this IP address instead of the provider's DNS answer.\nThe Host header keeps the provider's name.\nApplies to API and playlist requests, not playback.")
+                            .changed();
+                        if ipv4_changed || override_changed {
+                            self.config.force_ipv4 = self.force_ipv4;
+                            self.config.ip_override = self.ip_override.clone();
+                            self.config.save();
+                            // Also remember them for the current provider
+                            if let Some(idx) = self.find_current_playlist_idx() {
+                                self.playlist_entries[idx].force_ipv4 = self.force_ipv4;
+                                self.playlist_entries[idx].ip_override = self.ip_override.clone();
+                                save_playlist_entries(&self.playlist_entries);
+                            }
+                        }
+                    });
+                    
+                    ui.horizontal(|ui| {
+                        ui.label("🖼 Logo fallback:");
+                        let template_changed = ui.add(egui::TextEdit::singleline(&mut self.logo_url_template)
+                            .hint_text("{server}/logo/{stream_id}.png")
+                            .desired_width(260.0))
+                            .on_hover_text("Logo URL used when a channel has no icon.\n\nPlaceholders:\n• {server} - provider URL\n• {stream_id} - stream id\n• {epg_id} - EPG channel id\n• {name} - channel name\n\nApplies to channels loaded after the change.")
+                            .changed();
+                        if template_changed {
+                            self.config.logo_url_template = self.logo_url_template.clone();
+                            self.save_config_soon();
+                            if let Some(idx) = self.find_current_playlist_idx() {
+                                self.playlist_entries[idx].logo_url_template = self.logo_url_template.clone();
+                                self.save_playlists_soon();
+                            }
+                        }
+                    });
+                    
+                    ui.horizontal(|ui| {
+                        ui.label("🛠 Troubleshooting:");
+                        let config_dir = config_dir();
+                        if ui.button("📂 Config folder")
+                            .on_hover_text(format!("Settings, playlists and address book\n{}", config_dir.display()))
+                            .clicked()
+                        {
+                            self.open_folder(&config_dir);
+                        }
+                        let cache_dir = epg_cache_dir();
+                        if ui.button("📂 Cache folder")
+                            .on_hover_text(format!("Cached EPG data\n{}", cache_dir.display()))
+                            .clicked()
+                        {
+                            // Not there until the first EPG download
+                            let _ = std::fs::create_dir_all(&cache_dir);
+                            self.open_folder(&cache_dir);
+                        }
+                    });
                 });
         }
 
@@ -3888,7 +7288,54 @@ impl eframe::App for IPTVApp {
                 });
         }
 
+        // Merge Duplicate Favorites Confirmation Dialog
+        if self.show_merge_favorites_confirm {
+            let duplicates = duplicate_favorites(&self.favorites);
+            egui::Window::new("🧹 Merge Duplicate Favorites")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(egui::RichText::new(format!(
+                        "These {} favorites have a newer copy and will be removed:",
+                        duplicates.len()
+                    )).strong());
+                    ui.add_space(6.0);
+                    egui::ScrollArea::vertical().max_height(240.0).show(ui, |ui| {
+                        for &i in &duplicates {
+                            let fav = &self.favorites[i];
+                            ui.label(format!("  • {}", Self::sanitize_text(&fav.name)))
+                                .on_hover_text(&fav.url);
+                        }
+                    });
+                    ui.add_space(10.0);
+                    
+                    ui.horizontal(|ui| {
+                        if ui.button("Cancel").clicked() {
+                            self.show_merge_favorites_confirm = false;
+                        }
+                        
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui.button(egui::RichText::new(format!("Remove {}", duplicates.len())).color(egui::Color32::from_rgb(200, 80, 80))).clicked() {
+                                self.merge_duplicate_favorites();
+                                self.show_merge_favorites_confirm = false;
+                            }
+                        });
+                    });
+                });
+        }
+
         // User Agent Dialog
+        if self.show_settings_dialog {
+            self.show_settings_window(ctx);
+        }
+        
+        if self.show_my_shows {
+            self.show_my_shows_window(ctx);
+        }
+        
+        self.show_hls_quality_window(ctx);
+        
         if self.show_user_agent_dialog {
             egui::Window::new("🌐 User Agent Settings")
                 .collapsible(false)
@@ -3898,16 +7345,34 @@ impl eframe::App for IPTVApp {
                     ui.heading("Select User Agent");
                     ui.separator();
                     
+                    // Separate user agent for playback
+                    ui.checkbox(&mut self.separate_player_user_agent, "Use a different User Agent for playback");
+                    if self.separate_player_user_agent {
+                        ui.horizontal(|ui| {
+                            ui.label("Editing:");
+                            ui.selectable_value(&mut self.ua_dialog_editing_player, false, "🔌 API");
+                            ui.selectable_value(&mut self.ua_dialog_editing_player, true, "🎬 Player");
+                        });
+                    } else {
+                        self.ua_dialog_editing_player = false;
+                    }
+                    
+                    let (selected, custom, use_custom) = if self.ua_dialog_editing_player {
+                        (&mut self.player_selected_user_agent, &mut self.player_custom_user_agent, &mut self.player_use_custom_user_agent)
+                    } else {
+                        (&mut self.selected_user_agent, &mut self.custom_user_agent, &mut self.use_custom_user_agent)
+                    };
+                    
                     // Preset user agents
                     ui.label("Preset User Agents:");
                     egui::ScrollArea::vertical()
                         .max_height(200.0)
                         .show(ui, |ui| {
                             for (i, (name, _ua)) in USER_AGENTS.iter().enumerate() {
-                                let is_selected = !self.use_custom_user_agent && self.selected_user_agent == i;
+                                let is_selected = !*use_custom && *selected == i;
                                 if ui.selectable_label(is_selected, *name).clicked() {
-                                    self.selected_user_agent = i;
-                                    self.use_custom_user_agent = false;
+                                    *selected = i;
+                                    *use_custom = false;
                                 }
                             }
                         });
@@ -3915,10 +7380,10 @@ impl eframe::App for IPTVApp {
                     ui.separator();
                     
                     // Custom user agent
-                    ui.checkbox(&mut self.use_custom_user_agent, "Use custom User Agent");
+                    ui.checkbox(use_custom, "Use custom User Agent");
                     
-                    if self.use_custom_user_agent {
-                        ui.add(egui::TextEdit::multiline(&mut self.custom_user_agent)
+                    if *use_custom {
+                        ui.add(egui::TextEdit::multiline(custom)
                             .hint_text("Enter custom user agent string...")
                             .desired_width(f32::INFINITY)
                             .desired_rows(2));
@@ -3933,13 +7398,22 @@ impl eframe::App for IPTVApp {
                     ui.separator();
                     
                     // Current user agent display
-                    ui.label("Current User Agent:");
+                    ui.label(if self.separate_player_user_agent { "Current API User Agent:" } else { "Current User Agent:" });
                     let current_ua = self.get_user_agent();
                     ui.add(egui::TextEdit::multiline(&mut current_ua.clone())
                         .desired_width(f32::INFINITY)
                         .desired_rows(2)
                         .interactive(false));
                     
+                    if self.separate_player_user_agent {
+                        ui.label("Current Player User Agent:");
+                        let player_ua = self.get_player_user_agent();
+                        ui.add(egui::TextEdit::multiline(&mut player_ua.clone())
+                            .desired_width(f32::INFINITY)
+                            .desired_rows(2)
+                            .interactive(false));
+                    }
+                    
                     ui.separator();
                     
                     ui.horizontal(|ui| {
@@ -3949,6 +7423,10 @@ impl eframe::App for IPTVApp {
                             self.config.custom_user_agent = self.custom_user_agent.clone();
                             self.config.use_custom_user_agent = self.use_custom_user_agent;
                             self.config.pass_user_agent_to_player = self.pass_user_agent_to_player;
+                            self.config.separate_player_user_agent = self.separate_player_user_agent;
+                            self.config.player_selected_user_agent = self.player_selected_user_agent;
+                            self.config.player_custom_user_agent = self.player_custom_user_agent.clone();
+                            self.config.player_use_custom_user_agent = self.player_use_custom_user_agent;
                             self.config.save();
                             self.show_user_agent_dialog = false;
                         }
@@ -3958,12 +7436,104 @@ impl eframe::App for IPTVApp {
                             self.custom_user_agent = self.config.custom_user_agent.clone();
                             self.use_custom_user_agent = self.config.use_custom_user_agent;
                             self.pass_user_agent_to_player = self.config.pass_user_agent_to_player;
+                            self.separate_player_user_agent = self.config.separate_player_user_agent;
+                            self.player_selected_user_agent = self.config.player_selected_user_agent;
+                            self.player_custom_user_agent = self.config.player_custom_user_agent.clone();
+                            self.player_use_custom_user_agent = self.config.player_use_custom_user_agent;
                             self.show_user_agent_dialog = false;
                         }
                     });
                 });
         }
         
+        // Rename channel dialog
+        if let Some((url, original, mut input)) = self.alias_edit.take() {
+            let mut open = true;
+            let mut cancelled = false;
+            let mut action: Option<bool> = None; // Some(true) = save, Some(false) = reset
+            egui::Window::new("✏ Rename Channel")
+                .collapsible(false)
+                .resizable(false)
+                .open(&mut open)
+                .show(ctx, |ui| {
+                    ui.label(egui::RichText::new(format!("Original: {}", Self::sanitize_text(&original))).weak());
+                    let response = ui.add(egui::TextEdit::singleline(&mut input)
+                        .hint_text("Display name")
+                        .desired_width(300.0));
+                    if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                        action = Some(true);
+                    }
+                    ui.label(egui::RichText::new("Only changes the name shown here - EPG still matches the original").small().weak());
+                    ui.horizontal(|ui| {
+                        if ui.button("💾 Save").clicked() {
+                            action = Some(true);
+                        }
+                        if ui.button("↺ Reset").on_hover_text("Use the provider's name").clicked() {
+                            action = Some(false);
+                        }
+                        if ui.button("Cancel").clicked() {
+                            cancelled = true;
+                        }
+                    });
+                });
+            
+            match action {
+                Some(true) => self.set_channel_alias(&url, &original, &input),
+                Some(false) => self.set_channel_alias(&url, &original, ""),
+                None if open && !cancelled => self.alias_edit = Some((url, original, input)),
+                None => {}
+            }
+        }
+        
+        // Stream info (ffprobe) popup
+        if let Some((name, _, result)) = &self.stream_probe {
+            let mut open = true;
+            egui::Window::new("🔍 Stream Info")
+                .collapsible(false)
+                .resizable(false)
+                .open(&mut open)
+                .show(ctx, |ui| {
+                    ui.label(egui::RichText::new(Self::sanitize_text(name)).strong());
+                    ui.separator();
+                    match result {
+                        None => {
+                            ui.horizontal(|ui| {
+                                ui.spinner();
+                                ui.label("Probing stream...");
+                            });
+                        }
+                        Some(Err(e)) => {
+                            ui.colored_label(egui::Color32::from_rgb(255, 100, 100), e);
+                        }
+                        Some(Ok(info)) => {
+                            let unknown = || "-".to_string();
+                            egui::Grid::new("stream_probe_grid").num_columns(2).spacing([12.0, 4.0]).show(ui, |ui| {
+                                ui.label("Video:");
+                                ui.label(info.video_codec.clone().unwrap_or_else(unknown));
+                                ui.end_row();
+                                ui.label("Resolution:");
+                                ui.label(info.resolution().unwrap_or_else(unknown));
+                                ui.end_row();
+                                ui.label("Frame rate:");
+                                ui.label(info.fps.map(|f| format!("{:.2} fps", f)).unwrap_or_else(unknown));
+                                ui.end_row();
+                                ui.label("Audio:");
+                                let audio = match (&info.audio_codec, info.audio_channels) {
+                                    (Some(codec), Some(ch)) => format!("{} ({} ch)", codec, ch),
+                                    (Some(codec), None) => codec.clone(),
+                                    _ => unknown(),
+                                };
+                                ui.label(audio);
+                                ui.end_row();
+                            });
+                        }
+                    }
+                });
+            if !open {
+                self.stream_probe = None;
+            }
+        }
+        
         // EPG Dialog Window
         if self.show_epg_dialog {
             egui::Window::new("📺 EPG - Electronic Program Guide")
@@ -3976,9 +7546,11 @@ impl eframe::App for IPTVApp {
                     
                     ui.label("Enter XMLTV EPG URL:");
                     ui.horizontal(|ui| {
-                        ui.add(egui::TextEdit::singleline(&mut self.epg_url_input)
+                        ui.add(egui::TextEdit::multiline(&mut self.epg_url_input)
                             .hint_text("http://provider.com/xmltv.php?username=...&password=...")
-                            .desired_width(350.0));
+                            .desired_rows(1)
+                            .desired_width(350.0))
+                            .on_hover_text("One URL or file per line (or comma-separated) to merge several guides.\nWhen two guides cover the same channel, the one with more programs is used.");
                         
                         let button_text = if self.epg_loading { "⏳" } else { "📥" };
                         if ui.button(button_text)
@@ -4038,116 +7610,1151 @@ impl eframe::App for IPTVApp {
                         if ui.button("−").clicked() {
                             self.epg_time_offset = (self.epg_time_offset - 0.5).max(-60.0);
                         }
-                        ui.add(egui::Slider::new(&mut self.epg_time_offset, -60.0..=60.0)
-                            .step_by(0.5)
-                            .show_value(false)
-                            .trailing_fill(true));
-                        if ui.button("+").clicked() {
-                            self.epg_time_offset = (self.epg_time_offset + 0.5).min(60.0);
+                        ui.add(egui::Slider::new(&mut self.epg_time_offset, -60.0..=60.0)
+                            .step_by(0.5)
+                            .show_value(false)
+                            .trailing_fill(true));
+                        if ui.button("+").clicked() {
+                            self.epg_time_offset = (self.epg_time_offset + 0.5).min(60.0);
+                        }
+                        let sign = if self.epg_time_offset >= 0.0 { "+" } else { "" };
+                        ui.label(format!("{}{:.1} hours", sign, self.epg_time_offset));
+                        if self.epg_time_offset != 0.0 {
+                            if ui.small_button("Reset").clicked() {
+                                self.epg_time_offset = 0.0;
+                            }
+                        }
+                    });
+                    
+                    // EPG Grid display mode
+                    ui.horizontal(|ui| {
+                        ui.label("Grid Header:");
+                        ui.selectable_value(&mut self.epg_show_actual_time, false, "Offset (Now, +30m...)")
+                            .on_hover_text("Show relative time offsets");
+                        ui.selectable_value(&mut self.epg_show_actual_time, true, "Time (8:00, 8:30...)")
+                            .on_hover_text("Show actual times");
+                    });
+                    
+                    // EPG Grid time window
+                    ui.horizontal(|ui| {
+                        let mut changed = false;
+                        ui.label("Grid Window:");
+                        ui.add_enabled_ui(!self.epg_grid_full_day, |ui| {
+                            egui::ComboBox::from_id_salt("epg_grid_window")
+                                .selected_text(format!("{} hours", self.epg_grid_window_hours))
+                                .show_ui(ui, |ui| {
+                                    for hours in EPG_GRID_WINDOW_CHOICES {
+                                        changed |= ui.selectable_value(&mut self.epg_grid_window_hours, hours, format!("{} hours", hours)).changed();
+                                    }
+                                }).response.on_hover_text("How much time the guide grid shows at once");
+                        });
+                        ui.label("Step:");
+                        egui::ComboBox::from_id_salt("epg_grid_step")
+                            .selected_text(format!("{} min", self.epg_grid_step_mins))
+                            .show_ui(ui, |ui| {
+                                for mins in EPG_GRID_STEP_CHOICES {
+                                    changed |= ui.selectable_value(&mut self.epg_grid_step_mins, mins, format!("{} min", mins)).changed();
+                                }
+                            }).response.on_hover_text("Spacing of the time markers and of the ◀/▶ buttons");
+                        changed |= ui.checkbox(&mut self.epg_grid_full_day, "Full day")
+                            .on_hover_text("Lay out the whole day at a fixed scale and scroll through it (drag, Shift+wheel or the day bar below the grid)")
+                            .changed();
+                        if changed {
+                            self.epg_grid_offset_secs = 0;
+                            self.config.epg_grid_window_hours = self.epg_grid_window_hours;
+                            self.config.epg_grid_step_mins = self.epg_grid_step_mins;
+                            self.config.epg_grid_full_day = self.epg_grid_full_day;
+                            self.config.save();
+                        }
+                    });
+                    
+                    if ui.checkbox(&mut self.epg_show_next_inline, "Show next program in channel lists")
+                        .on_hover_text("Append \"→ Next: <title>\" after the current program (uses the Grid Header time format)")
+                        .changed()
+                    {
+                        self.config.epg_show_next_inline = self.epg_show_next_inline;
+                        self.config.save();
+                    }
+                    
+                    if !self.epg_status.is_empty() {
+                        ui.separator();
+                        ui.horizontal(|ui| {
+                            if self.epg_loading {
+                                ui.spinner();
+                            }
+                            let color = if self.epg_status.starts_with("Error") {
+                                egui::Color32::RED
+                            } else if self.epg_status.starts_with("Loaded") {
+                                egui::Color32::GREEN
+                            } else {
+                                egui::Color32::YELLOW
+                            };
+                            ui.label(egui::RichText::new(&self.epg_status).color(color));
+                        });
+                    }
+                    
+                    if let Some(ref epg) = self.epg_data {
+                        ui.separator();
+                        ui.heading("EPG Statistics");
+                        
+                        egui::Grid::new("epg_stats")
+                            .num_columns(2)
+                            .spacing([20.0, 4.0])
+                            .show(ui, |ui| {
+                                ui.label("Channels:");
+                                ui.label(format!("{}", epg.channels.len()));
+                                ui.end_row();
+                                
+                                ui.label("Programs:");
+                                ui.label(format!("{}", epg.program_count()));
+                                ui.end_row();
+                            });
+                        
+                        // What each merged source contributed
+                        if !epg.sources.is_empty() {
+                            ui.add_space(4.0);
+                            egui::Grid::new("epg_source_stats")
+                                .num_columns(3)
+                                .spacing([20.0, 4.0])
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    ui.label(egui::RichText::new("Source").strong());
+                                    ui.label(egui::RichText::new("Channels").strong());
+                                    ui.label(egui::RichText::new("Programs").strong());
+                                    ui.end_row();
+                                    for source in &epg.sources {
+                                        ui.label(Self::truncate_to_width(&source.source, 240.0)).on_hover_text(&source.source);
+                                        match &source.error {
+                                            Some(error) => {
+                                                ui.label(egui::RichText::new("❌ Failed").color(egui::Color32::RED))
+                                                    .on_hover_text(error);
+                                                ui.label("");
+                                            }
+                                            None => {
+                                                ui.label(source.channels.to_string());
+                                                ui.label(source.programs.to_string());
+                                            }
+                                        }
+                                        ui.end_row();
+                                    }
+                                });
+                        }
+                        
+                        // Parse warnings (malformed programmes/channels recovered during parsing)
+                        if epg.parse_error_count > 0 {
+                            let truncated = epg.parse_error_count.saturating_sub(epg.parse_errors.len());
+                            egui::CollapsingHeader::new(
+                                egui::RichText::new(format!("⚠ Parse warnings ({})", epg.parse_error_count))
+                                    .color(egui::Color32::YELLOW)
+                            )
+                                .id_salt("epg_parse_warnings")
+                                .show(ui, |ui| {
+                                    egui::ScrollArea::vertical()
+                                        .max_height(150.0)
+                                        .show(ui, |ui| {
+                                            for err in &epg.parse_errors {
+                                                ui.label(egui::RichText::new(err).monospace().small());
+                                            }
+                                        });
+                                    if truncated > 0 {
+                                        ui.label(egui::RichText::new(format!(
+                                            "... and {} more (only the first {} are kept)",
+                                            truncated,
+                                            epg.parse_errors.len()
+                                        )).weak().small());
+                                    }
+                                });
+                        }
+                    }
+                    
+                    if self.epg_data.is_some() {
+                        self.show_epg_coverage(ui);
+                    }
+                    
+                    // On-disk cache for the current provider
+                    if !self.server.is_empty() && !self.username.is_empty() {
+                        ui.separator();
+                        let cache_path = epg_cache_file(&self.server, &self.username);
+                        let cache_age = epg_cache_age_secs(&self.server, &self.username);
+                        ui.horizontal(|ui| {
+                            ui.label("💾 Cache:");
+                            match cache_age {
+                                Some(age) => {
+                                    let age_str = if age < 3600 {
+                                        format!("{}m old", age / 60)
+                                    } else if age < 86400 {
+                                        format!("{}h old", age / 3600)
+                                    } else {
+                                        format!("{}d old", age / 86400)
+                                    };
+                                    ui.label(age_str);
+                                    if ui.small_button("🗑 Delete cache")
+                                        .on_hover_text("Delete the cached EPG for this provider (loaded data stays until cleared)")
+                                        .clicked()
+                                    {
+                                        if clear_epg_cache(&self.server, &self.username) {
+                                            self.epg_status = "EPG cache deleted".to_string();
+                                            self.log(&format!("[INFO] Deleted EPG cache: {}", cache_path.display()));
+                                        } else {
+                                            self.epg_status = "Failed to delete EPG cache".to_string();
+                                        }
+                                    }
+                                }
+                                None => {
+                                    ui.label(egui::RichText::new("none").weak());
+                                }
+                            }
+                        });
+                        ui.add(egui::Label::new(egui::RichText::new(cache_path.display().to_string()).small().weak())
+                            .wrap());
+                    }
+                    
+                    ui.separator();
+                    
+                    // Close on left, Clear EPG Data on right - same row
+                    ui.horizontal(|ui| {
+                        if ui.button("Close").clicked() {
+                            self.show_epg_dialog = false;
+                        }
+                        
+                        if self.epg_data.is_some() {
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                if ui.button("🗑 Clear EPG Data").clicked() {
+                                    self.epg_data = None;
+                                    self.epg_last_update = None;
+                                    self.epg_status = "EPG data cleared".to_string();
+                                    self.log("[INFO] EPG data cleared");
+                                }
+                            });
+                        }
+                    });
+                });
+        }
+        
+        // Internal Player Window
+        if self.show_internal_player {
+            let mut open = self.show_internal_player;
+            
+            if self.internal_player_viewport {
+                // Separate OS window (can be moved to another monitor), repainted on its own
+                // so a playing video doesn't redraw the whole main UI every frame
+                let title = {
+                    let player = self.player_window();
+                    let name = player.player.channel_name();
+                    if name.is_empty() {
+                        "🎬 Internal Player".to_string()
+                    } else {
+                        format!("🎬 {}", Self::sanitize_text(name))
+                    }
+                };
+                let player = self.internal_player.clone();
+                let closed = self.internal_player_closed.clone();
+                ctx.show_viewport_deferred(
+                    egui::ViewportId::from_hash_of("internal_player"),
+                    egui::ViewportBuilder::default()
+                        .with_title(title)
+                        .with_inner_size([860.0, 540.0])
+                        .with_min_inner_size([320.0, 200.0])
+                        .with_window_level(self.window_level()),
+                    move |ctx, class| {
+                        let mut player = player.lock().unwrap_or_else(|e| e.into_inner());
+                        if class == egui::ViewportClass::Embedded {
+                            // Backend doesn't support multiple viewports - fall back to in-app window
+                            let mut open = true;
+                            egui::Window::new("🎬 Internal Player")
+                                .open(&mut open)
+                                .resizable(true)
+                                .default_size([860.0, 540.0])
+                                .show(ctx, |ui| {
+                                    player.show(ctx, ui);
+                                });
+                            if !open {
+                                closed.store(true, std::sync::atomic::Ordering::Relaxed);
+                            }
+                            return;
+                        }
+                        
+                        egui::CentralPanel::default().show(ctx, |ui| {
+                            player.show(ctx, ui);
+                        });
+                        
+                        if ctx.input(|i| i.viewport().close_requested()) {
+                            closed.store(true, std::sync::atomic::Ordering::Relaxed);
+                        }
+                    },
+                );
+                if self.internal_player_closed.swap(false, std::sync::atomic::Ordering::Relaxed) {
+                    open = false;
+                }
+            } else {
+                egui::Window::new("🎬 Internal Player")
+                    .open(&mut open)
+                    .resizable(true)
+                    .default_size([860.0, 540.0])
+                    .show(ctx, |ui| {
+                        self.player_window().show(ctx, ui);
+                    });
+            }
+            
+            if !open {
+                self.show_internal_player = false;
+                self.player_window().stop();
+            }
+        }
+        
+        // Movie/Series Detail Window
+        if self.show_content_detail {
+            let mut open = self.show_content_detail;
+            let mut to_play: Option<Channel> = None;
+            egui::Window::new("🎬 Details")
+                .open(&mut open)
+                .resizable(true)
+                .collapsible(false)
+                .default_size([620.0, 380.0])
+                .show(ctx, |ui| {
+                    let Some(detail) = self.content_detail.clone() else {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label("Loading details...");
+                        });
+                        return;
+                    };
+                    
+                    ui.horizontal_top(|ui| {
+                        // Poster (falls back to a placeholder when missing or failed)
+                        let poster_size = egui::vec2(200.0, 300.0);
+                        let state = match &detail.poster_url {
+                            Some(url) => self.image_cache.get(ctx, url),
+                            None => ImageState::Failed,
+                        };
+                        match state {
+                            ImageState::Ready(texture) => {
+                                ui.add(egui::Image::new(&texture)
+                                    .max_size(poster_size)
+                                    .corner_radius(4.0));
+                            }
+                            ImageState::Loading => {
+                                ui.allocate_ui(poster_size, |ui| {
+                                    ui.centered_and_justified(|ui| {
+                                        ui.spinner();
+                                    });
+                                });
+                            }
+                            ImageState::Failed => {
+                                ui.allocate_ui(poster_size, |ui| {
+                                    ui.centered_and_justified(|ui| {
+                                        ui.label(egui::RichText::new(
+                                            if detail.stream_type == "series" { "📺" } else { "🎬" }
+                                        ).size(64.0).color(egui::Color32::GRAY));
+                                    });
+                                });
+                            }
+                        }
+                        
+                        ui.vertical(|ui| {
+                            ui.heading(Self::sanitize_text(&detail.name));
+                            ui.add_space(4.0);
+                            
+                            egui::Grid::new("content_detail_grid")
+                                .num_columns(2)
+                                .spacing([10.0, 4.0])
+                                .show(ui, |ui| {
+                                    let fields = [
+                                        ("Genre:", &detail.genre),
+                                        ("Rating:", &detail.rating),
+                                        ("Duration:", &detail.duration),
+                                        ("Released:", &detail.release_date),
+                                        ("Director:", &detail.director),
+                                        ("Cast:", &detail.cast),
+                                    ];
+                                    for (label, value) in fields {
+                                        if let Some(value) = value {
+                                            ui.label(egui::RichText::new(label).strong());
+                                            ui.label(value);
+                                            ui.end_row();
+                                        }
+                                    }
+                                });
+                            
+                            ui.add_space(6.0);
+                            egui::ScrollArea::vertical()
+                                .max_height(180.0)
+                                .show(ui, |ui| {
+                                    ui.label(detail.plot.as_deref().unwrap_or("No description available."));
+                                });
+                            
+                            if let Some(ref channel) = self.content_detail_channel {
+                                ui.add_space(6.0);
+                                if ui.button("▶ Play").clicked() {
+                                    to_play = Some(channel.clone());
+                                }
+                            }
+                        });
+                    });
+                });
+            
+            if let Some(channel) = to_play {
+                self.play_channel(&channel);
+            }
+            
+            if !open {
+                self.show_content_detail = false;
+                self.content_detail = None;
+                self.content_detail_channel = None;
+            }
+        }
+    }
+}
+
+impl IPTVApp {
+    fn show_live_tab(&mut self, ui: &mut egui::Ui) {
+        self.show_category_tab(ui, "live");
+    }
+
+    fn show_movies_tab(&mut self, ui: &mut egui::Ui) {
+        self.show_continue_watching(ui, "movie");
+        self.show_category_tab(ui, "movie");
+    }
+    
+    /// "Continue Watching" band above the top level of the Movies/Series tabs,
+    /// built from the watch history (`stream_type` is "movie" or "series" for episodes)
+    fn show_continue_watching(&mut self, ui: &mut egui::Ui, stream_type: &str) {
+        if !matches!(self.navigation_stack.last(), None | Some(NavigationLevel::Categories)) {
+            return;
+        }
+        let items: Vec<FavoriteItem> = self.recent_watched.iter()
+            .filter(|r| r.stream_type == stream_type)
+            .take(CONTINUE_WATCHING_LIMIT)
+            .cloned()
+            .collect();
+        if items.is_empty() {
+            return;
+        }
+        
+        let mut to_play: Option<FavoriteItem> = None;
+        egui::CollapsingHeader::new(format!("▶ Continue Watching ({})", items.len()))
+            .id_salt(("continue_watching", stream_type))
+            .default_open(true)
+            .show(ui, |ui| {
+                egui::ScrollArea::horizontal()
+                    .id_salt(("continue_watching_scroll", stream_type))
+                    .show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            for item in &items {
+                                let name = self.display_name(&item.url, &item.name);
+                                let mut label = format!("▶ {}", Self::truncate_to_width(&name, 160.0));
+                                if let Some(secs) = item.resume_secs {
+                                    label.push_str(&format!(" ({})", mpv_ipc::format_position(secs)));
+                                }
+                                ui.vertical(|ui| {
+                                    let response = ui.button(label)
+                                        .on_hover_text(format!("{}\n{}", name, Self::sanitize_text(&item.category_name)));
+                                    if response.clicked() {
+                                        to_play = Some(item.clone());
+                                    }
+                                    // How far in - empty until a player reported the length
+                                    let progress = match (item.resume_secs, item.duration_secs) {
+                                        (Some(pos), Some(total)) if total > 0 => (pos as f32 / total as f32).clamp(0.0, 1.0),
+                                        _ => 0.0,
+                                    };
+                                    ui.add(egui::ProgressBar::new(progress)
+                                        .desired_width(response.rect.width())
+                                        .desired_height(4.0));
+                                });
+                            }
+                        });
+                    });
+            });
+        ui.separator();
+        
+        if let Some(item) = to_play {
+            // History entries keep the full stream URL, so play it directly
+            let channel = Channel {
+                name: item.name.clone(),
+                url: item.url.clone(),
+                stream_id: item.stream_id,
+                category_id: None,
+                epg_channel_id: None,
+                stream_icon: None,
+                series_id: item.series_id,
+                container_extension: item.container_extension.clone(),
+                playlist_source: item.playlist_source.clone(),
+                catchup: None,
+                catchup_source: None,
+                tvg_shift: None,
+                alternate_urls: Vec::new(),
+                archive_days: None,
+                genre: None,
+                user_agent: None,
+                referrer: None,
+            };
+            self.play_channel(&channel);
+        }
+    }
+
+    /// Distinct genres across a list (providers send comma-separated genre strings)
+    fn collect_genres<'a>(genres: impl Iterator<Item = Option<&'a str>>) -> BTreeSet<String> {
+        genres.flatten()
+            .flat_map(split_genres)
+            .map(str::to_string)
+            .collect()
+    }
+    
+    /// Whether a channel passes the quality filter - names without a quality tag always do
+    fn quality_matches(name: &str, filter: Option<Quality>) -> bool {
+        let Some(filter) = filter else { return true };
+        let quality = Quality::from_name(name);
+        quality == Quality::Unknown || quality == filter
+    }
+    
+    /// Whether an item's genre string contains the selected genre (no filter matches everything)
+    fn genre_matches(genre: Option<&str>, filter: Option<&str>) -> bool {
+        let Some(filter) = filter else { return true };
+        genre.is_some_and(|g| split_genres(g).any(|g| g.eq_ignore_ascii_case(filter)))
+    }
+    
+    /// Less frequently changed settings, grouped by area
+    fn show_settings_window(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+        egui::Window::new("⚙ Settings")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .min_width(420.0)
+            .show(ctx, |ui| {
+                ui.heading("🎬 Playback");
+                ui.separator();
+                
+                ui.horizontal(|ui| {
+                    ui.label("Player args:");
+                    ui.add(egui::TextEdit::singleline(&mut self.player_extra_args)
+                        .hint_text("extra args...")
+                        .desired_width(260.0))
+                        .on_hover_text("Extra arguments appended to the player command.\nQuote arguments containing spaces.\n\nPlaceholders:\n• {url} - stream URL\n• {title} - channel name\n\nExample: --sub-file=\"/path/to subs.srt\" --deinterlace=yes");
+                });
+                
+                ui.horizontal(|ui| {
+                    ui.label("Audio:");
+                    let audio_changed = ui.add(egui::TextEdit::singleline(&mut self.audio_language)
+                        .hint_text("eng,en")
+                        .desired_width(80.0))
+                        .on_hover_text("Preferred audio languages for this playlist (ISO codes, comma-separated)\n\nPassed to mpv (--alang), VLC (--audio-language) and ffplay (-ast, first code only).\nLeave empty for the player's default track.")
+                        .changed();
+                    ui.label("Subtitles:");
+                    let subtitle_changed = ui.add(egui::TextEdit::singleline(&mut self.subtitle_language)
+                        .hint_text("eng")
+                        .desired_width(80.0))
+                        .on_hover_text("Preferred subtitle languages for this playlist (ISO codes, comma-separated)\n\nPassed to mpv (--slang), VLC (--sub-language) and ffplay (-sst, first code only).\nLeave empty for the player's default.")
+                        .changed();
+                    if audio_changed || subtitle_changed {
+                        self.config.audio_language = self.audio_language.clone();
+                        self.config.subtitle_language = self.subtitle_language.clone();
+                        self.save_config_soon();
+                        if let Some(idx) = self.find_current_playlist_idx() {
+                            self.playlist_entries[idx].audio_language = self.audio_language.clone();
+                            self.playlist_entries[idx].subtitle_language = self.subtitle_language.clone();
+                            self.save_playlists_soon();
+                        }
+                    }
+                });
+                
+                ui.horizontal(|ui| {
+                    ui.label("Recordings:");
+                    if self.recording_dir.is_empty() {
+                        ui.label(egui::RichText::new("Asked on first ⏺").weak());
+                    } else {
+                        ui.label(egui::RichText::new(&self.recording_dir).monospace());
+                    }
+                    if ui.button("📂 Choose...").on_hover_text("Folder ⏺ saves ffmpeg recordings (.ts) to").clicked() {
+                        self.choose_recording_dir();
+                    }
+                });
+                
+                ui.checkbox(&mut self.hw_accel, "HW Acceleration")
+                    .on_hover_text("GPU Decoding\n\nEnable GPU hardware acceleration for video decoding\nDisable if you experience playback issues");
+                
+                ui.checkbox(&mut self.single_window_mode, "Single Window")
+                    .on_hover_text("Close previous player when opening new stream");
+                
+                if ui.checkbox(&mut self.live_hls, "HLS for live streams")
+                    .on_hover_text("Request live streams as HLS (.m3u8) instead of MPEG-TS (.ts)\n\nTry this if a stream stutters or won't open in your player.\nApplies to channels loaded after the change and to favorites.")
+                    .changed()
+                {
+                    self.config.live_hls = self.live_hls;
+                    self.config.save();
+                    if let Some(idx) = self.find_current_playlist_idx() {
+                        self.playlist_entries[idx].live_hls = self.live_hls;
+                        save_playlist_entries(&self.playlist_entries);
+                    }
+                }
+                
+                if ui.checkbox(&mut self.choose_hls_quality, "Choose HLS quality")
+                    .on_hover_text("Before playing an .m3u8 stream, check whether it offers several bitrates\nand ask which one to play instead of leaving it to the player")
+                    .changed()
+                {
+                    self.config.choose_hls_quality = self.choose_hls_quality;
+                    self.config.save();
+                }
+                
+                ui.add_space(8.0);
+                ui.heading("🎞 Internal Player");
+                ui.separator();
+                
+                if ui.checkbox(&mut self.internal_player_viewport, "🪟 Pop-out window")
+                    .on_hover_text("Open the internal player in its own window\n(can be moved to another monitor)")
+                    .changed()
+                {
+                    self.config.internal_player_viewport = self.internal_player_viewport;
+                    self.config.save();
+                }
+                ui.horizontal(|ui| {
+                    ui.label("🔁 Reconnect attempts:");
+                    if ui.add(egui::DragValue::new(&mut self.internal_player_max_retries).range(0..=10))
+                        .on_hover_text("Reconnect attempts when the stream fails (0 = off)")
+                        .changed()
+                    {
+                        self.config.internal_player_max_retries = self.internal_player_max_retries;
+                        self.config.save();
+                    }
+                });
+                if ui.checkbox(&mut self.autoplay_next_episode, "Autoplay next episode")
+                    .on_hover_text("When an episode ends in the internal player, start the next one
+(stops after the last episode of the season)")
+                    .changed()
+                {
+                    self.config.autoplay_next_episode = self.autoplay_next_episode;
+                    self.config.save();
+                }
+                
+                ui.add_space(8.0);
+                ui.heading("🎨 Appearance");
+                ui.separator();
+                
+                ui.checkbox(&mut self.dark_mode, "🌙 Dark mode");
+                
+                if ui.checkbox(&mut self.remember_sort_per_category, "⇅ Remember sort per category")
+                    .on_hover_text("Sorting chosen inside a category applies to that category only (saved per playlist).\nOther categories keep the tab's sort order.")
+                    .changed()
+                {
+                    self.config.remember_sort_per_category = self.remember_sort_per_category;
+                    self.config.save();
+                }
+                
+                if ui.checkbox(&mut self.show_container_labels, "🏷 Stream format labels")
+                    .on_hover_text("Show TS / HLS / MP4 / MKV next to channels and favorites, taken from the stream URL")
+                    .changed()
+                {
+                    self.config.show_container_labels = self.show_container_labels;
+                    self.config.save();
+                }
+                
+                if ui.checkbox(&mut self.show_channel_logos, "🖼 Channel logos")
+                    .on_hover_text("Show each channel's logo in lists. Logos are downloaded as rows scroll into view (not in low data mode).")
+                    .changed()
+                {
+                    self.config.show_channel_logos = self.show_channel_logos;
+                    self.config.save();
+                }
+                
+                if ui.checkbox(&mut self.always_on_top, "📌 Always on top")
+                    .on_hover_text("Keep this window (and the pop-out player) above other windows")
+                    .changed()
+                {
+                    self.config.always_on_top = self.always_on_top;
+                    self.config.save();
+                }
+                
+                if ui.button("🗗 Reset window layout")
+                    .on_hover_text("Forget the saved window size and position and return to the default size")
+                    .clicked()
+                {
+                    self.reset_window_layout(ui.ctx());
+                }
+                
+                ui.horizontal(|ui| {
+                    ui.label("Font Size:");
+                    egui::ComboBox::from_id_salt("font_size_selector")
+                        .selected_text(self.font_size_setting.label())
+                        .show_ui(ui, |ui| {
+                            for (size, label) in [
+                                (FontSize::Default, "Default (13px)"),
+                                (FontSize::Medium, "Medium (15px)"),
+                                (FontSize::Large, "Large (16px)"),
+                                (FontSize::XLarge, "X-Large (18px)"),
+                            ] {
+                                if ui.selectable_value(&mut self.font_size_setting, size, label).changed() {
+                                    self.config.font_size_setting = self.font_size_setting;
+                                    self.config.save();
+                                }
+                            }
+                        });
+                });
+                
+                ui.horizontal(|ui| {
+                    ui.label("UI Scale:");
+                    egui::ComboBox::from_id_salt("ui_scale_selector")
+                        .selected_text(self.ui_scale.label())
+                        .show_ui(ui, |ui| {
+                            for scale in UiScale::ALL {
+                                if ui.selectable_value(&mut self.ui_scale, scale, scale.label()).changed() {
+                                    self.config.ui_scale = self.ui_scale;
+                                    self.config.save();
+                                }
+                            }
+                        })
+                        .response
+                        .on_hover_text("Zoom the whole interface - text, buttons and spacing.\nUseful on 4K displays or when viewing from the couch.");
+                });
+                
+                ui.add_space(8.0);
+                ui.heading("🔌 Connection");
+                ui.separator();
+                
+                ui.checkbox(&mut self.save_state, "💾 Auto-Save")
+                    .on_hover_text("Auto-save logins to Playlist Manager");
+                
+                if ui.button("🌐 User Agent...").on_hover_text("Configure User Agent string sent to server").clicked() {
+                    self.show_user_agent_dialog = true;
+                }
+                
+                ui.add_space(8.0);
+                ui.heading("📡 LAN Playlist Server");
+                ui.separator();
+                
+                ui.horizontal(|ui| {
+                    let mut serve = self.serve_playlist;
+                    if ui.checkbox(&mut serve, "Serve loaded channels")
+                        .on_hover_text("Let TVs and phones on your network open the current channel list\n(hidden channels are left out; only private network addresses may connect)")
+                        .changed()
+                    {
+                        self.set_playlist_server(serve);
+                    }
+                    ui.label("Address:");
+                    ui.add_enabled(!self.serve_playlist, egui::TextEdit::singleline(&mut self.serve_playlist_addr)
+                        .desired_width(140.0))
+                        .on_hover_text("Bind address and port. Without a host (:8899) only this machine's LAN address is used;\n0.0.0.0:8899 listens on all interfaces");
+                });
+                if let Some(server) = &self.playlist_server {
+                    let url = server.playlist_url();
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(&url).monospace());
+                        if ui.small_button("📋").on_hover_text("Copy URL").clicked() {
+                            ui.ctx().copy_text(url.clone());
+                        }
+                    });
+                }
+                
+                ui.add_space(8.0);
+                ui.heading("🔒 App Lock");
+                ui.separator();
+                
+                if self.config.app_pin_hash.is_empty() {
+                    ui.label(egui::RichText::new("Ask for a PIN at startup before any playlist loads (4-12 digits)").weak());
+                    ui.horizontal(|ui| {
+                        ui.label("PIN:");
+                        ui.add(egui::TextEdit::singleline(&mut self.new_pin_input).password(true).desired_width(90.0));
+                        ui.label("Confirm:");
+                        ui.add(egui::TextEdit::singleline(&mut self.confirm_pin_input).password(true).desired_width(90.0));
+                        if ui.button("Set PIN").clicked() {
+                            let pin = self.new_pin_input.trim().to_string();
+                            if !app_lock::is_valid_pin(&pin) {
+                                self.status_message = "PIN must be 4-12 digits".to_string();
+                            } else if pin != self.confirm_pin_input.trim() {
+                                self.status_message = "PINs don't match".to_string();
+                            } else if let Some(hash) = app_lock::hash_pin(&pin) {
+                                self.config.app_pin_hash = hash;
+                                self.config.save();
+                                self.new_pin_input.clear();
+                                self.confirm_pin_input.clear();
+                                self.status_message = "PIN set - it will be asked for at startup".to_string();
+                                self.log("[INFO] App PIN lock enabled");
+                            }
+                        }
+                    });
+                } else {
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("✔ PIN required at startup").color(egui::Color32::GREEN));
+                        if ui.button("Remove PIN").clicked() {
+                            self.config.app_pin_hash.clear();
+                            self.config.save();
+                            self.status_message = "PIN lock removed".to_string();
+                            self.log("[INFO] App PIN lock disabled");
+                        }
+                    });
+                }
+                
+                ui.add_space(8.0);
+                ui.heading("🔐 Password Encryption");
+                ui.separator();
+                
+                if self.config.password_vault.is_empty() {
+                    ui.label(egui::RichText::new(format!("Encrypt saved Xtream passwords with a master passphrase, asked for at startup ({}+ characters)", vault::MIN_PASSPHRASE_LEN)).weak());
+                    ui.horizontal(|ui| {
+                        ui.label("Passphrase:");
+                        ui.add(egui::TextEdit::singleline(&mut self.new_vault_passphrase).password(true).desired_width(120.0));
+                        ui.label("Confirm:");
+                        ui.add(egui::TextEdit::singleline(&mut self.confirm_vault_passphrase).password(true).desired_width(120.0));
+                        if ui.button("Encrypt").clicked() {
+                            self.enable_password_encryption();
+                        }
+                    });
+                } else {
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("✔ Saved passwords are encrypted").color(egui::Color32::GREEN));
+                        if ui.button("Decrypt").on_hover_text("Store the passwords as plain text again").clicked() {
+                            self.disable_password_encryption();
+                        }
+                    });
+                }
+                
+                ui.add_space(8.0);
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("💾 Save").on_hover_text("Save current settings").clicked() {
+                        self.save_current_state();
+                    }
+                    if ui.button("Close").clicked() {
+                        self.show_settings_dialog = false;
+                    }
+                });
+            });
+        if !open {
+            self.show_settings_dialog = false;
+        }
+    }
+    
+    /// Encrypt saved passwords with the passphrase typed in Settings. Entries
+    /// saved before in plain text are re-written encrypted.
+    fn enable_password_encryption(&mut self) {
+        let passphrase = self.new_vault_passphrase.clone();
+        if passphrase.chars().count() < vault::MIN_PASSPHRASE_LEN {
+            self.status_message = format!("Passphrase must be at least {} characters", vault::MIN_PASSPHRASE_LEN);
+            return;
+        }
+        if passphrase != self.confirm_vault_passphrase {
+            self.status_message = "Passphrases don't match".to_string();
+            return;
+        }
+        let Some((header, key)) = vault::create(&passphrase) else {
+            self.status_message = "Could not set up encryption".to_string();
+            return;
+        };
+        vault::set_key(Some(key));
+        self.config.password_vault = header;
+        // Re-written encrypted - the saved login password too
+        self.config.save();
+        save_playlist_entries(&self.playlist_entries);
+        self.new_vault_passphrase.clear();
+        self.confirm_vault_passphrase.clear();
+        self.status_message = "Saved passwords encrypted - the passphrase will be asked for at startup".to_string();
+        self.log("[INFO] Saved password encryption enabled");
+    }
+    
+    fn disable_password_encryption(&mut self) {
+        vault::set_key(None);
+        self.config.password_vault.clear();
+        self.config.save();
+        save_playlist_entries(&self.playlist_entries);
+        self.status_message = "Saved passwords are stored as plain text".to_string();
+        self.log("[INFO] Saved password encryption disabled");
+    }
+    
+    /// Full-window passphrase prompt shown at startup when saved passwords are encrypted
+    fn show_vault_screen(&mut self, ctx: &egui::Context) {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.add_space(120.0);
+                ui.heading("🔐 Saved passwords are encrypted");
+                ui.add_space(12.0);
+                ui.label("Enter your master passphrase to continue");
+                ui.add_space(8.0);
+                
+                let response = ui.add(egui::TextEdit::singleline(&mut self.vault_passphrase_input)
+                    .password(true)
+                    .desired_width(200.0)
+                    .hint_text("Passphrase"));
+                if !response.has_focus() && self.vault_passphrase_input.is_empty() {
+                    response.request_focus();
+                }
+                let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                ui.add_space(8.0);
+                if ui.button("🔓 Unlock").clicked() || submitted {
+                    let passphrase = std::mem::take(&mut self.vault_passphrase_input);
+                    match vault::unlock(&passphrase, &self.config.password_vault) {
+                        Some(key) => {
+                            vault::set_key(Some(key));
+                            open_sealed_passwords(&mut self.playlist_entries);
+                            if let Some(plain) = vault::open(&self.config.saved_password) {
+                                if vault::is_sealed(&self.password) {
+                                    self.password = plain.clone();
+                                }
+                                self.config.saved_password = plain;
+                            }
+                            self.vault_locked = false;
+                            self.vault_unlock_failed = false;
+                            self.log("[INFO] Saved passwords unlocked");
+                        }
+                        None => {
+                            self.vault_unlock_failed = true;
+                            self.log("[WARN] Wrong master passphrase");
                         }
-                        let sign = if self.epg_time_offset >= 0.0 { "+" } else { "" };
-                        ui.label(format!("{}{:.1} hours", sign, self.epg_time_offset));
-                        if self.epg_time_offset != 0.0 {
-                            if ui.small_button("Reset").clicked() {
-                                self.epg_time_offset = 0.0;
+                    }
+                }
+                
+                if self.vault_unlock_failed {
+                    ui.add_space(8.0);
+                    ui.label(egui::RichText::new("Wrong passphrase").color(egui::Color32::from_rgb(220, 80, 80)));
+                }
+                
+                // A forgotten passphrase can't be recovered - the passwords have to be entered again
+                ui.add_space(24.0);
+                if ui.button("Forget saved passwords")
+                    .on_hover_text("Clear the encrypted passwords and continue - Xtream playlists will need their password again")
+                    .clicked()
+                {
+                    for entry in &mut self.playlist_entries {
+                        if let PlaylistType::Xtream { password, .. } = &mut entry.entry_type {
+                            if vault::is_sealed(password) {
+                                password.clear();
                             }
                         }
-                    });
-                    
-                    // EPG Grid display mode
-                    ui.horizontal(|ui| {
-                        ui.label("Grid Header:");
-                        ui.selectable_value(&mut self.epg_show_actual_time, false, "Offset (Now, +30m...)")
-                            .on_hover_text("Show relative time offsets");
-                        ui.selectable_value(&mut self.epg_show_actual_time, true, "Time (8:00, 8:30...)")
-                            .on_hover_text("Show actual times");
-                    });
-                    
-                    if !self.epg_status.is_empty() {
-                        ui.separator();
-                        ui.horizontal(|ui| {
-                            if self.epg_loading {
-                                ui.spinner();
-                            }
-                            let color = if self.epg_status.starts_with("Error") {
-                                egui::Color32::RED
-                            } else if self.epg_status.starts_with("Loaded") {
-                                egui::Color32::GREEN
-                            } else {
-                                egui::Color32::YELLOW
-                            };
-                            ui.label(egui::RichText::new(&self.epg_status).color(color));
-                        });
                     }
-                    
-                    if let Some(ref epg) = self.epg_data {
-                        ui.separator();
-                        ui.heading("EPG Statistics");
-                        
-                        egui::Grid::new("epg_stats")
-                            .num_columns(2)
-                            .spacing([20.0, 4.0])
-                            .show(ui, |ui| {
-                                ui.label("Channels:");
-                                ui.label(format!("{}", epg.channels.len()));
-                                ui.end_row();
-                                
-                                ui.label("Programs:");
-                                ui.label(format!("{}", epg.program_count()));
-                                ui.end_row();
-                            });
+                    if vault::is_sealed(&self.config.saved_password) {
+                        self.config.saved_password.clear();
+                        self.password.clear();
                     }
-                    
-                    ui.separator();
-                    
-                    // Close on left, Clear EPG Data on right - same row
-                    ui.horizontal(|ui| {
-                        if ui.button("Close").clicked() {
-                            self.show_epg_dialog = false;
-                        }
-                        
-                        if self.epg_data.is_some() {
-                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                                if ui.button("🗑 Clear EPG Data").clicked() {
-                                    self.epg_data = None;
-                                    self.epg_last_update = None;
-                                    self.epg_status = "EPG data cleared".to_string();
-                                    self.log("[INFO] EPG data cleared");
-                                }
-                            });
+                    self.config.password_vault.clear();
+                    self.config.save();
+                    save_playlist_entries(&self.playlist_entries);
+                    self.vault_locked = false;
+                    self.log("[WARN] Encrypted passwords cleared - password encryption is off");
+                }
+            });
+        });
+    }
+    
+    /// Full-window PIN prompt shown at startup when a PIN is set
+    fn show_lock_screen(&mut self, ctx: &egui::Context) {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.add_space(120.0);
+                ui.heading("🔒 Xtreme IPTV is locked");
+                ui.add_space(12.0);
+                ui.label("Enter your PIN to continue");
+                ui.add_space(8.0);
+                
+                let response = ui.add(egui::TextEdit::singleline(&mut self.pin_input)
+                    .password(true)
+                    .desired_width(140.0)
+                    .hint_text("PIN"));
+                if !response.has_focus() && self.pin_input.is_empty() {
+                    response.request_focus();
+                }
+                let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                ui.add_space(8.0);
+                if ui.button("🔓 Unlock").clicked() || submitted {
+                    let pin = std::mem::take(&mut self.pin_input);
+                    if app_lock::verify_pin(pin.trim(), &self.config.app_pin_hash) {
+                        self.app_locked = false;
+                        self.pin_attempts = 0;
+                        self.status_message = "Unlocked".to_string();
+                        self.log("[INFO] App unlocked");
+                    } else {
+                        self.pin_attempts += 1;
+                        self.log(&format!("[WARN] Wrong PIN ({}/{})", self.pin_attempts, app_lock::MAX_PIN_ATTEMPTS));
+                        if self.pin_attempts >= app_lock::MAX_PIN_ATTEMPTS {
+                            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
                         }
-                    });
-                });
+                    }
+                }
+                
+                if self.pin_attempts > 0 {
+                    let left = app_lock::MAX_PIN_ATTEMPTS.saturating_sub(self.pin_attempts);
+                    ui.add_space(8.0);
+                    ui.label(egui::RichText::new(format!("Wrong PIN - {} attempt{} left", left, if left == 1 { "" } else { "s" }))
+                        .color(egui::Color32::from_rgb(220, 80, 80)));
+                }
+            });
+        });
+    }
+    
+    /// Quality dropdown beside the sort order
+    fn quality_filter_combo(ui: &mut egui::Ui, id_salt: &str, selected: &mut Option<Quality>) {
+        egui::ComboBox::from_id_salt(id_salt)
+            .selected_text(selected.map_or("All qualities", |q| q.label()))
+            .width(90.0)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(selected, None, "All qualities");
+                for quality in Quality::ALL {
+                    ui.selectable_value(selected, Some(quality), quality.label());
+                }
+            }).response.on_hover_text("Filter by the quality in the channel name (channels without one are always shown)");
+    }
+    
+    /// Genre dropdown - hidden when the list has no genre information
+    fn genre_filter_combo(ui: &mut egui::Ui, id_salt: &str, genres: &BTreeSet<String>, selected: &mut Option<String>) {
+        if genres.is_empty() && selected.is_none() {
+            return;
         }
-        
-        // Internal Player Window
-        if self.show_internal_player {
-            let mut open = self.show_internal_player;
-            egui::Window::new("🎬 Internal Player")
-                .open(&mut open)
-                .resizable(true)
-                .default_size([860.0, 540.0])
-                .show(ctx, |ui| {
-                    self.internal_player.show(ctx, ui);
-                });
-            
-            if !open {
-                self.show_internal_player = false;
-                self.internal_player.stop();
+        ui.label("🎭");
+        egui::ComboBox::from_id_salt(id_salt)
+            .selected_text(selected.as_deref().unwrap_or("All genres"))
+            .width(130.0)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(selected, None, "All genres");
+                for genre in genres {
+                    ui.selectable_value(selected, Some(genre.clone()), genre);
+                }
+            }).response.on_hover_text("Filter by genre (from the provider's list or loaded details)");
+    }
+    
+    /// Remember the main window's size and position for the next launch. Maximized,
+    /// minimized and fullscreen states are skipped so the normal layout is kept.
+    fn track_window_layout(&mut self, ctx: &egui::Context) {
+        let (inner, outer, monitor_size, normal) = ctx.input(|i| {
+            let vp = i.viewport();
+            let normal = !vp.minimized.unwrap_or(false) && !vp.maximized.unwrap_or(false) && !vp.fullscreen.unwrap_or(false);
+            (vp.inner_rect, vp.outer_rect, vp.monitor_size, normal)
+        });
+        let (Some(inner), Some(outer)) = (inner, outer) else { return };
+        
+        if !self.window_position_checked {
+            self.window_position_checked = true;
+            // Platforms report the nearest monitor even for a window restored onto a
+            // disconnected one, and monitor positions aren't known - keep the saved
+            // position only if the title bar is on the monitor at the origin
+            let title_bar = egui::Rect::from_min_size(outer.min, egui::vec2(outer.width().min(120.0), 30.0));
+            let on_screen = monitor_size.is_some_and(|monitor| {
+                egui::Rect::from_min_size(egui::Pos2::ZERO, monitor).contains_rect(title_bar)
+            });
+            if !on_screen {
+                self.log("[WINDOW] Saved position may be off screen - moving the window back");
+                ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(egui::pos2(40.0, 40.0)));
             }
+            if let Some(monitor) = monitor_size.filter(|m| inner.width() > m.x || inner.height() > m.y) {
+                ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(inner.size().min(monitor * 0.9)));
+            }
+            return;
+        }
+        
+        if normal {
+            self.window_layout = Some(([inner.width(), inner.height()], [outer.min.x, outer.min.y]));
         }
     }
-}
-
-impl IPTVApp {
-    fn show_live_tab(&mut self, ui: &mut egui::Ui) {
-        self.show_category_tab(ui, "live");
+    
+    /// Drop the saved window geometry and put the window back at its default size
+    fn reset_window_layout(&mut self, ctx: &egui::Context) {
+        self.config.window_size = None;
+        self.config.window_position = None;
+        self.config.save();
+        self.window_layout = None;
+        ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(DEFAULT_WINDOW_SIZE.into()));
+        if let Some(center) = egui::ViewportCommand::center_on_screen(ctx) {
+            ctx.send_viewport_cmd(center);
+        }
+        self.status_message = "Window layout reset".to_string();
     }
-
-    fn show_movies_tab(&mut self, ui: &mut egui::Ui) {
-        self.show_category_tab(ui, "movie");
+    
+    /// Compiled search box query - rebuilt only when the text or regex mode changes
+    fn search_filter(&mut self) -> search::SearchQuery {
+        if !self.search_filter.is_for(&self.search_query, self.search_regex) {
+            self.search_filter = search::SearchQuery::parse(&self.search_query, self.search_regex);
+        }
+        self.search_filter.clone()
     }
-
+    
+    /// Re-sort and re-filter the channel list when the channels, sort order, search or filters changed
+    fn refresh_channel_list_view(&mut self, stream_type: &str, airing_favorites: &HashSet<String>, my_shows_only: bool) {
+        use std::hash::{Hash, Hasher};
+        
+        let sort_order = self.effective_sort_order(stream_type);
+        let search = self.search_filter();
+        let genre_filter = if stream_type == "movie" { self.movie_genre_filter.clone() } else { None };
+        let show_hidden = self.show_hidden;
+        // Source headers only make sense in the playlists' own order
+        let separators = self.playlist_mode && !self.playlist_sources.is_empty() && sort_order == SortOrder::Default;
+        
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        stream_type.hash(&mut hasher);
+        (sort_order as u8).hash(&mut hasher);
+        self.search_query.hash(&mut hasher);
+        self.search_regex.hash(&mut hasher);
+        genre_filter.hash(&mut hasher);
+        self.quality_filter.hash(&mut hasher);
+        show_hidden.hash(&mut hasher);
+        separators.hash(&mut hasher);
+        self.playlist_sources.len().hash(&mut hasher);
+        self.current_channels.len().hash(&mut hasher);
+        self.current_channels.first().map(|c| &c.url).hash(&mut hasher);
+        self.current_channels.last().map(|c| &c.url).hash(&mut hasher);
+        self.hidden_channels.len().hash(&mut hasher);
+        self.channel_aliases.len().hash(&mut hasher);
+        if sort_order == SortOrder::MostWatched {
+            self.play_counts.values().sum::<u32>().hash(&mut hasher);
+        }
+        my_shows_only.hash(&mut hasher);
+        if my_shows_only {
+            let mut airing: Vec<_> = airing_favorites.iter().collect();
+            airing.sort();
+            airing.hash(&mut hasher);
+        }
+        // 0 marks the view as stale
+        let signature = hasher.finish().max(1);
+        if signature == self.channel_list_view.signature {
+            return;
+        }
+        
+        let channels = &self.current_channels;
+        let mut sorted: Vec<usize> = (0..channels.len()).collect();
+        match sort_order {
+            SortOrder::NameAsc => sorted.sort_by_cached_key(|&i| channels[i].name.to_lowercase()),
+            SortOrder::NameDesc => {
+                sorted.sort_by_cached_key(|&i| channels[i].name.to_lowercase());
+                sorted.reverse();
+            }
+            SortOrder::MostWatched => {
+                // Stable sort - unwatched channels keep server order
+                let counts = &self.play_counts;
+                sorted.sort_by_key(|&i| std::cmp::Reverse(counts.get(&channels[i].url).copied().unwrap_or(0)));
+            }
+            SortOrder::Default => {} // Keep server order
+        }
+        
+        let mut rows = Vec::new();
+        let mut current_source = None;
+        for &idx in &sorted {
+            let c = &channels[idx];
+            let visible = search.matches_channel(&self.display_name(&c.url, &c.name), c.epg_channel_id.as_deref(), &c.url)
+                && Self::genre_matches(c.genre.as_deref(), genre_filter.as_deref())
+                && Self::quality_matches(&c.name, self.quality_filter)
+                && (show_hidden || !self.is_hidden(&c.url))
+                && (!my_shows_only || airing_favorites.contains(c.url.as_str()));
+            if !visible {
+                continue;
+            }
+            if separators {
+                let source = self.playlist_sources.iter().rposition(|(start_idx, _)| *start_idx <= idx);
+                if let Some(source) = source.filter(|_| source != current_source) {
+                    rows.push(ChannelRow::Source(source));
+                }
+                current_source = source;
+            }
+            rows.push(ChannelRow::Channel(idx));
+        }
+        
+        self.channel_list_view = ChannelListView {
+            signature,
+            genres: if stream_type == "movie" {
+                Self::collect_genres(channels.iter().map(|c| c.genre.as_deref()))
+            } else {
+                BTreeSet::new()
+            },
+            hidden_count: channels.iter().filter(|c| self.is_hidden(&c.url)).count(),
+            sorted,
+            rows,
+        };
+    }
+    
     fn show_category_tab(&mut self, ui: &mut egui::Ui, stream_type: &str) {
         let categories = match stream_type {
             "live" => &self.live_categories,
@@ -4158,7 +8765,6 @@ impl IPTVApp {
         // If we have channels loaded, show them
         if !self.current_channels.is_empty() && 
            matches!(self.navigation_stack.last(), Some(NavigationLevel::Channels(_))) {
-            let search = self.search_query.to_lowercase();
             let category_name = if let Some(NavigationLevel::Channels(name)) = self.navigation_stack.last() {
                 name.clone()
             } else {
@@ -4167,36 +8773,89 @@ impl IPTVApp {
             
             let name_width = self.channel_name_width;
             
-            // Clone and sort channels
-            let mut channels: Vec<_> = self.current_channels.clone();
-            
-            // Apply sort order based on stream type
-            let sort_order = match stream_type {
-                "live" => self.live_sort_order,
-                "movie" => self.movie_sort_order,
-                _ => SortOrder::Default,
+            // Live channels airing a starred program right now
+            let airing_favorites: HashSet<String> = if stream_type == "live" {
+                self.airing_favorites()
+            } else {
+                HashSet::new()
             };
+            let my_shows_only = self.my_shows_only && stream_type == "live";
             
-            match sort_order {
-                SortOrder::NameAsc => channels.sort_by_cached_key(|c| c.name.to_lowercase()),
-                SortOrder::NameDesc => {
-                    channels.sort_by_cached_key(|c| c.name.to_lowercase());
-                    channels.reverse();
-                }
-                SortOrder::Default => {} // Keep server order
-            }
+            // Sorted and filtered order is cached - only rows in view are laid out below
+            self.refresh_channel_list_view(stream_type, &airing_favorites, my_shows_only);
+            let view = std::mem::take(&mut self.channel_list_view);
             
-            // Filter by search
-            let filtered: Vec<_> = channels.iter()
-                .filter(|c| {
-                    let display_name = Self::sanitize_text(&c.name);
-                    search.is_empty() || display_name.to_lowercase().contains(&search)
-                })
-                .collect();
+            // Selection toolbar
+            let mut bulk_add = false;
+            let mut bulk_remove = false;
+            let mut bulk_play = false;
+            ui.horizontal(|ui| {
+                if ui.selectable_label(self.selection_mode, "☑ Select")
+                    .on_hover_text("Select multiple items for bulk actions")
+                    .clicked()
+                {
+                    self.selection_mode = !self.selection_mode;
+                    if !self.selection_mode {
+                        self.selected_urls.clear();
+                    }
+                }
+                
+                if self.selection_mode {
+                    ui.label(format!("{} selected", self.selected_urls.len()));
+                    if ui.small_button("All").on_hover_text("Select all visible").clicked() {
+                        self.selected_urls.extend(view.rows.iter().filter_map(|row| match row {
+                            ChannelRow::Channel(idx) => Some(self.current_channels[*idx].url.clone()),
+                            ChannelRow::Source(_) => None,
+                        }));
+                    }
+                    if ui.small_button("None").on_hover_text("Clear selection").clicked() {
+                        self.selected_urls.clear();
+                    }
+                    
+                    ui.separator();
+                    
+                    let has_selection = !self.selected_urls.is_empty();
+                    if ui.add_enabled(has_selection, egui::Button::new("★ Add to favorites")).clicked() {
+                        bulk_add = true;
+                    }
+                    if ui.add_enabled(has_selection, egui::Button::new("☆ Remove from favorites")).clicked() {
+                        bulk_remove = true;
+                    }
+                    if ui.add_enabled(has_selection, egui::Button::new("▶ Play first")).clicked() {
+                        bulk_play = true;
+                    }
+                }
+                
+                if stream_type == "movie" {
+                    Self::genre_filter_combo(ui, "movie_genre_filter", &view.genres, &mut self.movie_genre_filter);
+                }
+                
+                Self::show_hidden_toggle(ui, view.hidden_count, &mut self.show_hidden);
+                
+                if stream_type == "live" && self.epg_data.is_some() {
+                    if !self.favorite_programs.is_empty()
+                        && ui.selectable_label(self.my_shows_only, format!("⭐ On now ({})", airing_favorites.len()))
+                            .on_hover_text("Only show channels airing one of your starred shows")
+                            .clicked()
+                    {
+                        self.my_shows_only = !self.my_shows_only;
+                    }
+                    if ui.button("⭐ My shows").on_hover_text("Starred shows and what's on now").clicked() {
+                        self.show_my_shows = true;
+                    }
+                }
+            });
             
-            let playlist_sources = &self.playlist_sources;
             let mut toggle_fav: Option<FavoriteItem> = None;
             let mut to_play: Option<Channel> = None;
+            let mut to_show_detail: Option<Channel> = None;
+            let mut to_probe: Option<Channel> = None;
+            let mut to_record: Option<Channel> = None;
+            let mut to_toggle_select: Option<String> = None;
+            let mut to_rename: Option<(String, String)> = None;
+            let mut to_toggle_hidden: Option<(String, String)> = None;
+            let selection_mode = self.selection_mode;
+            let show_info_button = stream_type == "movie" && !self.playlist_mode;
             
             // Determine layout - don't use grid when EPG panel is shown (takes space)
             let has_epg = self.epg_data.is_some();
@@ -4225,145 +8884,374 @@ impl IPTVApp {
             
             let text_size = 14.0;
             let star_size = 18.0;
+            // Every row gets the same height so show_rows can skip the ones out of view
+            let row_height = ui.fonts_mut(|f| f.row_height(&egui::FontId::proportional(star_size)))
+                + 2.0 * ui.spacing().button_padding.y;
             
             // For playlist mode with separators, use single column
             let use_grid = num_columns > 1 && !self.playlist_mode;
             
-            if use_grid {
-                // Multi-column grid layout with calculated width
-                egui::Grid::new("channels_grid")
-                    .num_columns(num_columns)
-                    .spacing([4.0, 2.0])
-                    .min_col_width(item_width)
-                    .max_col_width(item_width)
-                    .show(ui, |ui| {
-                        for (i, channel) in filtered.iter().enumerate() {
-                            let is_fav = self.is_favorite(&channel.url);
-                            
+            // Poster wall for movies (selection mode falls back to the list for checkboxes)
+            let use_posters = stream_type == "movie" && self.vod_view_mode == ViewMode::Grid
+                && !self.playlist_mode && !selection_mode;
+            
+            // The list scrolls on its own, filling what is left of the view below the toolbar
+            let list_height = (ui.clip_rect().height() - (ui.cursor().top() - ui.min_rect().top())).max(200.0);
+            let list_scroll = egui::ScrollArea::vertical()
+                .id_salt(("channel_rows", stream_type, &category_name))
+                .auto_shrink([false, false])
+                .max_height(list_height);
+            
+            if use_posters {
+                let spacing = 10.0;
+                let per_row = (((available_width + spacing) / (POSTER_TILE_WIDTH + spacing)).floor() as usize).max(1);
+                let tile_rows = view.rows.len().div_ceil(per_row);
+                ui.scope(|ui| {
+                    ui.spacing_mut().item_spacing = egui::vec2(spacing, spacing);
+                    list_scroll.show_rows(ui, POSTER_TILE_HEIGHT + 20.0, tile_rows, |ui, row_range| {
+                        for row in row_range {
+                            let start = row * per_row;
+                            let end = (start + per_row).min(view.rows.len());
                             ui.horizontal(|ui| {
-                                let fav_text = if is_fav { 
-                                    egui::RichText::new("★").size(star_size).color(egui::Color32::GOLD)
-                                } else { 
-                                    egui::RichText::new("☆").size(star_size).color(egui::Color32::GRAY)
-                                };
-                                if ui.button(fav_text).on_hover_text(if is_fav { "Remove from favorites" } else { "Add to favorites" }).clicked() {
-                                    toggle_fav = Some(FavoriteItem {
-                                        name: channel.name.clone(),
-                                        url: channel.url.clone(),
-                                        stream_type: stream_type.to_string(),
-                                        stream_id: channel.stream_id,
-                                        series_id: None,
-                                        category_name: category_name.clone(),
-                                        container_extension: channel.container_extension.clone(),
-                                        season_num: None,
-                                        episode_num: None,
-                                        series_name: None,
-                                        playlist_source: channel.playlist_source.clone(),
+                                for row in &view.rows[start..end] {
+                                    let ChannelRow::Channel(idx) = *row else { continue };
+                                    let channel = self.current_channels[idx].clone();
+                                    let is_fav = self.is_favorite(&channel.url);
+                                    let title = self.display_name(&channel.url, &channel.name);
+                                    let response = self.show_poster_tile(ui, &title, channel.stream_icon.as_deref(), is_fav);
+                                    if response.clicked() {
+                                        to_play = Some(channel.clone());
+                                    }
+                                    response.context_menu(|ui| {
+                                        if ui.button("▶ Play").clicked() {
+                                            to_play = Some(channel.clone());
+                                            ui.close();
+                                        }
+                                        if channel.stream_id.is_some() && ui.button("ℹ Details").clicked() {
+                                            to_show_detail = Some(channel.clone());
+                                            ui.close();
+                                        }
+                                        if ui.button("⏺ Record").clicked() {
+                                            to_record = Some(channel.clone());
+                                            ui.close();
+                                        }
+                                        if ui.button("🔍 Stream info").clicked() {
+                                            to_probe = Some(channel.clone());
+                                            ui.close();
+                                        }
+                                        if ui.button(if self.is_hidden(&channel.url) { "👁 Unhide" } else { "🚫 Hide" }).clicked() {
+                                            to_toggle_hidden = Some((channel.url.clone(), channel.name.clone()));
+                                            ui.close();
+                                        }
+                                        let fav_label = if is_fav { "☆ Remove from favorites" } else { "★ Add to favorites" };
+                                        if ui.button(fav_label).clicked() {
+                                            toggle_fav = Some(FavoriteItem {
+                                                name: channel.name.clone(),
+                                                url: channel.url.clone(),
+                                                stream_type: stream_type.to_string(),
+                                                stream_id: channel.stream_id,
+                                                series_id: None,
+                                                category_name: category_name.clone(),
+                                                container_extension: channel.container_extension.clone(),
+                                                season_num: None,
+                                                episode_num: None,
+                                                series_name: None,
+                                                playlist_source: channel.playlist_source.clone(),
+                                                resume_secs: None,
+                                                duration_secs: None,
+                                            });
+                                            ui.close();
+                                        }
                                     });
                                 }
-                                
-                                if ui.button("▶").clicked() {
-                                    to_play = Some((*channel).clone());
-                                }
-                                
-                                // Name as button for grid - truncate to fit column width
-                                let display_name = Self::sanitize_text(&channel.name);
-                                let name_width = item_width - 70.0; // Account for star and play buttons
-                                let truncated = Self::truncate_to_width(&display_name, name_width);
-                                let response = ui.button(egui::RichText::new(&truncated).size(text_size).strong());
-                                if truncated != display_name {
-                                    response.clone().on_hover_text(&display_name);
-                                }
-                                if response.clicked() {
-                                    to_play = Some((*channel).clone());
-                                }
                             });
-                            
-                            if (i + 1) % num_columns == 0 {
-                                ui.end_row();
+                        }
+                    });
+                });
+            } else if use_grid {
+                // Multi-column grid - each shown row holds num_columns cells of the calculated width
+                let grid_rows = view.rows.len().div_ceil(num_columns);
+                // Taken out so rows can draw logos while borrowing the channel list
+                let mut image_cache = std::mem::take(&mut self.image_cache);
+                list_scroll.show_rows(ui, row_height, grid_rows, |ui, row_range| {
+                    for row in row_range {
+                        let start = row * num_columns;
+                        let end = (start + num_columns).min(view.rows.len());
+                        ui.horizontal(|ui| {
+                            for row in &view.rows[start..end] {
+                                let ChannelRow::Channel(idx) = *row else { continue };
+                                let channel = &self.current_channels[idx];
+                                let is_fav = self.is_favorite(&channel.url);
+                                
+                                ui.allocate_ui_with_layout(egui::vec2(item_width, row_height), egui::Layout::left_to_right(egui::Align::Center), |ui| {
+                                    ui.set_min_size(egui::vec2(item_width, row_height));
+                                    if selection_mode {
+                                        let mut checked = self.selected_urls.contains(&channel.url);
+                                        if ui.checkbox(&mut checked, "").changed() {
+                                            to_toggle_select = Some(channel.url.clone());
+                                        }
+                                    }
+                                    
+                                    let fav_text = if is_fav { 
+                                        egui::RichText::new("★").size(star_size).color(egui::Color32::GOLD)
+                                    } else { 
+                                        egui::RichText::new("☆").size(star_size).color(egui::Color32::GRAY)
+                                    };
+                                    if ui.button(fav_text).on_hover_text(if is_fav { "Remove from favorites" } else { "Add to favorites" }).clicked() {
+                                        toggle_fav = Some(FavoriteItem {
+                                            name: channel.name.clone(),
+                                            url: channel.url.clone(),
+                                            stream_type: stream_type.to_string(),
+                                            stream_id: channel.stream_id,
+                                            series_id: None,
+                                            category_name: category_name.clone(),
+                                            container_extension: channel.container_extension.clone(),
+                                            season_num: None,
+                                            episode_num: None,
+                                            series_name: None,
+                                            playlist_source: channel.playlist_source.clone(),
+                                            resume_secs: None,
+                                            duration_secs: None,
+                                        });
+                                    }
+                                    
+                                    if ui.button("▶").clicked() {
+                                        to_play = Some(channel.clone());
+                                    }
+                                    
+                                    if ui.button("⏺").on_hover_text("Record to file (ffmpeg)").clicked() {
+                                        to_record = Some(channel.clone());
+                                    }
+                                    
+                                    if ui.button("🔍").on_hover_text("Stream info (ffprobe)").clicked() {
+                                        to_probe = Some(channel.clone());
+                                    }
+                                    
+                                    if show_info_button && channel.stream_id.is_some()
+                                        && ui.button("ℹ").on_hover_text("Show details").clicked()
+                                    {
+                                        to_show_detail = Some(channel.clone());
+                                    }
+                                    
+                                    // Name as button for grid - truncate to fit column width
+                                    let display_name = self.display_name(&channel.url, &channel.name);
+                                    let quality = Quality::from_name(&channel.name);
+                                    let info_width = if show_info_button { 30.0 } else { 0.0 };
+                                    let badge_width = if quality == Quality::Unknown { 0.0 } else { 30.0 };
+                                    let logo_width = if self.show_channel_logos { row_height + 4.0 } else { 0.0 };
+                                    let name_width = item_width - 125.0 - info_width - badge_width - logo_width; // Account for star, play, record, probe and info buttons
+                                    let truncated = Self::truncate_to_width(&display_name, name_width);
+                                    if self.show_channel_logos {
+                                        Self::show_channel_logo(ui, &mut image_cache, channel.stream_icon.as_deref(), row_height - 4.0);
+                                    }
+                                    Self::show_quality_badge(ui, quality);
+                                    Self::show_archive_badge(ui, channel.archive_days);
+                                    if airing_favorites.contains(channel.url.as_str()) {
+                                        ui.label(egui::RichText::new("⭐").color(egui::Color32::GOLD))
+                                            .on_hover_text("Airing one of your starred shows");
+                                    }
+                                    let response = ui.button(egui::RichText::new(&truncated).size(text_size).strong());
+                                    if truncated != display_name {
+                                        response.clone().on_hover_text(&display_name);
+                                    }
+                                    if response.clicked() {
+                                        // Movies open their details - playing stays on ▶
+                                        if show_info_button && channel.stream_id.is_some() {
+                                            to_show_detail = Some(channel.clone());
+                                        } else {
+                                            to_play = Some(channel.clone());
+                                        }
+                                    }
+                                    Self::channel_context_menu(&response, &channel.url, &channel.name, self.is_hidden(&channel.url), &mut to_rename, &mut to_toggle_hidden);
+                                });
                             }
-                        }
-                    });
+                        });
+                    }
+                });
+                self.image_cache = image_cache;
             } else {
-                // Single column layout (or playlist mode)
-                for (idx, channel) in channels.iter().enumerate() {
-                    // Show separator header for playlist sources (only in playlist mode)
-                    if self.playlist_mode && !playlist_sources.is_empty() {
-                        for (start_idx, source_name) in playlist_sources {
-                            if *start_idx == idx {
-                                ui.add_space(8.0);
-                                ui.separator();
+                // Single column layout (or playlist mode, with a header row per source)
+                let mut image_cache = std::mem::take(&mut self.image_cache);
+                list_scroll.show_rows(ui, row_height, view.rows.len(), |ui, row_range| {
+                    for row in &view.rows[row_range] {
+                        let idx = match *row {
+                            ChannelRow::Source(source) => {
                                 ui.horizontal(|ui| {
-                                    ui.label(egui::RichText::new(format!("📺 {}", source_name))
+                                    ui.set_min_height(row_height);
+                                    ui.label(egui::RichText::new(format!("📺 {}", self.playlist_sources[source].1))
                                         .strong()
                                         .size(14.0)
                                         .color(egui::Color32::from_rgb(100, 149, 237)));
                                 });
-                                ui.separator();
-                                ui.add_space(4.0);
+                                continue;
                             }
-                        }
-                    }
-                    
-                    let display_name = Self::sanitize_text(&channel.name);
-                    if !search.is_empty() && !display_name.to_lowercase().contains(&search) {
-                        continue;
-                    }
-                    
-                    let is_fav = self.is_favorite(&channel.url);
-                    
-                    ui.horizontal(|ui| {
-                        let fav_text = if is_fav { 
-                            egui::RichText::new("★").size(star_size).color(egui::Color32::GOLD)
-                        } else { 
-                            egui::RichText::new("☆").size(star_size).color(egui::Color32::GRAY)
+                            ChannelRow::Channel(idx) => idx,
                         };
-                        if ui.button(fav_text).on_hover_text(if is_fav { "Remove from favorites" } else { "Add to favorites" }).clicked() {
-                            toggle_fav = Some(FavoriteItem {
-                                name: channel.name.clone(),
-                                url: channel.url.clone(),
-                                stream_type: stream_type.to_string(),
-                                stream_id: channel.stream_id,
-                                series_id: None,
-                                category_name: category_name.clone(),
-                                container_extension: channel.container_extension.clone(),
-                                season_num: None,
-                                episode_num: None,
-                                series_name: None,
-                                playlist_source: channel.playlist_source.clone(),
-                            });
-                        }
-                        
-                        if ui.button("▶").clicked() {
-                            to_play = Some(channel.clone());
-                        }
-                        
-                        self.show_channel_name(ui, &channel.name, name_width, true);
+                        let channel = &self.current_channels[idx];
+                        let is_hidden = self.is_hidden(&channel.url);
+                        let is_fav = self.is_favorite(&channel.url);
                         
-                        // Show EPG info if available (only for live streams)
-                        if stream_type == "live" {
-                            self.show_epg_inline(ui, &channel.name, channel.epg_channel_id.as_deref());
-                        }
-                    });
-                }
+                        ui.horizontal(|ui| {
+                            ui.set_min_height(row_height);
+                            if selection_mode {
+                                let mut checked = self.selected_urls.contains(&channel.url);
+                                if ui.checkbox(&mut checked, "").changed() {
+                                    to_toggle_select = Some(channel.url.clone());
+                                }
+                            }
+                            
+                            let fav_text = if is_fav { 
+                                egui::RichText::new("★").size(star_size).color(egui::Color32::GOLD)
+                            } else { 
+                                egui::RichText::new("☆").size(star_size).color(egui::Color32::GRAY)
+                            };
+                            if ui.button(fav_text).on_hover_text(if is_fav { "Remove from favorites" } else { "Add to favorites" }).clicked() {
+                                toggle_fav = Some(FavoriteItem {
+                                    name: channel.name.clone(),
+                                    url: channel.url.clone(),
+                                    stream_type: stream_type.to_string(),
+                                    stream_id: channel.stream_id,
+                                    series_id: None,
+                                    category_name: category_name.clone(),
+                                    container_extension: channel.container_extension.clone(),
+                                    season_num: None,
+                                    episode_num: None,
+                                    series_name: None,
+                                    playlist_source: channel.playlist_source.clone(),
+                                    resume_secs: None,
+                                    duration_secs: None,
+                                });
+                            }
+                            
+                            if ui.button("▶").clicked() {
+                                to_play = Some(channel.clone());
+                            }
+                            
+                            if ui.button("⏺").on_hover_text("Record to file (ffmpeg)").clicked() {
+                                to_record = Some(channel.clone());
+                            }
+                            
+                            if ui.button("🔍").on_hover_text("Stream info (ffprobe)").clicked() {
+                                to_probe = Some(channel.clone());
+                            }
+                            
+                            if show_info_button && channel.stream_id.is_some()
+                                && ui.button("ℹ").on_hover_text("Show details").clicked()
+                            {
+                                to_show_detail = Some(channel.clone());
+                            }
+                            
+                            if is_hidden {
+                                ui.label(egui::RichText::new("🚫").weak()).on_hover_text("Hidden");
+                            }
+                            if self.show_channel_logos {
+                                Self::show_channel_logo(ui, &mut image_cache, channel.stream_icon.as_deref(), row_height - 4.0);
+                            }
+                            let mut name_response = self.show_channel_name(ui, &channel.url, &channel.name, name_width, true);
+                            if show_info_button && channel.stream_id.is_some() && name_response.clicked() {
+                                to_show_detail = Some(channel.clone());
+                            }
+                            if stream_type == "live" {
+                                name_response = self.with_mini_epg(name_response, &channel.name, channel.epg_channel_id.as_deref(), channel.tvg_shift);
+                            }
+                            Self::channel_context_menu(&name_response, &channel.url, &channel.name, is_hidden, &mut to_rename, &mut to_toggle_hidden);
+                            self.show_container_label(ui, &channel.url, channel.container_extension.as_deref());
+                            Self::show_quality_badge(ui, Quality::from_name(&channel.name));
+                            Self::show_archive_badge(ui, channel.archive_days);
+                            
+                            // Show EPG info if available (only for live streams)
+                            if stream_type == "live" {
+                                self.show_epg_inline(ui, &channel.name, channel.epg_channel_id.as_deref(), channel.tvg_shift);
+                            }
+                        });
+                    }
+                });
+                self.image_cache = image_cache;
             }
             
             if let Some(channel) = to_play {
                 self.play_channel(&channel);
             }
             
+            if let Some(channel) = to_show_detail {
+                self.fetch_vod_detail(&channel);
+            }
+            
+            if let Some(channel) = to_probe {
+                self.probe_stream(&channel);
+            }
+            
+            if let Some(channel) = to_record {
+                self.start_recording(&channel);
+            }
+            
+            if let Some((url, name)) = to_rename {
+                self.start_rename(url, name);
+            }
+            
+            if let Some((url, name)) = to_toggle_hidden {
+                self.toggle_hidden(&url, &name);
+            }
+            
+            if let Some(url) = to_toggle_select {
+                if !self.selected_urls.remove(&url) {
+                    self.selected_urls.insert(url);
+                }
+            }
+            
+            if bulk_add || bulk_remove || bulk_play {
+                // Selected channels in display order
+                let selected: Vec<Channel> = view.sorted.iter()
+                    .map(|&idx| &self.current_channels[idx])
+                    .filter(|c| self.selected_urls.contains(&c.url))
+                    .cloned()
+                    .collect();
+                
+                if bulk_add {
+                    let items = selected.iter().map(|channel| FavoriteItem {
+                        name: channel.name.clone(),
+                        url: channel.url.clone(),
+                        stream_type: stream_type.to_string(),
+                        stream_id: channel.stream_id,
+                        series_id: None,
+                        category_name: category_name.clone(),
+                        container_extension: channel.container_extension.clone(),
+                        season_num: None,
+                        episode_num: None,
+                        series_name: None,
+                        playlist_source: channel.playlist_source.clone(),
+                        resume_secs: None,
+                        duration_secs: None,
+                    }).collect();
+                    self.add_favorites_bulk(items);
+                }
+                
+                if bulk_remove {
+                    let urls = self.selected_urls.clone();
+                    self.remove_favorites_bulk(&urls);
+                }
+                
+                if bulk_play {
+                    if let Some(channel) = selected.first() {
+                        self.play_channel(channel);
+                    }
+                }
+            }
+            
             if let Some(fav) = toggle_fav {
                 self.toggle_favorite(fav);
             }
+            self.channel_list_view = view;
             return;
         }
 
         // Show categories (sorted)
-        let search = self.search_query.to_lowercase();
         let mut clicked_category: Option<(String, String)> = None;
+        let mut toggle_fav_category: Option<(String, String)> = None;
         
         // Clone and sort categories
         let mut sorted_categories: Vec<_> = categories.clone();
+        let search = self.search_filter();
         let sort_order = match stream_type {
             "live" => self.live_sort_order,
             "movie" => self.movie_sort_order,
@@ -4376,14 +9264,16 @@ impl IPTVApp {
                 sorted_categories.sort_by_cached_key(|c| c.category_name.to_lowercase());
                 sorted_categories.reverse();
             }
-            SortOrder::Default => {} // Keep server order
+            SortOrder::MostWatched | SortOrder::Default => {} // Keep server order (play counts are per stream)
         }
         
+        // Pinned categories float to the top (stable sort keeps chosen order within each group)
+        sorted_categories.sort_by_key(|c| !self.is_favorite_category(stream_type, &c.category_id));
+        
         // Filter categories by search
         let filtered: Vec<_> = sorted_categories.iter()
             .filter(|cat| {
-                let display_name = Self::sanitize_text(&cat.category_name);
-                search.is_empty() || display_name.to_lowercase().contains(&search)
+                search.matches_name(&Self::sanitize_text(&cat.category_name))
             })
             .collect();
         
@@ -4410,13 +9300,20 @@ impl IPTVApp {
         
         let num_columns = requested_columns.min(max_columns_for_width).max(1);
         
+        self.show_count_prefetch_button(ui);
+        
         if num_columns == 1 {
             // Single column
             for cat in &filtered {
-                let display_name = Self::sanitize_text(&cat.category_name);
-                if ui.button(&display_name).clicked() {
-                    clicked_category = Some((cat.category_id.clone(), cat.category_name.clone()));
-                }
+                let button_text = self.category_button_text(stream_type, cat);
+                ui.horizontal(|ui| {
+                    if self.category_star_button(ui, stream_type, &cat.category_id) {
+                        toggle_fav_category = Some((cat.category_id.clone(), cat.category_name.clone()));
+                    }
+                    if ui.button(button_text).clicked() {
+                        clicked_category = Some((cat.category_id.clone(), cat.category_name.clone()));
+                    }
+                });
             }
         } else {
             // Multi-column grid
@@ -4428,10 +9325,15 @@ impl IPTVApp {
                 .max_col_width(item_width)
                 .show(ui, |ui| {
                     for (i, cat) in filtered.iter().enumerate() {
-                        let display_name = Self::sanitize_text(&cat.category_name);
-                        if ui.button(&display_name).clicked() {
-                            clicked_category = Some((cat.category_id.clone(), cat.category_name.clone()));
-                        }
+                        let button_text = self.category_button_text(stream_type, cat);
+                        ui.horizontal(|ui| {
+                            if self.category_star_button(ui, stream_type, &cat.category_id) {
+                                toggle_fav_category = Some((cat.category_id.clone(), cat.category_name.clone()));
+                            }
+                            if ui.button(button_text).clicked() {
+                                clicked_category = Some((cat.category_id.clone(), cat.category_name.clone()));
+                            }
+                        });
                         if (i + 1) % num_columns == 0 {
                             ui.end_row();
                         }
@@ -4439,6 +9341,10 @@ impl IPTVApp {
                 });
         }
         
+        if let Some((cat_id, cat_name)) = toggle_fav_category {
+            self.toggle_favorite_category(stream_type, &cat_id, &cat_name);
+        }
+        
         if let Some((cat_id, cat_name)) = clicked_category {
             self.save_scroll_position(ui.ctx());
             self.navigation_stack.push(NavigationLevel::Channels(cat_name));
@@ -4446,16 +9352,113 @@ impl IPTVApp {
         }
     }
 
+    /// Button to load item counts for all categories (shown until counts are loaded)
+    fn show_count_prefetch_button(&mut self, ui: &mut egui::Ui) {
+        if self.playlist_mode || self.category_counts_prefetching {
+            return;
+        }
+        // Categories of the tab being shown - the button stays until those are counted
+        let (stream_type, categories) = match self.current_tab {
+            Tab::Movies => ("movie", &self.movie_categories),
+            Tab::Series => ("series", &self.series_categories),
+            _ => ("live", &self.live_categories),
+        };
+        let all_counted = categories.iter()
+            .all(|c| self.category_counts.contains_key(&format!("{}:{}", stream_type, c.category_id)));
+        if !all_counted
+            && ui.small_button("🔢 Show item counts")
+                .on_hover_text("Download the full stream lists once to count items in every category")
+                .clicked()
+        {
+            self.prefetch_category_counts();
+        }
+    }
+
+    /// Star button for pinning a category - returns true when clicked
+    fn category_star_button(&self, ui: &mut egui::Ui, stream_type: &str, category_id: &str) -> bool {
+        let is_pinned = self.is_favorite_category(stream_type, category_id);
+        let star = if is_pinned {
+            egui::RichText::new("★").color(egui::Color32::GOLD)
+        } else {
+            egui::RichText::new("☆").color(egui::Color32::GRAY)
+        };
+        ui.small_button(star)
+            .on_hover_text(if is_pinned { "Unpin category" } else { "Pin category to top" })
+            .clicked()
+    }
+
+    /// While searching inside a series, list matching episodes from every season.
+    /// Returns false (draw the normal view) when there's no query or the series isn't cached.
+    fn show_episode_search_results(&mut self, ui: &mut egui::Ui, series_id: i64, search: &str) -> bool {
+        if search.is_empty() {
+            return false;
+        }
+        let Some((cached_id, episodes)) = &self.series_episodes else { return false };
+        if *cached_id != series_id {
+            return false;
+        }
+        
+        let matches: Vec<Episode> = episodes.iter()
+            .filter(|ep| Self::sanitize_text(&ep.title).to_lowercase().contains(search))
+            .cloned()
+            .collect();
+        let mut to_play: Option<Episode> = None;
+        
+        ui.label(egui::RichText::new(format!("{} matching episodes in all seasons", matches.len())).weak());
+        for ep in &matches {
+            ui.horizontal(|ui| {
+                if ui.button("▶").clicked() {
+                    to_play = Some(ep.clone());
+                }
+                ui.label(egui::RichText::new(format!("S{:02}", ep.season)).strong());
+                ui.label(format!("E{}: {}", ep.episode_num, Self::sanitize_text(&ep.title)));
+            });
+        }
+        
+        if let Some(ep) = to_play {
+            self.play_episode(&ep, series_id);
+        }
+        true
+    }
+
+    /// Episodes in the order chosen by the air date toggle
+    fn ordered_episodes(&self, mut episodes: Vec<Episode>) -> Vec<Episode> {
+        if self.episodes_by_air_date {
+            sort_episodes_by_air_date(&mut episodes);
+        }
+        episodes
+    }
+    
+    /// "By air date" toggle above an episode list - only offered when some episode has a date
+    fn episode_order_toggle(&mut self, ui: &mut egui::Ui, episodes: &[Episode]) {
+        if !episodes.iter().any(|ep| ep.air_date.is_some()) {
+            return;
+        }
+        if ui.selectable_label(self.episodes_by_air_date, "📅 By air date")
+            .on_hover_text("Sort episodes by air date instead of episode number\n(episodes without a date go last)")
+            .clicked()
+        {
+            self.episodes_by_air_date = !self.episodes_by_air_date;
+            self.config.episodes_by_air_date = self.episodes_by_air_date;
+            self.config.save();
+        }
+    }
+    
     fn show_series_tab(&mut self, ui: &mut egui::Ui) {
+        self.show_continue_watching(ui, "series");
         let search = self.search_query.to_lowercase();
 
         // Episodes level
         if !self.current_episodes.is_empty() {
             if let Some(NavigationLevel::Episodes(series_id, _)) = self.navigation_stack.last() {
                 let sid = *series_id;
-                let episodes: Vec<_> = self.current_episodes.clone();
+                if self.show_episode_search_results(ui, sid, &search) {
+                    return;
+                }
+                let episodes = self.ordered_episodes(self.current_episodes.clone());
                 let mut to_play: Option<(Episode, i64)> = None;
                 
+                self.episode_order_toggle(ui, &episodes);
                 for ep in &episodes {
                     let display_title = Self::sanitize_text(&ep.title);
                     if !search.is_empty() && !display_title.to_lowercase().contains(&search) {
@@ -4467,6 +9470,9 @@ impl IPTVApp {
                             to_play = Some((ep.clone(), sid));
                         }
                         ui.label(format!("E{}: {}", ep.episode_num, display_title));
+                        if let Some(date) = &ep.air_date {
+                            ui.label(egui::RichText::new(date).weak().small());
+                        }
                     });
                 }
                 
@@ -4481,6 +9487,9 @@ impl IPTVApp {
         if !self.current_seasons.is_empty() {
             if let Some(NavigationLevel::Seasons(series_id)) = self.navigation_stack.last() {
                 let sid = *series_id;
+                if self.show_episode_search_results(ui, sid, &search) {
+                    return;
+                }
                 let mut clicked_season: Option<i32> = None;
                 
                 for season in &self.current_seasons {
@@ -4507,24 +9516,40 @@ impl IPTVApp {
             
             // Clone and sort series
             let mut series_list: Vec<_> = self.current_series.clone();
-            match self.series_sort_order {
+            match self.effective_sort_order("series") {
                 SortOrder::NameAsc => series_list.sort_by_cached_key(|s| s.name.to_lowercase()),
                 SortOrder::NameDesc => {
                     series_list.sort_by_cached_key(|s| s.name.to_lowercase());
                     series_list.reverse();
                 }
-                SortOrder::Default => {} // Keep server order
+                SortOrder::MostWatched | SortOrder::Default => {} // Keep server order
             }
             
-            // Filter by search
+            // Genre filter
+            let genres = Self::collect_genres(series_list.iter().map(|s| s.genre.as_deref()));
+            let hidden_count = series_list.iter()
+                .filter(|s| self.is_hidden(&self.series_hide_key(s.series_id)))
+                .count();
+            ui.horizontal(|ui| {
+                Self::genre_filter_combo(ui, "series_genre_filter", &genres, &mut self.series_genre_filter);
+                Self::show_hidden_toggle(ui, hidden_count, &mut self.show_hidden);
+            });
+            let genre_filter = self.series_genre_filter.clone();
+            let show_hidden = self.show_hidden;
+            
+            // Filter by search, genre and hidden list
             let filtered: Vec<_> = series_list.iter()
                 .filter(|s| {
                     let display_name = Self::sanitize_text(&s.name);
-                    search.is_empty() || display_name.to_lowercase().contains(&search)
+                    (search.is_empty() || display_name.to_lowercase().contains(&search))
+                        && Self::genre_matches(s.genre.as_deref(), genre_filter.as_deref())
+                        && (show_hidden || !self.is_hidden(&self.series_hide_key(s.series_id)))
                 })
                 .collect();
             
+            let mut to_toggle_hidden: Option<(String, String)> = None;
             let mut clicked_series: Option<i64> = None;
+            let mut to_show_detail: Option<SeriesInfo> = None;
             let mut toggle_fav: Option<FavoriteItem> = None;
             
             // Calculate columns based on available width
@@ -4544,7 +9569,53 @@ impl IPTVApp {
             let text_size = 14.0;
             let star_size = 18.0;
             
-            if num_columns > 1 {
+            if self.vod_view_mode == ViewMode::Grid {
+                // Poster wall
+                ui.horizontal_wrapped(|ui| {
+                    ui.spacing_mut().item_spacing = egui::vec2(10.0, 10.0);
+                    for series in &filtered {
+                        let series_url = self.series_hide_key(series.series_id);
+                        let is_fav = self.is_favorite(&series_url);
+                        let response = self.show_poster_tile(ui, &series.name, series.cover.as_deref(), is_fav);
+                        if response.clicked() {
+                            clicked_series = Some(series.series_id);
+                        }
+                        response.context_menu(|ui| {
+                            if ui.button("📂 Open").clicked() {
+                                clicked_series = Some(series.series_id);
+                                ui.close();
+                            }
+                            if ui.button("ℹ Details").clicked() {
+                                to_show_detail = Some((*series).clone());
+                                ui.close();
+                            }
+                            if ui.button(if self.is_hidden(&series_url) { "👁 Unhide" } else { "🚫 Hide" }).clicked() {
+                                to_toggle_hidden = Some((series_url.clone(), series.name.clone()));
+                                ui.close();
+                            }
+                            let fav_label = if is_fav { "☆ Remove from favorites" } else { "★ Add to favorites" };
+                            if ui.button(fav_label).clicked() {
+                                toggle_fav = Some(FavoriteItem {
+                                    name: series.name.clone(),
+                                    url: series_url.clone(),
+                                    stream_type: "series".to_string(),
+                                    stream_id: None,
+                                    series_id: Some(series.series_id),
+                                    category_name: category_name.clone(),
+                                    container_extension: None,
+                                    season_num: None,
+                                    episode_num: None,
+                                    series_name: None,
+                                    playlist_source: None,
+                                    resume_secs: None,
+                                    duration_secs: None,
+                                });
+                                ui.close();
+                            }
+                        });
+                    }
+                });
+            } else if num_columns > 1 {
                 // Multi-column grid layout
                 egui::Grid::new("series_list_grid")
                     .num_columns(num_columns)
@@ -4553,8 +9624,10 @@ impl IPTVApp {
                     .max_col_width(item_width)
                     .show(ui, |ui| {
                         for (i, series) in filtered.iter().enumerate() {
-                            let series_url = format!("series://{}", series.series_id);
+                            let series_url = self.series_hide_key(series.series_id);
                             let is_fav = self.is_favorite(&series_url);
+                            let is_hidden = self.is_hidden(&series_url);
+                            let hide_key = series_url.clone();
                             
                             ui.horizontal(|ui| {
                                 let fav_text = if is_fav { 
@@ -4575,11 +9648,17 @@ impl IPTVApp {
                                         episode_num: None,
                                         series_name: None,
                                         playlist_source: None,
+                                        resume_secs: None,
+                                        duration_secs: None,
                                     });
                                 }
                                 
+                                if ui.button("ℹ").on_hover_text("Show details").clicked() {
+                                    to_show_detail = Some((*series).clone());
+                                }
+                                
                                 let display_name = Self::sanitize_text(&series.name);
-                                let name_width = item_width - 40.0;
+                                let name_width = item_width - 70.0;
                                 let truncated = Self::truncate_to_width(&display_name, name_width);
                                 let response = ui.button(egui::RichText::new(&truncated).size(text_size));
                                 if truncated != display_name {
@@ -4588,6 +9667,7 @@ impl IPTVApp {
                                 if response.clicked() {
                                     clicked_series = Some(series.series_id);
                                 }
+                                Self::hide_context_menu(&response, &hide_key, &series.name, is_hidden, &mut to_toggle_hidden);
                             });
                             
                             if (i + 1) % num_columns == 0 {
@@ -4599,8 +9679,10 @@ impl IPTVApp {
                 // Single column layout
                 for series in &filtered {
                     let display_name = Self::sanitize_text(&series.name);
-                    let series_url = format!("series://{}", series.series_id);
+                    let series_url = self.series_hide_key(series.series_id);
                     let is_fav = self.is_favorite(&series_url);
+                    let is_hidden = self.is_hidden(&series_url);
+                    let hide_key = series_url.clone();
                     
                     ui.horizontal(|ui| {
                         let fav_text = if is_fav { 
@@ -4621,12 +9703,23 @@ impl IPTVApp {
                                 episode_num: None,
                                 series_name: None,
                                 playlist_source: None,
+                                resume_secs: None,
+                                duration_secs: None,
                             });
                         }
                         
-                        if ui.button(&display_name).clicked() {
+                        if ui.button("ℹ").on_hover_text("Show details").clicked() {
+                            to_show_detail = Some((*series).clone());
+                        }
+                        
+                        if is_hidden {
+                            ui.label(egui::RichText::new("🚫").weak()).on_hover_text("Hidden");
+                        }
+                        let response = ui.button(&display_name);
+                        if response.clicked() {
                             clicked_series = Some(series.series_id);
                         }
+                        Self::hide_context_menu(&response, &hide_key, &series.name, is_hidden, &mut to_toggle_hidden);
                     });
                 }
             }
@@ -4635,6 +9728,14 @@ impl IPTVApp {
                 self.toggle_favorite(fav);
             }
             
+            if let Some((url, name)) = to_toggle_hidden {
+                self.toggle_hidden(&url, &name);
+            }
+            
+            if let Some(series) = to_show_detail {
+                self.fetch_series_detail(&series);
+            }
+            
             if let Some(sid) = clicked_series {
                 self.save_scroll_position(ui.ctx());
                 self.navigation_stack.push(NavigationLevel::Seasons(sid));
@@ -4645,6 +9746,7 @@ impl IPTVApp {
 
         // Categories (sorted)
         let mut clicked_category: Option<(String, String)> = None;
+        let mut toggle_fav_category: Option<(String, String)> = None;
         
         // Clone and sort categories
         let mut sorted_categories: Vec<_> = self.series_categories.clone();
@@ -4654,9 +9756,12 @@ impl IPTVApp {
                 sorted_categories.sort_by_cached_key(|c| c.category_name.to_lowercase());
                 sorted_categories.reverse();
             }
-            SortOrder::Default => {} // Keep server order
+            SortOrder::MostWatched | SortOrder::Default => {} // Keep server order (play counts are per stream)
         }
         
+        // Pinned categories float to the top
+        sorted_categories.sort_by_key(|c| !self.is_favorite_category("series", &c.category_id));
+        
         // Filter categories by search
         let filtered: Vec<_> = sorted_categories.iter()
             .filter(|cat| {
@@ -4678,13 +9783,20 @@ impl IPTVApp {
         };
         let num_columns = requested_columns.min(max_columns_for_width).max(1);
         
+        self.show_count_prefetch_button(ui);
+        
         if num_columns == 1 {
             // Single column
             for cat in &filtered {
-                let display_name = Self::sanitize_text(&cat.category_name);
-                if ui.button(&display_name).clicked() {
-                    clicked_category = Some((cat.category_id.clone(), cat.category_name.clone()));
-                }
+                let button_text = self.category_button_text("series", cat);
+                ui.horizontal(|ui| {
+                    if self.category_star_button(ui, "series", &cat.category_id) {
+                        toggle_fav_category = Some((cat.category_id.clone(), cat.category_name.clone()));
+                    }
+                    if ui.button(button_text).clicked() {
+                        clicked_category = Some((cat.category_id.clone(), cat.category_name.clone()));
+                    }
+                });
             }
         } else {
             // Multi-column grid
@@ -4696,10 +9808,15 @@ impl IPTVApp {
                 .max_col_width(item_width)
                 .show(ui, |ui| {
                     for (i, cat) in filtered.iter().enumerate() {
-                        let display_name = Self::sanitize_text(&cat.category_name);
-                        if ui.button(&display_name).clicked() {
-                            clicked_category = Some((cat.category_id.clone(), cat.category_name.clone()));
-                        }
+                        let button_text = self.category_button_text("series", cat);
+                        ui.horizontal(|ui| {
+                            if self.category_star_button(ui, "series", &cat.category_id) {
+                                toggle_fav_category = Some((cat.category_id.clone(), cat.category_name.clone()));
+                            }
+                            if ui.button(button_text).clicked() {
+                                clicked_category = Some((cat.category_id.clone(), cat.category_name.clone()));
+                            }
+                        });
                         if (i + 1) % num_columns == 0 {
                             ui.end_row();
                         }
@@ -4707,6 +9824,10 @@ impl IPTVApp {
                 });
         }
         
+        if let Some((cat_id, cat_name)) = toggle_fav_category {
+            self.toggle_favorite_category("series", &cat_id, &cat_name);
+        }
+        
         if let Some((cat_id, cat_name)) = clicked_category {
             self.save_scroll_position(ui.ctx());
             self.navigation_stack.push(NavigationLevel::Series(cat_name));
@@ -4714,6 +9835,23 @@ impl IPTVApp {
         }
     }
 
+    /// Order one favorites group by the chosen sort (favorites are stored oldest first)
+    fn sort_favorites(&self, favs: &mut [FavoriteItem]) {
+        match self.favorites_sort_order {
+            SortOrder::NameAsc => favs.sort_by_cached_key(|f| self.display_name(&f.url, &f.name).to_lowercase()),
+            SortOrder::NameDesc => {
+                favs.sort_by_cached_key(|f| self.display_name(&f.url, &f.name).to_lowercase());
+                favs.reverse();
+            }
+            SortOrder::MostWatched => {
+                // Stable sort - unwatched favorites keep the order they were added
+                let counts = &self.play_counts;
+                favs.sort_by_key(|f| std::cmp::Reverse(counts.get(&f.url).copied().unwrap_or(0)));
+            }
+            SortOrder::Default => {}
+        }
+    }
+    
     fn show_favorites_tab(&mut self, ui: &mut egui::Ui) {
         // Check if we're viewing a favorite series inline
         if let Some((series_id, ref series_name)) = self.fav_viewing_series.clone() {
@@ -4759,15 +9897,18 @@ impl IPTVApp {
                             episode_num: None,
                             series_name: Some(series_name.clone()),
                             playlist_source: None,
+                            resume_secs: None,
+                            duration_secs: None,
                         });
                     }
                 });
                 ui.separator();
                 
-                let episodes = self.fav_series_episodes.clone();
+                let episodes = self.ordered_episodes(self.fav_series_episodes.clone());
                 let mut to_play: Option<Episode> = None;
                 let mut toggle_ep_fav: Option<FavoriteItem> = None;
                 
+                self.episode_order_toggle(ui, &episodes);
                 for ep in &episodes {
                     let ep_url = format!("episode://{}:{}:{}", series_id, season, ep.id);
                     let is_ep_fav = self.is_favorite(&ep_url);
@@ -4792,6 +9933,8 @@ impl IPTVApp {
                                 episode_num: Some(ep.episode_num),
                                 series_name: Some(series_name.clone()),
                                 playlist_source: None,
+                                resume_secs: None,
+                                duration_secs: None,
                             });
                         }
                         
@@ -4799,6 +9942,9 @@ impl IPTVApp {
                             to_play = Some(ep.clone());
                         }
                         ui.label(format!("E{}: {}", ep.episode_num, Self::sanitize_text(&ep.title)));
+                        if let Some(date) = &ep.air_date {
+                            ui.label(egui::RichText::new(date).weak().small());
+                        }
                     });
                 }
                 
@@ -4841,6 +9987,8 @@ impl IPTVApp {
                             episode_num: None,
                             series_name: Some(series_name.clone()),
                             playlist_source: None,
+                            resume_secs: None,
+                            duration_secs: None,
                         });
                     }
                     
@@ -4872,32 +10020,65 @@ impl IPTVApp {
         
         let name_width = self.channel_name_width;
         
+        // Sort applies within each group below
+        ui.horizontal(|ui| {
+            ui.label("Sort:");
+            let selected = if self.favorites_sort_order == SortOrder::Default {
+                "🕐 Date added".to_string()
+            } else {
+                format!("{} {}", self.favorites_sort_order.icon(), self.favorites_sort_order.label())
+            };
+            egui::ComboBox::from_id_salt("favorites_sort")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    if ui.selectable_value(&mut self.favorites_sort_order, SortOrder::Default, "🕐 Date added").changed() {
+                        self.config.favorites_sort_order = self.favorites_sort_order;
+                        self.config.save();
+                    }
+                    if ui.selectable_value(&mut self.favorites_sort_order, SortOrder::NameAsc, "↑ Name A-Z").changed() {
+                        self.config.favorites_sort_order = self.favorites_sort_order;
+                        self.config.save();
+                    }
+                    if ui.selectable_value(&mut self.favorites_sort_order, SortOrder::NameDesc, "↓ Name Z-A").changed() {
+                        self.config.favorites_sort_order = self.favorites_sort_order;
+                        self.config.save();
+                    }
+                    if ui.selectable_value(&mut self.favorites_sort_order, SortOrder::MostWatched, "🔥 Most watched").changed() {
+                        self.config.favorites_sort_order = self.favorites_sort_order;
+                        self.config.save();
+                    }
+                });
+            ui.label(format!("({})", self.favorites.len()));
+            
+            ui.separator();
+            if ui.button("⏮").on_hover_text("Previous live favorite (PageUp)").clicked() {
+                self.zap_favorite(-1);
+            }
+            if ui.button("⏭").on_hover_text("Next live favorite (PageDown)").clicked() {
+                self.zap_favorite(1);
+            }
+        });
+        
         // Clone favorites to avoid borrow issues
-        let live_favs: Vec<_> = self.favorites.iter()
-            .filter(|f| f.stream_type == "live")
-            .cloned()
-            .collect();
-        let movie_favs: Vec<_> = self.favorites.iter()
-            .filter(|f| f.stream_type == "movie")
-            .cloned()
-            .collect();
-        let series_favs: Vec<_> = self.favorites.iter()
-            .filter(|f| f.stream_type == "series")
-            .cloned()
-            .collect();
-        let season_favs: Vec<_> = self.favorites.iter()
-            .filter(|f| f.stream_type == "season")
-            .cloned()
-            .collect();
-        let episode_favs: Vec<_> = self.favorites.iter()
-            .filter(|f| f.stream_type == "episode")
-            .cloned()
-            .collect();
+        let favorites_of = |stream_type: &str| -> Vec<FavoriteItem> {
+            let mut favs: Vec<_> = self.favorites.iter()
+                .filter(|f| f.stream_type == stream_type)
+                .cloned()
+                .collect();
+            self.sort_favorites(&mut favs);
+            favs
+        };
+        let live_favs = favorites_of("live");
+        let movie_favs = favorites_of("movie");
+        let series_favs = favorites_of("series");
+        let season_favs = favorites_of("season");
+        let episode_favs = favorites_of("episode");
         
         let mut to_remove: Option<String> = None;
         let mut to_play: Option<FavoriteItem> = None;
         let mut to_view_series: Option<(i64, String)> = None;
         let mut to_view_season: Option<(i64, i32, String)> = None; // series_id, season, series_name
+        let mut to_rename: Option<(String, String)> = None;
         
         if !live_favs.is_empty() {
             egui::CollapsingHeader::new(format!("📡 Live Channels ({})", live_favs.len()))
@@ -4911,8 +10092,11 @@ impl IPTVApp {
                             if ui.button("▶").clicked() {
                                 to_play = Some(fav.clone());
                             }
-                            self.show_channel_name(ui, &fav.name, name_width, false);
-                            self.show_epg_inline(ui, &fav.name, None);
+                            let name_response = self.show_channel_name(ui, &fav.url, &fav.name, name_width, false);
+                            let name_response = self.with_mini_epg(name_response, &fav.name, None, None);
+                            Self::rename_context_menu(&name_response, &fav.url, &fav.name, &mut to_rename);
+                            self.show_container_label(ui, &fav.url, fav.container_extension.as_deref());
+                            self.show_epg_inline(ui, &fav.name, None, None);
                             if let Some(ref src) = fav.playlist_source {
                                 ui.label(egui::RichText::new(format!("[{}]", src)).small().color(egui::Color32::from_rgb(100, 149, 237)));
                             } else {
@@ -4935,7 +10119,9 @@ impl IPTVApp {
                             if ui.button("▶").clicked() {
                                 to_play = Some(fav.clone());
                             }
-                            self.show_channel_name(ui, &fav.name, name_width, false);
+                            let name_response = self.show_channel_name(ui, &fav.url, &fav.name, name_width, false);
+                            Self::rename_context_menu(&name_response, &fav.url, &fav.name, &mut to_rename);
+                            self.show_container_label(ui, &fav.url, fav.container_extension.as_deref());
                             if let Some(ref src) = fav.playlist_source {
                                 ui.label(egui::RichText::new(format!("[{}]", src)).small().color(egui::Color32::from_rgb(100, 149, 237)));
                             } else {
@@ -4960,7 +10146,7 @@ impl IPTVApp {
                                     to_view_series = Some((series_id, fav.name.clone()));
                                 }
                             }
-                            ui.label(Self::sanitize_text(&fav.name));
+                            ui.label(self.display_name(&fav.url, &fav.name));
                             ui.label(egui::RichText::new(format!("({})", Self::sanitize_text(&fav.category_name))).weak());
                         });
                     }
@@ -4982,7 +10168,7 @@ impl IPTVApp {
                                     to_view_season = Some((series_id, season, series_name));
                                 }
                             }
-                            ui.label(Self::sanitize_text(&fav.name));
+                            ui.label(self.display_name(&fav.url, &fav.name));
                         });
                     }
                 });
@@ -5000,12 +10186,16 @@ impl IPTVApp {
                             if ui.button("▶").clicked() {
                                 to_play = Some(fav.clone());
                             }
-                            ui.label(Self::sanitize_text(&fav.name));
+                            ui.label(self.display_name(&fav.url, &fav.name));
                         });
                     }
                 });
         }
         
+        if let Some((url, name)) = to_rename {
+            self.start_rename(url, name);
+        }
+        
         // Handle view season action (stay in favorites)
         if let Some((series_id, season, series_name)) = to_view_season {
             self.fav_viewing_series = Some((series_id, series_name));
@@ -5037,13 +10227,24 @@ impl IPTVApp {
                 self.status_message = format!("Removed '{}' from favorites", name);
                 // Auto-save
                 self.config.favorites_json = serde_json::to_string(&self.favorites).unwrap_or_default();
-                self.config.save();
+                self.save_config_soon();
             }
         }
         
         ui.add_space(20.0);
         ui.separator();
         
+        if ui.button("🧹 Merge Duplicates")
+            .on_hover_text("Remove older favorites that point at the same stream or episode\nas a newer one with a different URL (e.g. after a provider change)")
+            .clicked()
+        {
+            if duplicate_favorites(&self.favorites).is_empty() {
+                self.status_message = "No duplicate favorites found".to_string();
+            } else {
+                self.show_merge_favorites_confirm = true;
+            }
+        }
+        
         if ui.button("🗑 Clear All Favorites").clicked() {
             self.favorites.clear();
             self.config.favorites_json.clear();
@@ -5082,6 +10283,7 @@ impl IPTVApp {
         let mut to_play: Option<FavoriteItem> = None;
         let mut to_remove: Option<usize> = None;
         let mut to_toggle_fav: Option<FavoriteItem> = None;
+        let mut to_rename: Option<(String, String)> = None;
         
         for (idx, item) in recent.iter().enumerate() {
             ui.horizontal(|ui| {
@@ -5117,10 +10319,15 @@ impl IPTVApp {
                 ui.label(type_icon);
                 
                 // Fixed-width name with truncation
-                self.show_channel_name(ui, &item.name, name_width, false);
+                let mut name_response = self.show_channel_name(ui, &item.url, &item.name, name_width, false);
+                if item.stream_type == "live" {
+                    name_response = self.with_mini_epg(name_response, &item.name, None, None);
+                }
+                Self::rename_context_menu(&name_response, &item.url, &item.name, &mut to_rename);
+                self.show_container_label(ui, &item.url, item.container_extension.as_deref());
                 
                 // Show EPG info (will only display if EPG match found)
-                self.show_epg_inline(ui, &item.name, None);
+                self.show_epg_inline(ui, &item.name, None, None);
                 
                 // Show playlist source or category
                 if let Some(ref src) = item.playlist_source {
@@ -5141,6 +10348,10 @@ impl IPTVApp {
             self.toggle_favorite(item);
         }
         
+        if let Some((url, name)) = to_rename {
+            self.start_rename(url, name);
+        }
+        
         // Handle play
         if let Some(item) = to_play {
             self.play_favorite(&item);
@@ -5154,9 +10365,13 @@ impl IPTVApp {
         }
     }
 
-    fn add_to_recent(&mut self, item: FavoriteItem, reorder: bool) {
+    fn add_to_recent(&mut self, mut item: FavoriteItem, reorder: bool) {
         if reorder {
-            // Remove if already in list (to move to top)
+            // Remove if already in list (to move to top), keeping its resume position
+            if let Some(previous) = self.recent_watched.iter().find(|r| r.url == item.url) {
+                item.resume_secs = item.resume_secs.or(previous.resume_secs);
+                item.duration_secs = item.duration_secs.or(previous.duration_secs);
+            }
             self.recent_watched.retain(|r| r.url != item.url);
             
             // Add to front (newest first)
@@ -5176,10 +10391,46 @@ impl IPTVApp {
         
         // Save
         self.config.recent_watched_json = serde_json::to_string(&self.recent_watched).unwrap_or_default();
-        self.config.save();
+        self.save_config_soon();
+    }
+
+    /// Whether a URL was last played as a movie or episode (live streams never resume)
+    fn is_vod_history_url(&self, url: &str) -> bool {
+        self.recent_watched.iter().chain(&self.favorites)
+            .find(|r| r.url == url)
+            .is_some_and(|r| r.stream_type != "live")
+    }
+    
+    /// Saved resume position of a movie or episode
+    fn resume_secs_for(&self, url: &str) -> Option<u64> {
+        self.recent_watched.iter().chain(&self.favorites)
+            .filter(|r| r.url == url && r.stream_type != "live")
+            .find_map(|r| r.resume_secs)
+    }
+    
+    /// Remember (or clear, with `None`) where a movie or episode was stopped, and its length when known
+    fn store_resume_position(&mut self, url: &str, resume_secs: Option<u64>, duration_secs: Option<u64>) {
+        let mut favorites_changed = false;
+        for item in self.recent_watched.iter_mut().filter(|r| r.url == url) {
+            item.resume_secs = resume_secs;
+            item.duration_secs = duration_secs.or(item.duration_secs);
+        }
+        for item in self.favorites.iter_mut().filter(|f| f.url == url) {
+            let duration_secs = duration_secs.or(item.duration_secs);
+            if item.resume_secs != resume_secs || item.duration_secs != duration_secs {
+                item.resume_secs = resume_secs;
+                item.duration_secs = duration_secs;
+                favorites_changed = true;
+            }
+        }
+        self.config.recent_watched_json = serde_json::to_string(&self.recent_watched).unwrap_or_default();
+        if favorites_changed {
+            self.config.favorites_json = serde_json::to_string(&self.favorites).unwrap_or_default();
+        }
+        self.save_config_soon();
     }
 
-    fn show_info_tab(&self, ui: &mut egui::Ui) {
+    fn show_info_tab(&mut self, ui: &mut egui::Ui) {
         ui.heading("Account Information");
         ui.separator();
         
@@ -5222,6 +10473,57 @@ impl IPTVApp {
                 ui.label("Expiry:");
                 ui.label(&self.user_info.expiry);
                 ui.end_row();
+                
+                ui.label("Expiry warning:");
+                if ui.add(egui::DragValue::new(&mut self.expiry_warning_days).range(0..=90).suffix(" days"))
+                    .on_hover_text("Show a banner when the subscription expires within this many days (0 = off)")
+                    .changed()
+                {
+                    self.config.expiry_warning_days = self.expiry_warning_days;
+                    self.config.save();
+                    self.expiry_banner_dismissed = false;
+                }
+                ui.end_row();
+            });
+        
+        ui.add_space(12.0);
+        ui.horizontal(|ui| {
+            ui.heading(format!("Recent failures ({})", self.recent_failures.len()));
+            if !self.recent_failures.is_empty() && ui.button("🗑 Clear").clicked() {
+                self.recent_failures.clear();
+            }
+        });
+        ui.separator();
+        
+        if self.recent_failures.is_empty() {
+            ui.label(egui::RichText::new("No failures this session").weak());
+            return;
+        }
+        
+        egui::ScrollArea::vertical()
+            .id_salt("recent_failures_scroll")
+            .max_height(260.0)
+            .show(ui, |ui| {
+                egui::Grid::new("recent_failures_grid")
+                    .num_columns(4)
+                    .striped(true)
+                    .spacing([12.0, 4.0])
+                    .show(ui, |ui| {
+                        ui.strong("Time");
+                        ui.strong("What");
+                        ui.strong("Playlist");
+                        ui.strong("Error");
+                        ui.end_row();
+                        
+                        // Newest first
+                        for failure in self.recent_failures.iter().rev() {
+                            ui.label(Self::format_datetime(failure.time));
+                            ui.label(&failure.what);
+                            ui.label(failure.playlist.as_deref().unwrap_or("-"));
+                            ui.label(egui::RichText::new(&failure.message).color(egui::Color32::from_rgb(230, 120, 120)));
+                            ui.end_row();
+                        }
+                    });
             });
     }
     
@@ -5264,33 +10566,121 @@ impl IPTVApp {
         ui.heading("📺 EPG Guide");
         ui.separator();
         
-        let adjusted_now = self.get_adjusted_now();
+        // A preview time only moves the grid - catch-up and reminders still use the live clock
+        let live_now = self.get_adjusted_now();
+        let adjusted_now = self.epg_preview_time.unwrap_or(live_now);
+        let now_color = if self.epg_preview_time.is_some() {
+            egui::Color32::from_rgb(255, 165, 0)
+        } else {
+            egui::Color32::from_rgb(220, 60, 60)
+        };
         
-        // Fixed layout for scrollable grid
+        // Timeline layout - program width is proportional to duration
         let channel_col_width = self.channel_name_width;
-        let prog_col_width = 130.0;
-        let num_progs = 7; // Show 7 programs (current + 6 upcoming), user scrolls to see more
-        
-        // Time header labels - either offset or actual time
-        let time_labels: Vec<String> = if self.epg_show_actual_time {
-            // Calculate actual times based on adjusted_now
-            let offsets_mins = [0, 30, 60, 90, 120, 150, 180];
-            offsets_mins.iter().map(|&offset| {
-                let ts = adjusted_now + (offset * 60);
-                Self::format_time(ts)
-            }).collect()
+        let row_height = 22.0;
+        let grid_tick_secs = self.epg_grid_step_mins.max(5) as i64 * 60;
+        // Full-day mode: the window is as much of a day as fits at a fixed scale
+        let grid_window_secs = if self.epg_grid_full_day {
+            let timeline_width = (ui.available_width() - channel_col_width).max(100.0);
+            ((timeline_width / EPG_FULL_DAY_PX_PER_HOUR * 3600.0) as i64).clamp(3600, 24 * 3600)
         } else {
-            // Offset mode
-            vec![
-                "Now".to_string(),
-                "+30m".to_string(),
-                "+60m".to_string(),
-                "+90m".to_string(),
-                "+2h".to_string(),
-                "+2.5h".to_string(),
-                "+3h".to_string(),
-            ]
+            self.epg_grid_window_hours.max(1) as i64 * 3600
         };
+        let step_text = if grid_tick_secs % 3600 == 0 {
+            format!("{} hour", grid_tick_secs / 3600)
+        } else {
+            format!("{} minutes", grid_tick_secs / 60)
+        };
+        let window_start = (adjusted_now - adjusted_now.rem_euclid(grid_tick_secs)) + self.epg_grid_offset_secs;
+        // The day bar shows the day the window is mostly in, so scrolling past midnight moves on to the next one
+        let day_start = epg::local_day_start(window_start + grid_window_secs / 2);
+        let day_end = epg::local_day_start(day_start + 30 * 3600);
+        let window_end = window_start + grid_window_secs;
+        // Time step containing "now" - tinted down every row so the current column stays easy to find
+        let now_column_start = adjusted_now - adjusted_now.rem_euclid(grid_tick_secs);
+        let now_column = (now_column_start < window_end && now_column_start + grid_tick_secs > window_start)
+            .then_some((now_column_start.max(window_start), (now_column_start + grid_tick_secs).min(window_end)));
+        
+        // Timeline navigation
+        ui.horizontal(|ui| {
+            if ui.button("⏪").on_hover_text("Back 1 hour").clicked() {
+                self.epg_grid_offset_secs -= 3600;
+            }
+            if ui.button("◀").on_hover_text(format!("Back {}", step_text)).clicked() {
+                self.epg_grid_offset_secs -= grid_tick_secs;
+            }
+            if ui.button("Now").on_hover_text("Jump to current time").clicked() {
+                self.epg_grid_offset_secs = 0;
+            }
+            if ui.button("▶").on_hover_text(format!("Forward {}", step_text)).clicked() {
+                self.epg_grid_offset_secs += grid_tick_secs;
+            }
+            if ui.button("⏩").on_hover_text("Forward 1 hour").clicked() {
+                self.epg_grid_offset_secs += 3600;
+            }
+            if ui.button("⤒ Top").on_hover_text("Scroll back to the first channel").clicked() {
+                self.epg_grid_scroll_to_top = true;
+            }
+            ui.label(egui::RichText::new(format!(
+                "{} - {}",
+                Self::format_datetime(window_start),
+                Self::format_time(window_end)
+            )).weak().small());
+        });
+        
+        // Preview time - view the guide as if it were another time of day
+        ui.horizontal(|ui| {
+            ui.label("👁 Preview at:");
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut self.epg_preview_input)
+                    .desired_width(120.0)
+                    .hint_text("HH:MM")
+            ).on_hover_text("Show the grid as if it were this time (HH:MM today or YYYY-MM-DD HH:MM).\nPlayback, catch-up and reminders keep using the real time.");
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if ui.button("Set").clicked() || submitted {
+                match epg::parse_local_time(&self.epg_preview_input, live_now) {
+                    Some(ts) => {
+                        self.epg_preview_time = Some(ts);
+                        self.epg_grid_offset_secs = 0;
+                    }
+                    None => self.status_message = format!("Invalid preview time: {}", self.epg_preview_input.trim()),
+                }
+            }
+            if let Some(preview) = self.epg_preview_time {
+                if ui.button("✖ Live").on_hover_text("Return to the current time").clicked() {
+                    self.epg_preview_time = None;
+                    self.epg_preview_input.clear();
+                    self.epg_grid_offset_secs = 0;
+                }
+                ui.label(egui::RichText::new(format!("PREVIEW - showing {} (not live)", Self::format_datetime(preview)))
+                    .color(egui::Color32::from_rgb(255, 165, 0))
+                    .strong());
+            }
+        });
+        
+        // Category legend - click a swatch to change its color
+        let mut colors_changed = false;
+        ui.horizontal_wrapped(|ui| {
+            ui.spacing_mut().item_spacing.x = 4.0;
+            for entry in &mut self.epg_category_colors {
+                if egui::color_picker::color_edit_button_srgb(ui, &mut entry.color)
+                    .on_hover_text(format!("Categories containing: {}", entry.keywords.join(", ")))
+                    .changed()
+                {
+                    colors_changed = true;
+                }
+                ui.label(egui::RichText::new(&entry.label).small());
+                ui.add_space(6.0);
+            }
+            if ui.small_button("↺").on_hover_text("Restore default category colors").clicked() {
+                self.epg_category_colors = default_epg_category_colors();
+                colors_changed = true;
+            }
+        });
+        if colors_changed {
+            self.config.epg_category_colors = self.epg_category_colors.clone();
+            self.config.save();
+        }
         
         // Get channels to display based on current view
         let channels_to_show: Vec<(String, Option<String>)> = match self.current_tab {
@@ -5357,18 +10747,60 @@ impl IPTVApp {
                 egui::Stroke::new(2.0, handle_color)
             );
             
-            // Time labels
-            for label in &time_labels {
-                ui.add_sized([prog_col_width, 20.0], egui::Label::new(egui::RichText::new(label).strong()));
+            // Time axis - ticks every step, positioned proportionally
+            let timeline_width = ui.available_width().max(100.0);
+            let (axis_rect, _) = ui.allocate_exact_size(egui::vec2(timeline_width, 20.0), egui::Sense::hover());
+            let px_per_sec = timeline_width / grid_window_secs as f32;
+            let painter = ui.painter_at(axis_rect);
+            let text_color = ui.visuals().strong_text_color();
+            
+            // First tick on a step boundary (window may be dragged off-boundary)
+            let mut tick = window_start + (grid_tick_secs - window_start.rem_euclid(grid_tick_secs)) % grid_tick_secs;
+            while tick < window_end {
+                let x = axis_rect.left() + (tick - window_start) as f32 * px_per_sec;
+                painter.vline(x, axis_rect.y_range(), egui::Stroke::new(1.0, egui::Color32::from_gray(90)));
+                
+                let label = if self.epg_show_actual_time {
+                    Self::format_time(tick)
+                } else {
+                    Self::format_relative_offset(tick - adjusted_now)
+                };
+                painter.text(
+                    egui::pos2(x + 3.0, axis_rect.center().y),
+                    egui::Align2::LEFT_CENTER,
+                    label,
+                    egui::FontId::proportional(12.0),
+                    text_color,
+                );
+                tick += grid_tick_secs;
+            }
+            
+            // "Now" marker (orange while previewing another time)
+            if let Some((from, to)) = now_column {
+                let x0 = axis_rect.left() + (from - window_start) as f32 * px_per_sec;
+                let x1 = axis_rect.left() + (to - window_start) as f32 * px_per_sec;
+                painter.rect_filled(egui::Rect::from_x_y_ranges(x0..=x1, axis_rect.y_range()), 0.0, now_color.gamma_multiply(0.12));
+            }
+            if adjusted_now >= window_start && adjusted_now < window_end {
+                let x = axis_rect.left() + (adjusted_now - window_start) as f32 * px_per_sec;
+                painter.vline(x, axis_rect.y_range(), egui::Stroke::new(2.0, now_color));
             }
         });
         
         ui.separator();
         
+        // Accumulated horizontal drag on the timeline (applied after the rows are drawn)
+        let mut timeline_drag = 0.0;
+        let mut timeline_px_per_sec = 0.0;
+        
         // Vertical scroll area for channel rows only
-        egui::ScrollArea::vertical()
+        let mut grid_scroll = egui::ScrollArea::vertical()
             .id_salt("epg_grid_scroll")
-            .auto_shrink([false, false])
+            .auto_shrink([false, false]);
+        if std::mem::take(&mut self.epg_grid_scroll_to_top) {
+            grid_scroll = grid_scroll.vertical_scroll_offset(0.0);
+        }
+        let grid_output = grid_scroll
             .show(ui, |ui| {
                 // Channel rows
                 for (channel_name, epg_id_opt) in &channels_to_show {
@@ -5430,12 +10862,46 @@ impl IPTVApp {
                     
                     let is_selected = self.selected_epg_channel.as_ref() == Some(channel_name);
                     
+                    // Logo from XMLTV <icon>, falling back to the stream's icon
+                    let logo_url = epg_id.as_ref()
+                        .and_then(|id| self.epg_data.as_ref()?.channels.get(id)?.icon.clone())
+                        .or_else(|| {
+                            self.current_channels.iter()
+                                .find(|c| c.name == *channel_name)
+                                .and_then(|c| c.stream_icon.clone())
+                        });
+                    
+                    // M3U tvg-shift moves this row's programs relative to the shared timeline
+                    let row_channel = self.current_channels.iter().find(|c| c.name == *channel_name);
+                    let shift_secs = Self::tvg_shift_secs(row_channel.and_then(|c| c.tvg_shift));
+                    let catchup = row_channel
+                        .filter(|c| c.catchup.is_some() || c.catchup_source.is_some())
+                        .map(|c| (c.url.clone(), c.catchup.clone(), c.catchup_source.clone()));
+                    // Xtream archive: stream id and how far back it reaches
+                    let archive = row_channel.and_then(|c| Some((c.stream_id?, c.archive_days?)));
+                    let (window_start, window_end, adjusted_now, live_now) =
+                        (window_start - shift_secs, window_end - shift_secs, adjusted_now - shift_secs, live_now - shift_secs);
+                    
                     ui.horizontal(|ui| {
+                        // Channel logo - the name button shrinks so the timeline stays aligned with the header
+                        let logo_size = 20.0;
+                        let name_width = channel_col_width - 5.0 - logo_size - ui.spacing().item_spacing.x;
+                        let (logo_rect, _) = ui.allocate_exact_size(egui::vec2(logo_size, logo_size), egui::Sense::hover());
+                        // Only rows on screen request their logo - scrolled-off rows would queue downloads
+                        if let Some(url) = logo_url.as_ref().filter(|_| ui.is_rect_visible(logo_rect)) {
+                            if let ImageState::Ready(texture) = self.image_cache.get(ui.ctx(), url) {
+                                let tex_size = texture.size_vec2();
+                                let scale = (logo_size / tex_size.x).min(logo_size / tex_size.y);
+                                let image_rect = egui::Rect::from_center_size(logo_rect.center(), tex_size * scale);
+                                egui::Image::new(&texture).paint_at(ui, image_rect);
+                            }
+                        }
+                        
                         // Channel name (clickable) - use truncate_to_width for dynamic sizing
                         let name_text = Self::sanitize_text(channel_name);
-                        let short_name = Self::truncate_to_width(&name_text, channel_col_width - 10.0);
+                        let short_name = Self::truncate_to_width(&name_text, name_width - 5.0);
                         
-                        let response = ui.add_sized([channel_col_width - 5.0, 20.0], 
+                        let response = ui.add_sized([name_width, 20.0], 
                             egui::Button::new(egui::RichText::new(&short_name).strong())
                                 .selected(is_selected)
                         );
@@ -5447,75 +10913,60 @@ impl IPTVApp {
                         
                         if response.clicked() {
                             self.selected_epg_channel = Some(channel_name.clone());
+                            self.selected_epg_program = None;
                         }
                         
                         if response.double_clicked() {
-                            // Find and play the channel - check current_channels first, then favorites/recent
-                            let channel_opt = self.current_channels.iter()
-                                .find(|c| c.name == *channel_name)
-                                .cloned()
-                                .or_else(|| {
-                                    // Check favorites
-                                    self.favorites.iter()
-                                        .find(|f| f.name == *channel_name && f.stream_type == "live")
-                                        .map(|f| Channel {
-                                            name: f.name.clone(),
-                                            url: f.url.clone(),
-                                            stream_id: f.stream_id,
-                                            category_id: None,
-                                            epg_channel_id: None,
-                                            stream_icon: None,
-                                            series_id: None,
-                                            container_extension: None,
-                                            playlist_source: f.playlist_source.clone(),
-                                        })
-                                })
-                                .or_else(|| {
-                                    // Check recent
-                                    self.recent_watched.iter()
-                                        .find(|f| f.name == *channel_name && f.stream_type == "live")
-                                        .map(|f| Channel {
-                                            name: f.name.clone(),
-                                            url: f.url.clone(),
-                                            stream_id: f.stream_id,
-                                            category_id: None,
-                                            epg_channel_id: None,
-                                            stream_icon: None,
-                                            series_id: None,
-                                            container_extension: None,
-                                            playlist_source: f.playlist_source.clone(),
-                                        })
-                                });
-                            
-                            if let Some(channel) = channel_opt {
+                            if let Some(channel) = self.find_live_channel(channel_name) {
                                 self.play_channel(&channel);
                             }
                         }
                         
                         response.on_hover_text(channel_name);
                         
-                        // Program blocks - fixed width each
+                        // Program blocks - positioned and sized by start/stop time
+                        let timeline_width = ui.available_width().max(100.0);
+                        let (row_rect, row_response) = ui.allocate_exact_size(
+                            egui::vec2(timeline_width, row_height),
+                            egui::Sense::drag(),
+                        );
+                        let px_per_sec = timeline_width / grid_window_secs as f32;
+                        
+                        // Drag the timeline horizontally to scroll through time
+                        if row_response.dragged() {
+                            timeline_drag += row_response.drag_delta().x;
+                        }
+                        if row_response.hovered() {
+                            row_response.ctx.set_cursor_icon(egui::CursorIcon::Grab);
+                        }
+                        
+                        let mut select_program: Option<i64> = None;
+                        let mut reminder_toggle: Option<(String, i64)> = None;
+                        let mut catchup_play: Option<(String, i64, i64)> = None;
+                        let mut star_toggle: Option<String> = None;
                         if let Some(ref id) = epg_id {
-                            let programs = self.get_upcoming_programs(id, num_progs);
+                            let programs = self.get_programs_in_window(id, window_start, window_end);
+                            let painter = ui.painter_at(row_rect);
                             
                             for (idx, prog) in programs.iter().enumerate() {
                                 let is_current = prog.start <= adjusted_now && prog.stop > adjusted_now;
                                 let duration_mins = (prog.stop - prog.start) / 60;
                                 
-                                // Fixed width for each program block
-                                let width = prog_col_width - 6.0;
-                                
-                                // Truncate title to fit - allow more chars (roughly 6px per char)
-                                let max_chars = ((width - 8.0) / 5.5) as usize;
-                                let title: String = prog.title.chars().take(max_chars).collect();
-                                let display = if prog.title.len() > max_chars {
-                                    format!("{}…", title)
-                                } else {
-                                    title
-                                };
+                                // Clip program to the visible window
+                                let x0 = row_rect.left() + (prog.start.max(window_start) - window_start) as f32 * px_per_sec;
+                                let x1 = row_rect.left() + (prog.stop.min(window_end) - window_start) as f32 * px_per_sec;
+                                let block_rect = egui::Rect::from_min_max(
+                                    egui::pos2(x0 + 1.0, row_rect.top() + 1.0),
+                                    egui::pos2((x1 - 1.0).max(x0 + 2.0), row_rect.bottom() - 1.0),
+                                );
                                 
+                                let category_color = prog.category.as_deref()
+                                    .and_then(|c| epg_category_color(&self.epg_category_colors, c))
+                                    .map(|entry| entry.color);
                                 let bg_color = if is_current {
                                     egui::Color32::from_rgb(60, 100, 60)
+                                } else if let Some([r, g, b]) = category_color {
+                                    egui::Color32::from_rgb(r, g, b)
                                 } else if idx % 2 == 0 {
                                     egui::Color32::from_rgb(50, 50, 70)
                                 } else {
@@ -5528,33 +10979,184 @@ impl IPTVApp {
                                     egui::Color32::LIGHT_GRAY
                                 };
                                 
-                                egui::Frame::new()
-                                    .fill(bg_color)
-                                    .inner_margin(egui::Margin::symmetric(4, 3))
-                                    .corner_radius(3.0)
-                                    .show(ui, |ui| {
-                                        ui.set_min_width(width);
-                                        ui.set_max_width(width);
-                                        let response = ui.label(
-                                            egui::RichText::new(&display)
-                                                .color(text_color)
-                                        );
-                                        response.on_hover_text(format!(
-                                            "{}\n{} - {}\n{}m",
-                                            prog.title,
-                                            Self::format_time(prog.start),
-                                            Self::format_time(prog.stop),
-                                            duration_mins
-                                        ));
-                                    });
+                                painter.rect_filled(block_rect, 3.0, bg_color);
+                                
+                                // Title clipped to the block
+                                if block_rect.width() > 12.0 {
+                                    ui.painter_at(block_rect.shrink2(egui::vec2(4.0, 0.0))).text(
+                                        egui::pos2(block_rect.left() + 4.0, block_rect.center().y),
+                                        egui::Align2::LEFT_CENTER,
+                                        if self.is_favorite_program(&prog.title) { format!("⭐ {}", prog.title) } else { prog.title.clone() },
+                                        egui::FontId::proportional(12.0),
+                                        text_color,
+                                    );
+                                }
+                                
+                                let has_reminder = self.has_reminder(channel_name, prog.start);
+                                if has_reminder && block_rect.width() > 24.0 {
+                                    painter.text(
+                                        egui::pos2(block_rect.right() - 3.0, block_rect.center().y),
+                                        egui::Align2::RIGHT_CENTER,
+                                        "🔔",
+                                        egui::FontId::proportional(11.0),
+                                        egui::Color32::GOLD,
+                                    );
+                                }
+                                
+                                let block_response = ui.interact(
+                                    block_rect,
+                                    ui.id().with(("epg_prog", id.as_str(), prog.start)),
+                                    egui::Sense::click(),
+                                );
+                                if block_response.clicked() {
+                                    select_program = Some(prog.start);
+                                }
+                                let upcoming = prog.start > live_now;
+                                let in_archive = archive.is_some_and(|(_, days)| prog.start >= live_now - days as i64 * 86400);
+                                let can_catchup = (catchup.is_some() || in_archive) && prog.start <= live_now;
+                                let block_response = block_response.on_hover_ui(|ui| {
+                                    ui.set_max_width(360.0);
+                                    ui.label(egui::RichText::new(&prog.title).strong());
+                                    ui.label(format!(
+                                        "{} - {} ({}m)",
+                                        Self::format_time(prog.start),
+                                        Self::format_time(prog.stop),
+                                        duration_mins
+                                    ));
+                                    if let Some(ref cat) = prog.category {
+                                        ui.label(egui::RichText::new(format!("Genre: {}", cat)).weak());
+                                    }
+                                    if let Some(ref ep) = prog.episode {
+                                        ui.label(egui::RichText::new(format!("Episode: {}", ep)).weak());
+                                    }
+                                    if let Some(ref desc) = prog.description {
+                                        ui.separator();
+                                        ui.label(desc);
+                                    }
+                                    ui.separator();
+                                    ui.label(egui::RichText::new(if upcoming {
+                                        "Click for details, right-click to set a reminder"
+                                    } else if can_catchup {
+                                        "Click for details, right-click to play from the archive"
+                                    } else {
+                                        "Click for details, right-click to star this show"
+                                    }).small().weak());
+                                });
+                                let is_starred = self.is_favorite_program(&prog.title);
+                                block_response.context_menu(|ui| {
+                                    if upcoming {
+                                        let label = if has_reminder { "🔕 Cancel reminder" } else { "🔔 Remind me" };
+                                        if ui.button(label).clicked() {
+                                            reminder_toggle = Some((prog.title.clone(), prog.start));
+                                            ui.close();
+                                        }
+                                    } else if can_catchup && ui.button("⏪ Play from archive").clicked() {
+                                        catchup_play = Some((prog.title.clone(), prog.start, prog.stop));
+                                        ui.close();
+                                    }
+                                    if ui.button(if is_starred { "☆ Unstar show" } else { "⭐ Star show" }).clicked() {
+                                        star_toggle = Some(prog.title.clone());
+                                        ui.close();
+                                    }
+                                });
                             }
                             
                             if programs.is_empty() {
-                                ui.label(egui::RichText::new("No EPG data").weak().small());
+                                painter.text(
+                                    egui::pos2(row_rect.left() + 4.0, row_rect.center().y),
+                                    egui::Align2::LEFT_CENTER,
+                                    "No EPG data",
+                                    egui::FontId::proportional(11.0),
+                                    egui::Color32::GRAY,
+                                );
                             }
                         } else {
-                            ui.label(egui::RichText::new("No EPG ID").weak().small());
+                            ui.painter_at(row_rect).text(
+                                egui::pos2(row_rect.left() + 4.0, row_rect.center().y),
+                                egui::Align2::LEFT_CENTER,
+                                "No EPG ID",
+                                egui::FontId::proportional(11.0),
+                                egui::Color32::GRAY,
+                            );
+                        }
+                        
+                        // Now column highlight, drawn over the blocks so it runs unbroken down the grid
+                        if let Some((from, to)) = now_column {
+                            let painter = ui.painter_at(row_rect);
+                            let x0 = row_rect.left() + (from - window_start) as f32 * px_per_sec;
+                            let x1 = row_rect.left() + (to - window_start) as f32 * px_per_sec;
+                            painter.rect_filled(egui::Rect::from_x_y_ranges(x0..=x1, row_rect.y_range()), 0.0, now_color.gamma_multiply(0.08));
+                            if adjusted_now >= window_start && adjusted_now < window_end {
+                                let x = row_rect.left() + (adjusted_now - window_start) as f32 * px_per_sec;
+                                painter.vline(x, row_rect.y_range(), egui::Stroke::new(1.0, now_color.gamma_multiply(0.6)));
+                            }
+                        }
+                        
+                        if let Some(start) = select_program {
+                            self.selected_epg_channel = Some(channel_name.clone());
+                            self.selected_epg_program = Some(start);
+                        }
+                        if let Some(title) = star_toggle {
+                            self.toggle_favorite_program(&title);
+                        }
+                        if let Some((title, start)) = reminder_toggle {
+                            self.toggle_reminder(channel_name, &title, start);
+                        }
+                        if let Some((title, start, stop)) = catchup_play {
+                            // Archive servers expect real UTC times, so undo both EPG offsets
+                            let to_utc = (self.epg_time_offset * 3600.0) as i64 + shift_secs;
+                            let archive_url = match (&catchup, archive) {
+                                (Some((url, mode, source)), _) => m3u_parser::build_catchup_url(
+                                    url, mode.as_deref(), source.as_deref(),
+                                    start + to_utc, stop + to_utc, unix_timestamp(),
+                                ),
+                                (None, Some((stream_id, _))) => Some(xtream_timeshift_url(
+                                    &self.server, &self.username, &self.password,
+                                    stream_id, start + to_utc, stop + to_utc,
+                                    self.server_info.utc_offset_secs.unwrap_or(0),
+                                )),
+                                (None, None) => None,
+                            };
+                            match archive_url {
+                                Some(archive_url) => {
+                                    let channel = Channel {
+                                        name: format!("{} - {}", channel_name, title),
+                                        url: archive_url,
+                                        stream_id: None,
+                                        category_id: None,
+                                        epg_channel_id: None,
+                                        stream_icon: None,
+                                        series_id: None,
+                                        container_extension: None,
+                                        playlist_source: None,
+                                        catchup: None,
+                                        catchup_source: None,
+                                        tvg_shift: None,
+                                        alternate_urls: Vec::new(),
+                                        archive_days: None,
+                                        genre: None,
+                                        user_agent: None,
+                                        referrer: None,
+                                    };
+                                    self.play_channel(&channel);
+                                }
+                                None => {
+                                    self.status_message = format!("No catchup source for {}", channel_name);
+                                }
+                            }
+                        }
+                        
+                        // "Now" marker line
+                        if adjusted_now >= window_start && adjusted_now < window_end {
+                            let x = row_rect.left() + (adjusted_now - window_start) as f32 * px_per_sec;
+                            ui.painter_at(row_rect).vline(
+                                x,
+                                row_rect.y_range(),
+                                egui::Stroke::new(2.0, now_color),
+                            );
                         }
+                        
+                        timeline_px_per_sec = px_per_sec;
                     });
                 }
                 
@@ -5563,56 +11165,315 @@ impl IPTVApp {
                 }
             });
         
+        // Horizontal wheel/trackpad scrolling (or Shift+wheel) over the grid moves the timeline
+        if ui.rect_contains_pointer(grid_output.inner_rect) {
+            timeline_drag += ui.input(|i| i.smooth_scroll_delta.x);
+        }
+        if timeline_drag != 0.0 && timeline_px_per_sec > 0.0 {
+            self.epg_grid_offset_secs -= (timeline_drag / timeline_px_per_sec) as i64;
+        }
+        
+        if self.epg_grid_full_day {
+            ui.horizontal(|ui| {
+                // Same time of day on the previous/next day
+                if ui.small_button("◀ Day").on_hover_text("Previous day").clicked() {
+                    self.epg_grid_offset_secs += epg::local_day_start(day_start - 1) - day_start;
+                }
+                let date = Self::format_datetime(day_start);
+                ui.label(egui::RichText::new(date.split(' ').next().unwrap_or_default()).small());
+                if ui.small_button("Day ▶").on_hover_text("Next day").clicked() {
+                    self.epg_grid_offset_secs += day_end - day_start;
+                }
+            });
+            if let Some(target) = Self::show_epg_day_bar(ui, day_start, day_end, window_start, window_end, adjusted_now, now_color) {
+                // Center the window on the clicked time
+                self.epg_grid_offset_secs += target - grid_window_secs / 2 - window_start;
+            }
+        }
+        
         ui.separator();
         
-        // Selected program details
+        // Selected program details - the clicked program, or the one airing now
         if let Some(ref channel_name) = self.selected_epg_channel.clone() {
             let epg_id = self.current_channels.iter()
                 .find(|c| c.name == *channel_name)
                 .and_then(|c| c.epg_channel_id.clone());
+            let epg_id = self.resolve_epg_id(channel_name, epg_id.as_deref());
             
-            if let Some(ref id) = epg_id {
-                if let Some(prog) = self.get_current_program(id) {
-                    ui.group(|ui| {
+            let program = epg_id.as_ref().and_then(|id| match self.selected_epg_program {
+                Some(start) => self.epg_data.as_ref()?.programs.get(id)?.iter().find(|p| p.start == start),
+                None => self.get_current_program(id),
+            }).cloned();
+            
+            if let Some(prog) = program {
+                let mut close = false;
+                ui.group(|ui| {
+                    ui.horizontal(|ui| {
                         ui.heading(egui::RichText::new(&prog.title).size(14.0));
-                        
-                        let duration_mins = (prog.stop - prog.start) / 60;
-                        let elapsed = (adjusted_now - prog.start).max(0) / 60;
-                        let remaining = duration_mins - elapsed;
-                        
-                        ui.horizontal(|ui| {
-                            ui.label(egui::RichText::new(format!(
-                                "{} - {} ({}m remaining)",
-                                Self::format_time(prog.start),
-                                Self::format_time(prog.stop),
-                                remaining
-                            )).small());
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            close = ui.small_button("✖").on_hover_text("Close details").clicked();
                         });
-                        
-                        // Progress bar
-                        let progress = if duration_mins > 0 {
-                            elapsed as f32 / duration_mins as f32
-                        } else {
-                            0.0
-                        };
+                    });
+                    
+                    let duration_mins = (prog.stop - prog.start) / 60;
+                    let elapsed = (live_now - prog.start).max(0) / 60;
+                    let status = if prog.start > live_now {
+                        format!("starts in {}m", (prog.start - live_now) / 60)
+                    } else if prog.stop <= live_now {
+                        "ended".to_string()
+                    } else {
+                        format!("{}m remaining", duration_mins - elapsed)
+                    };
+                    
+                    ui.label(egui::RichText::new(format!(
+                        "{} {} - {} ({})",
+                        channel_name,
+                        Self::format_time(prog.start),
+                        Self::format_time(prog.stop),
+                        status
+                    )).small());
+                    
+                    // Progress bar while airing
+                    if prog.start <= live_now && prog.stop > live_now && duration_mins > 0 {
+                        let progress = elapsed as f32 / duration_mins as f32;
                         ui.add(egui::ProgressBar::new(progress.clamp(0.0, 1.0))
                             .show_percentage());
-                        
-                        if let Some(ref desc) = prog.description {
-                            ui.separator();
-                            ui.label(egui::RichText::new(desc).small());
-                        }
-                        
-                        if let Some(ref cat) = prog.category {
-                            ui.label(egui::RichText::new(format!("Category: {}", cat)).weak().small());
+                    }
+                    
+                    if let Some(ref cat) = prog.category {
+                        ui.label(egui::RichText::new(format!("Genre: {}", cat)).weak().small());
+                    }
+                    
+                    if let Some(ref ep) = prog.episode {
+                        ui.label(egui::RichText::new(format!("Episode: {}", ep)).weak().small());
+                    }
+                    
+                    if let Some(ref desc) = prog.description {
+                        ui.separator();
+                        egui::ScrollArea::vertical()
+                            .id_salt("epg_program_details")
+                            .max_height(120.0)
+                            .show(ui, |ui| {
+                                ui.add(egui::Label::new(egui::RichText::new(desc).small()).wrap());
+                            });
+                    }
+                });
+                if close {
+                    self.selected_epg_channel = None;
+                    self.selected_epg_program = None;
+                }
+            }
+        }
+    }
+    
+    /// Find a live channel by name - current list first, then favorites/recent
+    fn find_live_channel(&self, channel_name: &str) -> Option<Channel> {
+        let from_item = |f: &FavoriteItem| Channel {
+            name: f.name.clone(),
+            url: f.url.clone(),
+            stream_id: f.stream_id,
+            category_id: None,
+            epg_channel_id: None,
+            stream_icon: None,
+            series_id: None,
+            container_extension: None,
+            playlist_source: f.playlist_source.clone(),
+            catchup: None,
+            catchup_source: None,
+            tvg_shift: None,
+            alternate_urls: Vec::new(),
+            archive_days: None,
+            genre: None,
+            user_agent: None,
+            referrer: None,
+        };
+        self.current_channels.iter()
+            .find(|c| c.name == channel_name)
+            .cloned()
+            .or_else(|| {
+                self.favorites.iter()
+                    .find(|f| f.name == channel_name && f.stream_type == "live")
+                    .map(from_item)
+            })
+            .or_else(|| {
+                self.recent_watched.iter()
+                    .find(|f| f.name == channel_name && f.stream_type == "live")
+                    .map(from_item)
+            })
+    }
+    
+    fn has_reminder(&self, channel_name: &str, start: i64) -> bool {
+        self.reminders.iter().any(|r| r.channel_name == channel_name && r.start == start)
+    }
+    
+    /// Set or cancel a reminder for a program
+    fn toggle_reminder(&mut self, channel_name: &str, title: &str, start: i64) {
+        if let Some(pos) = self.reminders.iter().position(|r| r.channel_name == channel_name && r.start == start) {
+            self.reminders.remove(pos);
+            self.status_message = format!("Reminder cancelled: {}", Self::sanitize_text(title));
+        } else {
+            let channel_url = self.find_live_channel(channel_name).map(|c| c.url).unwrap_or_default();
+            self.reminders.push(ProgramReminder {
+                channel_name: channel_name.to_string(),
+                channel_url,
+                title: title.to_string(),
+                start,
+                notified: false,
+            });
+            self.reminders.sort_by_key(|r| r.start);
+            self.status_message = format!("Reminder set: {} at {}", Self::sanitize_text(title), Self::format_time(start));
+        }
+        self.save_reminders();
+    }
+    
+    fn save_reminders(&mut self) {
+        self.config.reminders_json = serde_json::to_string(&self.reminders).unwrap_or_default();
+        self.config.save();
+    }
+    
+    /// Expire old reminders, flag ones that are due and schedule the next check
+    fn check_reminders(&mut self, ctx: &egui::Context) {
+        if self.reminders.is_empty() {
+            return;
+        }
+        let now = self.get_adjusted_now();
+        
+        let before = self.reminders.len();
+        self.reminders.retain(|r| r.start + REMINDER_EXPIRE_SECS > now);
+        if self.reminders.len() != before {
+            self.save_reminders();
+        }
+        
+        let mut newly_due = false;
+        for reminder in &mut self.reminders {
+            if !reminder.notified && reminder.start - REMINDER_LEAD_SECS <= now {
+                reminder.notified = true;
+                newly_due = true;
+            }
+        }
+        if newly_due {
+            self.log("[INFO] Program reminder due");
+            ctx.send_viewport_cmd(egui::ViewportCommand::RequestUserAttention(egui::UserAttentionType::Informational));
+        }
+        
+        // Wake up for the next reminder (and keep the banner's countdown fresh)
+        let next_check = self.reminders.iter()
+            .map(|r| if r.notified { 30 } else { r.start - REMINDER_LEAD_SECS - now })
+            .min()
+            .unwrap_or(60)
+            .clamp(1, 60);
+        ctx.request_repaint_after(std::time::Duration::from_secs(next_check as u64));
+    }
+    
+    /// Banner listing reminders that are due, with Watch/Dismiss actions
+    /// Whole days until the subscription expires (negative once expired)
+    fn expiry_days_left(&self) -> Option<i64> {
+        let ts = self.user_info.expiry_ts?;
+        Some((ts - unix_timestamp()).div_euclid(86400))
+    }
+    
+    /// Whether the expiry banner should show: within the warning window, or a trial account
+    fn expiry_warning_due(&self) -> bool {
+        match self.expiry_days_left() {
+            Some(days) => self.user_info.is_trial
+                || (self.expiry_warning_days > 0 && days < self.expiry_warning_days as i64),
+            None => false,
+        }
+    }
+    
+    /// Colored top banner warning that the provider subscription is about to expire
+    fn show_expiry_banner(&mut self, ctx: &egui::Context) {
+        if self.expiry_banner_dismissed || !self.expiry_warning_due() {
+            return;
+        }
+        let Some(days) = self.expiry_days_left() else { return };
+        
+        let trial = if self.user_info.is_trial { "Trial account - " } else { "" };
+        let (text, color) = if days < 0 {
+            (format!("⚠ {}Subscription expired on {}", trial, self.user_info.expiry), egui::Color32::from_rgb(140, 30, 30))
+        } else if days == 0 {
+            (format!("⚠ {}Subscription expires today ({})", trial, self.user_info.expiry), egui::Color32::from_rgb(140, 30, 30))
+        } else {
+            (format!("⚠ {}Subscription expires in {} day{} ({})", trial, days, if days == 1 { "" } else { "s" }, self.user_info.expiry),
+                egui::Color32::from_rgb(150, 80, 20))
+        };
+        
+        egui::TopBottomPanel::top("expiry_banner")
+            .frame(egui::Frame::side_top_panel(&ctx.style()).fill(color))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new(text).strong().color(egui::Color32::WHITE));
+                    if ui.button("✖").on_hover_text("Hide until next login").clicked() {
+                        self.expiry_banner_dismissed = true;
+                    }
+                });
+            });
+    }
+    
+    fn show_reminder_banner(&mut self, ctx: &egui::Context) {
+        let due: Vec<ProgramReminder> = self.reminders.iter().filter(|r| r.notified).cloned().collect();
+        if due.is_empty() {
+            return;
+        }
+        let now = self.get_adjusted_now();
+        let mut to_watch: Option<ProgramReminder> = None;
+        let mut to_dismiss: Option<ProgramReminder> = None;
+        
+        egui::TopBottomPanel::top("reminder_banner")
+            .frame(egui::Frame::side_top_panel(&ctx.style()).fill(egui::Color32::from_rgb(120, 90, 20)))
+            .show(ctx, |ui| {
+                for reminder in &due {
+                    ui.horizontal(|ui| {
+                        let mins = (reminder.start - now) / 60;
+                        let when = if mins > 0 { format!("starts in {}m", mins) } else { "has started".to_string() };
+                        ui.label(egui::RichText::new(format!(
+                            "🔔 {} {} on {}",
+                            Self::sanitize_text(&reminder.title),
+                            when,
+                            Self::sanitize_text(&reminder.channel_name)
+                        )).strong().color(egui::Color32::WHITE));
+                        if ui.button("▶ Watch").clicked() {
+                            to_watch = Some(reminder.clone());
                         }
-                        
-                        if let Some(ref ep) = prog.episode {
-                            ui.label(egui::RichText::new(format!("Episode: {}", ep)).weak().small());
+                        if ui.button("✖").on_hover_text("Dismiss").clicked() {
+                            to_dismiss = Some(reminder.clone());
                         }
                     });
                 }
+            });
+        
+        if let Some(reminder) = to_watch {
+            let channel = self.find_live_channel(&reminder.channel_name).or_else(|| {
+                (!reminder.channel_url.is_empty()).then(|| Channel {
+                    name: reminder.channel_name.clone(),
+                    url: reminder.channel_url.clone(),
+                    stream_id: None,
+                    category_id: None,
+                    epg_channel_id: None,
+                    stream_icon: None,
+                    series_id: None,
+                    container_extension: None,
+                    playlist_source: None,
+                    catchup: None,
+                    catchup_source: None,
+                    tvg_shift: None,
+                    alternate_urls: Vec::new(),
+                    archive_days: None,
+                    genre: None,
+                    user_agent: None,
+                    referrer: None,
+                })
+            });
+            match channel {
+                Some(channel) => self.play_channel(&channel),
+                None => self.status_message = format!("Channel '{}' not found", Self::sanitize_text(&reminder.channel_name)),
             }
+            self.reminders.retain(|r| *r != reminder);
+            self.save_reminders();
+        }
+        if let Some(reminder) = to_dismiss {
+            self.reminders.retain(|r| *r != reminder);
+            self.save_reminders();
         }
     }
     
@@ -5620,9 +11481,78 @@ impl IPTVApp {
         epg::format_time(ts)
     }
     
+    /// Format an offset from now for the EPG time axis (e.g. "Now", "+45m", "+1h30m", "-30m")
+    fn format_relative_offset(secs: i64) -> String {
+        // Round to nearest 5 minutes
+        let mins = ((secs as f64 / 300.0).round() as i64) * 5;
+        if mins == 0 {
+            return "Now".to_string();
+        }
+        let sign = if mins < 0 { "-" } else { "+" };
+        let abs = mins.abs();
+        match (abs / 60, abs % 60) {
+            (0, m) => format!("{}{}m", sign, m),
+            (h, 0) => format!("{}{}h", sign, h),
+            (h, m) => format!("{}{}h{}m", sign, h, m),
+        }
+    }
+    
     fn format_datetime(ts: i64) -> String {
         epg::format_datetime(ts)
     }
+    
+    /// Overview of the whole day under the full-day EPG grid - the visible window
+    /// is highlighted, clicking or dragging returns the time to scroll to
+    fn show_epg_day_bar(
+        ui: &mut egui::Ui,
+        day_start: i64,
+        day_end: i64,
+        window_start: i64,
+        window_end: i64,
+        now: i64,
+        now_color: egui::Color32,
+    ) -> Option<i64> {
+        let (rect, response) = ui.allocate_exact_size(
+            egui::vec2(ui.available_width().max(100.0), 18.0),
+            egui::Sense::click_and_drag(),
+        );
+        let day_secs = (day_end - day_start).max(1);
+        let x_at = |ts: i64| rect.left() + (ts - day_start).clamp(0, day_secs) as f32 * rect.width() / day_secs as f32;
+        let painter = ui.painter_at(rect);
+        let visuals = ui.visuals();
+        
+        painter.rect_filled(rect, 3.0, visuals.extreme_bg_color);
+        painter.rect_filled(
+            egui::Rect::from_x_y_ranges(x_at(window_start)..=x_at(window_end), rect.y_range()),
+            3.0,
+            visuals.selection.bg_fill.gamma_multiply(0.6),
+        );
+        let mut hour = day_start;
+        while hour < day_end {
+            let x = x_at(hour);
+            painter.vline(x, rect.y_range(), egui::Stroke::new(1.0, egui::Color32::from_gray(70)));
+            painter.text(
+                egui::pos2(x + 2.0, rect.center().y),
+                egui::Align2::LEFT_CENTER,
+                Self::format_time(hour),
+                egui::FontId::proportional(10.0),
+                visuals.weak_text_color(),
+            );
+            hour += 3 * 3600;
+        }
+        if now >= day_start && now < day_end {
+            painter.vline(x_at(now), rect.y_range(), egui::Stroke::new(2.0, now_color));
+        }
+        
+        if response.hovered() {
+            ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+        }
+        if response.clicked() || response.dragged() {
+            let x = response.interact_pointer_pos()?.x;
+            return Some(day_start + ((x - rect.left()) / rect.width() * day_secs as f32) as i64);
+        }
+        None
+    }
 }
 
 fn format_timestamp(ts: i64) -> String {
@@ -36,6 +36,39 @@ pub struct Channel {
     pub series_id: Option<i64>,
     pub container_extension: Option<String>,
     pub playlist_source: Option<String>, // Which playlist this came from
+    pub catchup: Option<String>,         // M3U catchup mode (default, append, shift)
+    pub catchup_source: Option<String>,  // M3U catchup URL template
+    pub tvg_shift: Option<f32>,          // M3U per-channel EPG offset in hours
+    pub alternate_urls: Vec<String>,     // Backup URLs tried if playback fails quickly
+    pub archive_days: Option<u32>,       // Xtream tv_archive depth when the channel can be replayed
+    pub genre: Option<String>,           // From the VOD list or detail info, when provided
+    pub user_agent: Option<String>,      // M3U #EXTVLCOPT:http-user-agent, overrides the global one
+    pub referrer: Option<String>,        // M3U #EXTVLCOPT:http-referrer
+}
+
+/// File extensions that mark a URL as a movie / episode rather than a live stream
+const VOD_EXTENSIONS: &[&str] = &["mp4", "mkv", "avi", "mov", "m4v", "wmv", "flv", "webm", "mpg", "mpeg", "3gp", "ogv"];
+
+impl Channel {
+    /// Whether this is a live stream (reopened when it ends) rather than VOD that
+    /// simply finishes. Xtream URLs say so in their path; otherwise a known video
+    /// file extension or a container extension means VOD.
+    pub fn is_live(&self) -> bool {
+        if self.series_id.is_some() {
+            return false;
+        }
+        let path = self.url.split(['?', '#']).next().unwrap_or_default();
+        if path.contains("/movie/") || path.contains("/series/") {
+            return false;
+        }
+        if path.contains("/live/") {
+            return true;
+        }
+        let file = path.rsplit('/').next().unwrap_or_default();
+        let is_vod_file = file.rsplit_once('.')
+            .is_some_and(|(_, ext)| VOD_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()));
+        !is_vod_file && self.container_extension.is_none()
+    }
 }
 
 /// User account information
@@ -48,6 +81,7 @@ pub struct UserInfo {
     pub active_connections: String,
     pub is_trial: bool,
     pub expiry: String,
+    pub expiry_ts: Option<i64>, // Unix expiry time, None when unlimited
     pub created_at: String,
 }
 
@@ -57,6 +91,7 @@ pub struct ServerInfo {
     pub url: String,
     pub port: String,
     pub timezone: String,
+    pub utc_offset_secs: Option<i64>, // From the server's local `time_now` vs `timestamp_now`
 }
 
 /// Favorite item (persisted to JSON)
@@ -79,4 +114,259 @@ pub struct FavoriteItem {
     // Playlist source tracking
     #[serde(default)]
     pub playlist_source: Option<String>,
+    // Where playback stopped last time, for movies and episodes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resume_secs: Option<u64>,
+    // Length of the movie / episode, known once a player reported it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_secs: Option<u64>,
+}
+
+impl FavoriteItem {
+    /// Where the favorite came from: its playlist, or the Xtream server and user
+    /// in its URL (`http://host/live/user/...`) - stream ids are only unique per provider
+    fn provider(&self) -> String {
+        if let Some(source) = &self.playlist_source {
+            return source.clone();
+        }
+        let Some(rest) = self.url.strip_prefix("http://").or_else(|| self.url.strip_prefix("https://")) else {
+            return String::new();
+        };
+        let mut parts = rest.split('/');
+        let host = parts.next().unwrap_or_default();
+        match (parts.next(), parts.next()) {
+            (Some("live" | "movie" | "series"), Some(user)) => format!("{}/{}", host, user),
+            _ => host.to_string(),
+        }
+    }
+    
+    /// What the favorite points at regardless of its URL: stream id for live/movies,
+    /// series (+ season / episode number) for series items. `None` for plain URL favorites.
+    fn identity(&self) -> Option<String> {
+        let item = match self.stream_type.as_str() {
+            "live" | "movie" => self.stream_id?.to_string(),
+            "series" => self.series_id?.to_string(),
+            "season" => format!("{}:{}", self.series_id?, self.season_num?),
+            "episode" => format!("{}:{}:{}", self.series_id?, self.season_num?, self.episode_num?),
+            _ => return None,
+        };
+        Some(format!("{}|{}|{}", self.provider(), self.stream_type, item))
+    }
+}
+
+/// Favorites that point at the same stream / episode of the same provider as a
+/// later one under a different URL (e.g. re-added after the extension changed),
+/// as indices in ascending order. The newest entry (last in the list) is kept.
+pub fn duplicate_favorites(favorites: &[FavoriteItem]) -> Vec<usize> {
+    let mut seen = std::collections::HashSet::new();
+    let mut duplicates: Vec<usize> = favorites.iter().enumerate().rev()
+        .filter(|(_, fav)| fav.identity().is_some_and(|identity| !seen.insert(identity)))
+        .map(|(i, _)| i)
+        .collect();
+    duplicates.reverse();
+    duplicates
+}
+
+/// Drop the favorites found by `duplicate_favorites`. Returns how many were removed.
+pub fn merge_duplicate_favorites(favorites: &mut Vec<FavoriteItem>) -> usize {
+    let duplicates = duplicate_favorites(favorites);
+    let mut index = 0;
+    favorites.retain(|_| {
+        index += 1;
+        duplicates.binary_search(&(index - 1)).is_err()
+    });
+    duplicates.len()
+}
+
+/// Loaded playlists as `(start index into the channel list, playlist name)`, in load order
+pub type PlaylistSources = Vec<(usize, String)>;
+
+/// Append a loaded playlist's channels after the ones already loaded
+pub fn add_playlist_source(channels: &mut Vec<Channel>, sources: &mut PlaylistSources, name: &str, loaded: Vec<Channel>) {
+    sources.push((channels.len(), name.to_string()));
+    channels.extend(loaded);
+}
+
+/// Remove the `idx`th loaded playlist and its channels. Returns its name and channel count.
+pub fn remove_playlist_source(channels: &mut Vec<Channel>, sources: &mut PlaylistSources, idx: usize) -> Option<(String, usize)> {
+    let (start_idx, name) = sources.get(idx).cloned()?;
+    let end_idx = sources.get(idx + 1)
+        .map(|(next_start, _)| *next_start)
+        .unwrap_or(channels.len())
+        .min(channels.len());
+    let removed = end_idx.saturating_sub(start_idx);
+    if start_idx < end_idx {
+        channels.drain(start_idx..end_idx);
+    }
+    sources.remove(idx);
+    for (start, _) in sources.iter_mut().skip(idx) {
+        *start = start.saturating_sub(removed);
+    }
+    Some((name, removed))
+}
+
+/// Reminder for an upcoming EPG program (persisted to JSON)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProgramReminder {
+    pub channel_name: String,
+    // Stream URL when known - otherwise the channel is looked up by name
+    #[serde(default)]
+    pub channel_url: String,
+    pub title: String,
+    pub start: i64, // EPG time (compare against the offset-adjusted now)
+    // Banner/attention request already shown this session
+    #[serde(skip)]
+    pub notified: bool,
+}
+
+/// A failed fetch or playback, kept for the Info tab's "Recent failures" list
+#[derive(Debug, Clone)]
+pub struct FailureRecord {
+    pub time: i64,
+    pub what: String,
+    pub message: String,
+    pub playlist: Option<String>,
+}
+
+/// Result of checking how many loaded live channels the EPG covers
+#[derive(Debug, Clone, Default)]
+pub struct EpgCoverage {
+    pub total: usize,
+    pub matched: usize,
+    pub unmatched: Vec<(String, String)>, // (channel name, reason)
+}
+
+impl EpgCoverage {
+    pub fn percent(&self) -> f32 {
+        if self.total == 0 { 0.0 } else { self.matched as f32 * 100.0 / self.total as f32 }
+    }
+}
+
+/// Split a provider genre string ("Action, Drama / Thriller") into trimmed genres
+pub fn split_genres(genre: &str) -> impl Iterator<Item = &str> {
+    genre.split([',', '/', '|', ';'])
+        .map(str::trim)
+        .filter(|g| !g.is_empty())
+}
+
+/// Picture quality advertised in a channel name ("BBC One FHD", "Movie [4K]")
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Quality {
+    Unknown,
+    Sd,
+    Hd,
+    Fhd,
+    Uhd,
+}
+
+impl Quality {
+    pub const ALL: [Quality; 4] = [Quality::Sd, Quality::Hd, Quality::Fhd, Quality::Uhd];
+
+    /// Best quality token in the name. Tokens are whole words, so "SHD" or
+    /// "HDTVNews" don't count as HD.
+    pub fn from_name(name: &str) -> Self {
+        let mut best = Quality::Unknown;
+        let mut previous = String::new();
+        for token in name.split(|c: char| !c.is_alphanumeric()).filter(|t| !t.is_empty()) {
+            let token = token.to_uppercase();
+            let quality = match token.as_str() {
+                "4K" | "UHD" | "2160" | "2160P" => Quality::Uhd,
+                "FHD" | "1080" | "1080P" | "1080I" | "FULLHD" => Quality::Fhd,
+                "HD" if previous == "FULL" => Quality::Fhd,
+                "HD" | "720" | "720P" => Quality::Hd,
+                "SD" | "480" | "480P" | "576" | "576P" => Quality::Sd,
+                _ => Quality::Unknown,
+            };
+            best = best.max(quality);
+            previous = token;
+        }
+        best
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Quality::Unknown => "",
+            Quality::Sd => "SD",
+            Quality::Hd => "HD",
+            Quality::Fhd => "FHD",
+            Quality::Uhd => "4K",
+        }
+    }
 }
+
+/// Movie/series metadata shown in the detail panel
+#[derive(Debug, Clone, Default)]
+pub struct ContentDetail {
+    pub name: String,
+    pub stream_type: String, // "movie" or "series"
+    pub poster_url: Option<String>,
+    pub plot: Option<String>,
+    pub genre: Option<String>,
+    pub cast: Option<String>,
+    pub director: Option<String>,
+    pub rating: Option<String>,
+    pub duration: Option<String>,
+    pub release_date: Option<String>,
+}
+
+impl ContentDetail {
+    /// Build from `get_vod_info` metadata, falling back to the list icon for the poster
+    pub fn from_vod_info(name: &str, info: &crate::api::VodInfo, fallback_icon: Option<&str>) -> Self {
+        Self {
+            name: info.name.clone().unwrap_or_else(|| name.to_string()),
+            stream_type: "movie".to_string(),
+            poster_url: info.movie_image.clone()
+                .or_else(|| fallback_icon.filter(|s| !s.trim().is_empty()).map(str::to_string)),
+            plot: info.plot.clone(),
+            genre: info.genre.clone(),
+            cast: info.cast.clone(),
+            director: info.director.clone(),
+            rating: info.rating.clone(),
+            duration: info.duration.clone(),
+            release_date: info.release_date.clone(),
+        }
+    }
+
+    /// Build from a `get_series_info` response, falling back to the list cover for the poster
+    pub fn from_series_info(name: &str, info: &serde_json::Value, fallback_cover: Option<&str>) -> Self {
+        let info = info.get("info").unwrap_or(info);
+        Self {
+            name: json_str(info, "name").unwrap_or_else(|| name.to_string()),
+            stream_type: "series".to_string(),
+            poster_url: json_str(info, "cover")
+                .or_else(|| json_first_str(info, "backdrop_path"))
+                .or_else(|| fallback_cover.filter(|s| !s.trim().is_empty()).map(str::to_string)),
+            plot: json_str(info, "plot"),
+            genre: json_str(info, "genre"),
+            cast: json_str(info, "cast"),
+            director: json_str(info, "director"),
+            rating: json_str(info, "rating"),
+            duration: json_str(info, "episode_run_time").map(|m| format!("{} min", m)),
+            release_date: json_str(info, "releaseDate").or_else(|| json_str(info, "release_date")),
+        }
+    }
+}
+
+/// Get a non-empty string (or number as string) from a JSON object
+pub fn json_str(value: &serde_json::Value, key: &str) -> Option<String> {
+    match value.get(key)? {
+        serde_json::Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Get the first non-empty string from a JSON array (e.g. backdrop_path)
+pub fn json_first_str(value: &serde_json::Value, key: &str) -> Option<String> {
+    value.get(key)?
+        .as_array()?
+        .iter()
+        .filter_map(|v| v.as_str())
+        .map(str::trim)
+        .find(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+#[path = "models_tests.rs"]
+mod tests;
@@ -0,0 +1,159 @@
+//! Tests for the shared data models
+
+use crate::models::*;
+
+fn favorite(url: &str, stream_type: &str, stream_id: Option<i64>, series_id: Option<i64>, season_episode: Option<(i32, i32)>) -> FavoriteItem {
+    FavoriteItem {
+        name: url.to_string(),
+        url: url.to_string(),
+        stream_type: stream_type.to_string(),
+        stream_id,
+        series_id,
+        category_name: String::new(),
+        container_extension: None,
+        season_num: season_episode.map(|(s, _)| s),
+        episode_num: season_episode.map(|(_, e)| e),
+        series_name: None,
+        playlist_source: None,
+        resume_secs: None,
+        duration_secs: None,
+    }
+}
+
+#[test]
+fn test_merge_duplicate_favorites() {
+    let mut favorites = vec![
+        favorite("http://tv/movie/u/p/7.avi", "movie", Some(7), None, None),
+        favorite("episode://3:1:100", "episode", Some(100), Some(3), Some((1, 2))),
+        favorite("http://tv/live/u/p/7.ts", "live", Some(7), None, None),
+        favorite("http://m3u/news.ts", "live", None, None, None),
+        favorite("http://m3u/news2.ts", "live", None, None, None),
+        favorite("http://tv/movie/u/p/7.mkv", "movie", Some(7), None, None),
+        favorite("episode://3:1:250", "episode", Some(250), Some(3), Some((1, 2))),
+        favorite("episode://3:1:251", "episode", Some(251), Some(3), Some((1, 3))),
+    ];
+
+    assert_eq!(merge_duplicate_favorites(&mut favorites), 2);
+    let urls: Vec<&str> = favorites.iter().map(|f| f.url.as_str()).collect();
+    assert_eq!(urls, [
+        "http://tv/live/u/p/7.ts",
+        "http://m3u/news.ts",
+        "http://m3u/news2.ts",
+        "http://tv/movie/u/p/7.mkv",
+        "episode://3:1:250",
+        "episode://3:1:251",
+    ]);
+    assert_eq!(merge_duplicate_favorites(&mut favorites), 0);
+}
+
+#[test]
+fn test_duplicate_favorites_per_provider() {
+    let favorites = vec![
+        favorite("http://tv-a:8080/live/alice/p/7.ts", "live", Some(7), None, None),
+        favorite("http://tv-b/live/bob/p/7.ts", "live", Some(7), None, None),
+        favorite("http://tv-a:8080/live/carol/p/7.ts", "live", Some(7), None, None),
+        favorite("http://tv-a:8080/live/alice/p/7.m3u8", "live", Some(7), None, None),
+    ];
+    // Same stream id on other servers or accounts is a different channel
+    assert_eq!(duplicate_favorites(&favorites), [0]);
+}
+
+#[test]
+fn test_channel_is_live() {
+    let channel = |url: &str, container: Option<&str>| Channel {
+        name: String::new(),
+        url: url.to_string(),
+        stream_id: None,
+        category_id: None,
+        epg_channel_id: None,
+        stream_icon: None,
+        series_id: None,
+        container_extension: container.map(str::to_string),
+        playlist_source: None,
+        catchup: None,
+        catchup_source: None,
+        tvg_shift: None,
+        alternate_urls: Vec::new(),
+        archive_days: None,
+        genre: None,
+        user_agent: None,
+        referrer: None,
+    };
+    assert!(channel("http://tv/live/u/p/7.ts", None).is_live());
+    assert!(channel("http://tv/live/u/p/7.m3u8", None).is_live());
+    assert!(channel("http://m3u/news", None).is_live());
+    assert!(channel("http://m3u/news.ts?token=a.mp4", None).is_live());
+    assert!(!channel("http://tv/movie/u/p/7.mkv", Some("mkv")).is_live());
+    assert!(!channel("http://tv/series/u/p/100", None).is_live());
+    // M3U VOD without a container extension
+    assert!(!channel("http://m3u/films/Big.Buck.Bunny.MP4", None).is_live());
+    assert!(!channel("http://m3u/clip.avi?x=1", None).is_live());
+    assert!(!channel("http://m3u/vod", Some("mp4")).is_live());
+    let mut episode = channel("http://m3u/vod", None);
+    episode.series_id = Some(3);
+    assert!(!episode.is_live());
+}
+
+#[test]
+fn test_quality_from_name() {
+    assert_eq!(Quality::from_name("BBC One HD"), Quality::Hd);
+    assert_eq!(Quality::from_name("UK: Sky Sports FHD"), Quality::Fhd);
+    assert_eq!(Quality::from_name("Movie (2023) [4k]"), Quality::Uhd);
+    assert_eq!(Quality::from_name("Nature UHD HDR"), Quality::Uhd);
+    assert_eq!(Quality::from_name("News 1080p"), Quality::Fhd);
+    assert_eq!(Quality::from_name("Discovery Full HD"), Quality::Fhd);
+    assert_eq!(Quality::from_name("ESPN SD"), Quality::Sd);
+    // The best token wins when a name lists several
+    assert_eq!(Quality::from_name("Cinema HD/4K"), Quality::Uhd);
+    // Tokens inside other words don't count
+    assert_eq!(Quality::from_name("SHD Channel"), Quality::Unknown);
+    assert_eq!(Quality::from_name("Channel 4"), Quality::Unknown);
+    assert_eq!(Quality::from_name("HDTVNews"), Quality::Unknown);
+}
+
+#[test]
+fn test_switching_playlists_replaces_channels() {
+    let channels_of = |names: &[&str]| -> Vec<Channel> {
+        names.iter().map(|name| Channel {
+            name: name.to_string(),
+            url: format!("http://m3u/{}.ts", name),
+            stream_id: None,
+            category_id: None,
+            epg_channel_id: None,
+            stream_icon: None,
+            series_id: None,
+            container_extension: None,
+            playlist_source: None,
+            catchup: None,
+            catchup_source: None,
+            tvg_shift: None,
+            alternate_urls: Vec::new(),
+            archive_days: None,
+            genre: None,
+            user_agent: None,
+            referrer: None,
+        }).collect()
+    };
+    let names = |channels: &[Channel]| channels.iter().map(|c| c.name.clone()).collect::<Vec<_>>();
+    let mut channels = Vec::new();
+    let mut sources = PlaylistSources::new();
+
+    add_playlist_source(&mut channels, &mut sources, "A", channels_of(&["a1", "a2"]));
+    // Switch A -> B -> A, unloading the active playlist each time
+    for (from, to, expected) in [("A", "B", vec!["b1"]), ("B", "A", vec!["a1", "a2"])] {
+        let idx = sources.iter().position(|(_, name)| name == from).unwrap();
+        assert_eq!(remove_playlist_source(&mut channels, &mut sources, idx).map(|(name, _)| name).as_deref(), Some(from));
+        let loaded = if to == "A" { channels_of(&["a1", "a2"]) } else { channels_of(&["b1"]) };
+        add_playlist_source(&mut channels, &mut sources, to, loaded);
+        assert_eq!(names(&channels), expected);
+        assert_eq!(sources, [(0, to.to_string())]);
+    }
+
+    // Removing a playlist in the middle shifts the ones after it
+    add_playlist_source(&mut channels, &mut sources, "B", channels_of(&["b1"]));
+    add_playlist_source(&mut channels, &mut sources, "C", channels_of(&["c1"]));
+    assert_eq!(remove_playlist_source(&mut channels, &mut sources, 1), Some(("B".to_string(), 1)));
+    assert_eq!(names(&channels), ["a1", "a2", "c1"]);
+    assert_eq!(sources, [(0, "A".to_string()), (2, "C".to_string())]);
+    assert_eq!(remove_playlist_source(&mut channels, &mut sources, 5), None);
+}
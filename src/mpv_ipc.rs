@@ -0,0 +1,117 @@
+//! Playback position read back from mpv over its JSON IPC socket
+//!
+//! Movies and episodes launched in mpv get `--input-ipc-server`. A watcher
+//! thread asks for `time-pos` and `duration` every few seconds; once mpv
+//! closes, the last answer is where the user stopped watching.
+
+use std::io::{BufRead, BufReader, Write};
+use std::thread;
+use std::time::Duration;
+
+/// How often the watcher asks mpv for its position
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Give up if mpv never opens its socket (e.g. an old build without IPC)
+const CONNECT_ATTEMPTS: u32 = 12;
+/// Positions this close to the start are not worth resuming
+const MIN_RESUME_SECS: f64 = 30.0;
+/// Stopping within the last 5% counts as watched to the end
+const FINISHED_FRACTION: f64 = 0.95;
+
+/// Socket (named pipe on Windows) for one mpv launch
+pub fn ipc_path(tag: &str) -> String {
+    #[cfg(windows)]
+    {
+        format!(r"\\.\pipe\xtreme_iptv_mpv_{}", tag)
+    }
+    #[cfg(not(windows))]
+    {
+        std::env::temp_dir().join(format!("xtreme_iptv_mpv_{}.sock", tag)).display().to_string()
+    }
+}
+
+/// Watch an mpv instance until it exits, then hand over its last `(position, duration)`.
+/// `on_exit` gets `None` when mpv never answered.
+pub fn watch_position(path: String, on_exit: impl FnOnce(Option<(f64, Option<f64>)>) + Send + 'static) {
+    thread::spawn(move || {
+        let mut last = None;
+        let mut attempts = 0;
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            match query_property(&path, "time-pos") {
+                Ok(Some(position)) => last = Some((position, query_property(&path, "duration").ok().flatten())),
+                Ok(None) => {} // Still opening the stream
+                Err(_) if last.is_none() && attempts < CONNECT_ATTEMPTS => attempts += 1,
+                Err(_) => break, // mpv has exited
+            }
+        }
+        #[cfg(not(windows))]
+        let _ = std::fs::remove_file(&path);
+        on_exit(last);
+    });
+}
+
+/// Ask mpv for a numeric property - `Ok(None)` while it has no value yet
+fn query_property(path: &str, name: &str) -> std::io::Result<Option<f64>> {
+    let mut stream = connect(path)?;
+    let request = format!("{{\"command\":[\"get_property\",\"{}\"],\"request_id\":1}}\n", name);
+    stream.write_all(request.as_bytes())?;
+    stream.flush()?;
+
+    // Event lines can arrive before the reply
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    for _ in 0..20 {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        if let Some(value) = parse_property_reply(&line, 1) {
+            return Ok(value);
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(not(windows))]
+fn connect(path: &str) -> std::io::Result<std::os::unix::net::UnixStream> {
+    let stream = std::os::unix::net::UnixStream::connect(path)?;
+    stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+    Ok(stream)
+}
+
+#[cfg(windows)]
+fn connect(path: &str) -> std::io::Result<std::fs::File> {
+    std::fs::OpenOptions::new().read(true).write(true).open(path)
+}
+
+/// Reply to the request with `request_id`: `Some(Some(value))` on success,
+/// `Some(None)` when mpv has no value (property unavailable), `None` for other lines
+pub fn parse_property_reply(line: &str, request_id: i64) -> Option<Option<f64>> {
+    let reply: serde_json::Value = serde_json::from_str(line.trim()).ok()?;
+    if reply.get("request_id")?.as_i64()? != request_id {
+        return None;
+    }
+    let success = reply.get("error").and_then(|e| e.as_str()) == Some("success");
+    Some(reply.get("data").and_then(|d| d.as_f64()).filter(|_| success))
+}
+
+/// Position to resume from next time - `None` (start over) when the user stopped
+/// near the beginning or watched to the end
+pub fn resume_position(position: f64, duration: Option<f64>) -> Option<u64> {
+    let finished = duration.is_some_and(|d| d > 0.0 && position >= d * FINISHED_FRACTION);
+    (position >= MIN_RESUME_SECS && !finished).then_some(position as u64)
+}
+
+/// "1:02:03" / "4:05" for a resume position
+pub fn format_position(secs: u64) -> String {
+    let (h, m, s) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if h > 0 {
+        format!("{}:{:02}:{:02}", h, m, s)
+    } else {
+        format!("{}:{:02}", m, s)
+    }
+}
+
+#[cfg(test)]
+#[path = "mpv_ipc_tests.rs"]
+mod tests;
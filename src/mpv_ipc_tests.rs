@@ -0,0 +1,28 @@
+//! Tests for reading the mpv playback position
+
+use crate::mpv_ipc::*;
+
+#[test]
+fn test_parse_property_reply() {
+    assert_eq!(parse_property_reply(r#"{"data":1234.5,"request_id":1,"error":"success"}"#, 1), Some(Some(1234.5)));
+    assert_eq!(parse_property_reply(r#"{"request_id":1,"error":"property unavailable"}"#, 1), Some(None));
+    // Events and other requests' replies are skipped
+    assert_eq!(parse_property_reply(r#"{"event":"playback-restart"}"#, 1), None);
+    assert_eq!(parse_property_reply(r#"{"data":5.0,"request_id":2,"error":"success"}"#, 1), None);
+    assert_eq!(parse_property_reply("not json", 1), None);
+}
+
+#[test]
+fn test_resume_position() {
+    assert_eq!(resume_position(1800.7, Some(5400.0)), Some(1800));
+    assert_eq!(resume_position(600.0, None), Some(600));
+    // Barely started, or watched to the end
+    assert_eq!(resume_position(12.0, Some(5400.0)), None);
+    assert_eq!(resume_position(5300.0, Some(5400.0)), None);
+}
+
+#[test]
+fn test_format_position() {
+    assert_eq!(format_position(245), "4:05");
+    assert_eq!(format_position(3723), "1:02:03");
+}
@@ -0,0 +1,191 @@
+//! User-defined extra player arguments
+//!
+//! Splits a free-form argument string the way a shell would (respecting
+//! single/double quotes, and backslashes escaping a quote or another backslash)
+//! and substitutes placeholders:
+//!
+//! - `{url}`   - stream URL
+//! - `{title}` - channel/stream name
+//!
+//! Also builds the audio/subtitle language flags for the supported players
+//! and the `ffmpeg` command line used to record a stream to a file.
+
+/// Split an argument string into individual arguments, respecting quotes
+pub fn split_args(input: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+    let mut chars = input.chars();
+
+    while let Some(c) = chars.next() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some('"') if c == '\\' => {
+                // Only escape quote and backslash inside double quotes
+                match chars.next() {
+                    Some(next @ ('"' | '\\')) => current.push(next),
+                    Some(next) => {
+                        current.push('\\');
+                        current.push(next);
+                    }
+                    None => current.push('\\'),
+                }
+            }
+            Some(_) => current.push(c),
+            None => match c {
+                '"' | '\'' => {
+                    quote = Some(c);
+                    in_arg = true;
+                }
+                '\\' => {
+                    // Only a quote or backslash is escaped, so unquoted Windows paths survive
+                    match chars.clone().next() {
+                        Some(next @ ('"' | '\'' | '\\')) => {
+                            chars.next();
+                            current.push(next);
+                        }
+                        _ => current.push('\\'),
+                    }
+                    in_arg = true;
+                }
+                c if c.is_whitespace() => {
+                    if in_arg {
+                        args.push(std::mem::take(&mut current));
+                        in_arg = false;
+                    }
+                }
+                _ => {
+                    current.push(c);
+                    in_arg = true;
+                }
+            },
+        }
+    }
+
+    if in_arg {
+        args.push(current);
+    }
+
+    args
+}
+
+/// Split an argument template and substitute `{url}` and `{title}` placeholders
+pub fn expand_args(template: &str, url: &str, title: &str) -> Vec<String> {
+    split_args(template)
+        .into_iter()
+        .map(|arg| arg.replace("{url}", url).replace("{title}", title))
+        .collect()
+}
+
+/// Normalise a language preference ("eng, en" -> "eng,en")
+fn language_list(langs: &str) -> String {
+    langs.split(',')
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Player flags selecting preferred audio and subtitle languages (ISO 639 codes,
+/// comma-separated in order of preference). Empty preferences add nothing, as do
+/// players without a language option.
+pub fn language_args(player_lower: &str, audio: &str, subtitle: &str) -> Vec<String> {
+    let (audio, subtitle) = (language_list(audio), language_list(subtitle));
+    let mut args = Vec::new();
+    if player_lower.contains("ffplay") {
+        // ffplay selects a single stream by metadata - only the first language is used
+        let first = |langs: &str| langs.split(',').next().unwrap_or("").to_string();
+        if !audio.is_empty() {
+            args.extend(["-ast".to_string(), format!("m:language:{}", first(&audio))]);
+        }
+        if !subtitle.is_empty() {
+            args.extend(["-sst".to_string(), format!("m:language:{}", first(&subtitle))]);
+        }
+    } else if player_lower.contains("celluloid") || player_lower.contains("gnome-mpv") {
+        if !audio.is_empty() {
+            args.push(format!("--mpv-alang={}", audio));
+        }
+        if !subtitle.is_empty() {
+            args.push(format!("--mpv-slang={}", subtitle));
+        }
+    } else if player_lower.contains("mpv") {
+        if !audio.is_empty() {
+            args.push(format!("--alang={}", audio));
+        }
+        if !subtitle.is_empty() {
+            args.push(format!("--slang={}", subtitle));
+        }
+    } else if player_lower.contains("vlc") {
+        if !audio.is_empty() {
+            args.push(format!("--audio-language={}", audio));
+        }
+        if !subtitle.is_empty() {
+            args.push(format!("--sub-language={}", subtitle));
+        }
+    }
+    args
+}
+
+/// Player flags starting playback `secs` into the stream (for resuming movies and episodes)
+pub fn start_args(player_lower: &str, secs: u64) -> Vec<String> {
+    if player_lower.contains("ffplay") {
+        vec!["-ss".to_string(), secs.to_string()]
+    } else if player_lower.contains("celluloid") || player_lower.contains("gnome-mpv") {
+        vec![format!("--mpv-start={}", secs)]
+    } else if player_lower.contains("mpv") {
+        vec![format!("--start={}", secs)]
+    } else if player_lower.contains("vlc") {
+        vec![format!("--start-time={}", secs)]
+    } else {
+        Vec::new()
+    }
+}
+
+/// ffmpeg/ffplay input options that reconnect a dropped HTTP stream (none for other protocols)
+pub fn reconnect_args(url: &str, slow: bool) -> Vec<String> {
+    if !url.starts_with("http") {
+        return Vec::new();
+    }
+    vec![
+        "-reconnect".to_string(), "1".to_string(),
+        "-reconnect_streamed".to_string(), "1".to_string(),
+        "-reconnect_delay_max".to_string(), if slow { "30".to_string() } else { "10".to_string() },
+    ]
+}
+
+/// `ffmpeg` arguments copying a stream into an MPEG-TS file without re-encoding
+pub fn record_args(url: &str, user_agent: Option<&str>, slow: bool, output: &str) -> Vec<String> {
+    let mut args = vec!["-hide_banner".to_string(), "-nostdin".to_string()];
+    if let Some(user_agent) = user_agent {
+        args.extend(["-user_agent".to_string(), user_agent.to_string()]);
+    }
+    args.extend(reconnect_args(url, slow));
+    args.extend([
+        "-i".to_string(), url.to_string(),
+        "-c".to_string(), "copy".to_string(),
+        "-f".to_string(), "mpegts".to_string(),
+        output.to_string(),
+    ]);
+    args
+}
+
+/// File name for a recording: the channel name made safe for any file system,
+/// then the timestamp (e.g. "BBC_One_HD_20240501-2000.ts")
+pub fn recording_file_name(channel_name: &str, timestamp: &str) -> String {
+    let mut name = String::new();
+    for c in channel_name.chars() {
+        if c.is_alphanumeric() || c == '-' || c == '.' {
+            name.push(c);
+        } else if !name.is_empty() && !name.ends_with('_') {
+            name.push('_');
+        }
+    }
+    let name: String = name.trim_matches(['_', '.']).chars().take(80).collect();
+    let name = if name.is_empty() { "recording".to_string() } else { name };
+    format!("{}_{}.ts", name, timestamp)
+}
+
+#[cfg(test)]
+#[path = "player_args_tests.rs"]
+mod tests;
@@ -0,0 +1,100 @@
+//! Tests for extra player argument parsing
+
+use crate::player_args::*;
+
+#[test]
+fn test_split_simple() {
+    assert_eq!(split_args("--mute --fs"), vec!["--mute", "--fs"]);
+    assert_eq!(split_args("  -a   1  "), vec!["-a", "1"]);
+    assert!(split_args("").is_empty());
+    assert!(split_args("   ").is_empty());
+}
+
+#[test]
+fn test_split_quotes() {
+    assert_eq!(
+        split_args(r#"--sub-file="/tmp/my subs.srt" -vf 'yadif=1'"#),
+        vec!["--sub-file=/tmp/my subs.srt", "-vf", "yadif=1"]
+    );
+    assert_eq!(split_args(r#""" x"#), vec!["", "x"]);
+    assert_eq!(split_args(r#"'it"s'"#), vec![r#"it"s"#]);
+}
+
+#[test]
+fn test_split_escapes() {
+    assert_eq!(split_args(r#"a\"b c"#), vec![r#"a"b"#, "c"]);
+    assert_eq!(split_args(r"a\\b"), vec![r"a\b"]);
+    assert_eq!(split_args(r#""say \"hi\"""#), vec![r#"say "hi""#]);
+    // Backslashes in Windows paths are kept inside double quotes
+    assert_eq!(split_args(r#""C:\Subs\a.srt""#), vec![r"C:\Subs\a.srt"]);
+    // ...and outside quotes, where only a quote or backslash is escaped
+    assert_eq!(split_args(r"--sub-file=C:\Subs\a.srt --fs"), vec![r"--sub-file=C:\Subs\a.srt", "--fs"]);
+    assert_eq!(split_args(r"a\ b"), vec![r"a\", "b"]);
+}
+
+#[test]
+fn test_split_unterminated_quote() {
+    assert_eq!(split_args(r#"--title="open end"#), vec!["--title=open end"]);
+}
+
+#[test]
+fn test_expand_placeholders() {
+    let args = expand_args(
+        r#"--force-media-title="{title}" --stream-record=/tmp/rec.ts {url}"#,
+        "http://example.com/live/1.ts",
+        "News HD",
+    );
+    assert_eq!(args, vec![
+        "--force-media-title=News HD",
+        "--stream-record=/tmp/rec.ts",
+        "http://example.com/live/1.ts",
+    ]);
+}
+
+#[test]
+fn test_expand_title_with_spaces_stays_one_arg() {
+    let args = expand_args("-t {title}", "u", "A B C");
+    assert_eq!(args, vec!["-t", "A B C"]);
+}
+
+#[test]
+fn test_language_args() {
+    assert_eq!(language_args("/usr/bin/mpv", "eng, en", "spa"), vec!["--alang=eng,en", "--slang=spa"]);
+    assert_eq!(language_args("vlc", "deu", ""), vec!["--audio-language=deu"]);
+    assert_eq!(language_args("ffplay", "fre,fra", "eng"), vec!["-ast", "m:language:fre", "-sst", "m:language:eng"]);
+    assert_eq!(language_args("celluloid", "", "eng"), vec!["--mpv-slang=eng"]);
+    assert!(language_args("mpv", " ", "").is_empty());
+    assert!(language_args("potplayer", "eng", "eng").is_empty());
+}
+
+#[test]
+fn test_record_args() {
+    assert_eq!(record_args("http://tv.example/1.ts", Some("VLC/3.0"), false, "/rec/out.ts"), vec![
+        "-hide_banner", "-nostdin",
+        "-user_agent", "VLC/3.0",
+        "-reconnect", "1", "-reconnect_streamed", "1", "-reconnect_delay_max", "10",
+        "-i", "http://tv.example/1.ts",
+        "-c", "copy", "-f", "mpegts", "/rec/out.ts",
+    ]);
+    // No reconnect options outside HTTP
+    assert_eq!(record_args("rtp://239.0.0.1:1234", None, true, "out.ts"), vec![
+        "-hide_banner", "-nostdin", "-i", "rtp://239.0.0.1:1234", "-c", "copy", "-f", "mpegts", "out.ts",
+    ]);
+}
+
+#[test]
+fn test_recording_file_name() {
+    assert_eq!(recording_file_name("BBC One HD", "20240501-2000"), "BBC_One_HD_20240501-2000.ts");
+    assert_eq!(recording_file_name("UK: Sky/News | 24*7?", "t"), "UK_Sky_News_24_7_t.ts");
+    assert_eq!(recording_file_name("../..", "t"), "recording_t.ts");
+    assert_eq!(recording_file_name("", "t"), "recording_t.ts");
+}
+
+#[test]
+fn test_start_args() {
+    assert_eq!(start_args("/usr/bin/mpv", 90), vec!["--start=90"]);
+    assert_eq!(start_args("c:\\program files\\videolan\\vlc\\vlc.exe", 90), vec!["--start-time=90"]);
+    assert_eq!(start_args("ffplay", 90), vec!["-ss", "90"]);
+    assert_eq!(start_args("celluloid", 90), vec!["--mpv-start=90"]);
+    assert!(start_args("potplayer", 90).is_empty());
+}
@@ -0,0 +1,164 @@
+//! Opt-in LAN server that re-serves the loaded channels as an M3U playlist
+//!
+//! A TV or phone on the same network can open `http://<this pc>:<port>/playlist.m3u`.
+//! The app pushes a freshly rendered playlist whenever the channel list changes;
+//! requests from outside private address ranges are refused.
+
+use std::io::{Read, Write};
+use std::net::{IpAddr, SocketAddr, TcpListener, TcpStream, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::thread;
+use std::time::Duration;
+
+/// Running server - stops when dropped
+pub struct PlaylistServer {
+    playlist: Arc<RwLock<String>>,
+    stop: Arc<AtomicBool>,
+    addr: SocketAddr,
+}
+
+impl PlaylistServer {
+    /// Bind `addr` (e.g. "192.168.1.20:8899") and serve on a background thread.
+    /// An empty host (":8899") binds this machine's LAN address, or loopback if
+    /// it isn't on a private network.
+    pub fn start(addr: &str) -> Result<Self, String> {
+        let addr = resolve_bind_addr(addr);
+        let listener = TcpListener::bind(&addr)
+            .map_err(|e| format!("Could not listen on {}: {}", addr, e))?;
+        let addr = listener.local_addr().map_err(|e| e.to_string())?;
+        // Non-blocking accept so the thread notices the stop flag
+        listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+
+        let playlist = Arc::new(RwLock::new("#EXTM3U\n".to_string()));
+        let stop = Arc::new(AtomicBool::new(false));
+        {
+            let playlist = playlist.clone();
+            let stop = stop.clone();
+            thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    match listener.accept() {
+                        Ok((stream, peer)) => {
+                            let playlist = playlist.clone();
+                            thread::spawn(move || handle_connection(stream, peer, &playlist));
+                        }
+                        Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                            thread::sleep(Duration::from_millis(100));
+                        }
+                        Err(_) => thread::sleep(Duration::from_millis(100)),
+                    }
+                }
+            });
+        }
+
+        Ok(Self { playlist, stop, addr })
+    }
+
+    /// Replace the served playlist
+    pub fn set_playlist(&self, m3u: String) {
+        *self.playlist.write().unwrap_or_else(|e| e.into_inner()) = m3u;
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// URL other devices can use (the unspecified bind address is swapped for this machine's LAN IP)
+    pub fn playlist_url(&self) -> String {
+        let ip = if self.addr.ip().is_unspecified() {
+            lan_ip().unwrap_or(self.addr.ip())
+        } else {
+            self.addr.ip()
+        };
+        format!("http://{}/playlist.m3u", SocketAddr::new(ip, self.addr.port()))
+    }
+}
+
+impl Drop for PlaylistServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Loopback, private, link-local and CGNAT addresses - everything a home LAN or VPN uses
+pub fn is_lan_addr(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, ..] = v4.octets();
+            v4.is_loopback() || v4.is_private() || v4.is_link_local() || (a == 100 && (64..128).contains(&b))
+        }
+        IpAddr::V6(v6) => {
+            if let Some(v4) = v6.to_ipv4_mapped() {
+                return is_lan_addr(IpAddr::V4(v4));
+            }
+            let first = v6.segments()[0];
+            v6.is_loopback() || (first & 0xfe00) == 0xfc00 || (first & 0xffc0) == 0xfe80
+        }
+    }
+}
+
+/// Fill in an empty host with the LAN address (never all interfaces by default)
+fn resolve_bind_addr(addr: &str) -> String {
+    let addr = addr.trim();
+    match addr.strip_prefix(':') {
+        Some(port) => {
+            let ip = lan_ip().filter(|ip| is_lan_addr(*ip)).unwrap_or(IpAddr::from([127, 0, 0, 1]));
+            format!("{}:{}", ip, port)
+        }
+        None => addr.to_string(),
+    }
+}
+
+/// This machine's address on the default route (no packets are sent)
+fn lan_ip() -> Option<IpAddr> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("192.0.2.1:80").ok()?;
+    Some(socket.local_addr().ok()?.ip())
+}
+
+fn handle_connection(mut stream: TcpStream, peer: SocketAddr, playlist: &RwLock<String>) {
+    let _ = stream.set_nonblocking(false);
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(5)));
+    let _ = stream.set_write_timeout(Some(Duration::from_secs(30)));
+
+    if !is_lan_addr(peer.ip()) {
+        let _ = write_response(&mut stream, "403 Forbidden", "text/plain", b"LAN clients only\n", false);
+        return;
+    }
+
+    // Only the request line matters
+    let mut buf = [0u8; 2048];
+    let n = stream.read(&mut buf).unwrap_or(0);
+    let request = String::from_utf8_lossy(&buf[..n]);
+    let mut parts = request.lines().next().unwrap_or("").split_whitespace();
+    let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+    let path = path.split('?').next().unwrap_or("");
+
+    let head_only = method == "HEAD";
+    let _ = match (method, path) {
+        ("GET" | "HEAD", "/playlist.m3u" | "/playlist.m3u8") => {
+            let body = playlist.read().unwrap_or_else(|e| e.into_inner()).clone();
+            write_response(&mut stream, "200 OK", "audio/x-mpegurl; charset=utf-8", body.as_bytes(), head_only)
+        }
+        ("GET" | "HEAD", "/") => {
+            write_response(&mut stream, "200 OK", "text/plain", b"Xtreme IPTV playlist server - open /playlist.m3u\n", head_only)
+        }
+        _ => write_response(&mut stream, "404 Not Found", "text/plain", b"Not found\n", head_only),
+    };
+}
+
+fn write_response(stream: &mut TcpStream, status: &str, content_type: &str, body: &[u8], head_only: bool) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status, content_type, body.len()
+    )?;
+    if !head_only {
+        stream.write_all(body)?;
+    }
+    stream.flush()
+}
+
+#[cfg(test)]
+#[path = "playlist_server_tests.rs"]
+mod tests;
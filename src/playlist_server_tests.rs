@@ -0,0 +1,49 @@
+//! Tests for the LAN playlist server
+
+use crate::playlist_server::*;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+
+fn get(server: &PlaylistServer, request: &str) -> String {
+    let mut stream = TcpStream::connect(server.local_addr()).unwrap();
+    stream.write_all(request.as_bytes()).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response
+}
+
+#[test]
+fn test_serves_current_playlist() {
+    let server = PlaylistServer::start("127.0.0.1:0").unwrap();
+    server.set_playlist("#EXTM3U\n#EXTINF:-1,News\nhttp://example.com/news.ts\n".to_string());
+
+    let response = get(&server, "GET /playlist.m3u HTTP/1.1\r\nHost: test\r\n\r\n");
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+    assert!(response.contains("audio/x-mpegurl"));
+    assert!(response.ends_with("http://example.com/news.ts\n"));
+
+    // Updates are picked up by the next request
+    server.set_playlist("#EXTM3U\n".to_string());
+    assert!(get(&server, "GET /playlist.m3u HTTP/1.1\r\n\r\n").ends_with("\r\n\r\n#EXTM3U\n"));
+
+    assert!(get(&server, "GET /other HTTP/1.1\r\n\r\n").starts_with("HTTP/1.1 404"));
+    assert!(server.playlist_url().ends_with("/playlist.m3u"));
+}
+
+#[test]
+fn test_default_bind_is_lan_only() {
+    let server = PlaylistServer::start(":0").unwrap();
+    let ip = server.local_addr().ip();
+    assert!(!ip.is_unspecified());
+    assert!(is_lan_addr(ip), "{}", ip);
+}
+
+#[test]
+fn test_lan_addresses() {
+    for ip in ["127.0.0.1", "192.168.1.20", "10.0.0.5", "172.16.3.4", "169.254.1.1", "100.100.1.1", "::1", "fd00::1", "fe80::1", "::ffff:192.168.0.2"] {
+        assert!(is_lan_addr(ip.parse().unwrap()), "{}", ip);
+    }
+    for ip in ["8.8.8.8", "100.200.1.1", "172.32.0.1", "2001:4860::8888", "::ffff:8.8.8.8"] {
+        assert!(!is_lan_addr(ip.parse().unwrap()), "{}", ip);
+    }
+}
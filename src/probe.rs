@@ -0,0 +1,115 @@
+//! Stream probing via ffprobe
+//!
+//! Runs `ffprobe` (if installed) against a stream URL and extracts the
+//! video/audio codecs, resolution and frame rate from its JSON output.
+
+use serde_json::Value;
+use std::process::{Command, Stdio};
+
+/// Summary of a probed stream
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StreamProbe {
+    pub video_codec: Option<String>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub fps: Option<f32>,
+    pub audio_codec: Option<String>,
+    pub audio_channels: Option<u32>,
+}
+
+impl StreamProbe {
+    /// Resolution as "1920x1080"
+    pub fn resolution(&self) -> Option<String> {
+        Some(format!("{}x{}", self.width?, self.height?))
+    }
+}
+
+/// Probe outcome - an error message when ffprobe is missing or fails
+pub type ProbeResult = Result<StreamProbe, String>;
+
+/// Parse an ffprobe frame rate like "30000/1001" or "25/1"
+pub fn parse_frame_rate(rate: &str) -> Option<f32> {
+    let (num, den) = rate.split_once('/').unwrap_or((rate, "1"));
+    let num: f32 = num.trim().parse().ok()?;
+    let den: f32 = den.trim().parse().ok()?;
+    if num <= 0.0 || den <= 0.0 {
+        return None;
+    }
+    Some(num / den)
+}
+
+/// Parse `ffprobe -print_format json -show_streams` output
+pub fn parse_ffprobe_json(json: &str) -> Result<StreamProbe, String> {
+    let root: Value = serde_json::from_str(json).map_err(|e| format!("Invalid ffprobe output: {}", e))?;
+    let streams = root.get("streams")
+        .and_then(|s| s.as_array())
+        .ok_or("No streams found")?;
+
+    let mut probe = StreamProbe::default();
+    for stream in streams {
+        let codec = stream.get("codec_name").and_then(|v| v.as_str()).map(str::to_string);
+        match stream.get("codec_type").and_then(|v| v.as_str()) {
+            Some("video") if probe.video_codec.is_none() => {
+                probe.video_codec = codec;
+                probe.width = stream.get("width").and_then(|v| v.as_u64()).map(|v| v as u32);
+                probe.height = stream.get("height").and_then(|v| v.as_u64()).map(|v| v as u32);
+                // avg_frame_rate is "0/0" for some live streams - fall back to r_frame_rate
+                probe.fps = ["avg_frame_rate", "r_frame_rate"].iter()
+                    .filter_map(|key| stream.get(*key).and_then(|v| v.as_str()))
+                    .find_map(parse_frame_rate);
+            }
+            Some("audio") if probe.audio_codec.is_none() => {
+                probe.audio_codec = codec;
+                probe.audio_channels = stream.get("channels").and_then(|v| v.as_u64()).map(|v| v as u32);
+            }
+            _ => {}
+        }
+    }
+
+    if probe.video_codec.is_none() && probe.audio_codec.is_none() {
+        return Err("No audio or video streams found".to_string());
+    }
+    Ok(probe)
+}
+
+/// Run ffprobe on a stream URL (blocking - call from a background thread)
+pub fn probe_stream(url: &str, user_agent: &str) -> ProbeResult {
+    let mut cmd = Command::new("ffprobe");
+    cmd.args(["-v", "error", "-print_format", "json", "-show_streams"])
+        // Give up on unresponsive servers after 15s (microseconds)
+        .args(["-rw_timeout", "15000000"]);
+    if !user_agent.is_empty() {
+        cmd.args(["-user_agent", user_agent]);
+    }
+    cmd.arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let output = cmd.output().map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            "ffprobe not found - install FFmpeg and make sure ffprobe is on your PATH".to_string()
+        } else {
+            format!("Failed to run ffprobe: {}", e)
+        }
+    })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().rev().find(|l| !l.trim().is_empty()).unwrap_or("unknown error");
+        return Err(format!("ffprobe failed: {}", reason.trim()));
+    }
+
+    parse_ffprobe_json(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(test)]
+#[path = "probe_tests.rs"]
+mod tests;
@@ -0,0 +1,47 @@
+//! Tests for ffprobe output parsing
+
+use crate::probe::*;
+
+#[test]
+fn test_parse_frame_rate() {
+    assert_eq!(parse_frame_rate("25/1"), Some(25.0));
+    assert!((parse_frame_rate("30000/1001").unwrap() - 29.97).abs() < 0.01);
+    assert_eq!(parse_frame_rate("50"), Some(50.0));
+    assert_eq!(parse_frame_rate("0/0"), None);
+    assert_eq!(parse_frame_rate("abc"), None);
+}
+
+#[test]
+fn test_parse_video_and_audio() {
+    let json = r#"{
+        "streams": [
+            {"index": 0, "codec_name": "h264", "codec_type": "video", "width": 1920, "height": 1080,
+             "r_frame_rate": "50/1", "avg_frame_rate": "25/1"},
+            {"index": 1, "codec_name": "aac", "codec_type": "audio", "channels": 2},
+            {"index": 2, "codec_name": "ac3", "codec_type": "audio", "channels": 6}
+        ]
+    }"#;
+    let probe = parse_ffprobe_json(json).unwrap();
+    assert_eq!(probe.video_codec.as_deref(), Some("h264"));
+    assert_eq!(probe.resolution().as_deref(), Some("1920x1080"));
+    assert_eq!(probe.fps, Some(25.0));
+    // First audio stream wins
+    assert_eq!(probe.audio_codec.as_deref(), Some("aac"));
+    assert_eq!(probe.audio_channels, Some(2));
+}
+
+#[test]
+fn test_parse_falls_back_to_r_frame_rate() {
+    let json = r#"{"streams": [{"codec_name": "hevc", "codec_type": "video", "width": 3840, "height": 2160,
+        "avg_frame_rate": "0/0", "r_frame_rate": "60/1"}]}"#;
+    let probe = parse_ffprobe_json(json).unwrap();
+    assert_eq!(probe.fps, Some(60.0));
+    assert_eq!(probe.audio_codec, None);
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!(parse_ffprobe_json("not json").is_err());
+    assert!(parse_ffprobe_json("{}").is_err());
+    assert!(parse_ffprobe_json(r#"{"streams": [{"codec_type": "data"}]}"#).is_err());
+}
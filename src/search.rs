@@ -0,0 +1,120 @@
+//! Channel search queries
+//!
+//! A query is a case-insensitive substring by default, or a regular expression
+//! when regex mode is on. A `tvg:` or `url:` prefix matches the EPG id or the
+//! stream URL instead of the name (`name:` is the default). A pattern that
+//! doesn't compile falls back to a literal search and is flagged as invalid.
+
+use regex::{Regex, RegexBuilder};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchField {
+    Name,
+    TvgId,
+    Url,
+}
+
+#[derive(Debug, Clone)]
+enum Matcher {
+    Any,
+    Literal(String),
+    Regex(Regex),
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchQuery {
+    text: String,
+    regex_mode: bool,
+    field: SearchField,
+    matcher: Matcher,
+    invalid: bool,
+}
+
+impl Default for SearchQuery {
+    fn default() -> Self {
+        Self::parse("", false)
+    }
+}
+
+impl SearchQuery {
+    pub fn parse(text: &str, regex_mode: bool) -> Self {
+        let (field, pattern) = split_scope(text);
+        let mut invalid = false;
+        let matcher = if pattern.is_empty() {
+            Matcher::Any
+        } else if regex_mode {
+            match RegexBuilder::new(pattern).case_insensitive(true).build() {
+                Ok(re) => Matcher::Regex(re),
+                Err(_) => {
+                    invalid = true;
+                    Matcher::Literal(pattern.to_lowercase())
+                }
+            }
+        } else {
+            Matcher::Literal(pattern.to_lowercase())
+        };
+        Self { text: text.to_string(), regex_mode, field, matcher, invalid }
+    }
+
+    /// Whether this was built from `text` in the given mode (to reuse a compiled query)
+    pub fn is_for(&self, text: &str, regex_mode: bool) -> bool {
+        self.text == text && self.regex_mode == regex_mode
+    }
+
+    pub fn is_empty(&self) -> bool {
+        matches!(self.matcher, Matcher::Any)
+    }
+
+    /// Regex mode is on but the pattern doesn't compile
+    pub fn is_invalid(&self) -> bool {
+        self.invalid
+    }
+
+    pub fn field(&self) -> SearchField {
+        self.field
+    }
+
+    /// Match a channel on the field the query is scoped to
+    pub fn matches_channel(&self, name: &str, tvg_id: Option<&str>, url: &str) -> bool {
+        match self.field {
+            SearchField::Name => self.matches(name),
+            SearchField::TvgId => tvg_id.is_some_and(|id| self.matches(id)),
+            SearchField::Url => self.matches(url),
+        }
+    }
+
+    /// Match a category or other plain name - `tvg:` and `url:` only narrow
+    /// down channels, so they let every category through
+    pub fn matches_name(&self, name: &str) -> bool {
+        self.field != SearchField::Name || self.matches(name)
+    }
+
+    fn matches(&self, value: &str) -> bool {
+        match &self.matcher {
+            Matcher::Any => true,
+            Matcher::Literal(needle) => value.to_lowercase().contains(needle),
+            Matcher::Regex(re) => re.is_match(value),
+        }
+    }
+}
+
+/// Split off a `tvg:` / `url:` / `name:` scope prefix
+fn split_scope(text: &str) -> (SearchField, &str) {
+    let text = text.trim();
+    if let Some((scope, rest)) = text.split_once(':') {
+        let field = match scope.to_ascii_lowercase().as_str() {
+            "tvg" => Some(SearchField::TvgId),
+            "url" => Some(SearchField::Url),
+            "name" => Some(SearchField::Name),
+            _ => None,
+        };
+        if let Some(field) = field {
+            return (field, rest.trim());
+        }
+    }
+    (SearchField::Name, text)
+}
+
+#[cfg(test)]
+#[path = "search_tests.rs"]
+mod tests;
@@ -0,0 +1,47 @@
+//! Tests for channel search queries
+
+use crate::search::*;
+
+#[test]
+fn test_literal_search() {
+    let query = SearchQuery::parse("bbc", false);
+    assert!(query.matches_channel("BBC One HD", None, "http://a/1.ts"));
+    assert!(!query.matches_channel("ITV", Some("bbc1.uk"), "http://a/bbc.ts"));
+    // Regex characters are literal unless regex mode is on
+    assert!(SearchQuery::parse("a.b", false).matches_channel("x a.b", None, ""));
+    assert!(!SearchQuery::parse("a.b", false).matches_channel("axb", None, ""));
+    assert!(SearchQuery::parse("  ", false).is_empty());
+}
+
+#[test]
+fn test_scoped_search() {
+    let query = SearchQuery::parse("tvg:BBC", false);
+    assert_eq!(query.field(), SearchField::TvgId);
+    assert!(query.matches_channel("One", Some("bbc1.uk"), "http://a/1.ts"));
+    assert!(!query.matches_channel("BBC One", None, "http://a/1.ts"));
+
+    let query = SearchQuery::parse("URL: mp4", false);
+    assert!(query.matches_channel("Film", None, "http://a/film.mp4"));
+    assert!(!query.matches_channel("mp4 Film", None, "http://a/film.mkv"));
+    // Scopes only narrow down channels
+    assert!(query.matches_name("Movies"));
+
+    // Unknown prefixes are part of the text
+    assert!(SearchQuery::parse("uk: news", false).matches_channel("UK: News", None, ""));
+}
+
+#[test]
+fn test_regex_search() {
+    let query = SearchQuery::parse("^(bbc|itv) ", true);
+    assert!(!query.is_invalid());
+    assert!(query.matches_channel("BBC One", None, ""));
+    assert!(query.matches_channel("itv 2", None, ""));
+    assert!(!query.matches_channel("CBBC", None, ""));
+    assert!(SearchQuery::parse(r"url:\.m3u8$", true).matches_channel("x", None, "http://a/live.m3u8"));
+
+    // Invalid patterns fall back to a literal match instead of hiding everything
+    let query = SearchQuery::parse("sport(", true);
+    assert!(query.is_invalid());
+    assert!(query.matches_channel("Sport(s) 1", None, ""));
+    assert!(!query.matches_channel("News", None, ""));
+}
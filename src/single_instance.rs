@@ -0,0 +1,194 @@
+//! Single-instance guard
+//!
+//! The first instance listens on a loopback port and records it in a lock file
+//! in the config dir. A second launch connects to that port, hands over its
+//! `--play` / `--playlist` option and exits, so two processes never write the
+//! same config files. A lock whose port no longer answers is left over from a
+//! crashed process and is taken over.
+
+use crate::cli::LaunchIntent;
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// First line sent by a second launch, so an unrelated program that happens to
+/// use a stale lock's port is not mistaken for us
+const HELLO: &str = "xtreme_iptv hello";
+const ACK: &str = "xtreme_iptv ok";
+
+/// Rounds of create / check / remove-stale before giving up on the lock
+const LOCK_ATTEMPTS: usize = 5;
+
+type Notify = Arc<Mutex<Option<Box<dyn Fn() + Send>>>>;
+
+pub enum Instance {
+    /// No other instance is running - keep the guard alive for the app's lifetime
+    Primary(InstanceGuard),
+    /// Another instance took over the launch options; this process should exit
+    Secondary,
+}
+
+/// Lock held by the running instance - released when dropped
+pub struct InstanceGuard {
+    lock_path: PathBuf,
+    addr: SocketAddr,
+    stop: Arc<AtomicBool>,
+    receiver: Receiver<Option<LaunchIntent>>,
+    notify: Notify,
+}
+
+impl InstanceGuard {
+    /// Next request from a second launch: `Some(None)` to just come to the front,
+    /// `Some(Some(intent))` to also act on its launch option
+    pub fn try_recv(&self) -> Option<Option<LaunchIntent>> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// Called from the listener thread whenever a request arrives (e.g. to wake the UI)
+    pub fn set_notify(&self, notify: impl Fn() + Send + 'static) {
+        *self.notify.lock().unwrap_or_else(|e| e.into_inner()) = Some(Box::new(notify));
+    }
+}
+
+impl Drop for InstanceGuard {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        // Leave the lock alone if another instance has taken it over since
+        if read_lock(&self.lock_path) == Some(self.addr) {
+            let _ = std::fs::remove_file(&self.lock_path);
+        }
+    }
+}
+
+/// Become the running instance, or hand `intent` to the one already running
+pub fn acquire(lock_path: &Path, intent: Option<&LaunchIntent>) -> Result<Instance, String> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+        .map_err(|e| format!("Could not open instance socket: {}", e))?;
+    let addr = listener.local_addr().map_err(|e| e.to_string())?;
+    listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+
+    // Creating the lock only succeeds if no file exists, so of two launches
+    // at the same moment exactly one becomes the running instance
+    for _ in 0..LOCK_ATTEMPTS {
+        match OpenOptions::new().write(true).create_new(true).open(lock_path) {
+            Ok(mut file) => {
+                file.write_all(addr.to_string().as_bytes())
+                    .map_err(|e| format!("Could not write {}: {}", lock_path.display(), e))?;
+                return Ok(Instance::Primary(listen(listener, lock_path, addr)));
+            }
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                let contents = read_lock_contents(lock_path);
+                if let Some(other) = contents.as_deref().and_then(|s| s.trim().parse().ok()) {
+                    if forward(other, intent).is_ok() {
+                        return Ok(Instance::Secondary);
+                    }
+                } else {
+                    // Just created by another launch that hasn't written its address yet?
+                    thread::sleep(Duration::from_millis(100));
+                    if read_lock_contents(lock_path) != contents {
+                        continue;
+                    }
+                }
+                // Nobody answered - stale lock from a crash, take it over unless
+                // another launch already replaced it
+                if read_lock_contents(lock_path) == contents {
+                    let _ = std::fs::remove_file(lock_path);
+                }
+            }
+            Err(e) => return Err(format!("Could not create {}: {}", lock_path.display(), e)),
+        }
+    }
+    Err(format!("Could not take over {}", lock_path.display()))
+}
+
+/// Serve requests from later launches until the guard is dropped
+fn listen(listener: TcpListener, lock_path: &Path, addr: SocketAddr) -> InstanceGuard {
+    let (sender, receiver) = channel();
+    let stop = Arc::new(AtomicBool::new(false));
+    let notify: Notify = Arc::new(Mutex::new(None));
+    {
+        let stop = stop.clone();
+        let notify = notify.clone();
+        thread::spawn(move || {
+            while !stop.load(Ordering::Relaxed) {
+                match listener.accept() {
+                    Ok((stream, _)) => {
+                        if handle_connection(stream, &sender) {
+                            if let Some(notify) = notify.lock().unwrap_or_else(|e| e.into_inner()).as_ref() {
+                                notify();
+                            }
+                        }
+                    }
+                    Err(_) => thread::sleep(Duration::from_millis(100)),
+                }
+            }
+        });
+    }
+
+    InstanceGuard { lock_path: lock_path.to_path_buf(), addr, stop, receiver, notify }
+}
+
+fn read_lock_contents(lock_path: &Path) -> Option<String> {
+    std::fs::read_to_string(lock_path).ok()
+}
+
+fn read_lock(lock_path: &Path) -> Option<SocketAddr> {
+    read_lock_contents(lock_path)?.trim().parse().ok()
+}
+
+/// Send the launch option to a running instance and wait for its acknowledgement
+fn forward(addr: SocketAddr, intent: Option<&LaunchIntent>) -> std::io::Result<()> {
+    let mut stream = TcpStream::connect_timeout(&addr, Duration::from_secs(2))?;
+    stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+    writeln!(stream, "{}", HELLO)?;
+    writeln!(stream, "{}", encode_intent(intent))?;
+    stream.flush()?;
+
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply)?;
+    if reply.trim() == ACK {
+        Ok(())
+    } else {
+        Err(std::io::Error::other("not an Xtreme IPTV instance"))
+    }
+}
+
+/// Read one request from a second launch - returns whether it was accepted
+fn handle_connection(stream: TcpStream, sender: &Sender<Option<LaunchIntent>>) -> bool {
+    let _ = stream.set_nonblocking(false);
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(2)));
+    let mut reader = BufReader::new(&stream);
+    let (mut hello, mut message) = (String::new(), String::new());
+    if reader.read_line(&mut hello).is_err() || hello.trim() != HELLO || reader.read_line(&mut message).is_err() {
+        return false;
+    }
+    let _ = sender.send(decode_intent(message.trim_end_matches(['\r', '\n'])));
+    let _ = writeln!(&stream, "{}", ACK);
+    true
+}
+
+fn encode_intent(intent: Option<&LaunchIntent>) -> String {
+    match intent {
+        Some(LaunchIntent::Play(url)) => format!("play {}", url),
+        Some(LaunchIntent::Playlist(url)) => format!("playlist {}", url),
+        None => "focus".to_string(),
+    }
+}
+
+fn decode_intent(message: &str) -> Option<LaunchIntent> {
+    match message.split_once(' ') {
+        Some(("play", url)) => Some(LaunchIntent::Play(url.to_string())),
+        Some(("playlist", url)) => Some(LaunchIntent::Playlist(url.to_string())),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+#[path = "single_instance_tests.rs"]
+mod tests;
@@ -0,0 +1,74 @@
+//! Tests for the single-instance guard
+
+use crate::cli::LaunchIntent;
+use crate::single_instance::*;
+use std::time::{Duration, Instant};
+
+fn temp_lock(name: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("xtreme_iptv_{}_{}.lock", name, std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+fn recv(guard: &InstanceGuard) -> Option<Option<LaunchIntent>> {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while Instant::now() < deadline {
+        if let Some(message) = guard.try_recv() {
+            return Some(message);
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    None
+}
+
+#[test]
+fn test_second_launch_hands_over() {
+    let lock = temp_lock("handover");
+    let Ok(Instance::Primary(guard)) = acquire(&lock, None) else { panic!("first launch should be primary") };
+
+    let intent = LaunchIntent::Play("http://tv.example/live/1.ts".to_string());
+    assert!(matches!(acquire(&lock, Some(&intent)), Ok(Instance::Secondary)));
+    assert_eq!(recv(&guard), Some(Some(intent)));
+
+    assert!(matches!(acquire(&lock, None), Ok(Instance::Secondary)));
+    assert_eq!(recv(&guard), Some(None));
+
+    drop(guard);
+    assert!(!lock.exists());
+}
+
+#[test]
+fn test_stale_lock_is_taken_over() {
+    let lock = temp_lock("stale");
+    // Port of a listener that has since gone away
+    let dead = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+    std::fs::write(&lock, dead.to_string()).unwrap();
+
+    let Ok(Instance::Primary(guard)) = acquire(&lock, None) else { panic!("stale lock should be taken over") };
+    assert_ne!(std::fs::read_to_string(&lock).unwrap(), dead.to_string());
+    drop(guard);
+
+    // Garbage in the lock file is ignored too
+    std::fs::write(&lock, "not an address").unwrap();
+    assert!(matches!(acquire(&lock, None), Ok(Instance::Primary(_))));
+}
+
+#[test]
+fn test_simultaneous_launches_have_one_primary() {
+    let lock = temp_lock("race");
+    let barrier = std::sync::Arc::new(std::sync::Barrier::new(4));
+    let launches: Vec<_> = (0..4).map(|_| {
+        let (lock, barrier) = (lock.clone(), barrier.clone());
+        std::thread::spawn(move || {
+            barrier.wait();
+            acquire(&lock, None)
+        })
+    }).collect();
+    let results: Vec<_> = launches.into_iter().map(|t| t.join().unwrap()).collect();
+
+    let primaries = results.iter().filter(|r| matches!(r, Ok(Instance::Primary(_)))).count();
+    assert_eq!(primaries, 1);
+    assert!(results.iter().all(|r| r.is_ok()));
+    drop(results);
+    assert!(!lock.exists());
+}
@@ -0,0 +1,257 @@
+//! Stalker / Ministra (MAG set-top box) portal client
+//!
+//! Portals identify the box by its MAC address: a handshake returns a bearer
+//! token, after which genres and channels can be listed. A channel's `cmd` is
+//! often a placeholder (`ffmpeg http://localhost/ch/123`) that only becomes a
+//! playable URL through `create_link`, so links are resolved right before playback.
+
+use crate::api::{url_encode, Category, HostResolution};
+use serde_json::Value;
+use std::time::Duration;
+
+type StalkerResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+/// User agent and model string of a MAG250, which most portals expect
+const MAG_USER_AGENT: &str = "Mozilla/5.0 (QtEmbedded; U; Linux; C) AppleWebKit/533.3 (KHTML, like Gecko) MAG200 stbapp ver: 2 rev: 250 Safari/533.3";
+const MAG_X_USER_AGENT: &str = "Model: MAG250; Link: WiFi";
+
+/// The portal refused the session token (expired, or the portal restarted)
+#[derive(Debug)]
+pub struct AuthFailed;
+
+impl std::fmt::Display for AuthFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Portal session expired")
+    }
+}
+
+impl std::error::Error for AuthFailed {}
+
+/// Channel as listed by `itv/get_all_channels`
+#[derive(Debug, Clone, PartialEq)]
+pub struct StalkerChannel {
+    pub id: String,
+    pub name: String,
+    pub cmd: String,
+    pub genre_id: Option<String>,
+    pub logo: Option<String>,
+    pub xmltv_id: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct StalkerClient {
+    api_url: String,
+    portal: String,
+    mac: String,
+    token: Option<String>,
+    connect_timeout: Duration,
+    read_timeout: Duration,
+    resolution: HostResolution,
+}
+
+impl StalkerClient {
+    pub fn new(portal: &str, mac: &str) -> Self {
+        Self {
+            api_url: portal_api_url(portal),
+            portal: portal.trim().to_string(),
+            mac: normalize_mac(mac).unwrap_or_else(|| mac.trim().to_string()),
+            token: None,
+            connect_timeout: Duration::from_secs(30),
+            read_timeout: Duration::from_secs(30),
+            resolution: HostResolution::default(),
+        }
+    }
+
+    /// Set connect and read timeouts in seconds (0 is treated as 1)
+    pub fn with_timeouts(mut self, connect_secs: u64, read_secs: u64) -> Self {
+        self.connect_timeout = Duration::from_secs(connect_secs.max(1));
+        self.read_timeout = Duration::from_secs(read_secs.max(1));
+        self
+    }
+
+    pub fn with_host_resolution(mut self, resolution: HostResolution) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
+    /// Call the portal API and return its `js` payload
+    fn request(&self, params: &[(&str, &str)]) -> StalkerResult<Value> {
+        let agent = self.resolution.agent(ureq::Agent::config_builder()
+            .timeout_connect(Some(self.connect_timeout))
+            .timeout_recv_response(Some(self.read_timeout))
+            .timeout_recv_body(Some(self.read_timeout)));
+
+        let mut request = agent.get(&self.api_url)
+            .header("User-Agent", MAG_USER_AGENT)
+            .header("X-User-Agent", MAG_X_USER_AGENT)
+            .header("Referer", &self.portal)
+            .header("Cookie", format!("mac={}; stb_lang=en; timezone=UTC", url_encode(&self.mac)));
+        if let Some(token) = &self.token {
+            request = request.header("Authorization", format!("Bearer {}", token));
+        }
+        for (key, value) in params {
+            request = request.query(*key, *value);
+        }
+        let mut response = match request.query("JsHttpRequest", "1-xml").call() {
+            Err(ureq::Error::StatusCode(status)) if is_auth_failure(status, "") => return Err(AuthFailed.into()),
+            result => result?,
+        };
+        let body = response.body_mut().read_to_string()?;
+        if is_auth_failure(response.status().as_u16(), &body) {
+            return Err(AuthFailed.into());
+        }
+
+        let json = crate::api::parse_json::<Value>(&body)?;
+        match json.get("js") {
+            Some(js) => Ok(js.clone()),
+            None => Err("Portal response has no data".into()),
+        }
+    }
+
+    /// Obtain a session token and register the box profile
+    pub fn handshake(&mut self) -> StalkerResult<()> {
+        self.token = None;
+        let js = self.request(&[("type", "stb"), ("action", "handshake"), ("token", "")])?;
+        let token = js.get("token")
+            .and_then(Value::as_str)
+            .filter(|t| !t.is_empty())
+            .ok_or("Portal refused the handshake - check the portal URL and MAC address")?;
+        self.token = Some(token.to_string());
+
+        // Portals only serve channel lists once the profile has been fetched
+        let profile = self.request(&[("type", "stb"), ("action", "get_profile"), ("hd", "1"), ("stb_type", "MAG250")])?;
+        if profile.get("status").and_then(value_as_i64) == Some(1) {
+            let message = profile.get("msg").and_then(Value::as_str).unwrap_or("MAC address is not authorised");
+            return Err(format!("Portal rejected this box: {}", message).into());
+        }
+        Ok(())
+    }
+
+    pub fn get_genres(&self) -> StalkerResult<Vec<Category>> {
+        let js = self.request(&[("type", "itv"), ("action", "get_genres")])?;
+        Ok(parse_genres(&js))
+    }
+
+    pub fn get_channels(&self) -> StalkerResult<Vec<StalkerChannel>> {
+        let js = self.request(&[("type", "itv"), ("action", "get_all_channels")])?;
+        Ok(parse_channels(&js))
+    }
+
+    /// Turn a channel `cmd` into a playable URL
+    pub fn create_link(&self, cmd: &str) -> StalkerResult<String> {
+        let js = self.request(&[("type", "itv"), ("action", "create_link"), ("cmd", cmd), ("series", ""), ("forced_storage", "undefined"), ("disable_ad", "0")])?;
+        js.get("cmd")
+            .and_then(Value::as_str)
+            .and_then(stream_url_from_cmd)
+            .ok_or_else(|| "Portal did not return a stream link".into())
+    }
+
+    /// `create_link`, redoing the handshake once if the session token was refused.
+    /// The flag tells whether the session was renewed (and should be kept).
+    pub fn create_link_renewing(&mut self, cmd: &str) -> StalkerResult<(String, bool)> {
+        match self.create_link(cmd) {
+            Err(e) if e.is::<AuthFailed>() => {
+                self.handshake()?;
+                Ok((self.create_link(cmd)?, true))
+            }
+            result => result.map(|url| (url, false)),
+        }
+    }
+}
+
+/// Whether a portal response means the session token was refused
+pub fn is_auth_failure(status: u16, body: &str) -> bool {
+    matches!(status, 401 | 403) || body.trim_start().starts_with("Authorization failed")
+}
+
+/// API endpoint for a portal address. `http://host/c/` and `http://host/`
+/// map to `http://host/portal.php`, `.../stalker_portal/c/` to
+/// `.../stalker_portal/server/load.php`; a URL ending in `.php` is used as is.
+pub fn portal_api_url(portal: &str) -> String {
+    let mut base = portal.trim().trim_end_matches('/').to_string();
+    if !base.starts_with("http://") && !base.starts_with("https://") {
+        base = format!("http://{}", base);
+    }
+    if base.ends_with(".php") {
+        return base;
+    }
+    if let Some(stripped) = base.strip_suffix("/c") {
+        base = stripped.to_string();
+    }
+    if base.ends_with("/stalker_portal") {
+        format!("{}/server/load.php", base)
+    } else {
+        format!("{}/portal.php", base)
+    }
+}
+
+/// Upper-case `AA:BB:CC:DD:EE:FF` form of a MAC address (`-` or no separators accepted)
+pub fn normalize_mac(mac: &str) -> Option<String> {
+    let hex: String = mac.trim().chars().filter(|c| !matches!(c, ':' | '-' | ' ')).collect();
+    if hex.len() != 12 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let hex = hex.to_ascii_uppercase();
+    Some(hex.as_bytes().chunks(2).map(|pair| String::from_utf8_lossy(pair).into_owned()).collect::<Vec<_>>().join(":"))
+}
+
+/// Stream URL from a channel `cmd` (`ffmpeg http://...`, `auto http://...` or a bare URL).
+/// `None` for placeholders that need `create_link` first.
+pub fn stream_url_from_cmd(cmd: &str) -> Option<String> {
+    let url = cmd.split_whitespace().last()?;
+    let is_http = url.starts_with("http://") || url.starts_with("https://");
+    let host = url.split_once("://").map_or("", |(_, rest)| rest.split(['/', ':', '?']).next().unwrap_or(""));
+    (is_http && !host.is_empty() && host != "localhost" && host != "127.0.0.1").then(|| url.to_string())
+}
+
+/// `itv/get_genres` payload - the "All" pseudo-genre is skipped
+pub fn parse_genres(js: &Value) -> Vec<Category> {
+    js.as_array()
+        .map(|genres| genres.iter()
+            .filter_map(|g| {
+                let id = g.get("id").and_then(value_as_string)?;
+                let title = g.get("title").and_then(Value::as_str)?.trim().to_string();
+                (id != "*" && !title.is_empty()).then_some(Category {
+                    category_id: id,
+                    category_name: title,
+                    parent_id: 0,
+                })
+            })
+            .collect())
+        .unwrap_or_default()
+}
+
+/// `itv/get_all_channels` payload (`{"data": [...]}`, or a bare list on some portals)
+pub fn parse_channels(js: &Value) -> Vec<StalkerChannel> {
+    let list = js.get("data").unwrap_or(js);
+    let non_empty = |v: Option<&Value>| v.and_then(Value::as_str).map(str::trim).filter(|s| !s.is_empty()).map(str::to_string);
+    list.as_array()
+        .map(|channels| channels.iter()
+            .filter_map(|c| Some(StalkerChannel {
+                id: c.get("id").and_then(value_as_string)?,
+                name: non_empty(c.get("name"))?,
+                cmd: non_empty(c.get("cmd"))?,
+                genre_id: c.get("tv_genre_id").and_then(value_as_string),
+                logo: non_empty(c.get("logo")),
+                xmltv_id: non_empty(c.get("xmltv_id")),
+            }))
+            .collect())
+        .unwrap_or_default()
+}
+
+/// Portals send ids as numbers or strings
+fn value_as_string(v: &Value) -> Option<String> {
+    match v {
+        Value::String(s) if !s.is_empty() => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn value_as_i64(v: &Value) -> Option<i64> {
+    v.as_i64().or_else(|| v.as_str()?.parse().ok())
+}
+
+#[cfg(test)]
+#[path = "stalker_tests.rs"]
+mod tests;
@@ -0,0 +1,68 @@
+//! Tests for the Stalker portal client
+
+use crate::stalker::*;
+use serde_json::json;
+
+#[test]
+fn test_portal_api_url() {
+    assert_eq!(portal_api_url("http://portal.example:8080/c/"), "http://portal.example:8080/portal.php");
+    assert_eq!(portal_api_url("portal.example"), "http://portal.example/portal.php");
+    assert_eq!(portal_api_url("http://portal.example/stalker_portal/c"), "http://portal.example/stalker_portal/server/load.php");
+    assert_eq!(portal_api_url("https://portal.example/server/load.php"), "https://portal.example/server/load.php");
+}
+
+#[test]
+fn test_normalize_mac() {
+    assert_eq!(normalize_mac("00:1a:79:ab:cd:ef").as_deref(), Some("00:1A:79:AB:CD:EF"));
+    assert_eq!(normalize_mac(" 00-1A-79-AB-CD-EF ").as_deref(), Some("00:1A:79:AB:CD:EF"));
+    assert_eq!(normalize_mac("001A79ABCDEF").as_deref(), Some("00:1A:79:AB:CD:EF"));
+    assert_eq!(normalize_mac("00:1A:79:AB:CD"), None);
+    assert_eq!(normalize_mac("00:1A:79:AB:CD:EG"), None);
+}
+
+#[test]
+fn test_stream_url_from_cmd() {
+    assert_eq!(stream_url_from_cmd("ffmpeg http://tv.example/play/live.php?stream=1").as_deref(), Some("http://tv.example/play/live.php?stream=1"));
+    assert_eq!(stream_url_from_cmd("http://tv.example:8000/ch/1.ts").as_deref(), Some("http://tv.example:8000/ch/1.ts"));
+    // Placeholders need create_link
+    assert_eq!(stream_url_from_cmd("ffmpeg http://localhost/ch/10245_"), None);
+    assert_eq!(stream_url_from_cmd("ffmpeg rtp://239.0.0.1:1234"), None);
+    assert_eq!(stream_url_from_cmd(""), None);
+}
+
+#[test]
+fn test_parse_genres_and_channels() {
+    let genres = parse_genres(&json!([
+        {"id": "*", "title": "All"},
+        {"id": 3, "title": "News"},
+        {"id": "7", "title": " Sports "},
+    ]));
+    assert_eq!(genres.len(), 2);
+    assert_eq!((genres[0].category_id.as_str(), genres[0].category_name.as_str()), ("3", "News"));
+    assert_eq!(genres[1].category_name, "Sports");
+
+    let channels = parse_channels(&json!({"data": [
+        {"id": 10, "name": "News 24", "cmd": "ffmpeg http://localhost/ch/10_", "tv_genre_id": "3", "logo": "", "xmltv_id": "news24.uk"},
+        {"id": "11", "name": "Sport", "cmd": "ffmpeg http://tv.example/11.ts", "tv_genre_id": 7, "logo": "http://tv.example/11.png"},
+        {"id": 12, "name": "No command", "cmd": ""},
+    ]}));
+    assert_eq!(channels.len(), 2);
+    assert_eq!(channels[0], StalkerChannel {
+        id: "10".to_string(),
+        name: "News 24".to_string(),
+        cmd: "ffmpeg http://localhost/ch/10_".to_string(),
+        genre_id: Some("3".to_string()),
+        logo: None,
+        xmltv_id: Some("news24.uk".to_string()),
+    });
+    assert_eq!(channels[1].genre_id.as_deref(), Some("7"));
+    assert_eq!(channels[1].logo.as_deref(), Some("http://tv.example/11.png"));
+}
+
+#[test]
+fn test_is_auth_failure() {
+    assert!(is_auth_failure(401, ""));
+    assert!(is_auth_failure(200, "Authorization failed."));
+    assert!(!is_auth_failure(200, r#"{"js":{"cmd":"ffmpeg http://tv.example/1.ts"}}"#));
+    assert!(!is_auth_failure(404, ""));
+}
@@ -0,0 +1,110 @@
+//! Optional at-rest encryption of saved Xtream passwords
+//!
+//! A key is derived from the user's master passphrase with Argon2id and used
+//! with ChaCha20-Poly1305. Encrypted passwords are stored as
+//! `enc:v1:<nonce hex><ciphertext hex>`. The config keeps only a header,
+//! `argon2id$<salt hex>$<encrypted check value>`, to tell whether a passphrase
+//! is right. Once unlocked, the key stays in memory so every save of the
+//! playlist entries encrypts again.
+
+use crate::app_lock::{from_hex, to_hex};
+use argon2::Argon2;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use std::sync::Mutex;
+
+const HEADER_PREFIX: &str = "argon2id";
+const SEALED_PREFIX: &str = "enc:v1:";
+const SALT_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+/// Encrypted in the header - decrypting it proves the passphrase
+const CHECK_VALUE: &str = "xtreme_iptv";
+
+pub type Key = [u8; KEY_LEN];
+
+/// Key of the unlocked vault, used by `seal` / `open`
+static CURRENT_KEY: Mutex<Option<Key>> = Mutex::new(None);
+
+/// Master passphrases need at least this many characters
+pub const MIN_PASSPHRASE_LEN: usize = 8;
+
+/// New header and key for a passphrase (random salt)
+pub fn create(passphrase: &str) -> Option<(String, Key)> {
+    let mut salt = [0u8; SALT_LEN];
+    SystemRandom::new().fill(&mut salt).ok()?;
+    let key = derive_key(passphrase, &salt)?;
+    let check = seal_with(&key, CHECK_VALUE)?;
+    Some((format!("{}${}${}", HEADER_PREFIX, to_hex(&salt), check), key))
+}
+
+/// Key for `passphrase` if it matches the header
+pub fn unlock(passphrase: &str, header: &str) -> Option<Key> {
+    let mut parts = header.split('$');
+    let (Some(HEADER_PREFIX), Some(salt), Some(check), None) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+        return None;
+    };
+    let key = derive_key(passphrase, &from_hex(salt)?)?;
+    (open_with(&key, check)? == CHECK_VALUE).then_some(key)
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Option<Key> {
+    let mut key = [0u8; KEY_LEN];
+    Argon2::default().hash_password_into(passphrase.as_bytes(), salt, &mut key).ok()?;
+    Some(key)
+}
+
+/// Whether a stored value is encrypted
+pub fn is_sealed(value: &str) -> bool {
+    value.starts_with(SEALED_PREFIX)
+}
+
+pub fn seal_with(key: &Key, plaintext: &str) -> Option<String> {
+    let key = LessSafeKey::new(UnboundKey::new(&CHACHA20_POLY1305, key).ok()?);
+    let mut nonce = [0u8; NONCE_LEN];
+    SystemRandom::new().fill(&mut nonce).ok()?;
+    let mut in_out = plaintext.as_bytes().to_vec();
+    key.seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut in_out).ok()?;
+    Some(format!("{}{}{}", SEALED_PREFIX, to_hex(&nonce), to_hex(&in_out)))
+}
+
+/// Decrypt a sealed value - `None` when it isn't sealed, was tampered with or the key is wrong
+pub fn open_with(key: &Key, sealed: &str) -> Option<String> {
+    let bytes = from_hex(sealed.strip_prefix(SEALED_PREFIX)?)?;
+    if bytes.len() < NONCE_LEN {
+        return None;
+    }
+    let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+    let key = LessSafeKey::new(UnboundKey::new(&CHACHA20_POLY1305, key).ok()?);
+    let mut in_out = ciphertext.to_vec();
+    let plaintext = key.open_in_place(Nonce::try_assume_unique_for_key(nonce).ok()?, Aad::empty(), &mut in_out).ok()?;
+    String::from_utf8(plaintext.to_vec()).ok()
+}
+
+/// Use `key` for `seal` / `open` from now on (`None` turns encryption off)
+pub fn set_key(key: Option<Key>) {
+    *CURRENT_KEY.lock().unwrap_or_else(|e| e.into_inner()) = key;
+}
+
+pub fn is_unlocked() -> bool {
+    CURRENT_KEY.lock().unwrap_or_else(|e| e.into_inner()).is_some()
+}
+
+/// Encrypt with the unlocked key. Empty, already sealed values (and everything
+/// while no key is set) are returned unchanged.
+pub fn seal(value: &str) -> String {
+    let key = *CURRENT_KEY.lock().unwrap_or_else(|e| e.into_inner());
+    match key {
+        Some(key) if !value.is_empty() && !is_sealed(value) => seal_with(&key, value).unwrap_or_else(|| value.to_string()),
+        _ => value.to_string(),
+    }
+}
+
+/// Decrypt with the unlocked key
+pub fn open(value: &str) -> Option<String> {
+    let key = (*CURRENT_KEY.lock().unwrap_or_else(|e| e.into_inner()))?;
+    open_with(&key, value)
+}
+
+#[cfg(test)]
+#[path = "vault_tests.rs"]
+mod tests;
@@ -0,0 +1,31 @@
+//! Tests for saved password encryption
+
+use crate::vault::*;
+
+#[test]
+fn test_create_and_unlock() {
+    let (header, key) = create("correct horse").unwrap();
+    assert!(header.starts_with("argon2id$"));
+    assert!(!header.contains("correct horse"));
+    assert_eq!(unlock("correct horse", &header), Some(key));
+    assert_eq!(unlock("wrong horse", &header), None);
+    assert_eq!(unlock("correct horse", "argon2id$zz$enc:v1:00"), None);
+    assert_eq!(unlock("correct horse", ""), None);
+}
+
+#[test]
+fn test_seal_and_open() {
+    let key = [7u8; 32];
+    let sealed = seal_with(&key, "s3cret!").unwrap();
+    assert!(is_sealed(&sealed));
+    assert!(!sealed.contains("s3cret"));
+    assert_eq!(open_with(&key, &sealed).as_deref(), Some("s3cret!"));
+    // Random nonce - the same password encrypts differently each time
+    assert_ne!(seal_with(&key, "s3cret!").unwrap(), sealed);
+
+    assert_eq!(open_with(&[8u8; 32], &sealed), None);
+    assert_eq!(open_with(&key, "s3cret!"), None);
+    let mut tampered = sealed.clone();
+    tampered.replace_range(sealed.len() - 2.., if sealed.ends_with("00") { "01" } else { "00" });
+    assert_eq!(open_with(&key, &tampered), None);
+}
@@ -279,6 +279,11 @@ pub fn to_m3u_channels(playlist: &XspfPlaylist) -> Vec<super::m3u_parser::M3uCha
             channel_number: track.track_num,
             catchup: None,
             catchup_days: None,
+            catchup_source: None,
+            tvg_shift: None,
+            group_logo: None,
+            user_agent: None,
+            referrer: None,
         });
     }
     